@@ -1,11 +1,13 @@
 //! Module for answer related database interactions.
-use crate::schema::answer;
+use crate::schema::{answer, answer_edit_token};
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// A struct for recording answers.
-#[derive(Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Insertable, Serialize, Deserialize,
+)]
 #[primary_key(uuid)]
 #[table_name = "answer"]
 pub struct Answer {
@@ -24,6 +26,8 @@ pub struct Answer {
     pub updated_at: NaiveDateTime,
     /// When the row was created.
     pub created_at: NaiveDateTime,
+    /// The session the answer was given in.
+    pub session_uuid: Option<Uuid>,
 }
 
 /// A struct for creating new answers
@@ -38,4 +42,32 @@ pub struct NewAnswer {
     pub publicly_visible: bool,
     /// The answer
     pub answer_text: String,
+    /// The session the answer was given in.
+    pub session_uuid: Option<Uuid>,
+}
+
+/// A changeset for editing answers.
+#[derive(Clone, AsChangeset, Identifiable, Debug, Serialize, Deserialize)]
+#[primary_key(uuid)]
+#[table_name = "answer"]
+pub struct AnswerChangeset {
+    /// The answer to edit.
+    pub uuid: Uuid,
+    /// Can the outside world see the answer.
+    pub publicly_visible: Option<bool>,
+    /// The answer
+    pub answer_text: Option<String>,
+}
+
+/// A secret that allows an answer given without logging in to be edited or deleted.
+#[derive(Clone, Copy, Debug, PartialEq, Identifiable, Queryable, Serialize, Deserialize)]
+#[primary_key(answer_uuid)]
+#[table_name = "answer_edit_token"]
+pub struct AnswerEditToken {
+    /// The answer the token allows to be managed.
+    pub answer_uuid: Uuid,
+    /// The token.
+    pub edit_token: Uuid,
+    /// When the token was created.
+    pub created_at: NaiveDateTime,
 }
@@ -2,10 +2,11 @@
 
 use crate::{
     answer::{
-        db_types::{Answer, NewAnswer},
+        db_types::{Answer, AnswerChangeset, AnswerEditToken, NewAnswer},
         interface::AnswerRepository,
     },
     fake::{DummyDbErrorInfo, FakeDatabase},
+    page::{paginate, Page, Pagination, RecencySort},
 };
 use diesel::result::{DatabaseErrorKind, Error};
 use std::sync::{Arc, Mutex};
@@ -22,6 +23,7 @@ impl AnswerRepository for Arc<Mutex<FakeDatabase>> {
             answer_text: answer.answer_text,
             updated_at: chrono::Utc::now().naive_utc(),
             created_at: chrono::Utc::now().naive_utc(),
+            session_uuid: answer.session_uuid,
         };
         let mut db = self.lock().unwrap();
         if db.answers.iter().find(|q| q.uuid == uuid).is_some() {
@@ -41,9 +43,72 @@ impl AnswerRepository for Arc<Mutex<FakeDatabase>> {
             .iter()
             .position(|a| a.uuid == uuid)
             .ok_or_else(|| Error::NotFound)?;
+        db.answer_edit_tokens.retain(|t| t.answer_uuid != uuid);
         Ok(db.answers.remove(index))
     }
 
+    fn get_answer(&self, uuid: Uuid) -> Result<Answer, Error> {
+        let db = self.lock().unwrap();
+        db.answers
+            .iter()
+            .find(|a| a.uuid == uuid)
+            .cloned()
+            .ok_or(Error::NotFound)
+    }
+
+    fn update_answer(&self, changeset: AnswerChangeset) -> Result<Answer, Error> {
+        let mut db = self.lock().unwrap();
+        let answer = db
+            .answers
+            .iter_mut()
+            .find(|a| a.uuid == changeset.uuid)
+            .ok_or(Error::NotFound)?;
+        if let Some(publicly_visible) = changeset.publicly_visible {
+            answer.publicly_visible = publicly_visible;
+        }
+        if let Some(answer_text) = changeset.answer_text {
+            answer.answer_text = answer_text;
+        }
+        answer.updated_at = chrono::Utc::now().naive_utc();
+        Ok(answer.clone())
+    }
+
+    fn create_edit_token(&self, answer_uuid: Uuid) -> Result<AnswerEditToken, Error> {
+        let mut db = self.lock().unwrap();
+        if !db.answers.iter().any(|a| a.uuid == answer_uuid) {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::ForeignKeyViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+        if db
+            .answer_edit_tokens
+            .iter()
+            .any(|t| t.answer_uuid == answer_uuid)
+        {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::UniqueViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+        let token = AnswerEditToken {
+            answer_uuid,
+            edit_token: Uuid::new_v4(),
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+        db.answer_edit_tokens.push(token);
+        Ok(token)
+    }
+
+    fn get_edit_token(&self, answer_uuid: Uuid) -> Result<AnswerEditToken, Error> {
+        let db = self.lock().unwrap();
+        db.answer_edit_tokens
+            .iter()
+            .find(|t| t.answer_uuid == answer_uuid)
+            .cloned()
+            .ok_or(Error::NotFound)
+    }
+
     fn get_answers_for_question(
         &self,
         question_uuid: Uuid,
@@ -69,4 +134,87 @@ impl AnswerRepository for Arc<Mutex<FakeDatabase>> {
             Ok(answers)
         }
     }
+    fn get_visible_answers_for_question(
+        &self,
+        question_uuid: Uuid,
+        viewer_uuid: Option<Uuid>,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Answer>, Error> {
+        let db = self.lock().unwrap();
+        let answers = db
+            .answers
+            .iter()
+            .filter(|a| a.question_uuid == question_uuid && is_visible(a, viewer_uuid))
+            .cloned()
+            .collect();
+        page_of_answers(answers, &db.answers, pagination)
+    }
+
+    fn get_answers_for_user(
+        &self,
+        user_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Answer>, Error> {
+        let db = self.lock().unwrap();
+        let answers = db
+            .answers
+            .iter()
+            .filter(|a| a.user_uuid == Some(user_uuid))
+            .cloned()
+            .collect();
+        page_of_answers(answers, &db.answers, pagination)
+    }
+
+    fn get_visible_answers_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        viewer_uuid: Option<Uuid>,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Answer>, Error> {
+        let db = self.lock().unwrap();
+        let questions_in_bucket: Vec<Uuid> = db
+            .questions
+            .iter()
+            .filter(|q| q.bucket_uuid == bucket_uuid)
+            .map(|q| q.uuid)
+            .collect();
+        let answers = db
+            .answers
+            .iter()
+            .filter(|a| questions_in_bucket.contains(&a.question_uuid))
+            .filter(|a| is_visible(a, viewer_uuid))
+            .cloned()
+            .collect();
+        page_of_answers(answers, &db.answers, pagination)
+    }
+}
+
+/// Is the answer public, or was it given by the viewer.
+fn is_visible(answer: &Answer, viewer_uuid: Option<Uuid>) -> bool {
+    answer.publicly_visible || (viewer_uuid.is_some() && answer.user_uuid == viewer_uuid)
+}
+
+/// Gets the page of the answers, sorted by when they were given.
+///
+/// The last answer of the previous page is looked up among all of the answers,
+/// as it may not be one of the answers being paged through anymore.
+fn page_of_answers(
+    answers: Vec<Answer>,
+    all_answers: &[Answer],
+    pagination: Pagination<RecencySort>,
+) -> Result<Page<Answer>, Error> {
+    let last = match pagination.after {
+        Some(after) => Some(
+            all_answers
+                .iter()
+                .find(|a| a.uuid == after)
+                .ok_or(Error::NotFound)?,
+        ),
+        None => None,
+    };
+    let order = |a: &Answer, b: &Answer| match pagination.sort {
+        RecencySort::Newest => (b.created_at, b.uuid).cmp(&(a.created_at, a.uuid)),
+        RecencySort::Oldest => (a.created_at, a.uuid).cmp(&(b.created_at, b.uuid)),
+    };
+    Ok(paginate(answers, last, pagination.limit, order, |a| a.uuid))
 }
@@ -1,5 +1,8 @@
 //! Specification of functions.
-use crate::answer::db_types::{Answer, NewAnswer};
+use crate::{
+    answer::db_types::{Answer, AnswerChangeset, AnswerEditToken, NewAnswer},
+    page::{Page, Pagination, RecencySort},
+};
 use diesel::QueryResult;
 use uuid::Uuid;
 
@@ -9,6 +12,14 @@ pub trait AnswerRepository {
     fn create_answer(&self, answer: NewAnswer) -> QueryResult<Answer>;
     /// Delete an answer
     fn delete_answer(&self, uuid: Uuid) -> QueryResult<Answer>;
+    /// Gets an answer by its uuid.
+    fn get_answer(&self, uuid: Uuid) -> QueryResult<Answer>;
+    /// Edits an answer
+    fn update_answer(&self, changeset: AnswerChangeset) -> QueryResult<Answer>;
+    /// Creates a new edit token for the answer.
+    fn create_edit_token(&self, answer_uuid: Uuid) -> QueryResult<AnswerEditToken>;
+    /// Gets the edit token for the answer, if one was created.
+    fn get_edit_token(&self, answer_uuid: Uuid) -> QueryResult<AnswerEditToken>;
     /// Gets answers for the question.
     /// This should only be the publicly visible answers.
     fn get_answers_for_question(
@@ -16,4 +27,30 @@ pub trait AnswerRepository {
         question_uuid: Uuid,
         visibility_required: bool,
     ) -> QueryResult<Vec<Answer>>;
+    /// Gets a page of the answers for the question that the viewer is allowed to see,
+    /// sorted by when they were given.
+    ///
+    /// Private answers are only visible to the user who gave them.
+    fn get_visible_answers_for_question(
+        &self,
+        question_uuid: Uuid,
+        viewer_uuid: Option<Uuid>,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<Answer>>;
+    /// Gets a page of every answer the user gave, sorted by when they were given.
+    fn get_answers_for_user(
+        &self,
+        user_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<Answer>>;
+    /// Gets a page of the answers to questions in the bucket that the viewer is allowed to see,
+    /// sorted by when they were given.
+    ///
+    /// Private answers are only visible to the user who gave them.
+    fn get_visible_answers_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        viewer_uuid: Option<Uuid>,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<Answer>>;
 }
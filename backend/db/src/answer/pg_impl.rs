@@ -2,14 +2,16 @@
 
 use crate::{
     answer::{
-        db_types::{Answer, NewAnswer},
+        db_types::{Answer, AnswerChangeset, AnswerEditToken, NewAnswer},
         interface::AnswerRepository,
     },
-    schema::answer,
+    page::{Page, Pagination, RecencySort},
+    schema::{answer, answer_edit_token, question},
     AsConnRef,
 };
 use diesel::{
-    query_dsl::QueryDsl, result::Error, BoolExpressionMethods, ExpressionMethods, RunQueryDsl,
+    pg::Pg, query_dsl::QueryDsl, result::Error, BoolExpressionMethods, ExpressionMethods,
+    PgConnection, RunQueryDsl,
 };
 use uuid::Uuid;
 
@@ -25,6 +27,28 @@ where
         crate::util::delete_row(answer::table, uuid, self.as_conn())
     }
 
+    fn get_answer(&self, uuid: Uuid) -> Result<Answer, Error> {
+        crate::util::get_row(answer::table, uuid, self.as_conn())
+    }
+
+    fn update_answer(&self, changeset: AnswerChangeset) -> Result<Answer, Error> {
+        diesel::update(answer::table.find(changeset.uuid))
+            .set((&changeset, answer::updated_at.eq(diesel::dsl::now)))
+            .get_result(self.as_conn())
+    }
+
+    fn create_edit_token(&self, answer_uuid: Uuid) -> Result<AnswerEditToken, Error> {
+        diesel::insert_into(answer_edit_token::table)
+            .values(answer_edit_token::answer_uuid.eq(answer_uuid))
+            .get_result(self.as_conn())
+    }
+
+    fn get_edit_token(&self, answer_uuid: Uuid) -> Result<AnswerEditToken, Error> {
+        answer_edit_token::table
+            .find(answer_uuid)
+            .get_result(self.as_conn())
+    }
+
     fn get_answers_for_question(
         &self,
         question_uuid: Uuid,
@@ -47,4 +71,91 @@ where
                 .get_results(self.as_conn())
         }
     }
+    fn get_visible_answers_for_question(
+        &self,
+        question_uuid: Uuid,
+        viewer_uuid: Option<Uuid>,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Answer>, Error> {
+        let query = answer::table
+            .filter(answer::question_uuid.eq(question_uuid))
+            .into_boxed();
+        let query = filter_by_visibility(query, viewer_uuid);
+        page_of_answers(query, pagination, self.as_conn())
+    }
+
+    fn get_answers_for_user(
+        &self,
+        user_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Answer>, Error> {
+        let query = answer::table
+            .filter(answer::user_uuid.eq(user_uuid))
+            .into_boxed();
+        page_of_answers(query, pagination, self.as_conn())
+    }
+
+    fn get_visible_answers_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        viewer_uuid: Option<Uuid>,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Answer>, Error> {
+        let questions_in_bucket = question::table
+            .filter(question::bucket_uuid.eq(bucket_uuid))
+            .select(question::uuid);
+        let query = answer::table
+            .filter(answer::question_uuid.eq_any(questions_in_bucket))
+            .into_boxed();
+        let query = filter_by_visibility(query, viewer_uuid);
+        page_of_answers(query, pagination, self.as_conn())
+    }
+}
+
+/// Gets the page of the answers the query finds, sorted by when they were given.
+fn page_of_answers<'a>(
+    query: answer::BoxedQuery<'a, Pg>,
+    pagination: Pagination<RecencySort>,
+    conn: &PgConnection,
+) -> Result<Page<Answer>, Error> {
+    // Only the answers that sort after the last one of the previous page are on this page.
+    let query = match pagination.after {
+        Some(after) => {
+            let last: Answer = answer::table.find(after).first(conn)?;
+            match pagination.sort {
+                RecencySort::Newest => query.filter(
+                    answer::created_at.lt(last.created_at).or(answer::created_at
+                        .eq(last.created_at)
+                        .and(answer::uuid.lt(last.uuid))),
+                ),
+                RecencySort::Oldest => query.filter(
+                    answer::created_at.gt(last.created_at).or(answer::created_at
+                        .eq(last.created_at)
+                        .and(answer::uuid.gt(last.uuid))),
+                ),
+            }
+        }
+        None => query,
+    };
+    let query = match pagination.sort {
+        RecencySort::Newest => query.order_by((answer::created_at.desc(), answer::uuid.desc())),
+        RecencySort::Oldest => query.order_by((answer::created_at, answer::uuid)),
+    };
+    let answers = query.limit(pagination.fetch_limit()).get_results(conn)?;
+    Ok(Page::from_fetched(answers, pagination.limit, |a| a.uuid))
+}
+
+/// Restricts the query to public answers, and the viewer's own answers.
+fn filter_by_visibility<'a>(
+    query: answer::BoxedQuery<'a, Pg>,
+    viewer_uuid: Option<Uuid>,
+) -> answer::BoxedQuery<'a, Pg> {
+    match viewer_uuid {
+        Some(viewer_uuid) => query.filter(
+            answer::publicly_visible
+                .eq(true)
+                .or(answer::user_uuid.eq(viewer_uuid)),
+        ),
+        None => query.filter(answer::publicly_visible.eq(true)),
+    }
 }
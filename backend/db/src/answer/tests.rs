@@ -1,8 +1,15 @@
 use crate::{
-    answer::db_types::{Answer, NewAnswer},
+    answer::db_types::{Answer, AnswerChangeset, NewAnswer},
+    page::{Pagination, RecencySort},
     test::{answer_fixture::AnswerFixture, util::execute_test},
     BoxedRepository,
 };
+use uuid::Uuid;
+
+/// The first page of answers, which is big enough to hold all of the answers in these tests.
+fn first_page() -> Pagination<RecencySort> {
+    Pagination::first(10, RecencySort::Newest)
+}
 
 #[test]
 fn create_duplicate_answer() {
@@ -14,6 +21,7 @@ fn create_duplicate_answer() {
             question_uuid: fixture.question.uuid,
             publicly_visible: false,
             answer_text: "I think this is an answer".to_string(),
+            session_uuid: None,
         };
         let _answer: Answer = db
             .create_answer(new_answer)
@@ -29,6 +37,7 @@ fn create_answer_without_answer() {
             question_uuid: fixture.question.uuid,
             publicly_visible: false,
             answer_text: "I think this is an answer".to_string(),
+            session_uuid: None,
         };
         let _answer: Answer = db
             .create_answer(new_answer)
@@ -62,3 +71,154 @@ fn get_answers_for_question() {
         assert_eq!(answers[0], fixture.answer);
     });
 }
+
+#[test]
+fn private_answers_only_visible_to_author() {
+    execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+        let answers = db
+            .get_visible_answers_for_question(fixture.question.uuid, None, first_page())
+            .expect("Should get answers")
+            .items;
+        assert!(answers.is_empty());
+
+        let answers = db
+            .get_visible_answers_for_question(
+                fixture.question.uuid,
+                Some(Uuid::new_v4()),
+                first_page(),
+            )
+            .expect("Should get answers")
+            .items;
+        assert!(answers.is_empty());
+
+        let answers = db
+            .get_visible_answers_for_question(
+                fixture.question.uuid,
+                Some(fixture.user.uuid),
+                first_page(),
+            )
+            .expect("Should get answers")
+            .items;
+        assert_eq!(answers, vec![fixture.answer.clone()]);
+    });
+}
+
+#[test]
+fn get_answers_for_user_paginated() {
+    execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+        for answer_text in &["Second answer", "Third answer"] {
+            let new_answer = NewAnswer {
+                user_uuid: Some(fixture.user.uuid),
+                question_uuid: fixture.question.uuid,
+                publicly_visible: true,
+                answer_text: answer_text.to_string(),
+                session_uuid: None,
+            };
+            db.create_answer(new_answer)
+                .expect("Should create new answer");
+        }
+
+        let first = Pagination::first(2, RecencySort::Newest);
+        let first_page = db
+            .get_answers_for_user(fixture.user.uuid, first)
+            .expect("Should get answers");
+        assert_eq!(first_page.items.len(), 2);
+        assert_eq!(first_page.items[0].answer_text, "Third answer");
+        let second_page = db
+            .get_answers_for_user(
+                fixture.user.uuid,
+                first.next(&first_page).expect("Should have another page"),
+            )
+            .expect("Should get answers");
+        assert_eq!(second_page.items, vec![fixture.answer.clone()]);
+        assert_eq!(second_page.next_cursor, None);
+    });
+}
+
+#[test]
+fn get_visible_answers_for_bucket() {
+    execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+        let new_answer = NewAnswer {
+            user_uuid: None,
+            question_uuid: fixture.question.uuid,
+            publicly_visible: true,
+            answer_text: "Everyone can see this".to_string(),
+            session_uuid: None,
+        };
+        let public_answer = db
+            .create_answer(new_answer)
+            .expect("Should create new answer");
+
+        let answers = db
+            .get_visible_answers_for_bucket(fixture.bucket.uuid, None, first_page())
+            .expect("Should get answers")
+            .items;
+        assert_eq!(answers, vec![public_answer]);
+
+        let answers = db
+            .get_visible_answers_for_bucket(
+                fixture.bucket.uuid,
+                Some(fixture.user.uuid),
+                first_page(),
+            )
+            .expect("Should get answers")
+            .items;
+        assert_eq!(answers.len(), 2);
+
+        let answers = db
+            .get_visible_answers_for_bucket(Uuid::new_v4(), Some(fixture.user.uuid), first_page())
+            .expect("Should get answers")
+            .items;
+        assert!(answers.is_empty());
+    });
+}
+
+#[test]
+fn update_answer() {
+    execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+        let changeset = AnswerChangeset {
+            uuid: fixture.answer.uuid,
+            publicly_visible: Some(true),
+            answer_text: None,
+        };
+        let answer = db.update_answer(changeset).expect("Should update answer");
+        assert!(answer.publicly_visible);
+        assert_eq!(answer.answer_text, fixture.answer.answer_text);
+
+        let changeset = AnswerChangeset {
+            uuid: fixture.answer.uuid,
+            publicly_visible: None,
+            answer_text: Some("A better answer".to_string()),
+        };
+        let answer = db.update_answer(changeset).expect("Should update answer");
+        assert!(answer.publicly_visible);
+        assert_eq!(answer.answer_text, "A better answer");
+        assert_eq!(
+            db.get_answer(answer.uuid).expect("Should get answer"),
+            answer
+        );
+    });
+}
+
+#[test]
+fn edit_tokens() {
+    execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+        db.get_edit_token(fixture.answer.uuid)
+            .expect_err("Answer shouldn't have an edit token yet");
+        let token = db
+            .create_edit_token(fixture.answer.uuid)
+            .expect("Should create edit token");
+        assert_eq!(
+            db.get_edit_token(fixture.answer.uuid)
+                .expect("Should get edit token"),
+            token
+        );
+        db.create_edit_token(fixture.answer.uuid)
+            .expect_err("Answers only have one edit token");
+
+        db.delete_answer(fixture.answer.uuid)
+            .expect("Should delete answer");
+        db.get_edit_token(fixture.answer.uuid)
+            .expect_err("Edit token should be deleted with the answer");
+    });
+}
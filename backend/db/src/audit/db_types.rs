@@ -0,0 +1,217 @@
+//! Database types for the audit log of buckets.
+use crate::schema::bucket_audit_event;
+use chrono::NaiveDateTime;
+use diesel::{
+    deserialize::{self, FromSql},
+    pg::Pg,
+    serialize::{self, Output, ToSql},
+    sql_types::Text,
+    Identifiable, Queryable,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Write;
+use uuid::Uuid;
+
+/// A record of something that changed within a bucket.
+///
+/// Events are only ever added, never changed or removed, unless the bucket itself is removed.
+#[derive(Clone, Debug, PartialEq, Identifiable, Queryable, Serialize, Deserialize)]
+#[primary_key(uuid)]
+#[table_name = "bucket_audit_event"]
+pub struct AuditEvent {
+    /// The unique identifier of the event.
+    pub uuid: Uuid,
+    /// The bucket the change was made in.
+    pub bucket_uuid: Uuid,
+    /// The user who made the change, if they were logged in.
+    pub actor_uuid: Option<Uuid>,
+    /// The user, question or answer that was changed, if any.
+    pub target_uuid: Option<Uuid>,
+    /// What was done.
+    pub action: AuditAction,
+    /// The fields that changed, as `{"field": {"old": .., "new": ..}}`.
+    pub diff: Value,
+    /// When the change was made.
+    pub created_at: NaiveDateTime,
+}
+
+/// Structure used to record an audit event.
+#[derive(Clone, Debug, Insertable, Serialize, Deserialize)]
+#[table_name = "bucket_audit_event"]
+pub struct NewAuditEvent {
+    /// The bucket the change was made in.
+    pub bucket_uuid: Uuid,
+    /// The user who made the change, if they were logged in.
+    pub actor_uuid: Option<Uuid>,
+    /// The user, question or answer that was changed, if any.
+    pub target_uuid: Option<Uuid>,
+    /// What was done.
+    pub action: AuditAction,
+    /// The fields that changed.
+    pub diff: Value,
+}
+
+/// The kinds of changes that are recorded in the audit log.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, AsExpression, FromSqlRow, Serialize, Deserialize,
+)]
+#[sql_type = "Text"]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// The bucket was created.
+    BucketCreated,
+    /// The bucket was renamed, or given a new slug.
+    BucketRenamed,
+    /// The bucket was made as a copy of another bucket or template.
+    BucketCloned,
+    /// The bucket was made as a template out of another bucket.
+    TemplateSaved,
+    /// The bucket was restored from a backup of another bucket.
+    BucketRestored,
+    /// A user joined the bucket.
+    MemberJoined,
+    /// A member left the bucket, or was kicked from it.
+    MemberRemoved,
+    /// A user was banned from the bucket.
+    MemberBanned,
+    /// A user's ban from the bucket was lifted.
+    BanLifted,
+    /// A member's permissions were changed.
+    PermissionsChanged,
+    /// A member was given a role.
+    RoleSet,
+    /// Another member became the owner of the bucket.
+    OwnershipTransferred,
+    /// An invite to the bucket was created.
+    InviteCreated,
+    /// An invite to the bucket was deleted.
+    InviteDeleted,
+    /// The bucket's join code was replaced by a new one.
+    JoinCodeRegenerated,
+    /// The bucket's flags, or its draw strategy, were changed.
+    FlagsChanged,
+    /// The current turn was skipped.
+    TurnSkipped,
+    /// A session was started.
+    SessionStarted,
+    /// A session was ended.
+    SessionEnded,
+    /// A question was added to the bucket.
+    QuestionCreated,
+    /// Many questions were added to the bucket at once.
+    QuestionsImported,
+    /// A question's text was changed.
+    QuestionEdited,
+    /// A question's text was restored from one of its revisions.
+    QuestionReverted,
+    /// A question was deleted.
+    QuestionDeleted,
+    /// A question was drawn from the bucket.
+    QuestionDrawn,
+    /// A question was moved onto or off of the floor.
+    QuestionArchiveStateSet,
+    /// A question was tagged.
+    QuestionTagged,
+    /// A tag was removed from a question.
+    QuestionUntagged,
+    /// A vote was cast on a question.
+    QuestionVoted,
+    /// A vote on a question was taken back.
+    QuestionVoteRemoved,
+    /// A question was answered.
+    AnswerPosted,
+    /// An answer was changed.
+    AnswerEdited,
+    /// An answer was deleted.
+    AnswerDeleted,
+}
+
+impl AuditAction {
+    /// The representation of the action within the database.
+    fn as_sql_str(self) -> &'static str {
+        match self {
+            AuditAction::BucketCreated => "bucket_created",
+            AuditAction::BucketRenamed => "bucket_renamed",
+            AuditAction::BucketCloned => "bucket_cloned",
+            AuditAction::TemplateSaved => "template_saved",
+            AuditAction::BucketRestored => "bucket_restored",
+            AuditAction::MemberJoined => "member_joined",
+            AuditAction::MemberRemoved => "member_removed",
+            AuditAction::MemberBanned => "member_banned",
+            AuditAction::BanLifted => "ban_lifted",
+            AuditAction::PermissionsChanged => "permissions_changed",
+            AuditAction::RoleSet => "role_set",
+            AuditAction::OwnershipTransferred => "ownership_transferred",
+            AuditAction::InviteCreated => "invite_created",
+            AuditAction::InviteDeleted => "invite_deleted",
+            AuditAction::JoinCodeRegenerated => "join_code_regenerated",
+            AuditAction::FlagsChanged => "flags_changed",
+            AuditAction::TurnSkipped => "turn_skipped",
+            AuditAction::SessionStarted => "session_started",
+            AuditAction::SessionEnded => "session_ended",
+            AuditAction::QuestionCreated => "question_created",
+            AuditAction::QuestionsImported => "questions_imported",
+            AuditAction::QuestionEdited => "question_edited",
+            AuditAction::QuestionReverted => "question_reverted",
+            AuditAction::QuestionDeleted => "question_deleted",
+            AuditAction::QuestionDrawn => "question_drawn",
+            AuditAction::QuestionArchiveStateSet => "question_archive_state_set",
+            AuditAction::QuestionTagged => "question_tagged",
+            AuditAction::QuestionUntagged => "question_untagged",
+            AuditAction::QuestionVoted => "question_voted",
+            AuditAction::QuestionVoteRemoved => "question_vote_removed",
+            AuditAction::AnswerPosted => "answer_posted",
+            AuditAction::AnswerEdited => "answer_edited",
+            AuditAction::AnswerDeleted => "answer_deleted",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for AuditAction {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        ToSql::<Text, Pg>::to_sql(self.as_sql_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for AuditAction {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        let action: String = FromSql::<Text, Pg>::from_sql(bytes)?;
+        match action.as_str() {
+            "bucket_created" => Ok(AuditAction::BucketCreated),
+            "bucket_renamed" => Ok(AuditAction::BucketRenamed),
+            "bucket_cloned" => Ok(AuditAction::BucketCloned),
+            "template_saved" => Ok(AuditAction::TemplateSaved),
+            "bucket_restored" => Ok(AuditAction::BucketRestored),
+            "member_joined" => Ok(AuditAction::MemberJoined),
+            "member_removed" => Ok(AuditAction::MemberRemoved),
+            "member_banned" => Ok(AuditAction::MemberBanned),
+            "ban_lifted" => Ok(AuditAction::BanLifted),
+            "permissions_changed" => Ok(AuditAction::PermissionsChanged),
+            "role_set" => Ok(AuditAction::RoleSet),
+            "ownership_transferred" => Ok(AuditAction::OwnershipTransferred),
+            "invite_created" => Ok(AuditAction::InviteCreated),
+            "invite_deleted" => Ok(AuditAction::InviteDeleted),
+            "join_code_regenerated" => Ok(AuditAction::JoinCodeRegenerated),
+            "flags_changed" => Ok(AuditAction::FlagsChanged),
+            "turn_skipped" => Ok(AuditAction::TurnSkipped),
+            "session_started" => Ok(AuditAction::SessionStarted),
+            "session_ended" => Ok(AuditAction::SessionEnded),
+            "question_created" => Ok(AuditAction::QuestionCreated),
+            "questions_imported" => Ok(AuditAction::QuestionsImported),
+            "question_edited" => Ok(AuditAction::QuestionEdited),
+            "question_reverted" => Ok(AuditAction::QuestionReverted),
+            "question_deleted" => Ok(AuditAction::QuestionDeleted),
+            "question_drawn" => Ok(AuditAction::QuestionDrawn),
+            "question_archive_state_set" => Ok(AuditAction::QuestionArchiveStateSet),
+            "question_tagged" => Ok(AuditAction::QuestionTagged),
+            "question_untagged" => Ok(AuditAction::QuestionUntagged),
+            "question_voted" => Ok(AuditAction::QuestionVoted),
+            "question_vote_removed" => Ok(AuditAction::QuestionVoteRemoved),
+            "answer_posted" => Ok(AuditAction::AnswerPosted),
+            "answer_edited" => Ok(AuditAction::AnswerEdited),
+            "answer_deleted" => Ok(AuditAction::AnswerDeleted),
+            other => Err(format!("Unrecognized audit action: {}", other).into()),
+        }
+    }
+}
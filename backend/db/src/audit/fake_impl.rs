@@ -0,0 +1,63 @@
+//! Mock impl
+use crate::{
+    audit::{
+        db_types::{AuditEvent, NewAuditEvent},
+        interface::AuditRepository,
+    },
+    fake::{DummyDbErrorInfo, FakeDatabase},
+    page::{paginate, Page, Pagination, RecencySort},
+};
+use diesel::result::{DatabaseErrorKind, Error};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+impl AuditRepository for Arc<Mutex<FakeDatabase>> {
+    fn record_audit_event(&self, new_event: NewAuditEvent) -> Result<AuditEvent, Error> {
+        let mut db = self.lock().unwrap();
+        if !db.buckets.iter().any(|b| b.uuid == new_event.bucket_uuid) {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::ForeignKeyViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+        let event = AuditEvent {
+            uuid: Uuid::new_v4(),
+            bucket_uuid: new_event.bucket_uuid,
+            actor_uuid: new_event.actor_uuid,
+            target_uuid: new_event.target_uuid,
+            action: new_event.action,
+            diff: new_event.diff,
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+        db.audit_events.push(event.clone());
+        Ok(event)
+    }
+
+    fn get_audit_events(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<AuditEvent>, Error> {
+        let db = self.lock().unwrap();
+        let last = match pagination.after {
+            Some(after) => Some(
+                db.audit_events
+                    .iter()
+                    .find(|e| e.uuid == after)
+                    .ok_or(Error::NotFound)?,
+            ),
+            None => None,
+        };
+        let events = db
+            .audit_events
+            .iter()
+            .filter(|e| e.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        let order = |a: &AuditEvent, b: &AuditEvent| match pagination.sort {
+            RecencySort::Newest => (b.created_at, b.uuid).cmp(&(a.created_at, a.uuid)),
+            RecencySort::Oldest => (a.created_at, a.uuid).cmp(&(b.created_at, b.uuid)),
+        };
+        Ok(paginate(events, last, pagination.limit, order, |e| e.uuid))
+    }
+}
@@ -0,0 +1,23 @@
+//! Specification of functions.
+use crate::{
+    audit::db_types::{AuditEvent, NewAuditEvent},
+    page::{Page, Pagination, RecencySort},
+};
+use diesel::QueryResult;
+use uuid::Uuid;
+
+/// Functions for specifically working with the audit log of buckets.
+///
+/// The log is append-only, so there is no way to change or remove events.
+pub trait AuditRepository {
+    /// Records that something changed within a bucket.
+    fn record_audit_event(&self, new_event: NewAuditEvent) -> QueryResult<AuditEvent>;
+    /// Gets a page of the events in the bucket.
+    ///
+    /// If the event the page should start after doesn't exist anymore, this will return a `NotFound` error.
+    fn get_audit_events(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<AuditEvent>>;
+}
@@ -0,0 +1,7 @@
+//! Module containing all structures and functions required for recording what happens within buckets.
+pub mod db_types;
+pub mod fake_impl;
+pub mod interface;
+pub mod pg_impl;
+#[cfg(test)]
+mod tests;
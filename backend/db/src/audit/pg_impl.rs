@@ -0,0 +1,70 @@
+//! Implementation of the specified interfaces for PgConnection.
+use crate::{
+    audit::{
+        db_types::{AuditEvent, NewAuditEvent},
+        interface::AuditRepository,
+    },
+    page::{Page, Pagination, RecencySort},
+    schema::bucket_audit_event,
+    AsConnRef,
+};
+use diesel::{
+    query_dsl::{QueryDsl, RunQueryDsl},
+    result::Error,
+    BoolExpressionMethods, ExpressionMethods,
+};
+use uuid::Uuid;
+
+impl<T> AuditRepository for T
+where
+    T: AsConnRef,
+{
+    fn record_audit_event(&self, new_event: NewAuditEvent) -> Result<AuditEvent, Error> {
+        crate::util::create_row(bucket_audit_event::table, new_event, self.as_conn())
+    }
+
+    fn get_audit_events(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<AuditEvent>, Error> {
+        let conn = self.as_conn();
+        let query = bucket_audit_event::table
+            .filter(bucket_audit_event::bucket_uuid.eq(bucket_uuid))
+            .into_boxed();
+        // Only the events that sort after the last one of the previous page are on this page.
+        let query = match pagination.after {
+            Some(after) => {
+                let last: AuditEvent = bucket_audit_event::table.find(after).first(conn)?;
+                match pagination.sort {
+                    RecencySort::Newest => query.filter(
+                        bucket_audit_event::created_at.lt(last.created_at).or(
+                            bucket_audit_event::created_at
+                                .eq(last.created_at)
+                                .and(bucket_audit_event::uuid.lt(last.uuid)),
+                        ),
+                    ),
+                    RecencySort::Oldest => query.filter(
+                        bucket_audit_event::created_at.gt(last.created_at).or(
+                            bucket_audit_event::created_at
+                                .eq(last.created_at)
+                                .and(bucket_audit_event::uuid.gt(last.uuid)),
+                        ),
+                    ),
+                }
+            }
+            None => query,
+        };
+        let query = match pagination.sort {
+            RecencySort::Newest => query.order_by((
+                bucket_audit_event::created_at.desc(),
+                bucket_audit_event::uuid.desc(),
+            )),
+            RecencySort::Oldest => {
+                query.order_by((bucket_audit_event::created_at, bucket_audit_event::uuid))
+            }
+        };
+        let events = query.limit(pagination.fetch_limit()).get_results(conn)?;
+        Ok(Page::from_fetched(events, pagination.limit, |e| e.uuid))
+    }
+}
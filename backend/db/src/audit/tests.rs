@@ -0,0 +1,77 @@
+use crate::{
+    audit::db_types::{AuditAction, NewAuditEvent},
+    page::{Pagination, RecencySort},
+    test::{bucket_user_relation_fixture::UserBucketRelationFixture, util::execute_test},
+    BoxedRepository,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+#[test]
+fn record_audit_event() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let new_event = NewAuditEvent {
+            bucket_uuid: fixture.bucket.uuid,
+            actor_uuid: Some(fixture.user1.uuid),
+            target_uuid: Some(fixture.user2.uuid),
+            action: AuditAction::PermissionsChanged,
+            diff: json!({"kick_permission": {"old": false, "new": true}}),
+        };
+        let event = db
+            .record_audit_event(new_event.clone())
+            .expect("Should record event");
+        assert_eq!(event.action, AuditAction::PermissionsChanged);
+        assert_eq!(event.diff, new_event.diff);
+        assert_eq!(event.target_uuid, Some(fixture.user2.uuid));
+    });
+}
+
+#[test]
+fn cant_record_audit_event_for_missing_bucket() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let new_event = NewAuditEvent {
+            bucket_uuid: Uuid::new_v4(),
+            actor_uuid: Some(fixture.user1.uuid),
+            target_uuid: None,
+            action: AuditAction::FlagsChanged,
+            diff: json!({}),
+        };
+        db.record_audit_event(new_event)
+            .expect_err("Should not record event for a bucket that doesn't exist");
+    });
+}
+
+#[test]
+fn get_audit_events_most_recent_first() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let actions = [
+            AuditAction::MemberJoined,
+            AuditAction::QuestionCreated,
+            AuditAction::QuestionDeleted,
+        ];
+        for action in &actions {
+            let new_event = NewAuditEvent {
+                bucket_uuid: fixture.bucket.uuid,
+                actor_uuid: Some(fixture.user1.uuid),
+                target_uuid: None,
+                action: *action,
+                diff: json!({}),
+            };
+            db.record_audit_event(new_event)
+                .expect("Should record event");
+        }
+
+        let events = db
+            .get_audit_events(
+                fixture.bucket.uuid,
+                Pagination::first(2, RecencySort::Newest),
+            )
+            .expect("Should get events");
+        let listed: Vec<AuditAction> = events.items.iter().map(|e| e.action).collect();
+        assert_eq!(
+            listed,
+            vec![AuditAction::QuestionDeleted, AuditAction::QuestionCreated]
+        );
+        assert!(events.next_cursor.is_some());
+    });
+}
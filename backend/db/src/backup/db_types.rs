@@ -0,0 +1,171 @@
+//! Module for bucket archive types.
+use crate::{
+    answer::db_types::Answer,
+    bucket::db_types::Bucket,
+    bucket_user_relation::db_types::BucketUserRelation,
+    favorite_question::db_types::FavoriteQuestionRelation,
+    question::db_types::Question,
+    tag::db_types::{QuestionTag, Tag},
+    user::db_types::User,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// The version of the archive format that is written.
+///
+/// It should be incremented whenever a change is made to the archive that older versions can't read.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+/// Everything needed to recreate a bucket, possibly on another instance.
+///
+/// Sessions, draws, votes, and the audit log are not kept.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BucketArchive {
+    /// The version of the format the archive was written in.
+    pub version: u32,
+    /// The bucket itself.
+    pub bucket: Bucket,
+    /// Every user that the rest of the archive refers to.
+    pub users: Vec<User>,
+    /// The bucket's members, and their permissions.
+    pub members: Vec<BucketUserRelation>,
+    /// The bucket's tags.
+    pub tags: Vec<Tag>,
+    /// The bucket's questions, the oldest first.
+    pub questions: Vec<Question>,
+    /// Which tags each question has.
+    pub question_tags: Vec<QuestionTag>,
+    /// The answers given to the bucket's questions.
+    pub answers: Vec<Answer>,
+    /// Which users have favorited the bucket's questions.
+    pub favorites: Vec<FavoriteQuestionRelation>,
+}
+
+/// How the archive is restored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RestoreOptions {
+    /// The slug the restored bucket will have. It must not already be in use.
+    pub bucket_slug: String,
+    /// The user that will own the restored bucket, instead of its original owner.
+    ///
+    /// They are made a member with every permission if they weren't one already.
+    pub owner_uuid: Option<Uuid>,
+    /// Whether the archive's users are restored.
+    ///
+    /// If they are, they are matched with existing users by their google id,
+    /// and are created if there aren't any.
+    /// Otherwise, nobody in the archive is trusted to be who it says they are:
+    /// their memberships and favorites are dropped, and anything they wrote is kept without an author.
+    pub keep_users: bool,
+}
+
+impl BucketArchive {
+    /// Gives the bucket, and everything in it, new unique identifiers,
+    /// and replaces the users' identifiers with the ones they have where the archive is restored.
+    ///
+    /// Rows belonging to users that are missing from `user_uuids` are dropped,
+    /// and anything those users wrote is kept without an author.
+    pub(crate) fn remapped(
+        mut self,
+        options: RestoreOptions,
+        user_uuids: &HashMap<Uuid, Uuid>,
+    ) -> BucketArchive {
+        let bucket_uuid = Uuid::new_v4();
+        let new_uuids = |uuids: Vec<Uuid>| -> HashMap<Uuid, Uuid> {
+            uuids
+                .into_iter()
+                .map(|uuid| (uuid, Uuid::new_v4()))
+                .collect()
+        };
+        let question_uuids = new_uuids(self.questions.iter().map(|q| q.uuid).collect());
+        let tag_uuids = new_uuids(self.tags.iter().map(|t| t.uuid).collect());
+        let author = |uuid: Option<Uuid>| uuid.and_then(|uuid| user_uuids.get(&uuid).cloned());
+
+        self.bucket.uuid = bucket_uuid;
+        self.bucket.bucket_slug = options.bucket_slug;
+        self.bucket.owner_uuid = options
+            .owner_uuid
+            .or_else(|| author(self.bucket.owner_uuid));
+
+        let mut members: Vec<BucketUserRelation> = self
+            .members
+            .into_iter()
+            .filter_map(|mut member| {
+                member.user_uuid = *user_uuids.get(&member.user_uuid)?;
+                member.bucket_uuid = bucket_uuid;
+                Some(member)
+            })
+            .collect();
+        if let Some(owner_uuid) = options.owner_uuid {
+            if !members.iter().any(|member| member.user_uuid == owner_uuid) {
+                let now = Utc::now().naive_utc();
+                members.push(BucketUserRelation {
+                    user_uuid: owner_uuid,
+                    bucket_uuid,
+                    set_public_permission: true,
+                    set_drawing_permission: true,
+                    set_exclusive_permission: true,
+                    kick_permission: true,
+                    grant_permissions_permission: true,
+                    updated_at: now,
+                    created_at: now,
+                    moderate_questions_permission: true,
+                    delete_questions_permission: true,
+                    invite_permission: true,
+                });
+            }
+        }
+        self.members = members;
+
+        for tag in &mut self.tags {
+            tag.uuid = tag_uuids[&tag.uuid];
+            tag.bucket_uuid = bucket_uuid;
+        }
+        for question in &mut self.questions {
+            question.uuid = question_uuids[&question.uuid];
+            question.bucket_uuid = bucket_uuid;
+            question.user_uuid = author(question.user_uuid);
+        }
+        self.question_tags = self
+            .question_tags
+            .into_iter()
+            .filter_map(|mut question_tag| {
+                question_tag.question_uuid = *question_uuids.get(&question_tag.question_uuid)?;
+                question_tag.tag_uuid = *tag_uuids.get(&question_tag.tag_uuid)?;
+                Some(question_tag)
+            })
+            .collect();
+        self.answers = self
+            .answers
+            .into_iter()
+            .filter_map(|mut answer| {
+                answer.uuid = Uuid::new_v4();
+                answer.question_uuid = *question_uuids.get(&answer.question_uuid)?;
+                answer.user_uuid = author(answer.user_uuid);
+                // Sessions aren't archived, so there is nothing for the answer to refer to.
+                answer.session_uuid = None;
+                Some(answer)
+            })
+            .collect();
+        self.favorites = self
+            .favorites
+            .into_iter()
+            .filter_map(|mut favorite| {
+                favorite.question_uuid = *question_uuids.get(&favorite.question_uuid)?;
+                favorite.user_uuid = *user_uuids.get(&favorite.user_uuid)?;
+                Some(favorite)
+            })
+            .collect();
+        self.users = self
+            .users
+            .into_iter()
+            .filter_map(|mut user| {
+                user.uuid = *user_uuids.get(&user.uuid)?;
+                Some(user)
+            })
+            .collect();
+        self
+    }
+}
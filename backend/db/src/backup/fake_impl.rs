@@ -0,0 +1,160 @@
+//! Mock impl
+use crate::{
+    backup::{
+        db_types::{BucketArchive, RestoreOptions, ARCHIVE_VERSION},
+        interface::BackupRepository,
+    },
+    bucket::db_types::Bucket,
+    fake::{DummyDbErrorInfo, FakeDatabase},
+    user::db_types::User,
+};
+use diesel::result::{DatabaseErrorKind, Error};
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, Mutex},
+};
+use uuid::Uuid;
+
+impl BackupRepository for Arc<Mutex<FakeDatabase>> {
+    fn export_bucket(&self, bucket_uuid: Uuid) -> Result<BucketArchive, Error> {
+        let db = self.lock().unwrap();
+        let bucket = db
+            .buckets
+            .iter()
+            .find(|b| b.uuid == bucket_uuid)
+            .cloned()
+            .ok_or_else(|| Error::NotFound)?;
+        let mut members: Vec<_> = db
+            .user_bucket_relations
+            .iter()
+            .filter(|r| r.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        members.sort_by_key(|m| m.created_at);
+        let mut tags: Vec<_> = db
+            .tags
+            .iter()
+            .filter(|t| t.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        tags.sort_by(|a, b| a.tag_name.cmp(&b.tag_name));
+        let mut questions: Vec<_> = db
+            .questions
+            .iter()
+            .filter(|q| q.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        questions.sort_by_key(|q| q.created_at);
+        let question_uuids: BTreeSet<Uuid> = questions.iter().map(|q| q.uuid).collect();
+        let question_tags: Vec<_> = db
+            .question_tags
+            .iter()
+            .filter(|qt| question_uuids.contains(&qt.question_uuid))
+            .cloned()
+            .collect();
+        let mut answers: Vec<_> = db
+            .answers
+            .iter()
+            .filter(|a| question_uuids.contains(&a.question_uuid))
+            .cloned()
+            .collect();
+        answers.sort_by_key(|a| a.created_at);
+        let favorites: Vec<_> = db
+            .favorite_question_relations
+            .iter()
+            .filter(|f| question_uuids.contains(&f.question_uuid))
+            .cloned()
+            .collect();
+
+        let user_uuids: BTreeSet<Uuid> = bucket
+            .owner_uuid
+            .into_iter()
+            .chain(members.iter().map(|m| m.user_uuid))
+            .chain(questions.iter().filter_map(|q| q.user_uuid))
+            .chain(answers.iter().filter_map(|a| a.user_uuid))
+            .chain(favorites.iter().map(|f| f.user_uuid))
+            .collect();
+        let mut users: Vec<_> = db
+            .users
+            .iter()
+            .filter(|u| user_uuids.contains(&u.uuid))
+            .cloned()
+            .collect();
+        users.sort_by_key(|u| u.uuid);
+
+        Ok(BucketArchive {
+            version: ARCHIVE_VERSION,
+            bucket,
+            users,
+            members,
+            tags,
+            questions,
+            question_tags,
+            answers,
+            favorites,
+        })
+    }
+
+    fn restore_bucket(
+        &self,
+        archive: BucketArchive,
+        options: RestoreOptions,
+    ) -> Result<Bucket, Error> {
+        let mut db = self.lock().unwrap();
+        if db
+            .buckets
+            .iter()
+            .any(|b| b.bucket_slug == options.bucket_slug)
+        {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::UniqueViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+        if let Some(owner_uuid) = options.owner_uuid {
+            if !db.users.iter().any(|u| u.uuid == owner_uuid) {
+                return Err(Error::DatabaseError(
+                    DatabaseErrorKind::ForeignKeyViolation,
+                    Box::new(DummyDbErrorInfo::new()),
+                ));
+            }
+        }
+
+        let mut user_uuids = HashMap::new();
+        let users: &[User] = if options.keep_users {
+            archive.users.as_slice()
+        } else {
+            &[]
+        };
+        for user in users {
+            let existing_uuid = db
+                .users
+                .iter()
+                .find(|u| u.google_user_id == user.google_user_id)
+                .map(|u| u.uuid);
+            let restored_uuid = match existing_uuid {
+                Some(uuid) => uuid,
+                None => {
+                    let restored_user = User {
+                        uuid: Uuid::new_v4(),
+                        google_user_id: user.google_user_id.clone(),
+                        google_name: user.google_name.clone(),
+                    };
+                    db.users.push(restored_user.clone());
+                    restored_user.uuid
+                }
+            };
+            user_uuids.insert(user.uuid, restored_uuid);
+        }
+        let archive = archive.remapped(options, &user_uuids);
+
+        db.buckets.push(archive.bucket.clone());
+        db.user_bucket_relations.extend(archive.members);
+        db.tags.extend(archive.tags);
+        db.questions.extend(archive.questions);
+        db.question_tags.extend(archive.question_tags);
+        db.answers.extend(archive.answers);
+        db.favorite_question_relations.extend(archive.favorites);
+        Ok(archive.bucket)
+    }
+}
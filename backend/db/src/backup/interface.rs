@@ -0,0 +1,23 @@
+//! Specification of functions.
+use crate::{
+    backup::db_types::{BucketArchive, RestoreOptions},
+    bucket::db_types::Bucket,
+};
+use diesel::QueryResult;
+use uuid::Uuid;
+
+/// Functions for backing up and restoring whole buckets.
+pub trait BackupRepository {
+    /// Gets everything needed to recreate the bucket.
+    fn export_bucket(&self, bucket_uuid: Uuid) -> QueryResult<BucketArchive>;
+    /// Recreates the archived bucket as a new bucket, keeping the timestamps of everything in it.
+    ///
+    /// Everything in the bucket gets a new unique identifier.
+    /// If `options.keep_users` is set, the archive's users are matched with existing users by their google id,
+    /// and are created if there aren't any.
+    fn restore_bucket(
+        &self,
+        archive: BucketArchive,
+        options: RestoreOptions,
+    ) -> QueryResult<Bucket>;
+}
@@ -0,0 +1,143 @@
+//! Implementation of the specified interfaces for PgConnection.
+use crate::{
+    answer::db_types::Answer,
+    backup::{
+        db_types::{BucketArchive, RestoreOptions, ARCHIVE_VERSION},
+        interface::BackupRepository,
+    },
+    bucket::db_types::Bucket,
+    bucket_user_relation::db_types::BucketUserRelation,
+    favorite_question::db_types::FavoriteQuestionRelation,
+    question::db_types::Question,
+    schema::{
+        answer, bq_user, bucket, bucket_user_relation, question, question_tag, tag,
+        user_question_favorite_relation,
+    },
+    tag::db_types::{QuestionTag, Tag},
+    user::{
+        db_types::{NewUser, User},
+        interface::UserRepository,
+    },
+    AsConnRef,
+};
+use diesel::{
+    query_dsl::{QueryDsl, RunQueryDsl},
+    result::Error,
+    Connection, ExpressionMethods,
+};
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+impl<T> BackupRepository for T
+where
+    T: AsConnRef,
+{
+    fn export_bucket(&self, bucket_uuid: Uuid) -> Result<BucketArchive, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            let bucket: Bucket = bucket::table.find(bucket_uuid).get_result(conn)?;
+            let members: Vec<BucketUserRelation> = bucket_user_relation::table
+                .filter(bucket_user_relation::bucket_uuid.eq(bucket_uuid))
+                .order_by(bucket_user_relation::created_at)
+                .get_results(conn)?;
+            let tags: Vec<Tag> = tag::table
+                .filter(tag::bucket_uuid.eq(bucket_uuid))
+                .order_by(tag::tag_name)
+                .get_results(conn)?;
+            let questions: Vec<Question> = question::table
+                .filter(question::bucket_uuid.eq(bucket_uuid))
+                .order_by(question::created_at)
+                .get_results(conn)?;
+            let question_uuids: Vec<Uuid> = questions.iter().map(|q| q.uuid).collect();
+            let question_tags: Vec<QuestionTag> = question_tag::table
+                .filter(question_tag::question_uuid.eq_any(&question_uuids))
+                .order_by(question_tag::created_at)
+                .get_results(conn)?;
+            let answers: Vec<Answer> = answer::table
+                .filter(answer::question_uuid.eq_any(&question_uuids))
+                .order_by(answer::created_at)
+                .get_results(conn)?;
+            let favorites: Vec<FavoriteQuestionRelation> = user_question_favorite_relation::table
+                .filter(user_question_favorite_relation::question_uuid.eq_any(&question_uuids))
+                .order_by(user_question_favorite_relation::created_at)
+                .get_results(conn)?;
+
+            let user_uuids: BTreeSet<Uuid> = bucket
+                .owner_uuid
+                .into_iter()
+                .chain(members.iter().map(|m| m.user_uuid))
+                .chain(questions.iter().filter_map(|q| q.user_uuid))
+                .chain(answers.iter().filter_map(|a| a.user_uuid))
+                .chain(favorites.iter().map(|f| f.user_uuid))
+                .collect();
+            let user_uuids: Vec<Uuid> = user_uuids.into_iter().collect();
+            let users: Vec<User> = bq_user::table
+                .filter(bq_user::uuid.eq_any(&user_uuids))
+                .order_by(bq_user::uuid)
+                .get_results(conn)?;
+
+            Ok(BucketArchive {
+                version: ARCHIVE_VERSION,
+                bucket,
+                users,
+                members,
+                tags,
+                questions,
+                question_tags,
+                answers,
+                favorites,
+            })
+        })
+    }
+
+    fn restore_bucket(
+        &self,
+        archive: BucketArchive,
+        options: RestoreOptions,
+    ) -> Result<Bucket, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            let mut user_uuids = HashMap::new();
+            let users: &[User] = if options.keep_users {
+                archive.users.as_slice()
+            } else {
+                &[]
+            };
+            for user in users {
+                let restored_user = match self.get_user_by_google_id(user.google_user_id.clone()) {
+                    Ok(existing_user) => existing_user,
+                    Err(Error::NotFound) => self.create_user(NewUser {
+                        google_user_id: user.google_user_id.clone(),
+                        google_name: user.google_name.clone(),
+                    })?,
+                    Err(e) => return Err(e),
+                };
+                user_uuids.insert(user.uuid, restored_user.uuid);
+            }
+            let archive = archive.remapped(options, &user_uuids);
+
+            let bucket: Bucket = diesel::insert_into(bucket::table)
+                .values(&archive.bucket)
+                .get_result(conn)?;
+            diesel::insert_into(bucket_user_relation::table)
+                .values(&archive.members)
+                .execute(conn)?;
+            diesel::insert_into(tag::table)
+                .values(&archive.tags)
+                .execute(conn)?;
+            diesel::insert_into(question::table)
+                .values(&archive.questions)
+                .execute(conn)?;
+            diesel::insert_into(question_tag::table)
+                .values(&archive.question_tags)
+                .execute(conn)?;
+            diesel::insert_into(answer::table)
+                .values(&archive.answers)
+                .execute(conn)?;
+            diesel::insert_into(user_question_favorite_relation::table)
+                .values(&archive.favorites)
+                .execute(conn)?;
+            Ok(bucket)
+        })
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{
+    answer::db_types::NewAnswer,
+    backup::db_types::RestoreOptions,
+    fake::FakeDatabase,
+    favorite_question::db_types::NewFavoriteQuestionRelation,
+    question::db_types::NewQuestion,
+    tag::db_types::{NewQuestionTag, NewTag},
+    test::{bucket_user_relation_fixture::UserBucketRelationFixture, util::execute_test},
+    user::db_types::User,
+    BoxedRepository,
+};
+use std::sync::{Arc, Mutex};
+
+/// Gives the fixture's bucket a tagged question, which has been answered and favorited by user2.
+fn fill_bucket(fixture: &UserBucketRelationFixture, db: &BoxedRepository) {
+    let new_question = NewQuestion {
+        bucket_uuid: fixture.bucket.uuid,
+        user_uuid: Some(fixture.user1.uuid),
+        question_text: "What's your favorite color?".to_string(),
+    };
+    let question = db
+        .create_question(new_question)
+        .expect("Should create question");
+    let new_tag = NewTag {
+        bucket_uuid: fixture.bucket.uuid,
+        tag_name: "icebreaker".to_string(),
+    };
+    let tag = db.get_or_create_tag(new_tag).expect("Should create tag");
+    let relation = NewQuestionTag {
+        question_uuid: question.uuid,
+        tag_uuid: tag.uuid,
+    };
+    db.tag_question(relation).expect("Should tag question");
+    let new_answer = NewAnswer {
+        user_uuid: Some(fixture.user2.uuid),
+        question_uuid: question.uuid,
+        publicly_visible: true,
+        answer_text: "Blue".to_string(),
+        session_uuid: None,
+    };
+    db.create_answer(new_answer).expect("Should create answer");
+    let favorite = NewFavoriteQuestionRelation {
+        user_uuid: fixture.user2.uuid,
+        question_uuid: question.uuid,
+    };
+    db.favorite_question(favorite)
+        .expect("Should favorite question");
+}
+
+#[test]
+fn export_includes_everything_in_the_bucket() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        fill_bucket(fixture, &db);
+
+        let archive = db
+            .export_bucket(fixture.bucket.uuid)
+            .expect("Should export bucket");
+        assert_eq!(archive.bucket, fixture.bucket);
+        assert_eq!(archive.members, vec![fixture.relation]);
+        assert_eq!(archive.users, {
+            let mut users = vec![fixture.user1.clone(), fixture.user2.clone()];
+            users.sort_by_key(|u| u.uuid);
+            users
+        });
+        assert_eq!(archive.tags.len(), 1);
+        assert_eq!(archive.questions.len(), 1);
+        assert_eq!(archive.question_tags.len(), 1);
+        assert_eq!(archive.answers.len(), 1);
+        assert_eq!(archive.favorites.len(), 1);
+    });
+}
+
+#[test]
+fn restore_remaps_uuids_and_keeps_timestamps() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        fill_bucket(fixture, &db);
+        let archive = db
+            .export_bucket(fixture.bucket.uuid)
+            .expect("Should export bucket");
+
+        let options = RestoreOptions {
+            bucket_slug: "restored".to_string(),
+            owner_uuid: Some(fixture.user2.uuid),
+            keep_users: true,
+        };
+        let bucket = db
+            .restore_bucket(archive.clone(), options)
+            .expect("Should restore bucket");
+        assert_ne!(bucket.uuid, fixture.bucket.uuid);
+        assert_eq!(bucket.bucket_slug, "restored");
+        assert_eq!(bucket.created_at, fixture.bucket.created_at);
+        assert_eq!(bucket.owner_uuid, Some(fixture.user2.uuid));
+
+        let restored = db
+            .export_bucket(bucket.uuid)
+            .expect("Should export restored bucket");
+        // The users already exist, so they are reused rather than created again.
+        assert_eq!(restored.users, archive.users);
+        assert_eq!(restored.members.len(), 2);
+        let question = &restored.questions[0];
+        assert_ne!(question.uuid, archive.questions[0].uuid);
+        assert_eq!(question.question_text, archive.questions[0].question_text);
+        assert_eq!(question.created_at, archive.questions[0].created_at);
+        assert_eq!(restored.tags[0].tag_name, "icebreaker");
+        assert_eq!(restored.question_tags[0].question_uuid, question.uuid);
+        assert_eq!(restored.question_tags[0].tag_uuid, restored.tags[0].uuid);
+        assert_eq!(restored.answers[0].question_uuid, question.uuid);
+        assert_eq!(restored.answers[0].user_uuid, Some(fixture.user2.uuid));
+        assert_eq!(restored.favorites[0].question_uuid, question.uuid);
+
+        // The original bucket is left alone.
+        let original = db
+            .export_bucket(fixture.bucket.uuid)
+            .expect("Should export bucket");
+        assert_eq!(original, archive);
+    });
+}
+
+#[test]
+fn restore_into_another_database_creates_users() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        fill_bucket(fixture, &db);
+        let archive = db
+            .export_bucket(fixture.bucket.uuid)
+            .expect("Should export bucket");
+
+        let other: BoxedRepository = Box::new(Arc::new(Mutex::new(FakeDatabase::default())));
+        let options = RestoreOptions {
+            bucket_slug: "slug".to_string(),
+            owner_uuid: None,
+            keep_users: true,
+        };
+        let bucket = other
+            .restore_bucket(archive.clone(), options)
+            .expect("Should restore bucket");
+
+        let restored = other
+            .export_bucket(bucket.uuid)
+            .expect("Should export restored bucket");
+        let google_ids = |users: &[User]| {
+            let mut ids: Vec<String> = users.iter().map(|u| u.google_user_id.clone()).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(google_ids(&restored.users), google_ids(&archive.users));
+        let user1 = other
+            .get_user_by_google_id(fixture.user1.google_user_id.clone())
+            .expect("Should create user");
+        assert_eq!(restored.members[0].user_uuid, user1.uuid);
+        assert_eq!(restored.questions[0].user_uuid, Some(user1.uuid));
+    });
+}
+
+#[test]
+fn restore_without_users_drops_them() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        fill_bucket(fixture, &db);
+        let archive = db
+            .export_bucket(fixture.bucket.uuid)
+            .expect("Should export bucket");
+
+        let other: BoxedRepository = Box::new(Arc::new(Mutex::new(FakeDatabase::default())));
+        let options = RestoreOptions {
+            bucket_slug: "slug".to_string(),
+            owner_uuid: None,
+            keep_users: false,
+        };
+        let bucket = other
+            .restore_bucket(archive, options)
+            .expect("Should restore bucket");
+        assert_eq!(bucket.owner_uuid, None);
+
+        let restored = other
+            .export_bucket(bucket.uuid)
+            .expect("Should export restored bucket");
+        assert!(restored.users.is_empty());
+        assert!(restored.members.is_empty());
+        assert!(restored.favorites.is_empty());
+        assert_eq!(restored.questions[0].user_uuid, None);
+        assert_eq!(restored.answers[0].user_uuid, None);
+        other
+            .get_user_by_google_id(fixture.user1.google_user_id.clone())
+            .expect_err("Should not create user");
+    });
+}
+
+#[test]
+fn restore_rejects_a_slug_in_use() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let archive = db
+            .export_bucket(fixture.bucket.uuid)
+            .expect("Should export bucket");
+        let options = RestoreOptions {
+            bucket_slug: fixture.bucket.bucket_slug.clone(),
+            owner_uuid: None,
+            keep_users: true,
+        };
+        db.restore_bucket(archive, options)
+            .expect_err("Should not restore over another bucket's slug");
+    });
+}
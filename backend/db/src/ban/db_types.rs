@@ -0,0 +1,50 @@
+//! Database types for bans.
+use crate::schema::bucket_ban;
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A ban that keeps a user from joining a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize)]
+#[primary_key(bucket_uuid, user_uuid)]
+#[table_name = "bucket_ban"]
+pub struct Ban {
+    /// The bucket the user is banned from.
+    pub bucket_uuid: Uuid,
+    /// The user who is banned.
+    pub user_uuid: Uuid,
+    /// The user who banned them.
+    pub banned_by_uuid: Option<Uuid>,
+    /// Why the user was banned.
+    pub reason: Option<String>,
+    /// When the ban stops applying, if ever.
+    pub expires_at: Option<NaiveDateTime>,
+    /// When the user was banned.
+    pub created_at: NaiveDateTime,
+}
+
+impl Ban {
+    /// Does the ban still keep the user from joining the bucket at the given time.
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        self.expires_at
+            .map(|expires_at| expires_at > now)
+            .unwrap_or(true)
+    }
+}
+
+/// Structure used to ban a user.
+#[derive(Clone, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "bucket_ban"]
+pub struct NewBan {
+    /// The bucket the user is banned from.
+    pub bucket_uuid: Uuid,
+    /// The user who is banned.
+    pub user_uuid: Uuid,
+    /// The user who banned them.
+    pub banned_by_uuid: Option<Uuid>,
+    /// Why the user was banned.
+    pub reason: Option<String>,
+    /// When the ban stops applying, if ever.
+    pub expires_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,89 @@
+//! Mock impl
+use crate::{
+    ban::{
+        db_types::{Ban, NewBan},
+        interface::BanRepository,
+    },
+    fake::{DummyDbErrorInfo, FakeDatabase},
+    page::{paginate, Page, Pagination, RecencySort},
+};
+use diesel::result::{DatabaseErrorKind, Error};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+impl BanRepository for Arc<Mutex<FakeDatabase>> {
+    fn ban_user(&self, new_ban: NewBan) -> Result<Ban, Error> {
+        let mut db = self.lock().unwrap();
+        if !db.buckets.iter().any(|b| b.uuid == new_ban.bucket_uuid)
+            || !db.users.iter().any(|u| u.uuid == new_ban.user_uuid)
+        {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::ForeignKeyViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+        let ban = Ban {
+            bucket_uuid: new_ban.bucket_uuid,
+            user_uuid: new_ban.user_uuid,
+            banned_by_uuid: new_ban.banned_by_uuid,
+            reason: new_ban.reason,
+            expires_at: new_ban.expires_at,
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+        db.user_bucket_relations
+            .retain(|r| !(r.bucket_uuid == ban.bucket_uuid && r.user_uuid == ban.user_uuid));
+        db.bans
+            .retain(|b| !(b.bucket_uuid == ban.bucket_uuid && b.user_uuid == ban.user_uuid));
+        db.bans.push(ban.clone());
+        Ok(ban)
+    }
+
+    fn get_ban(&self, bucket_uuid: Uuid, user_uuid: Uuid) -> Result<Ban, Error> {
+        let db = self.lock().unwrap();
+        db.bans
+            .iter()
+            .find(|b| b.bucket_uuid == bucket_uuid && b.user_uuid == user_uuid)
+            .cloned()
+            .ok_or_else(|| Error::NotFound)
+    }
+
+    fn get_bans_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Ban>, Error> {
+        let db = self.lock().unwrap();
+        let last = match pagination.after {
+            Some(after) => Some(
+                db.bans
+                    .iter()
+                    .find(|b| b.bucket_uuid == bucket_uuid && b.user_uuid == after)
+                    .ok_or(Error::NotFound)?,
+            ),
+            None => None,
+        };
+        let bans = db
+            .bans
+            .iter()
+            .filter(|b| b.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        let order = |a: &Ban, b: &Ban| match pagination.sort {
+            RecencySort::Newest => (b.created_at, b.user_uuid).cmp(&(a.created_at, a.user_uuid)),
+            RecencySort::Oldest => (a.created_at, a.user_uuid).cmp(&(b.created_at, b.user_uuid)),
+        };
+        Ok(paginate(bans, last, pagination.limit, order, |b| {
+            b.user_uuid
+        }))
+    }
+
+    fn lift_ban(&self, bucket_uuid: Uuid, user_uuid: Uuid) -> Result<Ban, Error> {
+        let mut db = self.lock().unwrap();
+        let index = db
+            .bans
+            .iter()
+            .position(|b| b.bucket_uuid == bucket_uuid && b.user_uuid == user_uuid)
+            .ok_or_else(|| Error::NotFound)?;
+        Ok(db.bans.remove(index))
+    }
+}
@@ -0,0 +1,30 @@
+//! Specification of functions.
+use crate::{
+    ban::db_types::{Ban, NewBan},
+    page::{Page, Pagination, RecencySort},
+};
+use diesel::QueryResult;
+use uuid::Uuid;
+
+/// Functions for specifically working with bans.
+pub trait BanRepository {
+    /// Bans a user from a bucket, replacing their previous ban from it if there was one.
+    ///
+    /// If the user is a member of the bucket, they are removed from it along with being banned.
+    fn ban_user(&self, new_ban: NewBan) -> QueryResult<Ban>;
+    /// Gets the user's ban from the bucket.
+    ///
+    /// The ban is returned even if it has expired.
+    fn get_ban(&self, bucket_uuid: Uuid, user_uuid: Uuid) -> QueryResult<Ban>;
+    /// Gets a page of the bucket's bans.
+    ///
+    /// Bans are identified by the uuid of the banned user, so that is what the page starts after.
+    /// If that user's ban has been lifted, this will return a `NotFound` error.
+    fn get_bans_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<Ban>>;
+    /// Lifts the user's ban from the bucket, so they can join it again.
+    fn lift_ban(&self, bucket_uuid: Uuid, user_uuid: Uuid) -> QueryResult<Ban>;
+}
@@ -0,0 +1,98 @@
+//! Implementation of the specified interfaces for PgConnection.
+use crate::{
+    ban::{
+        db_types::{Ban, NewBan},
+        interface::BanRepository,
+    },
+    page::{Page, Pagination, RecencySort},
+    schema::{bucket_ban, bucket_user_relation},
+    AsConnRef,
+};
+use diesel::{
+    dsl::now,
+    query_dsl::{QueryDsl, RunQueryDsl},
+    result::Error,
+    BoolExpressionMethods, Connection, ExpressionMethods,
+};
+use uuid::Uuid;
+
+impl<T> BanRepository for T
+where
+    T: AsConnRef,
+{
+    fn ban_user(&self, new_ban: NewBan) -> Result<Ban, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            let membership = bucket_user_relation::table.filter(
+                bucket_user_relation::user_uuid
+                    .eq(new_ban.user_uuid)
+                    .and(bucket_user_relation::bucket_uuid.eq(new_ban.bucket_uuid)),
+            );
+            diesel::delete(membership).execute(conn)?;
+
+            diesel::insert_into(bucket_ban::table)
+                .values(&new_ban)
+                .on_conflict((bucket_ban::bucket_uuid, bucket_ban::user_uuid))
+                .do_update()
+                .set((
+                    bucket_ban::banned_by_uuid.eq(new_ban.banned_by_uuid),
+                    bucket_ban::reason.eq(&new_ban.reason),
+                    bucket_ban::expires_at.eq(new_ban.expires_at),
+                    bucket_ban::created_at.eq(now),
+                ))
+                .get_result(conn)
+        })
+    }
+
+    fn get_ban(&self, bucket_uuid: Uuid, user_uuid: Uuid) -> Result<Ban, Error> {
+        bucket_ban::table
+            .find((bucket_uuid, user_uuid))
+            .get_result(self.as_conn())
+    }
+
+    fn get_bans_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Ban>, Error> {
+        let conn = self.as_conn();
+        let query = bucket_ban::table
+            .filter(bucket_ban::bucket_uuid.eq(bucket_uuid))
+            .into_boxed();
+        // Only the bans that sort after the last one of the previous page are on this page.
+        let query = match pagination.after {
+            Some(after) => {
+                let last: Ban = bucket_ban::table.find((bucket_uuid, after)).first(conn)?;
+                match pagination.sort {
+                    RecencySort::Newest => query.filter(
+                        bucket_ban::created_at
+                            .lt(last.created_at)
+                            .or(bucket_ban::created_at
+                                .eq(last.created_at)
+                                .and(bucket_ban::user_uuid.lt(last.user_uuid))),
+                    ),
+                    RecencySort::Oldest => query.filter(
+                        bucket_ban::created_at
+                            .gt(last.created_at)
+                            .or(bucket_ban::created_at
+                                .eq(last.created_at)
+                                .and(bucket_ban::user_uuid.gt(last.user_uuid))),
+                    ),
+                }
+            }
+            None => query,
+        };
+        let query = match pagination.sort {
+            RecencySort::Newest => {
+                query.order_by((bucket_ban::created_at.desc(), bucket_ban::user_uuid.desc()))
+            }
+            RecencySort::Oldest => query.order_by((bucket_ban::created_at, bucket_ban::user_uuid)),
+        };
+        let bans = query.limit(pagination.fetch_limit()).get_results(conn)?;
+        Ok(Page::from_fetched(bans, pagination.limit, |b| b.user_uuid))
+    }
+
+    fn lift_ban(&self, bucket_uuid: Uuid, user_uuid: Uuid) -> Result<Ban, Error> {
+        diesel::delete(bucket_ban::table.find((bucket_uuid, user_uuid))).get_result(self.as_conn())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    ban::db_types::NewBan,
+    page::{Pagination, RecencySort},
+    test::{
+        bucket_user_relation_fixture::{UserBucketRelationFixture, NO_PERMISSIONS},
+        util::execute_test,
+    },
+    BoxedRepository,
+};
+use chrono::{Duration, Utc};
+use diesel::result::Error;
+
+fn new_ban(fixture: &UserBucketRelationFixture) -> NewBan {
+    NewBan {
+        bucket_uuid: fixture.bucket.uuid,
+        user_uuid: fixture.user2.uuid,
+        banned_by_uuid: Some(fixture.user1.uuid),
+        reason: Some("Spamming questions".to_string()),
+        expires_at: None,
+    }
+}
+
+#[test]
+fn ban_and_lift() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let ban = db.ban_user(new_ban(fixture)).expect("Should ban user");
+        assert!(ban.is_active(Utc::now().naive_utc()));
+
+        let bans = db
+            .get_bans_for_bucket(
+                fixture.bucket.uuid,
+                Pagination::first(10, RecencySort::Newest),
+            )
+            .expect("Should get bans");
+        assert_eq!(bans.items, vec![ban.clone()]);
+
+        let lifted = db
+            .lift_ban(fixture.bucket.uuid, fixture.user2.uuid)
+            .expect("Should lift ban");
+        assert_eq!(lifted, ban);
+        let error = db
+            .get_ban(fixture.bucket.uuid, fixture.user2.uuid)
+            .unwrap_err();
+        assert_eq!(error, Error::NotFound);
+    });
+}
+
+#[test]
+fn banning_a_member_removes_them() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        fixture.add_user2(NO_PERMISSIONS, &db);
+        db.ban_user(new_ban(fixture)).expect("Should ban user");
+
+        let error = db
+            .get_user_bucket_relation(fixture.user2.uuid, fixture.bucket.uuid)
+            .unwrap_err();
+        assert_eq!(error, Error::NotFound);
+    });
+}
+
+#[test]
+fn banning_again_replaces_ban() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        db.ban_user(new_ban(fixture)).expect("Should ban user");
+        let new_ban = NewBan {
+            reason: None,
+            expires_at: Some(Utc::now().naive_utc() + Duration::days(1)),
+            ..new_ban(fixture)
+        };
+        db.ban_user(new_ban.clone()).expect("Should ban user again");
+
+        let bans = db
+            .get_bans_for_bucket(
+                fixture.bucket.uuid,
+                Pagination::first(10, RecencySort::Newest),
+            )
+            .expect("Should get bans")
+            .items;
+        assert_eq!(bans.len(), 1);
+        assert_eq!(bans[0].reason, None);
+        assert_eq!(bans[0].expires_at, new_ban.expires_at);
+    });
+}
+
+#[test]
+fn expired_ban_is_not_active() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let new_ban = NewBan {
+            expires_at: Some(Utc::now().naive_utc() - Duration::minutes(1)),
+            ..new_ban(fixture)
+        };
+        db.ban_user(new_ban).expect("Should ban user");
+        let ban = db
+            .get_ban(fixture.bucket.uuid, fixture.user2.uuid)
+            .expect("Should get ban");
+        assert!(!ban.is_active(Utc::now().naive_utc()));
+    });
+}
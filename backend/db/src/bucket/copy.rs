@@ -0,0 +1,86 @@
+//! Copying a bucket's questions into a new bucket.
+//!
+//! The copy is made one step at a time through the other repository functions,
+//! which both the Postgres and fake repositories then make all or nothing in their own way.
+use crate::{
+    bucket::db_types::{Bucket, BucketCopy, BucketFlagChangeset},
+    bucket_user_relation::db_types::NewBucketUserRelation,
+    tag::db_types::{NewQuestionTag, NewTag},
+    Repository,
+};
+use diesel::QueryResult;
+use std::collections::HashMap;
+
+/// Makes the copy, returning it as it is once every step is done.
+pub(crate) fn copy_bucket<R>(repo: &R, copy: BucketCopy) -> QueryResult<Bucket>
+where
+    R: Repository + ?Sized,
+{
+    let bucket = repo.create_bucket(copy.new_bucket)?;
+    let owner_relation = NewBucketUserRelation {
+        user_uuid: copy.owner_uuid,
+        bucket_uuid: bucket.uuid,
+        set_public_permission: true,
+        set_drawing_permission: true,
+        set_exclusive_permission: true,
+        kick_permission: true,
+        grant_permissions_permission: true,
+        moderate_questions_permission: true,
+        delete_questions_permission: true,
+        invite_permission: true,
+    };
+    repo.add_user_to_bucket(owner_relation)?;
+    repo.set_bucket_owner(bucket.uuid, copy.owner_uuid)?;
+
+    // The copies are the new owner's, as whoever asked the originals may not be in the new bucket.
+    let copies = repo.copy_questions(copy.source_uuid, bucket.uuid, Some(copy.owner_uuid))?;
+    if copy.copy_tags {
+        let mut tag_copies = HashMap::new();
+        for tag in repo.get_tags_in_bucket(copy.source_uuid)? {
+            let new_tag = NewTag {
+                bucket_uuid: bucket.uuid,
+                tag_name: tag.tag_name,
+            };
+            tag_copies.insert(tag.uuid, repo.get_or_create_tag(new_tag)?.uuid);
+        }
+        for (original_uuid, question) in &copies {
+            for tag in repo.get_tags_for_question(*original_uuid)? {
+                if let Some(tag_uuid) = tag_copies.get(&tag.uuid) {
+                    repo.tag_question(NewQuestionTag {
+                        question_uuid: question.uuid,
+                        tag_uuid: *tag_uuid,
+                    })?;
+                }
+            }
+        }
+    }
+
+    if copy.copy_members {
+        for (permissions, user) in repo.get_permissions_all_users_in_bucket(copy.source_uuid)? {
+            if user.uuid == copy.owner_uuid {
+                continue;
+            }
+            let new_relation = NewBucketUserRelation {
+                user_uuid: user.uuid,
+                bucket_uuid: bucket.uuid,
+                set_public_permission: permissions.set_public_permission,
+                set_drawing_permission: permissions.set_drawing_permission,
+                set_exclusive_permission: permissions.set_exclusive_permission,
+                kick_permission: permissions.kick_permission,
+                grant_permissions_permission: permissions.grant_permissions_permission,
+                moderate_questions_permission: permissions.moderate_questions_permission,
+                delete_questions_permission: permissions.delete_questions_permission,
+                invite_permission: permissions.invite_permission,
+            };
+            repo.add_user_to_bucket(new_relation)?;
+        }
+    }
+
+    if copy.is_template {
+        repo.set_bucket_template(bucket.uuid, true)?;
+    }
+    repo.change_bucket_flags(BucketFlagChangeset {
+        uuid: bucket.uuid,
+        ..copy.flags
+    })
+}
@@ -1,14 +1,23 @@
 //! Module for bucket related database interactions.
 
-use crate::schema::bucket;
+use crate::schema::{bucket, bucket_slug_redirect};
 use chrono::NaiveDateTime;
-use diesel::{Identifiable, Queryable};
+use diesel::{
+    deserialize::{self, FromSql},
+    pg::Pg,
+    serialize::{self, Output, ToSql},
+    sql_types::Text,
+    Identifiable, Queryable,
+};
 use serde::{Deserialize, Serialize};
+use std::io::Write;
 use uuid::Uuid;
 
 /// A struct representing a bucket.
 /// A bucket is a session associated with questions.
-#[derive(Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Insertable, Serialize, Deserialize,
+)]
 #[primary_key(uuid)]
 #[table_name = "bucket"]
 pub struct Bucket {
@@ -29,6 +38,18 @@ pub struct Bucket {
     pub updated_at: NaiveDateTime,
     /// When the row was created.
     pub created_at: NaiveDateTime,
+    /// How questions are picked when drawing from the bucket.
+    pub draw_strategy: DrawStrategy,
+    /// The user who owns the bucket.
+    /// They hold every permission, and can't be kicked.
+    pub owner_uuid: Option<Uuid>,
+    /// Is the bucket a template that new buckets are made from, rather than one that is played in.
+    pub is_template: bool,
+    /// What happens when a question is asked that is already in the bucket.
+    ///
+    /// Backups made before buckets had this setting are restored with the default.
+    #[serde(default)]
+    pub duplicate_policy: DuplicatePolicy,
 }
 
 /// Structure used to create new users.
@@ -54,4 +75,178 @@ pub struct BucketFlagChangeset {
     pub drawing_enabled: Option<bool>,
     /// Can an unjoined user join the bucket.
     pub exclusive: Option<bool>,
+    /// How questions are picked when drawing from the bucket.
+    pub draw_strategy: Option<DrawStrategy>,
+    /// What happens when a question is asked that is already in the bucket.
+    pub duplicate_policy: Option<DuplicatePolicy>,
+}
+
+/// A new bucket to make out of the questions in another bucket.
+#[derive(Clone, Debug)]
+pub struct BucketCopy {
+    /// The bucket the questions are copied from.
+    pub source_uuid: Uuid,
+    /// The name and slug of the copy.
+    pub new_bucket: NewBucket,
+    /// The user who owns the copy.
+    pub owner_uuid: Uuid,
+    /// Do the copied questions keep their tags.
+    pub copy_tags: bool,
+    /// Are the other members of the source bucket added to the copy, with the same permissions.
+    pub copy_members: bool,
+    /// Is the copy a template.
+    pub is_template: bool,
+    /// The flags to change on the copy.
+    /// The changeset's uuid is ignored in favor of the copy's.
+    pub flags: BucketFlagChangeset,
+}
+
+/// A changeset for the bucket's name, and optionally its slug.
+#[derive(Clone, AsChangeset, Identifiable, Debug, Serialize, Deserialize)]
+#[primary_key(uuid)]
+#[table_name = "bucket"]
+pub struct BucketNameChangeset {
+    /// Identifier of bucket
+    pub uuid: Uuid,
+    /// The name of the bucket that is shown to users.
+    pub bucket_name: String,
+    /// The new slug in the url for the bucket, if it should change.
+    pub bucket_slug: Option<String>,
+}
+
+/// A slug that a bucket had before it was changed.
+/// Getting a bucket by this slug still finds the bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize)]
+#[primary_key(slug)]
+#[table_name = "bucket_slug_redirect"]
+pub struct SlugRedirect {
+    /// The old slug.
+    pub slug: String,
+    /// The bucket the slug leads to.
+    pub bucket_uuid: Uuid,
+    /// When the bucket stopped using the slug.
+    pub created_at: NaiveDateTime,
+}
+
+/// The order in which lists of buckets are returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BucketSort {
+    /// Least recently updated first.
+    Updated,
+    /// Most recently created first.
+    Newest,
+    /// Alphabetically by name.
+    Name,
+}
+
+/// How questions are picked when drawing from a bucket.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    AsExpression,
+    FromSqlRow,
+    Serialize,
+    Deserialize,
+)]
+#[sql_type = "Text"]
+#[serde(rename_all = "snake_case")]
+pub enum DrawStrategy {
+    /// Every question has the same chance of being drawn.
+    #[default]
+    Uniform,
+    /// Questions with more votes are more likely to be drawn.
+    Upvotes,
+    /// Questions that have been in the bucket longer are more likely to be drawn.
+    OlderFirst,
+    /// Questions written by members who haven't had one of their questions drawn recently
+    /// are more likely to be drawn.
+    MembersNotRecentlyDrawn,
+}
+
+impl DrawStrategy {
+    /// The representation of the strategy within the database.
+    fn as_sql_str(self) -> &'static str {
+        match self {
+            DrawStrategy::Uniform => "uniform",
+            DrawStrategy::Upvotes => "upvotes",
+            DrawStrategy::OlderFirst => "older_first",
+            DrawStrategy::MembersNotRecentlyDrawn => "members_not_recently_drawn",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for DrawStrategy {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        ToSql::<Text, Pg>::to_sql(self.as_sql_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for DrawStrategy {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        let strategy: String = FromSql::<Text, Pg>::from_sql(bytes)?;
+        match strategy.as_str() {
+            "uniform" => Ok(DrawStrategy::Uniform),
+            "upvotes" => Ok(DrawStrategy::Upvotes),
+            "older_first" => Ok(DrawStrategy::OlderFirst),
+            "members_not_recently_drawn" => Ok(DrawStrategy::MembersNotRecentlyDrawn),
+            other => Err(format!("Unrecognized draw strategy: {}", other).into()),
+        }
+    }
+}
+
+/// What happens when a question is asked that is the same as, or nearly the same as,
+/// a question already in the bucket.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    AsExpression,
+    FromSqlRow,
+    Serialize,
+    Deserialize,
+)]
+#[sql_type = "Text"]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    /// The question is added, and the similar questions are returned alongside it.
+    #[default]
+    Warn,
+    /// The question is not added.
+    Reject,
+}
+
+impl DuplicatePolicy {
+    /// The representation of the policy within the database.
+    fn as_sql_str(self) -> &'static str {
+        match self {
+            DuplicatePolicy::Warn => "warn",
+            DuplicatePolicy::Reject => "reject",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for DuplicatePolicy {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
+        ToSql::<Text, Pg>::to_sql(self.as_sql_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for DuplicatePolicy {
+    fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
+        let policy: String = FromSql::<Text, Pg>::from_sql(bytes)?;
+        match policy.as_str() {
+            "warn" => Ok(DuplicatePolicy::Warn),
+            "reject" => Ok(DuplicatePolicy::Reject),
+            other => Err(format!("Unrecognized duplicate policy: {}", other).into()),
+        }
+    }
 }
@@ -1,10 +1,15 @@
 //! Mock impl
 use crate::{
     bucket::{
-        db_types::{Bucket, BucketFlagChangeset, NewBucket},
+        copy,
+        db_types::{
+            Bucket, BucketCopy, BucketFlagChangeset, BucketNameChangeset, BucketSort, DrawStrategy,
+            DuplicatePolicy, NewBucket, SlugRedirect,
+        },
         interface::BucketRepository,
     },
     fake::{DummyDbErrorInfo, FakeDatabase},
+    page::{paginate, Page, Pagination},
 };
 use diesel::result::{DatabaseErrorKind, Error};
 use std::sync::{Arc, Mutex};
@@ -23,6 +28,10 @@ impl BucketRepository for Arc<Mutex<FakeDatabase>> {
             exclusive: false,
             updated_at: chrono::Utc::now().naive_utc(),
             created_at: chrono::Utc::now().naive_utc(),
+            draw_strategy: DrawStrategy::Uniform,
+            owner_uuid: None,
+            is_template: false,
+            duplicate_policy: DuplicatePolicy::Warn,
         };
         if db.buckets.iter().find(|b| b.uuid == uuid).is_some() {
             return Err(Error::DatabaseError(
@@ -41,25 +50,98 @@ impl BucketRepository for Arc<Mutex<FakeDatabase>> {
             .iter()
             .position(|b| b.uuid == bucket_uuid)
             .ok_or_else(|| Error::NotFound)?;
+        // Mirrors the database, which cascades the delete to the rows that belong to the bucket,
+        // and on to the rows that belong to its questions, answers and sessions.
+        let question_uuids: Vec<Uuid> = db
+            .questions
+            .iter()
+            .filter(|q| q.bucket_uuid == bucket_uuid)
+            .map(|q| q.uuid)
+            .collect();
+        let answer_uuids: Vec<Uuid> = db
+            .answers
+            .iter()
+            .filter(|a| question_uuids.contains(&a.question_uuid))
+            .map(|a| a.uuid)
+            .collect();
+        let session_uuids: Vec<Uuid> = db
+            .sessions
+            .iter()
+            .filter(|s| s.bucket_uuid == bucket_uuid)
+            .map(|s| s.uuid)
+            .collect();
+        db.answer_edit_tokens
+            .retain(|t| !answer_uuids.contains(&t.answer_uuid));
+        db.answers
+            .retain(|a| !question_uuids.contains(&a.question_uuid));
+        db.question_revisions
+            .retain(|r| !question_uuids.contains(&r.question_uuid));
+        db.question_votes
+            .retain(|v| !question_uuids.contains(&v.question_uuid));
+        db.question_tags
+            .retain(|t| !question_uuids.contains(&t.question_uuid));
+        db.favorite_question_relations
+            .retain(|f| !question_uuids.contains(&f.question_uuid));
+        db.session_participants
+            .retain(|p| !session_uuids.contains(&p.session_uuid));
+        db.user_bucket_relations
+            .retain(|r| r.bucket_uuid != bucket_uuid);
+        db.permission_grants
+            .retain(|g| g.bucket_uuid != bucket_uuid);
+        db.questions.retain(|q| q.bucket_uuid != bucket_uuid);
+        db.question_draws.retain(|d| d.bucket_uuid != bucket_uuid);
+        db.turns.retain(|t| t.bucket_uuid != bucket_uuid);
+        db.presences.retain(|p| p.bucket_uuid != bucket_uuid);
+        db.invites.retain(|i| i.bucket_uuid != bucket_uuid);
+        db.join_codes.retain(|c| c.bucket_uuid != bucket_uuid);
+        db.audit_events.retain(|e| e.bucket_uuid != bucket_uuid);
+        db.bans.retain(|b| b.bucket_uuid != bucket_uuid);
+        db.slug_redirects.retain(|r| r.bucket_uuid != bucket_uuid);
+        db.sessions.retain(|s| s.bucket_uuid != bucket_uuid);
+        db.tags.retain(|t| t.bucket_uuid != bucket_uuid);
         Ok(db.buckets.remove(index))
     }
 
-    fn get_publicly_visible_buckets(&self) -> Result<Vec<Bucket>, Error> {
+    fn get_publicly_visible_buckets(
+        &self,
+        pagination: Pagination<BucketSort>,
+    ) -> Result<Page<Bucket>, Error> {
         let db = self.lock().unwrap();
         let visible = db
             .buckets
             .iter()
-            .filter(|b| b.public_viewable)
+            .filter(|b| b.public_viewable && !b.is_template)
             .cloned()
             .collect();
-        Ok(visible)
+        page_of_buckets(visible, &db.buckets, pagination)
+    }
+
+    fn get_public_templates(
+        &self,
+        pagination: Pagination<BucketSort>,
+    ) -> Result<Page<Bucket>, Error> {
+        let db = self.lock().unwrap();
+        let templates = db
+            .buckets
+            .iter()
+            .filter(|b| b.public_viewable && b.is_template)
+            .cloned()
+            .collect();
+        page_of_buckets(templates, &db.buckets, pagination)
     }
 
     fn get_bucket_by_slug(&self, slug: String) -> Result<Bucket, Error> {
         let db = self.lock().unwrap();
+        // The slug might be one the bucket had before.
+        let bucket_uuid = db
+            .slug_redirects
+            .iter()
+            .find(|r| r.slug == slug)
+            .map(|r| r.bucket_uuid);
         db.buckets
             .iter()
             .find(|b| b.bucket_slug == slug)
+            .or_else(|| db.buckets.iter().find(|b| Some(b.uuid) == bucket_uuid))
             .cloned()
             .ok_or_else(|| Error::NotFound)
     }
@@ -91,7 +173,142 @@ impl BucketRepository for Arc<Mutex<FakeDatabase>> {
         if let Some(private) = changeset.exclusive {
             bucket.exclusive = private;
         }
+        if let Some(draw_strategy) = changeset.draw_strategy {
+            bucket.draw_strategy = draw_strategy;
+        }
+        if let Some(duplicate_policy) = changeset.duplicate_policy {
+            bucket.duplicate_policy = duplicate_policy;
+        }
 
         Ok(bucket.clone())
     }
+
+    fn rename_bucket(&self, changeset: BucketNameChangeset) -> Result<Bucket, Error> {
+        let mut db = self.lock().unwrap();
+        let bucket = db
+            .buckets
+            .iter()
+            .find(|b| b.uuid == changeset.uuid)
+            .cloned()
+            .ok_or_else(|| Error::NotFound)?;
+        if let Some(new_slug) = &changeset.bucket_slug {
+            if new_slug != &bucket.bucket_slug {
+                if db
+                    .slug_redirects
+                    .iter()
+                    .any(|r| r.slug == bucket.bucket_slug)
+                {
+                    return Err(Error::DatabaseError(
+                        DatabaseErrorKind::UniqueViolation,
+                        Box::new(DummyDbErrorInfo::new()),
+                    ));
+                }
+                db.slug_redirects.push(SlugRedirect {
+                    slug: bucket.bucket_slug.clone(),
+                    bucket_uuid: bucket.uuid,
+                    created_at: chrono::Utc::now().naive_utc(),
+                });
+                // The bucket may be going back to a slug it had before.
+                db.slug_redirects
+                    .retain(|r| !(&r.slug == new_slug && r.bucket_uuid == bucket.uuid));
+            }
+        }
+
+        let bucket = db
+            .buckets
+            .iter_mut()
+            .find(|b| b.uuid == changeset.uuid)
+            .ok_or_else(|| Error::NotFound)?;
+        bucket.bucket_name = changeset.bucket_name;
+        if let Some(new_slug) = changeset.bucket_slug {
+            bucket.bucket_slug = new_slug;
+        }
+        bucket.updated_at = chrono::Utc::now().naive_utc();
+        Ok(bucket.clone())
+    }
+
+    fn set_bucket_owner(&self, bucket_uuid: Uuid, user_uuid: Uuid) -> Result<Bucket, Error> {
+        let mut db = self.lock().unwrap();
+        let relation = db
+            .user_bucket_relations
+            .iter_mut()
+            .find(|r| r.bucket_uuid == bucket_uuid && r.user_uuid == user_uuid)
+            .ok_or_else(|| Error::NotFound)?;
+        relation.set_public_permission = true;
+        relation.set_drawing_permission = true;
+        relation.set_exclusive_permission = true;
+        relation.kick_permission = true;
+        relation.grant_permissions_permission = true;
+        relation.moderate_questions_permission = true;
+        relation.delete_questions_permission = true;
+        relation.invite_permission = true;
+        relation.updated_at = chrono::Utc::now().naive_utc();
+
+        let bucket = db
+            .buckets
+            .iter_mut()
+            .find(|b| b.uuid == bucket_uuid)
+            .ok_or_else(|| Error::NotFound)?;
+        bucket.owner_uuid = Some(user_uuid);
+        bucket.updated_at = chrono::Utc::now().naive_utc();
+        Ok(bucket.clone())
+    }
+
+    fn set_bucket_template(&self, bucket_uuid: Uuid, is_template: bool) -> Result<Bucket, Error> {
+        let mut db = self.lock().unwrap();
+        let bucket = db
+            .buckets
+            .iter_mut()
+            .find(|b| b.uuid == bucket_uuid)
+            .ok_or_else(|| Error::NotFound)?;
+        bucket.is_template = is_template;
+        bucket.updated_at = chrono::Utc::now().naive_utc();
+        Ok(bucket.clone())
+    }
+
+    fn copy_bucket(&self, copy: BucketCopy) -> Result<Bucket, Error> {
+        // The lock is held for the whole copy, so no one else sees it half done.
+        // Each step takes a lock of its own, so they are made on the database moved out from behind it.
+        let mut db = self.lock().unwrap();
+        let buckets = db.buckets.len();
+        let relations = db.user_bucket_relations.len();
+        let questions = db.questions.len();
+        let tags = db.tags.len();
+        let question_tags = db.question_tags.len();
+        let working = Arc::new(Mutex::new(std::mem::take(&mut *db)));
+        let result = copy::copy_bucket(&working, copy);
+        *db = std::mem::take(&mut *working.lock().unwrap());
+        if result.is_err() {
+            // The steps only add rows to the end, so cutting them off undoes the copy.
+            db.buckets.truncate(buckets);
+            db.user_bucket_relations.truncate(relations);
+            db.questions.truncate(questions);
+            db.tags.truncate(tags);
+            db.question_tags.truncate(question_tags);
+        }
+        result
+    }
+}
+
+/// Gets the page of the given buckets.
+pub(crate) fn page_of_buckets(
+    buckets: Vec<Bucket>,
+    all_buckets: &[Bucket],
+    pagination: Pagination<BucketSort>,
+) -> Result<Page<Bucket>, Error> {
+    let last = match pagination.after {
+        Some(after) => Some(
+            all_buckets
+                .iter()
+                .find(|b| b.uuid == after)
+                .ok_or(Error::NotFound)?,
+        ),
+        None => None,
+    };
+    let order = |a: &Bucket, b: &Bucket| match pagination.sort {
+        BucketSort::Updated => (a.updated_at, a.uuid).cmp(&(b.updated_at, b.uuid)),
+        BucketSort::Newest => (b.created_at, b.uuid).cmp(&(a.created_at, a.uuid)),
+        BucketSort::Name => (&a.bucket_name, a.uuid).cmp(&(&b.bucket_name, b.uuid)),
+    };
+    Ok(paginate(buckets, last, pagination.limit, order, |b| b.uuid))
 }
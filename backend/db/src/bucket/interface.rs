@@ -3,7 +3,12 @@
 //! These traits should try to not include significant quantities of business logic.
 //! It should try to deal with only the types specified in db_types, and avoid wire types.
 
-use crate::bucket::db_types::{Bucket, BucketFlagChangeset, NewBucket};
+use crate::{
+    bucket::db_types::{
+        Bucket, BucketCopy, BucketFlagChangeset, BucketNameChangeset, BucketSort, NewBucket,
+    },
+    page::{Page, Pagination},
+};
 use diesel::QueryResult;
 use uuid::Uuid;
 
@@ -13,12 +18,36 @@ pub trait BucketRepository {
     fn create_bucket(&self, new_bucket: NewBucket) -> QueryResult<Bucket>;
     /// Delete a bucket.
     fn delete_bucket(&self, bucket_uuid: Uuid) -> QueryResult<Bucket>;
-    /// Gets all publicly visible buckets.
-    fn get_publicly_visible_buckets(&self) -> QueryResult<Vec<Bucket>>;
-    /// Gets the bucket via its slug.
+    /// Gets a page of the publicly visible buckets that aren't templates.
+    ///
+    /// If the bucket the page should start after doesn't exist anymore, this will return a `NotFound` error.
+    fn get_publicly_visible_buckets(
+        &self,
+        pagination: Pagination<BucketSort>,
+    ) -> QueryResult<Page<Bucket>>;
+    /// Gets a page of the publicly visible templates.
+    ///
+    /// If the template the page should start after doesn't exist anymore, this will return a `NotFound` error.
+    fn get_public_templates(&self, pagination: Pagination<BucketSort>)
+        -> QueryResult<Page<Bucket>>;
+    /// Gets the bucket via its slug, or via a slug it had before.
     fn get_bucket_by_slug(&self, slug: String) -> QueryResult<Bucket>;
     /// Gets the bucket via its uuid.
     fn get_bucket_by_uuid(&self, uuid: Uuid) -> QueryResult<Bucket>;
     /// Change the blags that govern the buckets behavior
     fn change_bucket_flags(&self, changeset: BucketFlagChangeset) -> QueryResult<Bucket>;
+    /// Renames the bucket, and changes its slug if a new one is given.
+    ///
+    /// The bucket's previous slug is kept as a redirect to it.
+    fn rename_bucket(&self, changeset: BucketNameChangeset) -> QueryResult<Bucket>;
+    /// Makes the user the owner of the bucket, giving them every permission.
+    ///
+    /// The user must already be in the bucket, otherwise this will return a `NotFound` error.
+    fn set_bucket_owner(&self, bucket_uuid: Uuid, user_uuid: Uuid) -> QueryResult<Bucket>;
+    /// Marks the bucket as a template, or as a bucket that is played in.
+    fn set_bucket_template(&self, bucket_uuid: Uuid, is_template: bool) -> QueryResult<Bucket>;
+    /// Makes a new bucket out of the questions in another bucket, either all of it or none of it.
+    ///
+    /// The owner of the copy is added to it with every permission.
+    fn copy_bucket(&self, copy: BucketCopy) -> QueryResult<Bucket>;
 }
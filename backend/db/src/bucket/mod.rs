@@ -1,4 +1,5 @@
 //! Module containing all structures and functions required for bucket related database functionality.
+pub(crate) mod copy;
 pub mod db_types;
 pub mod fake_impl;
 pub mod interface;
@@ -2,16 +2,23 @@
 
 use crate::{
     bucket::{
-        db_types::{Bucket, BucketFlagChangeset, NewBucket},
+        copy,
+        db_types::{
+            Bucket, BucketCopy, BucketFlagChangeset, BucketNameChangeset, BucketSort, NewBucket,
+        },
         interface::BucketRepository,
     },
-    schema::bucket,
+    bucket_user_relation::db_types::BucketUserRelation,
+    page::{Page, Pagination},
+    schema::{bucket, bucket_slug_redirect, bucket_user_relation},
     AsConnRef,
 };
 use diesel::{
+    dsl::now,
+    pg::Pg,
     query_dsl::{QueryDsl, RunQueryDsl},
     result::Error,
-    ExpressionMethods, SaveChangesDsl,
+    BoolExpressionMethods, Connection, ExpressionMethods, PgConnection, SaveChangesDsl,
 };
 //use log::info;
 use uuid::Uuid;
@@ -28,17 +35,38 @@ where
         crate::util::delete_row(bucket::table, bucket_uuid, self.as_conn())
     }
 
-    fn get_publicly_visible_buckets(&self) -> Result<Vec<Bucket>, Error> {
-        bucket::table
+    fn get_publicly_visible_buckets(
+        &self,
+        pagination: Pagination<BucketSort>,
+    ) -> Result<Page<Bucket>, Error> {
+        let query = bucket::table
             .filter(bucket::public_viewable.eq(true))
-            .order_by(bucket::updated_at)
-            .get_results(self.as_conn())
+            .filter(bucket::is_template.eq(false))
+            .into_boxed();
+        page_of_buckets(query, pagination, self.as_conn())
+    }
+
+    fn get_public_templates(&self, pagination: Pagination<BucketSort>) -> Result<Page<Bucket>, Error> {
+        let query = bucket::table
+            .filter(bucket::public_viewable.eq(true))
+            .filter(bucket::is_template.eq(true))
+            .into_boxed();
+        page_of_buckets(query, pagination, self.as_conn())
     }
 
     fn get_bucket_by_slug(&self, slug: String) -> Result<Bucket, Error> {
         bucket::table
-            .filter(&bucket::bucket_slug.eq(slug))
+            .filter(&bucket::bucket_slug.eq(&slug))
             .first(self.as_conn())
+            .or_else(|error: Error| match error {
+                // The slug might be one the bucket had before.
+                Error::NotFound => bucket_slug_redirect::table
+                    .inner_join(bucket::table)
+                    .filter(bucket_slug_redirect::slug.eq(&slug))
+                    .select(bucket::all_columns)
+                    .first(self.as_conn()),
+                other => Err(other),
+            })
     }
 
     fn get_bucket_by_uuid(&self, uuid: Uuid) -> Result<Bucket, Error> {
@@ -57,4 +85,113 @@ where
                 }
             })
     }
+
+    fn rename_bucket(&self, changeset: BucketNameChangeset) -> Result<Bucket, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            let bucket: Bucket = bucket::table.find(changeset.uuid).get_result(conn)?;
+            if let Some(new_slug) = &changeset.bucket_slug {
+                if new_slug != &bucket.bucket_slug {
+                    diesel::insert_into(bucket_slug_redirect::table)
+                        .values((
+                            bucket_slug_redirect::slug.eq(&bucket.bucket_slug),
+                            bucket_slug_redirect::bucket_uuid.eq(bucket.uuid),
+                        ))
+                        .execute(conn)?;
+                    // The bucket may be going back to a slug it had before.
+                    diesel::delete(
+                        bucket_slug_redirect::table.filter(
+                            bucket_slug_redirect::slug
+                                .eq(new_slug)
+                                .and(bucket_slug_redirect::bucket_uuid.eq(bucket.uuid)),
+                        ),
+                    )
+                    .execute(conn)?;
+                }
+            }
+            diesel::update(bucket::table.find(changeset.uuid))
+                .set((&changeset, bucket::updated_at.eq(now)))
+                .get_result(conn)
+        })
+    }
+
+    fn set_bucket_owner(&self, bucket_uuid: Uuid, user_uuid: Uuid) -> Result<Bucket, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            let target = bucket_user_relation::table.filter(
+                bucket_user_relation::user_uuid
+                    .eq(user_uuid)
+                    .and(bucket_user_relation::bucket_uuid.eq(bucket_uuid)),
+            );
+            // Fails with NotFound if the user isn't in the bucket.
+            diesel::update(target)
+                .set((
+                    bucket_user_relation::set_public_permission.eq(true),
+                    bucket_user_relation::set_drawing_permission.eq(true),
+                    bucket_user_relation::set_exclusive_permission.eq(true),
+                    bucket_user_relation::kick_permission.eq(true),
+                    bucket_user_relation::grant_permissions_permission.eq(true),
+                    bucket_user_relation::moderate_questions_permission.eq(true),
+                    bucket_user_relation::delete_questions_permission.eq(true),
+                    bucket_user_relation::invite_permission.eq(true),
+                    bucket_user_relation::updated_at.eq(now),
+                ))
+                .get_result::<BucketUserRelation>(conn)?;
+
+            diesel::update(bucket::table.find(bucket_uuid))
+                .set((bucket::owner_uuid.eq(Some(user_uuid)), bucket::updated_at.eq(now)))
+                .get_result(conn)
+        })
+    }
+
+    fn set_bucket_template(&self, bucket_uuid: Uuid, is_template: bool) -> Result<Bucket, Error> {
+        diesel::update(bucket::table.find(bucket_uuid))
+            .set((bucket::is_template.eq(is_template), bucket::updated_at.eq(now)))
+            .get_result(self.as_conn())
+    }
+
+    fn copy_bucket(&self, copy: BucketCopy) -> Result<Bucket, Error> {
+        self.as_conn().transaction(|| copy::copy_bucket(self, copy))
+    }
+}
+
+/// Gets the page of the buckets the query selects.
+pub(crate) fn page_of_buckets<'a>(
+    query: bucket::BoxedQuery<'a, Pg>,
+    pagination: Pagination<BucketSort>,
+    conn: &PgConnection,
+) -> Result<Page<Bucket>, Error> {
+    // Only the buckets that sort after the last one of the previous page are on this page.
+    let query = match pagination.after {
+        Some(after) => {
+            let last: Bucket = bucket::table.find(after).first(conn)?;
+            match pagination.sort {
+                BucketSort::Updated => query.filter(
+                    bucket::updated_at.gt(last.updated_at).or(bucket::updated_at
+                        .eq(last.updated_at)
+                        .and(bucket::uuid.gt(last.uuid))),
+                ),
+                BucketSort::Newest => query.filter(
+                    bucket::created_at.lt(last.created_at).or(bucket::created_at
+                        .eq(last.created_at)
+                        .and(bucket::uuid.lt(last.uuid))),
+                ),
+                BucketSort::Name => query.filter(
+                    bucket::bucket_name
+                        .gt(last.bucket_name.clone())
+                        .or(bucket::bucket_name
+                            .eq(last.bucket_name)
+                            .and(bucket::uuid.gt(last.uuid))),
+                ),
+            }
+        }
+        None => query,
+    };
+    let query = match pagination.sort {
+        BucketSort::Updated => query.order_by((bucket::updated_at, bucket::uuid)),
+        BucketSort::Newest => query.order_by((bucket::created_at.desc(), bucket::uuid.desc())),
+        BucketSort::Name => query.order_by((bucket::bucket_name, bucket::uuid)),
+    };
+    let buckets = query.limit(pagination.fetch_limit()).get_results(conn)?;
+    Ok(Page::from_fetched(buckets, pagination.limit, |b| b.uuid))
 }
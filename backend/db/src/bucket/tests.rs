@@ -1,6 +1,16 @@
 use crate::{
-    bucket::db_types::{BucketFlagChangeset, NewBucket},
-    test::{bucket_fixture::BucketFixture, empty_fixture::EmptyFixture},
+    bucket::db_types::{
+        BucketCopy, BucketFlagChangeset, BucketNameChangeset, BucketSort, DrawStrategy,
+        DuplicatePolicy, NewBucket,
+    },
+    page::{Pagination, RecencySort},
+    question::db_types::NewQuestionDraw,
+    test::{
+        answer_fixture::AnswerFixture,
+        bucket_fixture::BucketFixture,
+        bucket_user_relation_fixture::{UserBucketRelationFixture, NO_PERMISSIONS},
+        empty_fixture::EmptyFixture,
+    },
 };
 use diesel::result::Error;
 
@@ -76,6 +86,8 @@ mod bucket {
                 public_viewable: Some(true),
                 drawing_enabled: None,
                 exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: None,
             };
             let bucket = db
                 .change_bucket_flags(changeset)
@@ -98,6 +110,8 @@ mod bucket {
                 public_viewable: None,
                 drawing_enabled: None,
                 exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: None,
             };
             let bucket = db
                 .change_bucket_flags(changeset)
@@ -106,6 +120,44 @@ mod bucket {
         });
     }
 
+    #[test]
+    fn change_draw_strategy() {
+        execute_test(|fixture: &BucketFixture, db: BoxedRepository| {
+            assert_eq!(fixture.bucket.draw_strategy, DrawStrategy::Uniform);
+            let changeset = BucketFlagChangeset {
+                uuid: fixture.bucket.uuid,
+                public_viewable: None,
+                drawing_enabled: None,
+                exclusive: None,
+                draw_strategy: Some(DrawStrategy::OlderFirst),
+                duplicate_policy: None,
+            };
+            let bucket = db
+                .change_bucket_flags(changeset)
+                .expect("Should be able to change draw strategy");
+            assert_eq!(bucket.draw_strategy, DrawStrategy::OlderFirst);
+        });
+    }
+
+    #[test]
+    fn change_duplicate_policy() {
+        execute_test(|fixture: &BucketFixture, db: BoxedRepository| {
+            assert_eq!(fixture.bucket.duplicate_policy, DuplicatePolicy::Warn);
+            let changeset = BucketFlagChangeset {
+                uuid: fixture.bucket.uuid,
+                public_viewable: None,
+                drawing_enabled: None,
+                exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: Some(DuplicatePolicy::Reject),
+            };
+            let bucket = db
+                .change_bucket_flags(changeset)
+                .expect("Should be able to change duplicate policy");
+            assert_eq!(bucket.duplicate_policy, DuplicatePolicy::Reject);
+        });
+    }
+
     #[test]
     fn get_visible_buckets() {
         execute_test(|fixture: &BucketFixture, db: BoxedRepository| {
@@ -114,15 +166,259 @@ mod bucket {
                 public_viewable: Some(true),
                 drawing_enabled: None,
                 exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: None,
             };
             let _bucket = db
                 .change_bucket_flags(changeset)
                 .expect("Should be able to change visibility");
 
             let visible_buckets = db
-                .get_publicly_visible_buckets()
+                .get_publicly_visible_buckets(Pagination::first(10, BucketSort::Updated))
+                .expect("Should find public buckets");
+            assert!(visible_buckets.items.contains(&fixture.bucket))
+        });
+    }
+
+    #[test]
+    fn page_through_visible_buckets() {
+        execute_test(|_fixture: &EmptyFixture, db: BoxedRepository| {
+            for name in &["e", "a", "d", "b", "c"] {
+                let new_bucket = NewBucket {
+                    bucket_name: name.to_string(),
+                    bucket_slug: name.to_string(),
+                };
+                db.create_bucket(new_bucket)
+                    .expect("Bucket should be created");
+            }
+
+            let mut pagination = Some(Pagination::first(2, BucketSort::Name));
+            let mut pages = vec![];
+            while let Some(next) = pagination {
+                let page = db
+                    .get_publicly_visible_buckets(next)
+                    .expect("Should find public buckets");
+                pagination = next.next(&page);
+                pages.push(
+                    page.items
+                        .into_iter()
+                        .map(|b| b.bucket_name)
+                        .collect::<Vec<_>>(),
+                );
+            }
+            assert_eq!(pages, vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]]);
+        });
+    }
+
+    #[test]
+    fn page_after_missing_bucket() {
+        execute_test(|fixture: &BucketFixture, db: BoxedRepository| {
+            let pagination = Pagination {
+                after: Some(fixture.bucket.uuid),
+                limit: 10,
+                sort: BucketSort::Newest,
+            };
+            db.delete_bucket(fixture.bucket.uuid)
+                .expect("Should delete bucket");
+            assert_eq!(
+                db.get_publicly_visible_buckets(pagination),
+                Err(Error::NotFound)
+            );
+        });
+    }
+
+    #[test]
+    fn templates_are_listed_apart_from_buckets() {
+        execute_test(|fixture: &BucketFixture, db: BoxedRepository| {
+            let template = db
+                .set_bucket_template(fixture.bucket.uuid, true)
+                .expect("Should make the bucket a template");
+            assert!(template.is_template);
+
+            let visible_buckets = db
+                .get_publicly_visible_buckets(Pagination::first(10, BucketSort::Updated))
                 .expect("Should find public buckets");
-            assert!(visible_buckets.contains(&fixture.bucket))
+            assert!(visible_buckets
+                .items
+                .iter()
+                .all(|b| b.uuid != template.uuid));
+            let templates = db
+                .get_public_templates(Pagination::first(10, BucketSort::Name))
+                .expect("Should find public templates");
+            assert_eq!(templates.items, vec![template]);
+        });
+    }
+
+    #[test]
+    fn set_bucket_owner_grants_every_permission() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            fixture.add_user2(NO_PERMISSIONS, &db);
+
+            let bucket = db
+                .set_bucket_owner(fixture.bucket.uuid, fixture.user2.uuid)
+                .expect("Should set owner");
+            assert_eq!(bucket.owner_uuid, Some(fixture.user2.uuid));
+
+            let permissions = db
+                .get_permissions(fixture.user2.uuid, fixture.bucket.uuid)
+                .expect("Should get permissions");
+            assert!(permissions.grant_permissions_permission);
+            assert!(permissions.kick_permission);
+            assert!(permissions.invite_permission);
+        });
+    }
+
+    #[test]
+    fn set_bucket_owner_requires_membership() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            assert_eq!(
+                db.set_bucket_owner(fixture.bucket.uuid, fixture.user2.uuid),
+                Err(Error::NotFound)
+            );
+            let bucket = db
+                .get_bucket_by_uuid(fixture.bucket.uuid)
+                .expect("Should get bucket");
+            assert_eq!(bucket.owner_uuid, None);
+        });
+    }
+
+    #[test]
+    fn rename_bucket_keeps_old_slug_as_redirect() {
+        execute_test(|fixture: &BucketFixture, db: BoxedRepository| {
+            let changeset = BucketNameChangeset {
+                uuid: fixture.bucket.uuid,
+                bucket_name: "Renamed".to_string(),
+                bucket_slug: Some("renamed".to_string()),
+            };
+            let bucket = db.rename_bucket(changeset).expect("Should rename bucket");
+            assert_eq!(bucket.bucket_name, "Renamed");
+            assert_eq!(bucket.bucket_slug, "renamed");
+
+            let by_new_slug = db
+                .get_bucket_by_slug("renamed".to_string())
+                .expect("Should get bucket by new slug");
+            let by_old_slug = db
+                .get_bucket_by_slug(fixture.bucket.bucket_slug.clone())
+                .expect("Should get bucket by old slug");
+            assert_eq!(by_new_slug, bucket);
+            assert_eq!(by_old_slug, bucket);
+
+            // Going back to the old slug works, and keeps the other one as a redirect.
+            let changeset = BucketNameChangeset {
+                uuid: fixture.bucket.uuid,
+                bucket_name: "Renamed".to_string(),
+                bucket_slug: Some(fixture.bucket.bucket_slug.clone()),
+            };
+            let bucket = db.rename_bucket(changeset).expect("Should rename bucket");
+            assert_eq!(bucket.bucket_slug, fixture.bucket.bucket_slug);
+            let by_redirect = db
+                .get_bucket_by_slug("renamed".to_string())
+                .expect("Should get bucket by redirect");
+            assert_eq!(by_redirect.uuid, fixture.bucket.uuid);
+        });
+    }
+
+    #[test]
+    fn rename_bucket_without_slug_keeps_slug() {
+        execute_test(|fixture: &BucketFixture, db: BoxedRepository| {
+            let changeset = BucketNameChangeset {
+                uuid: fixture.bucket.uuid,
+                bucket_name: "Renamed".to_string(),
+                bucket_slug: None,
+            };
+            let bucket = db.rename_bucket(changeset).expect("Should rename bucket");
+            assert_eq!(bucket.bucket_name, "Renamed");
+            assert_eq!(bucket.bucket_slug, fixture.bucket.bucket_slug);
+        });
+    }
+
+    #[test]
+    fn delete_bucket_removes_members() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            db.delete_bucket(fixture.bucket.uuid)
+                .expect("Should delete bucket");
+            assert_eq!(
+                db.get_bucket_by_uuid(fixture.bucket.uuid),
+                Err(Error::NotFound)
+            );
+            let buckets = db
+                .get_buckets_user_is_a_part_of(
+                    fixture.user1.uuid,
+                    Pagination::first(10, BucketSort::Updated),
+                )
+                .expect("Should get buckets");
+            assert!(buckets.items.is_empty());
+            assert_eq!(
+                db.get_user_bucket_relation(fixture.user1.uuid, fixture.bucket.uuid),
+                Err(Error::NotFound)
+            );
+        });
+    }
+
+    #[test]
+    fn delete_bucket_removes_answers_and_draws() {
+        execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+            let draw = NewQuestionDraw {
+                question_uuid: fixture.question.uuid,
+                bucket_uuid: fixture.bucket.uuid,
+                user_uuid: Some(fixture.user.uuid),
+                session_uuid: None,
+            };
+            db.record_draw(draw).expect("Should record draw");
+
+            db.delete_bucket(fixture.bucket.uuid)
+                .expect("Should delete bucket");
+            let answers = db
+                .get_answers_for_question(fixture.question.uuid, false)
+                .expect("Should get answers");
+            assert!(answers.is_empty());
+            let draws = db
+                .get_recent_draws(
+                    fixture.bucket.uuid,
+                    Pagination::first(10, RecencySort::Newest),
+                )
+                .expect("Should get draws");
+            assert!(draws.items.is_empty());
+        });
+    }
+
+    #[test]
+    fn copy_bucket() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            let copy = BucketCopy {
+                source_uuid: fixture.bucket.uuid,
+                new_bucket: NewBucket {
+                    bucket_name: "Copy".to_string(),
+                    bucket_slug: "copy".to_string(),
+                },
+                owner_uuid: fixture.user2.uuid,
+                copy_tags: true,
+                copy_members: true,
+                is_template: true,
+                flags: BucketFlagChangeset {
+                    uuid: fixture.bucket.uuid,
+                    public_viewable: Some(false),
+                    drawing_enabled: None,
+                    exclusive: None,
+                    draw_strategy: Some(DrawStrategy::Upvotes),
+                    duplicate_policy: None,
+                },
+            };
+            let bucket = db.copy_bucket(copy).expect("Should copy bucket");
+            assert_ne!(bucket.uuid, fixture.bucket.uuid);
+            assert_eq!(bucket.owner_uuid, Some(fixture.user2.uuid));
+            assert!(bucket.is_template);
+            assert!(!bucket.public_viewable);
+            assert_eq!(bucket.draw_strategy, DrawStrategy::Upvotes);
+            let permissions = db
+                .get_permissions(fixture.user1.uuid, bucket.uuid)
+                .expect("The other member should be copied");
+            assert!(permissions.invite_permission);
+            let source = db
+                .get_bucket_by_uuid(fixture.bucket.uuid)
+                .expect("Should get source");
+            assert!(source.public_viewable);
+            assert!(!source.is_template);
         });
     }
 }
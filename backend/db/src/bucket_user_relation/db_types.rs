@@ -1,5 +1,5 @@
 //! Database types for relations between buckets and users.
-use crate::schema::bucket_user_relation;
+use crate::schema::{bucket_permission_grant, bucket_user_relation};
 use chrono::NaiveDateTime;
 use diesel::{Identifiable, Queryable};
 use serde::{Deserialize, Serialize};
@@ -8,7 +8,16 @@ use uuid::Uuid;
 /// A relation between users and buckets.
 /// It also contains permissions for what users can do to the bucket.
 #[derive(
-    Clone, Copy, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    PartialOrd,
+    Identifiable,
+    Queryable,
+    Insertable,
+    Serialize,
+    Deserialize,
 )]
 #[primary_key(user_uuid, bucket_uuid)]
 #[table_name = "bucket_user_relation"]
@@ -31,6 +40,28 @@ pub struct BucketUserRelation {
     pub updated_at: NaiveDateTime,
     /// When the row was created.
     pub created_at: NaiveDateTime,
+    /// Can the user edit any of the bucket's questions.
+    pub moderate_questions_permission: bool,
+    /// Can the user delete any of the bucket's questions.
+    pub delete_questions_permission: bool,
+    /// Can the user invite others to the bucket.
+    pub invite_permission: bool,
+}
+
+impl BucketUserRelation {
+    /// The permissions the user has in the bucket.
+    pub fn permissions(&self) -> BucketUserPermissions {
+        BucketUserPermissions {
+            set_public_permission: self.set_public_permission,
+            set_drawing_permission: self.set_drawing_permission,
+            set_exclusive_permission: self.set_exclusive_permission,
+            kick_permission: self.kick_permission,
+            grant_permissions_permission: self.grant_permissions_permission,
+            moderate_questions_permission: self.moderate_questions_permission,
+            delete_questions_permission: self.delete_questions_permission,
+            invite_permission: self.invite_permission,
+        }
+    }
 }
 
 /// Structure used to create new join relations between users and buckets.
@@ -51,6 +82,12 @@ pub struct NewBucketUserRelation {
     pub kick_permission: bool,
     /// Can the user grant permissions to other users.
     pub grant_permissions_permission: bool,
+    /// Can the user edit any of the bucket's questions.
+    pub moderate_questions_permission: bool,
+    /// Can the user delete any of the bucket's questions.
+    pub delete_questions_permission: bool,
+    /// Can the user invite others to the bucket.
+    pub invite_permission: bool,
 }
 /// Structure used to create new join relations between users and buckets.
 #[derive(Clone, Copy, AsChangeset, Identifiable, Debug, Serialize, Deserialize)]
@@ -71,6 +108,12 @@ pub struct BucketUserPermissionsChangeset {
     pub kick_permission: Option<bool>,
     /// Can the user grant permissions to other users.
     pub grant_permissions_permission: Option<bool>,
+    /// Can the user edit any of the bucket's questions.
+    pub moderate_questions_permission: Option<bool>,
+    /// Can the user delete any of the bucket's questions.
+    pub delete_questions_permission: Option<bool>,
+    /// Can the user invite others to the bucket.
+    pub invite_permission: Option<bool>,
 }
 
 
@@ -83,9 +126,93 @@ pub struct BucketUserPermissions {
     pub set_drawing_permission: bool,
     /// Can the user make the bucket private
     pub set_exclusive_permission: bool,
-    /// Can the user grant permissions to other users.
-    pub grant_permissions_permission: bool,
     /// Can the user kick other users.
     pub kick_permission: bool,
+    /// Can the user grant permissions to other users.
+    pub grant_permissions_permission: bool,
+    /// Can the user edit any of the bucket's questions.
+    pub moderate_questions_permission: bool,
+    /// Can the user delete any of the bucket's questions.
+    pub delete_questions_permission: bool,
+    /// Can the user invite others to the bucket.
+    pub invite_permission: bool,
+}
+
+impl BucketUserPermissions {
+    /// Does this set of permissions hold every permission that the other one does.
+    pub fn includes(&self, other: &BucketUserPermissions) -> bool {
+        (self.set_public_permission || !other.set_public_permission)
+            && (self.set_drawing_permission || !other.set_drawing_permission)
+            && (self.set_exclusive_permission || !other.set_exclusive_permission)
+            && (self.kick_permission || !other.kick_permission)
+            && (self.grant_permissions_permission || !other.grant_permissions_permission)
+            && (self.moderate_questions_permission || !other.moderate_questions_permission)
+            && (self.delete_questions_permission || !other.delete_questions_permission)
+            && (self.invite_permission || !other.invite_permission)
+    }
+
+    /// The permissions that differ between this set and a newer one.
+    /// Each is named after its column, and paired with whether it was granted or revoked.
+    pub fn changes_to(&self, new: &BucketUserPermissions) -> Vec<(&'static str, bool)> {
+        vec![
+            ("set_public_permission", self.set_public_permission, new.set_public_permission),
+            ("set_drawing_permission", self.set_drawing_permission, new.set_drawing_permission),
+            ("set_exclusive_permission", self.set_exclusive_permission, new.set_exclusive_permission),
+            ("kick_permission", self.kick_permission, new.kick_permission),
+            ("grant_permissions_permission", self.grant_permissions_permission, new.grant_permissions_permission),
+            ("moderate_questions_permission", self.moderate_questions_permission, new.moderate_questions_permission),
+            ("delete_questions_permission", self.delete_questions_permission, new.delete_questions_permission),
+            ("invite_permission", self.invite_permission, new.invite_permission),
+        ]
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(permission, _, new)| (permission, new))
+        .collect()
+    }
 }
 
+/// A record of a permission being granted to, or revoked from, a member of a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize)]
+#[primary_key(uuid)]
+#[table_name = "bucket_permission_grant"]
+pub struct PermissionGrant {
+    /// The unique identifier of the grant.
+    pub uuid: Uuid,
+    /// The bucket the permission applies to.
+    pub bucket_uuid: Uuid,
+    /// The user who changed the permission.
+    pub granter_uuid: Option<Uuid>,
+    /// The user whose permission was changed.
+    pub target_uuid: Uuid,
+    /// The name of the permission's column.
+    pub permission: String,
+    /// True if the permission was granted, false if it was revoked.
+    pub granted: bool,
+    /// When the permission was changed.
+    pub created_at: NaiveDateTime,
+}
+
+/// Structure used to record a permission grant.
+#[derive(Clone, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "bucket_permission_grant"]
+pub struct NewPermissionGrant {
+    /// The bucket the permission applies to.
+    pub bucket_uuid: Uuid,
+    /// The user who changed the permission.
+    pub granter_uuid: Option<Uuid>,
+    /// The user whose permission was changed.
+    pub target_uuid: Uuid,
+    /// The name of the permission's column.
+    pub permission: String,
+    /// True if the permission was granted, false if it was revoked.
+    pub granted: bool,
+}
+
+/// The order in which lists of a bucket's members are returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemberSort {
+    /// In the order they joined the bucket.
+    Joined,
+    /// Alphabetically by name, with members who don't have a name first.
+    Name,
+}
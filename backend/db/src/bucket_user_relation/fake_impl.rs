@@ -1,16 +1,18 @@
 //! Mock impl
 use crate::{
-    bucket::db_types::Bucket,
+    bucket::{db_types::{Bucket, BucketSort}, fake_impl::page_of_buckets},
     bucket_user_relation::{
         db_types::{
             BucketUserPermissions, BucketUserPermissionsChangeset, BucketUserRelation,
-            NewBucketUserRelation,
+            MemberSort, NewBucketUserRelation, NewPermissionGrant, PermissionGrant,
         },
         interface::BucketUserRelationRepository,
     },
     fake::{DummyDbErrorInfo, FakeDatabase},
+    page::{paginate, Page, Pagination},
     user::db_types::User,
 };
+use chrono::NaiveDateTime;
 use diesel::result::{DatabaseErrorKind, Error};
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
@@ -29,6 +31,9 @@ impl BucketUserRelationRepository for Arc<Mutex<FakeDatabase>> {
             set_exclusive_permission: relation.set_exclusive_permission,
             kick_permission: relation.kick_permission,
             grant_permissions_permission: relation.grant_permissions_permission,
+            moderate_questions_permission: relation.moderate_questions_permission,
+            delete_questions_permission: relation.delete_questions_permission,
+            invite_permission: relation.invite_permission,
             updated_at: chrono::Utc::now().naive_utc(),
             created_at: chrono::Utc::now().naive_utc(),
         };
@@ -98,9 +103,21 @@ impl BucketUserRelationRepository for Arc<Mutex<FakeDatabase>> {
         if let Some(exclusive) = permissions_changeset.set_exclusive_permission {
             relation.set_exclusive_permission = exclusive;
         }
+        if let Some(kick) = permissions_changeset.kick_permission {
+            relation.kick_permission = kick;
+        }
         if let Some(admin) = permissions_changeset.grant_permissions_permission {
             relation.grant_permissions_permission = admin
         }
+        if let Some(moderate) = permissions_changeset.moderate_questions_permission {
+            relation.moderate_questions_permission = moderate;
+        }
+        if let Some(delete) = permissions_changeset.delete_questions_permission {
+            relation.delete_questions_permission = delete;
+        }
+        if let Some(invite) = permissions_changeset.invite_permission {
+            relation.invite_permission = invite;
+        }
 
         Ok(relation.clone())
     }
@@ -116,11 +133,18 @@ impl BucketUserRelationRepository for Arc<Mutex<FakeDatabase>> {
                 set_drawing_permission: r.set_drawing_permission,
                 set_exclusive_permission: r.set_exclusive_permission,
                 grant_permissions_permission: r.grant_permissions_permission,
+                moderate_questions_permission: r.moderate_questions_permission,
+                delete_questions_permission: r.delete_questions_permission,
+                invite_permission: r.invite_permission,
                 kick_permission: r.kick_permission,
             })
     }
 
-    fn get_buckets_user_is_a_part_of(&self, user_uuid: Uuid) -> Result<Vec<Bucket>, Error> {
+    fn get_buckets_user_is_a_part_of(
+        &self,
+        user_uuid: Uuid,
+        pagination: Pagination<BucketSort>,
+    ) -> Result<Page<Bucket>, Error> {
         let db = self.lock().unwrap();
         let bucket_uuids: Vec<Uuid> = db
             .user_bucket_relations
@@ -134,33 +158,54 @@ impl BucketUserRelationRepository for Arc<Mutex<FakeDatabase>> {
             .filter(|b| bucket_uuids.iter().any(|uuid| &b.uuid == uuid))
             .cloned()
             .collect();
-        Ok(buckets)
+        page_of_buckets(buckets, &db.buckets, pagination)
     }
 
-    fn get_users_in_bucket(&self, bucket_uuid: Uuid) -> Result<Vec<User>, Error> {
+    fn get_users_in_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<MemberSort>,
+    ) -> Result<Page<User>, Error> {
         let db = self.lock().unwrap();
-        let user_uuids: Vec<Uuid> = db
-            .user_bucket_relations
-            .iter()
-            .filter(|r| r.bucket_uuid == bucket_uuid)
-            .map(|r| r.user_uuid)
-            .collect();
-        let users = db
-            .users
-            .iter()
-            .filter(|b| user_uuids.iter().any(|uuid| &b.uuid == uuid))
-            .cloned()
-            .collect();
-
-        Ok(users)
+        page_of_members(&db, bucket_uuid, None, pagination)
     }
 
-    fn get_permissions_all_users_in_bucket(&self, bucket_uuid: Uuid) -> Result<Vec<(BucketUserPermissions, User)>, Error> {
+    fn get_permissions_of_users_in_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<MemberSort>,
+    ) -> Result<Page<(BucketUserPermissions, User)>, Error> {
         let db = self.lock().unwrap();
-        let users = self.get_users_in_bucket(bucket_uuid)?;
+        let page = page_of_members(&db, bucket_uuid, None, pagination)?;
+        let permissions = page
+            .items
+            .iter()
+            .map(|user| {
+                db.user_bucket_relations
+                    .iter()
+                    .find(|r| r.bucket_uuid == bucket_uuid && r.user_uuid == user.uuid)
+                    .map(BucketUserRelation::permissions)
+                    .ok_or(Error::NotFound)
+            })
+            .collect::<Result<Vec<BucketUserPermissions>, Error>>()?;
+        Ok(Page {
+            items: permissions.into_iter().zip(page.items).collect(),
+            next_cursor: page.next_cursor,
+        })
+    }
 
-        // unlock
-        drop(db);
+    fn get_permissions_all_users_in_bucket(&self, bucket_uuid: Uuid) -> Result<Vec<(BucketUserPermissions, User)>, Error> {
+        // The lock is taken by each of these calls, so it can't be held here.
+        let users: Vec<User> = {
+            let db = self.lock().unwrap();
+            // Relations are kept in the order they were created, which matches the ordering of the real database.
+            db.user_bucket_relations
+                .iter()
+                .filter(|r| r.bucket_uuid == bucket_uuid)
+                .filter_map(|r| db.users.iter().find(|u| u.uuid == r.user_uuid))
+                .cloned()
+                .collect()
+        };
 
         let permissions: Vec<BucketUserPermissions> = users
             .iter()
@@ -170,7 +215,110 @@ impl BucketUserRelationRepository for Arc<Mutex<FakeDatabase>> {
             .collect::<Result<Vec<BucketUserPermissions>, Error>>()?;
 
         Ok(permissions.into_iter().zip(users).collect())
+    }
 
+    fn record_permission_grants(
+        &self,
+        grants: Vec<NewPermissionGrant>,
+    ) -> Result<Vec<PermissionGrant>, Error> {
+        let mut db = self.lock().unwrap();
+        let created_at = chrono::Utc::now().naive_utc();
+        let grants: Vec<PermissionGrant> = grants
+            .into_iter()
+            .map(|grant| PermissionGrant {
+                uuid: Uuid::new_v4(),
+                bucket_uuid: grant.bucket_uuid,
+                granter_uuid: grant.granter_uuid,
+                target_uuid: grant.target_uuid,
+                permission: grant.permission,
+                granted: grant.granted,
+                created_at,
+            })
+            .collect();
+        db.permission_grants.extend(grants.iter().cloned());
+        Ok(grants)
+    }
 
+    fn get_permission_grants(&self, bucket_uuid: Uuid) -> Result<Vec<PermissionGrant>, Error> {
+        let db = self.lock().unwrap();
+        // Grants are pushed in the order they were recorded, so reversing puts the most recent first.
+        let grants = db
+            .permission_grants
+            .iter()
+            .rev()
+            .filter(|g| g.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        Ok(grants)
     }
 }
+
+/// Gets a page of the members of the bucket.
+///
+/// If a time is given, only the members that have been seen in the bucket since then are on the page.
+pub(crate) fn page_of_members(
+    db: &FakeDatabase,
+    bucket_uuid: Uuid,
+    present_since: Option<NaiveDateTime>,
+    pagination: Pagination<MemberSort>,
+) -> Result<Page<User>, Error> {
+    let members: Vec<(NaiveDateTime, User)> = db
+        .user_bucket_relations
+        .iter()
+        .filter(|r| r.bucket_uuid == bucket_uuid)
+        // Users that left the bucket may still have a recent heartbeat,
+        // so only the presence of current members counts.
+        .filter(|r| {
+            present_since.map_or(true, |present_since| {
+                db.presences.iter().any(|p| {
+                    p.bucket_uuid == bucket_uuid
+                        && p.user_uuid == r.user_uuid
+                        && p.last_seen_at >= present_since
+                })
+            })
+        })
+        .filter_map(|r| {
+            let user = db.users.iter().find(|u| u.uuid == r.user_uuid)?;
+            Some((r.created_at, user.clone()))
+        })
+        .collect();
+    let last = match (pagination.after, pagination.sort) {
+        (Some(after), MemberSort::Joined) => {
+            // The user may not be present anymore, but they still need to be a member.
+            let relation = db
+                .user_bucket_relations
+                .iter()
+                .find(|r| r.bucket_uuid == bucket_uuid && r.user_uuid == after)
+                .ok_or(Error::NotFound)?;
+            let user = db
+                .users
+                .iter()
+                .find(|u| u.uuid == after)
+                .ok_or(Error::NotFound)?;
+            Some((relation.created_at, user.clone()))
+        }
+        (Some(after), MemberSort::Name) => {
+            let user = db
+                .users
+                .iter()
+                .find(|u| u.uuid == after)
+                .ok_or(Error::NotFound)?;
+            // Only the name matters when sorting by name, so the user doesn't need to be
+            // in the bucket anymore, and any time will do for when they joined.
+            Some((chrono::Utc::now().naive_utc(), user.clone()))
+        }
+        (None, _) => None,
+    };
+    let order = |(a_joined_at, a): &(NaiveDateTime, User),
+                 (b_joined_at, b): &(NaiveDateTime, User)| match pagination
+        .sort
+    {
+        MemberSort::Joined => (a_joined_at, a.uuid).cmp(&(b_joined_at, b.uuid)),
+        MemberSort::Name => (a.google_name.as_deref().unwrap_or(""), a.uuid)
+            .cmp(&(b.google_name.as_deref().unwrap_or(""), b.uuid)),
+    };
+    let page = paginate(members, last.as_ref(), pagination.limit, order, |(_, u)| {
+        u.uuid
+    });
+    Ok(page.map(|(_, user)| user))
+}
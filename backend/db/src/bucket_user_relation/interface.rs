@@ -1,11 +1,12 @@
 //! Specification of functions.
 
 use crate::{
-    bucket::db_types::Bucket,
+    bucket::db_types::{Bucket, BucketSort},
     bucket_user_relation::db_types::{
         BucketUserPermissions, BucketUserPermissionsChangeset, BucketUserRelation,
-        NewBucketUserRelation,
+        MemberSort, NewBucketUserRelation, NewPermissionGrant, PermissionGrant,
     },
+    page::{Page, Pagination},
     user::db_types::User,
 };
 use diesel::QueryResult;
@@ -42,11 +43,40 @@ pub trait BucketUserRelationRepository {
         user_uuid: Uuid,
         bucket_uuid: Uuid,
     ) -> QueryResult<BucketUserPermissions>;
-    /// Gets the buckets the user has joined.
-    fn get_buckets_user_is_a_part_of(&self, user_uuid: Uuid) -> QueryResult<Vec<Bucket>>;
-    /// Gets the users in a given bucket.
-    fn get_users_in_bucket(&self, bucket_uuid: Uuid) -> QueryResult<Vec<User>>;
+    /// Gets a page of the buckets the user has joined.
+    ///
+    /// If the bucket the page should start after doesn't exist anymore, this will return a `NotFound` error.
+    fn get_buckets_user_is_a_part_of(
+        &self,
+        user_uuid: Uuid,
+        pagination: Pagination<BucketSort>,
+    ) -> QueryResult<Page<Bucket>>;
+    /// Gets a page of the users in a given bucket.
+    ///
+    /// If the user the page should start after has left the bucket, this will return a `NotFound` error
+    /// when the members are sorted by when they joined.
+    fn get_users_in_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<MemberSort>,
+    ) -> QueryResult<Page<User>>;
+    /// Gets a page of the users in a given bucket, as well as their permissions.
+    ///
+    /// This pages through the members the same way as `get_users_in_bucket`.
+    fn get_permissions_of_users_in_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<MemberSort>,
+    ) -> QueryResult<Page<(BucketUserPermissions, User)>>;
 
-    /// Gets the users in a given bucket, as well as their relation
+    /// Gets every user in a given bucket, as well as their relation, in the order they joined.
     fn get_permissions_all_users_in_bucket(&self, bucket_uuid: Uuid) -> QueryResult<Vec<(BucketUserPermissions, User)>>;
+
+    /// Records that permissions were granted or revoked.
+    fn record_permission_grants(
+        &self,
+        grants: Vec<NewPermissionGrant>,
+    ) -> QueryResult<Vec<PermissionGrant>>;
+    /// Gets the record of permissions granted and revoked in the bucket, most recent first.
+    fn get_permission_grants(&self, bucket_uuid: Uuid) -> QueryResult<Vec<PermissionGrant>>;
 }
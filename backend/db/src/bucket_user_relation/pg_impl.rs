@@ -1,26 +1,34 @@
 //! Implementation of the specified interfaces for PgConnection.
 
 use crate::{
-    bucket::db_types::Bucket,
+    bucket::{db_types::{Bucket, BucketSort}, pg_impl::page_of_buckets},
     bucket_user_relation::{
         db_types::{
             BucketUserPermissions, BucketUserPermissionsChangeset, BucketUserRelation,
-            NewBucketUserRelation,
+            MemberSort, NewBucketUserRelation, NewPermissionGrant, PermissionGrant,
         },
         interface::BucketUserRelationRepository,
     },
-    schema::{bq_user, bucket, bucket_user_relation},
+    page::{Page, Pagination},
+    schema::{bq_user, bucket, bucket_permission_grant, bucket_presence, bucket_user_relation},
     user::db_types::User,
     AsConnRef,
 };
+use chrono::NaiveDateTime;
 use diesel::{
     query_dsl::{QueryDsl, RunQueryDsl},
     result::Error,
-    BoolExpressionMethods, ExpressionMethods, SaveChangesDsl,
+    sql_types::{Nullable, Text},
+    BoolExpressionMethods, ExpressionMethods, PgConnection, SaveChangesDsl,
 };
 use log::info;
 use uuid::Uuid;
 
+sql_function! {
+    /// The first of the values that isn't null.
+    fn coalesce(x: Nullable<Text>, y: Text) -> Text;
+}
+
 impl<T> BucketUserRelationRepository for T
 where
     T: AsConnRef,
@@ -95,30 +103,45 @@ where
                 bucket_user_relation::set_exclusive_permission,
                 bucket_user_relation::kick_permission,
                 bucket_user_relation::grant_permissions_permission,
+                bucket_user_relation::moderate_questions_permission,
+                bucket_user_relation::delete_questions_permission,
+                bucket_user_relation::invite_permission,
             ))
             .get_result::<BucketUserPermissions>(self.as_conn())
     }
 
-    fn get_buckets_user_is_a_part_of(&self, user_uuid: Uuid) -> Result<Vec<Bucket>, Error> {
+    fn get_buckets_user_is_a_part_of(
+        &self,
+        user_uuid: Uuid,
+        pagination: Pagination<BucketSort>,
+    ) -> Result<Page<Bucket>, Error> {
         info!("get_buckets_user_is_a_part_of");
-        bucket_user_relation::table
+        let bucket_uuids = bucket_user_relation::table
             .filter(bucket_user_relation::user_uuid.eq(user_uuid))
-            .select(bucket_user_relation::bucket_uuid)
-            .inner_join(bucket::table)
-            .order_by(bucket::updated_at)
-            .select(bucket::all_columns)
-            .get_results(self.as_conn())
+            .select(bucket_user_relation::bucket_uuid);
+        let query = bucket::table
+            .filter(bucket::uuid.eq_any(bucket_uuids))
+            .into_boxed();
+        page_of_buckets(query, pagination, self.as_conn())
     }
 
-    fn get_users_in_bucket(&self, bucket_uuid: Uuid) -> Result<Vec<User>, Error> {
+    fn get_users_in_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<MemberSort>,
+    ) -> Result<Page<User>, Error> {
         info!("get_users_in_bucket");
-        bucket_user_relation::table
-            .filter(bucket_user_relation::bucket_uuid.eq(bucket_uuid))
-            .select(bucket_user_relation::user_uuid)
-            .inner_join(bq_user::table)
-            .order_by(bucket_user_relation::created_at)
-            .select(bq_user::all_columns)
-            .get_results(self.as_conn())
+        let members = page_of_members(bucket_uuid, None, pagination, self.as_conn())?;
+        Ok(members.map(|(_, user)| user))
+    }
+
+    fn get_permissions_of_users_in_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<MemberSort>,
+    ) -> Result<Page<(BucketUserPermissions, User)>, Error> {
+        let members = page_of_members(bucket_uuid, None, pagination, self.as_conn())?;
+        Ok(members.map(|(relation, user)| (relation.permissions(), user)))
     }
 
     fn get_permissions_all_users_in_bucket(&self, bucket_uuid: Uuid) -> Result<Vec<(BucketUserPermissions, User)>, Error> {
@@ -135,8 +158,88 @@ where
                     bucket_user_relation::set_exclusive_permission,
                     bucket_user_relation::kick_permission,
                     bucket_user_relation::grant_permissions_permission,
+                    bucket_user_relation::moderate_questions_permission,
+                    bucket_user_relation::delete_questions_permission,
+                    bucket_user_relation::invite_permission,
                 )
                 , bq_user::all_columns))
             .get_results(self.as_conn())
     }
+
+    fn record_permission_grants(
+        &self,
+        grants: Vec<NewPermissionGrant>,
+    ) -> Result<Vec<PermissionGrant>, Error> {
+        diesel::insert_into(bucket_permission_grant::table)
+            .values(&grants)
+            .get_results(self.as_conn())
+    }
+
+    fn get_permission_grants(&self, bucket_uuid: Uuid) -> Result<Vec<PermissionGrant>, Error> {
+        bucket_permission_grant::table
+            .filter(bucket_permission_grant::bucket_uuid.eq(bucket_uuid))
+            .order_by(bucket_permission_grant::created_at.desc())
+            .get_results(self.as_conn())
+    }
+}
+
+/// Gets a page of the members of the bucket, along with their relation to it.
+///
+/// If a time is given, only the members that have been seen in the bucket since then are on the page.
+pub(crate) fn page_of_members(
+    bucket_uuid: Uuid,
+    present_since: Option<NaiveDateTime>,
+    pagination: Pagination<MemberSort>,
+    conn: &PgConnection,
+) -> Result<Page<(BucketUserRelation, User)>, Error> {
+    let query = bucket_user_relation::table
+        .inner_join(bq_user::table)
+        .filter(bucket_user_relation::bucket_uuid.eq(bucket_uuid))
+        .into_boxed();
+    // Users that left the bucket may still have a recent heartbeat,
+    // so only the presence of current members counts.
+    let query = match present_since {
+        Some(present_since) => query.filter(
+            bq_user::uuid.eq_any(
+                bucket_presence::table
+                    .filter(bucket_presence::bucket_uuid.eq(bucket_uuid))
+                    .filter(bucket_presence::last_seen_at.ge(present_since))
+                    .select(bucket_presence::user_uuid),
+            ),
+        ),
+        None => query,
+    };
+    // Members without a name sort as if their name was empty.
+    let name = coalesce(bq_user::google_name, "");
+    // Only the users that sort after the last one of the previous page are on this page.
+    let query = match (pagination.after, pagination.sort) {
+        (Some(after), MemberSort::Joined) => {
+            let joined_at: NaiveDateTime = bucket_user_relation::table
+                .find((after, bucket_uuid))
+                .select(bucket_user_relation::created_at)
+                .first(conn)?;
+            query.filter(
+                bucket_user_relation::created_at.gt(joined_at).or(
+                    bucket_user_relation::created_at
+                        .eq(joined_at)
+                        .and(bq_user::uuid.gt(after)),
+                ),
+            )
+        }
+        (Some(after), MemberSort::Name) => {
+            let last: User = bq_user::table.find(after).first(conn)?;
+            let last_name = last.google_name.unwrap_or_default();
+            query.filter(
+                name.gt(last_name.clone())
+                    .or(name.eq(last_name).and(bq_user::uuid.gt(after))),
+            )
+        }
+        (None, _) => query,
+    };
+    let query = match pagination.sort {
+        MemberSort::Joined => query.order_by((bucket_user_relation::created_at, bq_user::uuid)),
+        MemberSort::Name => query.order_by((name, bq_user::uuid)),
+    };
+    let members = query.limit(pagination.fetch_limit()).get_results(conn)?;
+    Ok(Page::from_fetched(members, pagination.limit, |(_, u)| u.uuid))
 }
@@ -1,8 +1,32 @@
 use crate::{
-    bucket_user_relation::db_types::{BucketUserPermissionsChangeset, NewBucketUserRelation},
+    bucket::db_types::BucketSort,
+    bucket_user_relation::db_types::{
+        BucketUserPermissionsChangeset, MemberSort, NewBucketUserRelation, NewPermissionGrant,
+    },
+    page::Pagination,
     test::{bucket_user_relation_fixture::UserBucketRelationFixture, util::execute_test},
+    user::db_types::NewUser,
     BoxedRepository,
 };
+use uuid::Uuid;
+
+/// Adds the user to the bucket without any permissions.
+fn join(db: &BoxedRepository, user_uuid: Uuid, bucket_uuid: Uuid) {
+    let new_relation = NewBucketUserRelation {
+        user_uuid,
+        bucket_uuid,
+        set_public_permission: false,
+        set_drawing_permission: false,
+        set_exclusive_permission: false,
+        kick_permission: false,
+        grant_permissions_permission: false,
+        moderate_questions_permission: false,
+        delete_questions_permission: false,
+        invite_permission: false,
+    };
+    db.add_user_to_bucket(new_relation)
+        .expect("Should be able to add user to bucket");
+}
 
 #[test]
 fn create_relation() {
@@ -15,6 +39,9 @@ fn create_relation() {
             set_exclusive_permission: false,
             kick_permission: false,
             grant_permissions_permission: false,
+            moderate_questions_permission: false,
+            delete_questions_permission: false,
+            invite_permission: false,
         };
         db.add_user_to_bucket(new_relation)
             .expect("Should be able to add user to bucket");
@@ -32,6 +59,9 @@ fn cant_create_duplicate_relation() {
             set_exclusive_permission: false,
             kick_permission: false,
             grant_permissions_permission: false,
+            moderate_questions_permission: false,
+            delete_questions_permission: false,
+            invite_permission: false,
         };
         db.add_user_to_bucket(new_relation)
             .expect_err("Should not able to add user to bucket twice");
@@ -70,6 +100,9 @@ fn set_permissions() {
             set_exclusive_permission: None,
             kick_permission: None,
             grant_permissions_permission: Some(false),
+            moderate_questions_permission: Some(false),
+            delete_questions_permission: Some(false),
+            invite_permission: Some(false),
         };
 
         assert_eq!(fixture.relation.grant_permissions_permission, true); // precondition
@@ -92,6 +125,9 @@ fn set_empty_permissions() {
             set_exclusive_permission: None,
             kick_permission: None,
             grant_permissions_permission: None,
+            moderate_questions_permission: None,
+            delete_questions_permission: None,
+            invite_permission: None,
         };
         let _relation = db
             .set_permissions(changeset)
@@ -99,6 +135,65 @@ fn set_empty_permissions() {
     });
 }
 
+#[test]
+fn permission_changes_are_listed() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let before = db
+            .get_permissions(fixture.user1.uuid, fixture.bucket.uuid)
+            .expect("Should get permissions");
+        let changeset = BucketUserPermissionsChangeset {
+            user_uuid: fixture.user1.uuid,
+            bucket_uuid: fixture.bucket.uuid,
+            set_public_permission: None,
+            set_drawing_permission: None,
+            set_exclusive_permission: None,
+            kick_permission: Some(true),
+            grant_permissions_permission: Some(false),
+            moderate_questions_permission: Some(false),
+            delete_questions_permission: None,
+            invite_permission: None,
+        };
+        db.set_permissions(changeset)
+            .expect("Should be able to set permissions");
+        let after = db
+            .get_permissions(fixture.user1.uuid, fixture.bucket.uuid)
+            .expect("Should get permissions");
+
+        // Kick was already held, so only the revoked permissions are changes.
+        let expected = vec![
+            ("grant_permissions_permission", false),
+            ("moderate_questions_permission", false),
+        ];
+        assert_eq!(before.changes_to(&after), expected);
+        assert!(after.changes_to(&after).is_empty());
+    });
+}
+
+#[test]
+fn record_permission_grants() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let grant = NewPermissionGrant {
+            bucket_uuid: fixture.bucket.uuid,
+            granter_uuid: Some(fixture.user1.uuid),
+            target_uuid: fixture.user2.uuid,
+            permission: "kick_permission".to_string(),
+            granted: true,
+        };
+        let recorded = db
+            .record_permission_grants(vec![grant])
+            .expect("Should record grants");
+        assert_eq!(recorded.len(), 1);
+
+        let grants = db
+            .get_permission_grants(fixture.bucket.uuid)
+            .expect("Should get grants");
+        assert_eq!(grants, recorded);
+        assert_eq!(grants[0].target_uuid, fixture.user2.uuid);
+        assert_eq!(grants[0].permission, "kick_permission");
+        assert!(grants[0].granted);
+    });
+}
+
 #[test]
 fn get_relation() {
     execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
@@ -122,30 +217,106 @@ fn cant_get_relation() {
 fn get_associated_users() {
     execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
         let users = db
-            .get_users_in_bucket(fixture.bucket.uuid)
-            .expect("Should get users");
+            .get_users_in_bucket(
+                fixture.bucket.uuid,
+                Pagination::first(10, MemberSort::Joined),
+            )
+            .expect("Should get users")
+            .items;
         assert_eq!(users.len(), 1);
         assert_eq!(users.get(0).expect("Should get user"), &fixture.user1);
     });
 }
 
+#[test]
+fn page_through_associated_users() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let new_user = NewUser {
+            google_user_id: "192837465".to_string(),
+            google_name: Some("Alex".to_owned()),
+        };
+        let user3 = db.create_user(new_user).expect("Should create user");
+        join(&db, fixture.user2.uuid, fixture.bucket.uuid);
+        join(&db, user3.uuid, fixture.bucket.uuid);
+
+        let get_all = |sort: MemberSort| {
+            let mut pagination = Some(Pagination::first(2, sort));
+            let mut users = vec![];
+            while let Some(next) = pagination {
+                let page = db
+                    .get_users_in_bucket(fixture.bucket.uuid, next)
+                    .expect("Should get users");
+                assert!(page.items.len() <= 2);
+                pagination = next.next(&page);
+                users.extend(page.items);
+            }
+            users
+        };
+        assert_eq!(
+            get_all(MemberSort::Joined),
+            vec![fixture.user1.clone(), fixture.user2.clone(), user3.clone()]
+        );
+        assert_eq!(
+            get_all(MemberSort::Name),
+            vec![user3, fixture.user1.clone(), fixture.user2.clone()]
+        );
+    });
+}
+
+#[test]
+fn get_associated_users_with_permissions() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        join(&db, fixture.user2.uuid, fixture.bucket.uuid);
+
+        let page = db
+            .get_permissions_of_users_in_bucket(
+                fixture.bucket.uuid,
+                Pagination::first(1, MemberSort::Joined),
+            )
+            .expect("Should get users");
+        assert_eq!(page.items.len(), 1);
+        let (permissions, user) = &page.items[0];
+        assert_eq!(user, &fixture.user1);
+        assert!(permissions.includes(&fixture.relation.permissions()));
+
+        let pagination = Pagination::first(1, MemberSort::Joined)
+            .next(&page)
+            .expect("Should have another page");
+        let page = db
+            .get_permissions_of_users_in_bucket(fixture.bucket.uuid, pagination)
+            .expect("Should get users");
+        assert_eq!(page.next_cursor, None);
+        let (permissions, user) = &page.items[0];
+        assert_eq!(user, &fixture.user2);
+        assert!(!permissions.kick_permission);
+    });
+}
+
 #[test]
 fn get_associated_buckets() {
     execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
-        let users = db
-            .get_buckets_user_is_a_part_of(fixture.user1.uuid)
-            .expect("Should get related buckets");
-        assert_eq!(users.len(), 1);
-        assert_eq!(users.get(0).unwrap(), &fixture.bucket);
+        let buckets = db
+            .get_buckets_user_is_a_part_of(
+                fixture.user1.uuid,
+                Pagination::first(10, BucketSort::Updated),
+            )
+            .expect("Should get related buckets")
+            .items;
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets.get(0).unwrap(), &fixture.bucket);
     });
 }
 
 #[test]
 fn dont_get_unassociated_buckets() {
     execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
-        let users = db
-            .get_buckets_user_is_a_part_of(fixture.user2.uuid)
-            .expect("Should get related buckets");
-        assert_eq!(users.len(), 0);
+        let buckets = db
+            .get_buckets_user_is_a_part_of(
+                fixture.user2.uuid,
+                Pagination::first(10, BucketSort::Updated),
+            )
+            .expect("Should get related buckets")
+            .items;
+        assert_eq!(buckets.len(), 0);
     });
 }
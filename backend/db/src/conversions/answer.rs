@@ -13,6 +13,7 @@ impl Into<wire::answer::Answer> for Answer {
             answer_text: self.answer_text,
             updated_at: self.updated_at,
             created_at: self.created_at,
+            session_uuid: self.session_uuid,
         }
     }
 }
@@ -27,6 +28,7 @@ impl From<wire::answer::Answer> for Answer {
             answer_text: answer.answer_text,
             updated_at: answer.updated_at,
             created_at: answer.created_at,
+            session_uuid: answer.session_uuid,
         }
     }
 }
@@ -38,6 +40,7 @@ impl Into<wire::answer::NewAnswer> for NewAnswer {
             question_uuid: self.question_uuid,
             publicly_visible: self.publicly_visible,
             answer_text: self.answer_text,
+            session_uuid: self.session_uuid,
         }
     }
 }
@@ -49,6 +52,7 @@ impl From<wire::answer::NewAnswer> for NewAnswer {
             question_uuid: new_answer.question_uuid,
             publicly_visible: new_answer.publicly_visible,
             answer_text: new_answer.answer_text,
+            session_uuid: new_answer.session_uuid,
         }
     }
 }
@@ -0,0 +1,116 @@
+//! Audit conversions
+
+use crate::audit::db_types::{AuditAction, AuditEvent};
+use wire;
+
+impl Into<wire::audit::AuditEvent> for AuditEvent {
+    fn into(self) -> wire::audit::AuditEvent {
+        wire::audit::AuditEvent {
+            uuid: self.uuid,
+            bucket_uuid: self.bucket_uuid,
+            actor_uuid: self.actor_uuid,
+            target_uuid: self.target_uuid,
+            action: self.action.into(),
+            diff: self.diff,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl From<wire::audit::AuditEvent> for AuditEvent {
+    fn from(event: wire::audit::AuditEvent) -> Self {
+        AuditEvent {
+            uuid: event.uuid,
+            bucket_uuid: event.bucket_uuid,
+            actor_uuid: event.actor_uuid,
+            target_uuid: event.target_uuid,
+            action: event.action.into(),
+            diff: event.diff,
+            created_at: event.created_at,
+        }
+    }
+}
+
+impl Into<wire::audit::AuditAction> for AuditAction {
+    fn into(self) -> wire::audit::AuditAction {
+        match self {
+            AuditAction::BucketCreated => wire::audit::AuditAction::BucketCreated,
+            AuditAction::BucketRenamed => wire::audit::AuditAction::BucketRenamed,
+            AuditAction::BucketCloned => wire::audit::AuditAction::BucketCloned,
+            AuditAction::TemplateSaved => wire::audit::AuditAction::TemplateSaved,
+            AuditAction::BucketRestored => wire::audit::AuditAction::BucketRestored,
+            AuditAction::MemberJoined => wire::audit::AuditAction::MemberJoined,
+            AuditAction::MemberRemoved => wire::audit::AuditAction::MemberRemoved,
+            AuditAction::MemberBanned => wire::audit::AuditAction::MemberBanned,
+            AuditAction::BanLifted => wire::audit::AuditAction::BanLifted,
+            AuditAction::PermissionsChanged => wire::audit::AuditAction::PermissionsChanged,
+            AuditAction::RoleSet => wire::audit::AuditAction::RoleSet,
+            AuditAction::OwnershipTransferred => wire::audit::AuditAction::OwnershipTransferred,
+            AuditAction::InviteCreated => wire::audit::AuditAction::InviteCreated,
+            AuditAction::InviteDeleted => wire::audit::AuditAction::InviteDeleted,
+            AuditAction::JoinCodeRegenerated => wire::audit::AuditAction::JoinCodeRegenerated,
+            AuditAction::FlagsChanged => wire::audit::AuditAction::FlagsChanged,
+            AuditAction::TurnSkipped => wire::audit::AuditAction::TurnSkipped,
+            AuditAction::SessionStarted => wire::audit::AuditAction::SessionStarted,
+            AuditAction::SessionEnded => wire::audit::AuditAction::SessionEnded,
+            AuditAction::QuestionCreated => wire::audit::AuditAction::QuestionCreated,
+            AuditAction::QuestionsImported => wire::audit::AuditAction::QuestionsImported,
+            AuditAction::QuestionEdited => wire::audit::AuditAction::QuestionEdited,
+            AuditAction::QuestionReverted => wire::audit::AuditAction::QuestionReverted,
+            AuditAction::QuestionDeleted => wire::audit::AuditAction::QuestionDeleted,
+            AuditAction::QuestionDrawn => wire::audit::AuditAction::QuestionDrawn,
+            AuditAction::QuestionArchiveStateSet => {
+                wire::audit::AuditAction::QuestionArchiveStateSet
+            }
+            AuditAction::QuestionTagged => wire::audit::AuditAction::QuestionTagged,
+            AuditAction::QuestionUntagged => wire::audit::AuditAction::QuestionUntagged,
+            AuditAction::QuestionVoted => wire::audit::AuditAction::QuestionVoted,
+            AuditAction::QuestionVoteRemoved => wire::audit::AuditAction::QuestionVoteRemoved,
+            AuditAction::AnswerPosted => wire::audit::AuditAction::AnswerPosted,
+            AuditAction::AnswerEdited => wire::audit::AuditAction::AnswerEdited,
+            AuditAction::AnswerDeleted => wire::audit::AuditAction::AnswerDeleted,
+        }
+    }
+}
+
+impl From<wire::audit::AuditAction> for AuditAction {
+    fn from(action: wire::audit::AuditAction) -> Self {
+        match action {
+            wire::audit::AuditAction::BucketCreated => AuditAction::BucketCreated,
+            wire::audit::AuditAction::BucketRenamed => AuditAction::BucketRenamed,
+            wire::audit::AuditAction::BucketCloned => AuditAction::BucketCloned,
+            wire::audit::AuditAction::TemplateSaved => AuditAction::TemplateSaved,
+            wire::audit::AuditAction::BucketRestored => AuditAction::BucketRestored,
+            wire::audit::AuditAction::MemberJoined => AuditAction::MemberJoined,
+            wire::audit::AuditAction::MemberRemoved => AuditAction::MemberRemoved,
+            wire::audit::AuditAction::MemberBanned => AuditAction::MemberBanned,
+            wire::audit::AuditAction::BanLifted => AuditAction::BanLifted,
+            wire::audit::AuditAction::PermissionsChanged => AuditAction::PermissionsChanged,
+            wire::audit::AuditAction::RoleSet => AuditAction::RoleSet,
+            wire::audit::AuditAction::OwnershipTransferred => AuditAction::OwnershipTransferred,
+            wire::audit::AuditAction::InviteCreated => AuditAction::InviteCreated,
+            wire::audit::AuditAction::InviteDeleted => AuditAction::InviteDeleted,
+            wire::audit::AuditAction::JoinCodeRegenerated => AuditAction::JoinCodeRegenerated,
+            wire::audit::AuditAction::FlagsChanged => AuditAction::FlagsChanged,
+            wire::audit::AuditAction::TurnSkipped => AuditAction::TurnSkipped,
+            wire::audit::AuditAction::SessionStarted => AuditAction::SessionStarted,
+            wire::audit::AuditAction::SessionEnded => AuditAction::SessionEnded,
+            wire::audit::AuditAction::QuestionCreated => AuditAction::QuestionCreated,
+            wire::audit::AuditAction::QuestionsImported => AuditAction::QuestionsImported,
+            wire::audit::AuditAction::QuestionEdited => AuditAction::QuestionEdited,
+            wire::audit::AuditAction::QuestionReverted => AuditAction::QuestionReverted,
+            wire::audit::AuditAction::QuestionDeleted => AuditAction::QuestionDeleted,
+            wire::audit::AuditAction::QuestionDrawn => AuditAction::QuestionDrawn,
+            wire::audit::AuditAction::QuestionArchiveStateSet => {
+                AuditAction::QuestionArchiveStateSet
+            }
+            wire::audit::AuditAction::QuestionTagged => AuditAction::QuestionTagged,
+            wire::audit::AuditAction::QuestionUntagged => AuditAction::QuestionUntagged,
+            wire::audit::AuditAction::QuestionVoted => AuditAction::QuestionVoted,
+            wire::audit::AuditAction::QuestionVoteRemoved => AuditAction::QuestionVoteRemoved,
+            wire::audit::AuditAction::AnswerPosted => AuditAction::AnswerPosted,
+            wire::audit::AuditAction::AnswerEdited => AuditAction::AnswerEdited,
+            wire::audit::AuditAction::AnswerDeleted => AuditAction::AnswerDeleted,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Ban conversions
+
+use crate::ban::db_types::Ban;
+use wire;
+
+impl Into<wire::ban::Ban> for Ban {
+    fn into(self) -> wire::ban::Ban {
+        wire::ban::Ban {
+            bucket_uuid: self.bucket_uuid,
+            user_uuid: self.user_uuid,
+            banned_by_uuid: self.banned_by_uuid,
+            reason: self.reason,
+            expires_at: self.expires_at,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl From<wire::ban::Ban> for Ban {
+    fn from(ban: wire::ban::Ban) -> Self {
+        Ban {
+            bucket_uuid: ban.bucket_uuid,
+            user_uuid: ban.user_uuid,
+            banned_by_uuid: ban.banned_by_uuid,
+            reason: ban.reason,
+            expires_at: ban.expires_at,
+            created_at: ban.created_at,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! Bucket conversions
 
-use crate::bucket::db_types::{Bucket, BucketFlagChangeset, NewBucket};
+use crate::bucket::db_types::{
+    Bucket, BucketFlagChangeset, BucketSort, DrawStrategy, DuplicatePolicy, NewBucket,
+};
 use wire;
 
 impl Into<wire::bucket::Bucket> for Bucket {
@@ -14,6 +16,10 @@ impl Into<wire::bucket::Bucket> for Bucket {
             exclusive: self.exclusive,
             updated_at: self.updated_at,
             created_at: self.created_at,
+            draw_strategy: self.draw_strategy.into(),
+            owner_uuid: self.owner_uuid,
+            is_template: self.is_template,
+            duplicate_policy: self.duplicate_policy.into(),
         }
     }
 }
@@ -29,6 +35,10 @@ impl From<wire::bucket::Bucket> for Bucket {
             exclusive: bucket.exclusive,
             updated_at: bucket.updated_at,
             created_at: bucket.created_at,
+            draw_strategy: bucket.draw_strategy.into(),
+            owner_uuid: bucket.owner_uuid,
+            is_template: bucket.is_template,
+            duplicate_policy: bucket.duplicate_policy.into(),
         }
     }
 }
@@ -58,6 +68,8 @@ impl Into<wire::bucket::BucketFlagChangeset> for BucketFlagChangeset {
             public_viewable: self.public_viewable,
             drawing_enabled: self.drawing_enabled,
             exclusive: self.exclusive,
+            draw_strategy: self.draw_strategy.map(Into::into),
+            duplicate_policy: self.duplicate_policy.map(Into::into),
         }
     }
 }
@@ -69,6 +81,72 @@ impl From<wire::bucket::BucketFlagChangeset> for BucketFlagChangeset {
             public_viewable: bfc.public_viewable,
             drawing_enabled: bfc.drawing_enabled,
             exclusive: bfc.exclusive,
+            draw_strategy: bfc.draw_strategy.map(Into::into),
+            duplicate_policy: bfc.duplicate_policy.map(Into::into),
+        }
+    }
+}
+
+impl Into<wire::bucket::DrawStrategy> for DrawStrategy {
+    fn into(self) -> wire::bucket::DrawStrategy {
+        match self {
+            DrawStrategy::Uniform => wire::bucket::DrawStrategy::Uniform,
+            DrawStrategy::Upvotes => wire::bucket::DrawStrategy::Upvotes,
+            DrawStrategy::OlderFirst => wire::bucket::DrawStrategy::OlderFirst,
+            DrawStrategy::MembersNotRecentlyDrawn => {
+                wire::bucket::DrawStrategy::MembersNotRecentlyDrawn
+            }
+        }
+    }
+}
+
+impl From<wire::bucket::DrawStrategy> for DrawStrategy {
+    fn from(strategy: wire::bucket::DrawStrategy) -> Self {
+        match strategy {
+            wire::bucket::DrawStrategy::Uniform => DrawStrategy::Uniform,
+            wire::bucket::DrawStrategy::Upvotes => DrawStrategy::Upvotes,
+            wire::bucket::DrawStrategy::OlderFirst => DrawStrategy::OlderFirst,
+            wire::bucket::DrawStrategy::MembersNotRecentlyDrawn => {
+                DrawStrategy::MembersNotRecentlyDrawn
+            }
+        }
+    }
+}
+
+impl Into<wire::bucket::DuplicatePolicy> for DuplicatePolicy {
+    fn into(self) -> wire::bucket::DuplicatePolicy {
+        match self {
+            DuplicatePolicy::Warn => wire::bucket::DuplicatePolicy::Warn,
+            DuplicatePolicy::Reject => wire::bucket::DuplicatePolicy::Reject,
+        }
+    }
+}
+
+impl From<wire::bucket::DuplicatePolicy> for DuplicatePolicy {
+    fn from(policy: wire::bucket::DuplicatePolicy) -> Self {
+        match policy {
+            wire::bucket::DuplicatePolicy::Warn => DuplicatePolicy::Warn,
+            wire::bucket::DuplicatePolicy::Reject => DuplicatePolicy::Reject,
+        }
+    }
+}
+
+impl Into<wire::bucket::BucketSort> for BucketSort {
+    fn into(self) -> wire::bucket::BucketSort {
+        match self {
+            BucketSort::Updated => wire::bucket::BucketSort::Updated,
+            BucketSort::Newest => wire::bucket::BucketSort::Newest,
+            BucketSort::Name => wire::bucket::BucketSort::Name,
+        }
+    }
+}
+
+impl From<wire::bucket::BucketSort> for BucketSort {
+    fn from(sort: wire::bucket::BucketSort) -> Self {
+        match sort {
+            wire::bucket::BucketSort::Updated => BucketSort::Updated,
+            wire::bucket::BucketSort::Newest => BucketSort::Newest,
+            wire::bucket::BucketSort::Name => BucketSort::Name,
         }
     }
 }
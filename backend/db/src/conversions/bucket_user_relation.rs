@@ -1,6 +1,6 @@
 //! Bucket-User relation conversions
 
-use crate::bucket_user_relation::db_types::{BucketUserPermissionsChangeset, BucketUserRelation, NewBucketUserRelation, BucketUserPermissions};
+use crate::bucket_user_relation::db_types::{BucketUserPermissionsChangeset, BucketUserRelation, NewBucketUserRelation, BucketUserPermissions, MemberSort};
 use wire;
 
 impl Into<wire::bucket_user_relation::BucketUserRelation> for BucketUserRelation {
@@ -13,6 +13,9 @@ impl Into<wire::bucket_user_relation::BucketUserRelation> for BucketUserRelation
             set_exclusive_permission: self.set_exclusive_permission,
             kick_permission: self.kick_permission,
             grant_permissions_permission: self.grant_permissions_permission,
+            moderate_questions_permission: self.moderate_questions_permission,
+            delete_questions_permission: self.delete_questions_permission,
+            invite_permission: self.invite_permission,
             updated_at: self.updated_at,
             created_at: self.created_at,
         }
@@ -29,6 +32,9 @@ impl From<wire::bucket_user_relation::BucketUserRelation> for BucketUserRelation
             set_exclusive_permission: bur.set_exclusive_permission,
             kick_permission: bur.kick_permission,
             grant_permissions_permission: bur.grant_permissions_permission,
+            moderate_questions_permission: bur.moderate_questions_permission,
+            delete_questions_permission: bur.delete_questions_permission,
+            invite_permission: bur.invite_permission,
             updated_at: bur.updated_at,
             created_at: bur.created_at,
         }
@@ -45,6 +51,9 @@ impl Into<wire::bucket_user_relation::NewBucketUserRelation> for NewBucketUserRe
             set_exclusive_permission: self.set_exclusive_permission,
             kick_permission: self.kick_permission,
             grant_permissions_permission: self.grant_permissions_permission,
+            moderate_questions_permission: self.moderate_questions_permission,
+            delete_questions_permission: self.delete_questions_permission,
+            invite_permission: self.invite_permission,
         }
     }
 }
@@ -59,6 +68,9 @@ impl From<wire::bucket_user_relation::NewBucketUserRelation> for NewBucketUserRe
             set_exclusive_permission: bur.set_exclusive_permission,
             kick_permission: bur.kick_permission,
             grant_permissions_permission: bur.grant_permissions_permission,
+            moderate_questions_permission: bur.moderate_questions_permission,
+            delete_questions_permission: bur.delete_questions_permission,
+            invite_permission: bur.invite_permission,
         }
     }
 }
@@ -75,6 +87,9 @@ impl Into<wire::bucket_user_relation::BucketUserPermissionsChangeset>
             set_exclusive_permission: self.set_exclusive_permission,
             kick_permission: self.kick_permission,
             grant_permissions_permission: self.grant_permissions_permission,
+            moderate_questions_permission: self.moderate_questions_permission,
+            delete_questions_permission: self.delete_questions_permission,
+            invite_permission: self.invite_permission,
         }
     }
 }
@@ -91,6 +106,9 @@ impl From<wire::bucket_user_relation::BucketUserPermissionsChangeset>
             set_exclusive_permission: bupc.set_exclusive_permission,
             kick_permission: bupc.kick_permission,
             grant_permissions_permission: bupc.grant_permissions_permission,
+            moderate_questions_permission: bupc.moderate_questions_permission,
+            delete_questions_permission: bupc.delete_questions_permission,
+            invite_permission: bupc.invite_permission,
         }
     }
 }
@@ -102,7 +120,58 @@ impl Into<wire::bucket_user_relation::BucketUserPermissions> for BucketUserPermi
             set_drawing_permission: self.set_drawing_permission,
             set_exclusive_permission: self.set_exclusive_permission,
             grant_permissions_permission: self.grant_permissions_permission,
+            moderate_questions_permission: self.moderate_questions_permission,
+            delete_questions_permission: self.delete_questions_permission,
+            invite_permission: self.invite_permission,
             kick_permission: self.kick_permission
         }
     }
-}
\ No newline at end of file
+}
+
+impl From<wire::bucket_user_relation::BucketUserPermissions> for BucketUserPermissions {
+    fn from(permissions: wire::bucket_user_relation::BucketUserPermissions) -> Self {
+        BucketUserPermissions {
+            set_public_permission: permissions.set_public_permission,
+            set_drawing_permission: permissions.set_drawing_permission,
+            set_exclusive_permission: permissions.set_exclusive_permission,
+            kick_permission: permissions.kick_permission,
+            grant_permissions_permission: permissions.grant_permissions_permission,
+            moderate_questions_permission: permissions.moderate_questions_permission,
+            delete_questions_permission: permissions.delete_questions_permission,
+            invite_permission: permissions.invite_permission,
+        }
+    }
+}
+
+impl Into<wire::bucket_user_relation::BucketUserPermissions> for BucketUserRelation {
+    fn into(self) -> wire::bucket_user_relation::BucketUserPermissions {
+        wire::bucket_user_relation::BucketUserPermissions {
+            set_public_permission: self.set_public_permission,
+            set_drawing_permission: self.set_drawing_permission,
+            set_exclusive_permission: self.set_exclusive_permission,
+            grant_permissions_permission: self.grant_permissions_permission,
+            moderate_questions_permission: self.moderate_questions_permission,
+            delete_questions_permission: self.delete_questions_permission,
+            invite_permission: self.invite_permission,
+            kick_permission: self.kick_permission
+        }
+    }
+}
+
+impl Into<wire::bucket_user_relation::MemberSort> for MemberSort {
+    fn into(self) -> wire::bucket_user_relation::MemberSort {
+        match self {
+            MemberSort::Joined => wire::bucket_user_relation::MemberSort::Joined,
+            MemberSort::Name => wire::bucket_user_relation::MemberSort::Name,
+        }
+    }
+}
+
+impl From<wire::bucket_user_relation::MemberSort> for MemberSort {
+    fn from(sort: wire::bucket_user_relation::MemberSort) -> Self {
+        match sort {
+            wire::bucket_user_relation::MemberSort::Joined => MemberSort::Joined,
+            wire::bucket_user_relation::MemberSort::Name => MemberSort::Name,
+        }
+    }
+}
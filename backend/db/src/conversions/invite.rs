@@ -0,0 +1,68 @@
+//! Invite conversions
+
+use crate::invite::db_types::{Invite, JoinCode};
+use wire;
+
+impl Into<wire::invite::Invite> for Invite {
+    fn into(self) -> wire::invite::Invite {
+        wire::invite::Invite {
+            uuid: self.uuid,
+            bucket_uuid: self.bucket_uuid,
+            user_uuid: self.user_uuid,
+            expires_at: self.expires_at,
+            max_uses: self.max_uses,
+            uses: self.uses,
+            set_public_permission: self.set_public_permission,
+            set_drawing_permission: self.set_drawing_permission,
+            set_exclusive_permission: self.set_exclusive_permission,
+            kick_permission: self.kick_permission,
+            grant_permissions_permission: self.grant_permissions_permission,
+            moderate_questions_permission: self.moderate_questions_permission,
+            delete_questions_permission: self.delete_questions_permission,
+            invite_permission: self.invite_permission,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl From<wire::invite::Invite> for Invite {
+    fn from(invite: wire::invite::Invite) -> Self {
+        Invite {
+            uuid: invite.uuid,
+            bucket_uuid: invite.bucket_uuid,
+            user_uuid: invite.user_uuid,
+            expires_at: invite.expires_at,
+            max_uses: invite.max_uses,
+            uses: invite.uses,
+            set_public_permission: invite.set_public_permission,
+            set_drawing_permission: invite.set_drawing_permission,
+            set_exclusive_permission: invite.set_exclusive_permission,
+            kick_permission: invite.kick_permission,
+            grant_permissions_permission: invite.grant_permissions_permission,
+            moderate_questions_permission: invite.moderate_questions_permission,
+            delete_questions_permission: invite.delete_questions_permission,
+            invite_permission: invite.invite_permission,
+            created_at: invite.created_at,
+        }
+    }
+}
+
+impl Into<wire::invite::JoinCode> for JoinCode {
+    fn into(self) -> wire::invite::JoinCode {
+        wire::invite::JoinCode {
+            bucket_uuid: self.bucket_uuid,
+            join_code: self.join_code,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl From<wire::invite::JoinCode> for JoinCode {
+    fn from(join_code: wire::invite::JoinCode) -> Self {
+        JoinCode {
+            bucket_uuid: join_code.bucket_uuid,
+            join_code: join_code.join_code,
+            created_at: join_code.created_at,
+        }
+    }
+}
@@ -1,8 +1,18 @@
 //! Conversions to wire types.
 
 mod answer;
+mod audit;
+mod ban;
 mod bucket;
 mod bucket_user_relation;
 mod favorite_question;
+mod invite;
+mod page;
+mod presence;
 mod question;
+mod question_vote;
+mod search;
+mod session;
+mod tag;
+mod turn;
 mod user;
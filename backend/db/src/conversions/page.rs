@@ -0,0 +1,34 @@
+//! Page conversions
+
+use crate::page::{Page, RecencySort};
+use wire;
+
+impl<T, U> Into<wire::page::Page<U>> for Page<T>
+where
+    T: Into<U>,
+{
+    fn into(self) -> wire::page::Page<U> {
+        wire::page::Page {
+            items: self.items.into_iter().map(T::into).collect(),
+            next_cursor: self.next_cursor.map(|cursor| cursor.to_string()),
+        }
+    }
+}
+
+impl Into<wire::page::RecencySort> for RecencySort {
+    fn into(self) -> wire::page::RecencySort {
+        match self {
+            RecencySort::Newest => wire::page::RecencySort::Newest,
+            RecencySort::Oldest => wire::page::RecencySort::Oldest,
+        }
+    }
+}
+
+impl From<wire::page::RecencySort> for RecencySort {
+    fn from(sort: wire::page::RecencySort) -> Self {
+        match sort {
+            wire::page::RecencySort::Newest => RecencySort::Newest,
+            wire::page::RecencySort::Oldest => RecencySort::Oldest,
+        }
+    }
+}
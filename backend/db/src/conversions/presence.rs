@@ -0,0 +1,24 @@
+//! Presence conversions
+
+use crate::presence::db_types::Presence;
+use wire;
+
+impl Into<wire::presence::Presence> for Presence {
+    fn into(self) -> wire::presence::Presence {
+        wire::presence::Presence {
+            bucket_uuid: self.bucket_uuid,
+            user_uuid: self.user_uuid,
+            last_seen_at: self.last_seen_at,
+        }
+    }
+}
+
+impl From<wire::presence::Presence> for Presence {
+    fn from(presence: wire::presence::Presence) -> Self {
+        Presence {
+            bucket_uuid: presence.bucket_uuid,
+            user_uuid: presence.user_uuid,
+            last_seen_at: presence.last_seen_at,
+        }
+    }
+}
@@ -1,6 +1,9 @@
 //! Question conversions.
 
-use crate::question::db_types::{NewQuestion, Question};
+use crate::question::db_types::{
+    NewQuestion, Question, QuestionDraw, QuestionExport, QuestionRevision, QuestionSort,
+    SimilarQuestion,
+};
 use wire;
 
 impl Into<wire::question::Question> for Question {
@@ -13,6 +16,8 @@ impl Into<wire::question::Question> for Question {
             archived: self.archived,
             updated_at: self.updated_at,
             created_at: self.created_at,
+            upvotes: self.upvotes,
+            downvotes: self.downvotes,
         }
     }
 }
@@ -27,6 +32,8 @@ impl From<wire::question::Question> for Question {
             archived: question.archived,
             updated_at: question.updated_at,
             created_at: question.created_at,
+            upvotes: question.upvotes,
+            downvotes: question.downvotes,
         }
     }
 }
@@ -50,3 +57,111 @@ impl From<wire::question::NewQuestion> for NewQuestion {
         }
     }
 }
+
+impl Into<wire::question::QuestionDraw> for QuestionDraw {
+    fn into(self) -> wire::question::QuestionDraw {
+        wire::question::QuestionDraw {
+            uuid: self.uuid,
+            question_uuid: self.question_uuid,
+            bucket_uuid: self.bucket_uuid,
+            user_uuid: self.user_uuid,
+            session_uuid: self.session_uuid,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl From<wire::question::QuestionDraw> for QuestionDraw {
+    fn from(draw: wire::question::QuestionDraw) -> Self {
+        QuestionDraw {
+            uuid: draw.uuid,
+            question_uuid: draw.question_uuid,
+            bucket_uuid: draw.bucket_uuid,
+            user_uuid: draw.user_uuid,
+            session_uuid: draw.session_uuid,
+            created_at: draw.created_at,
+        }
+    }
+}
+
+impl Into<wire::question::QuestionRevision> for QuestionRevision {
+    fn into(self) -> wire::question::QuestionRevision {
+        wire::question::QuestionRevision {
+            uuid: self.uuid,
+            question_uuid: self.question_uuid,
+            question_text: self.question_text,
+            user_uuid: self.user_uuid,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl From<wire::question::QuestionRevision> for QuestionRevision {
+    fn from(revision: wire::question::QuestionRevision) -> Self {
+        QuestionRevision {
+            uuid: revision.uuid,
+            question_uuid: revision.question_uuid,
+            question_text: revision.question_text,
+            user_uuid: revision.user_uuid,
+            created_at: revision.created_at,
+        }
+    }
+}
+
+impl Into<wire::question::QuestionSort> for QuestionSort {
+    fn into(self) -> wire::question::QuestionSort {
+        match self {
+            QuestionSort::Updated => wire::question::QuestionSort::Updated,
+            QuestionSort::Score => wire::question::QuestionSort::Score,
+        }
+    }
+}
+
+impl From<wire::question::QuestionSort> for QuestionSort {
+    fn from(sort: wire::question::QuestionSort) -> Self {
+        match sort {
+            wire::question::QuestionSort::Updated => QuestionSort::Updated,
+            wire::question::QuestionSort::Score => QuestionSort::Score,
+        }
+    }
+}
+
+impl Into<wire::question::ExportedQuestion> for QuestionExport {
+    fn into(self) -> wire::question::ExportedQuestion {
+        wire::question::ExportedQuestion {
+            question: self.question.into(),
+            tags: self.tags,
+            answer_count: self.answer_count,
+        }
+    }
+}
+
+impl From<wire::question::ExportedQuestion> for QuestionExport {
+    fn from(export: wire::question::ExportedQuestion) -> Self {
+        QuestionExport {
+            question: export.question.into(),
+            tags: export.tags,
+            answer_count: export.answer_count,
+        }
+    }
+}
+
+impl Into<wire::question::SimilarQuestion> for SimilarQuestion {
+    fn into(self) -> wire::question::SimilarQuestion {
+        wire::question::SimilarQuestion {
+            question: self.question.into(),
+            similarity: self.similarity,
+            exact: self.exact,
+        }
+    }
+}
+
+impl From<wire::question::SimilarQuestion> for SimilarQuestion {
+    fn from(similar: wire::question::SimilarQuestion) -> Self {
+        SimilarQuestion {
+            question: similar.question.into(),
+            similarity: similar.similarity,
+            exact: similar.exact,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Question vote conversions.
+
+use crate::question_vote::db_types::{QuestionVote, DOWNVOTE, UPVOTE};
+use wire;
+
+impl Into<wire::question::QuestionVote> for QuestionVote {
+    fn into(self) -> wire::question::QuestionVote {
+        let vote = if self.vote == DOWNVOTE {
+            wire::question::Vote::Down
+        } else {
+            wire::question::Vote::Up
+        };
+        wire::question::QuestionVote {
+            user_uuid: self.user_uuid,
+            question_uuid: self.question_uuid,
+            vote,
+            updated_at: self.updated_at,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl From<wire::question::QuestionVote> for QuestionVote {
+    fn from(vote: wire::question::QuestionVote) -> Self {
+        QuestionVote {
+            user_uuid: vote.user_uuid,
+            question_uuid: vote.question_uuid,
+            vote: match vote.vote {
+                wire::question::Vote::Up => UPVOTE,
+                wire::question::Vote::Down => DOWNVOTE,
+            },
+            updated_at: vote.updated_at,
+            created_at: vote.created_at,
+        }
+    }
+}
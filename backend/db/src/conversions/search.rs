@@ -0,0 +1,64 @@
+//! Search conversions
+
+use crate::search::db_types::{AnswerMatch, QuestionMatch, SearchResults};
+use wire;
+
+impl Into<wire::search::QuestionMatch> for QuestionMatch {
+    fn into(self) -> wire::search::QuestionMatch {
+        wire::search::QuestionMatch {
+            question: self.question.into(),
+            snippet: self.snippet,
+            rank: self.rank,
+        }
+    }
+}
+
+impl From<wire::search::QuestionMatch> for QuestionMatch {
+    fn from(question_match: wire::search::QuestionMatch) -> Self {
+        QuestionMatch {
+            question: question_match.question.into(),
+            snippet: question_match.snippet,
+            rank: question_match.rank,
+        }
+    }
+}
+
+impl Into<wire::search::AnswerMatch> for AnswerMatch {
+    fn into(self) -> wire::search::AnswerMatch {
+        wire::search::AnswerMatch {
+            answer: self.answer.into(),
+            bucket_uuid: self.bucket_uuid,
+            snippet: self.snippet,
+            rank: self.rank,
+        }
+    }
+}
+
+impl From<wire::search::AnswerMatch> for AnswerMatch {
+    fn from(answer_match: wire::search::AnswerMatch) -> Self {
+        AnswerMatch {
+            answer: answer_match.answer.into(),
+            bucket_uuid: answer_match.bucket_uuid,
+            snippet: answer_match.snippet,
+            rank: answer_match.rank,
+        }
+    }
+}
+
+impl Into<wire::search::SearchResults> for SearchResults {
+    fn into(self) -> wire::search::SearchResults {
+        wire::search::SearchResults {
+            questions: self.questions.into_iter().map(Into::into).collect(),
+            answers: self.answers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<wire::search::SearchResults> for SearchResults {
+    fn from(results: wire::search::SearchResults) -> Self {
+        SearchResults {
+            questions: results.questions.into_iter().map(Into::into).collect(),
+            answers: results.answers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
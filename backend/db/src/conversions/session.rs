@@ -0,0 +1,30 @@
+//! Session conversions
+
+use crate::session::db_types::Session;
+use wire;
+
+impl Into<wire::session::Session> for Session {
+    fn into(self) -> wire::session::Session {
+        wire::session::Session {
+            uuid: self.uuid,
+            bucket_uuid: self.bucket_uuid,
+            started_at: self.started_at,
+            ended_at: self.ended_at,
+            updated_at: self.updated_at,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl From<wire::session::Session> for Session {
+    fn from(session: wire::session::Session) -> Self {
+        Session {
+            uuid: session.uuid,
+            bucket_uuid: session.bucket_uuid,
+            started_at: session.started_at,
+            ended_at: session.ended_at,
+            updated_at: session.updated_at,
+            created_at: session.created_at,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Tag conversions.
+
+use crate::tag::db_types::Tag;
+use wire;
+
+impl Into<wire::tag::Tag> for Tag {
+    fn into(self) -> wire::tag::Tag {
+        wire::tag::Tag {
+            uuid: self.uuid,
+            bucket_uuid: self.bucket_uuid,
+            tag_name: self.tag_name,
+            updated_at: self.updated_at,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl From<wire::tag::Tag> for Tag {
+    fn from(tag: wire::tag::Tag) -> Self {
+        Tag {
+            uuid: tag.uuid,
+            bucket_uuid: tag.bucket_uuid,
+            tag_name: tag.tag_name,
+            updated_at: tag.updated_at,
+            created_at: tag.created_at,
+        }
+    }
+}
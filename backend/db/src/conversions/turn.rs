@@ -0,0 +1,30 @@
+//! Turn conversions
+
+use crate::turn::db_types::Turn;
+use wire;
+
+impl Into<wire::turn::Turn> for Turn {
+    fn into(self) -> wire::turn::Turn {
+        wire::turn::Turn {
+            bucket_uuid: self.bucket_uuid,
+            asker_uuid: self.asker_uuid,
+            answerer_uuid: self.answerer_uuid,
+            turn_number: self.turn_number,
+            updated_at: self.updated_at,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl From<wire::turn::Turn> for Turn {
+    fn from(turn: wire::turn::Turn) -> Self {
+        Turn {
+            bucket_uuid: turn.bucket_uuid,
+            asker_uuid: turn.asker_uuid,
+            answerer_uuid: turn.answerer_uuid,
+            turn_number: turn.turn_number,
+            updated_at: turn.updated_at,
+            created_at: turn.created_at,
+        }
+    }
+}
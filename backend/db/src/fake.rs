@@ -1,11 +1,22 @@
 //! Module for the database mock object.
 use crate::{
-    answer::db_types::Answer, bucket::db_types::Bucket,
-    bucket_user_relation::db_types::BucketUserRelation,
-    favorite_question::db_types::FavoriteQuestionRelation, question::db_types::Question,
+    answer::db_types::{Answer, AnswerEditToken},
+    audit::db_types::AuditEvent,
+    ban::db_types::Ban,
+    bucket::db_types::{Bucket, SlugRedirect},
+    bucket_user_relation::db_types::{BucketUserRelation, PermissionGrant},
+    favorite_question::db_types::FavoriteQuestionRelation,
+    invite::db_types::{Invite, JoinCode},
+    presence::db_types::Presence,
+    question::db_types::{Question, QuestionDraw, QuestionRevision},
+    question_vote::db_types::QuestionVote,
+    session::db_types::{Session, SessionParticipant},
+    tag::db_types::{QuestionTag, Tag},
+    turn::db_types::Turn,
     user::db_types::User,
 };
 use diesel::result::DatabaseErrorInformation;
+use rand::{rngs::StdRng, FromEntropy, RngCore, SeedableRng};
 
 /// This isn't expected to match on the info provided by the actual database.
 ///
@@ -50,8 +61,67 @@ impl DatabaseErrorInformation for DummyDbErrorInfo {
 pub struct FakeDatabase {
     pub(crate) users: Vec<User>,
     pub(crate) buckets: Vec<Bucket>,
+    pub(crate) slug_redirects: Vec<SlugRedirect>,
     pub(crate) user_bucket_relations: Vec<BucketUserRelation>,
+    pub(crate) permission_grants: Vec<PermissionGrant>,
     pub(crate) questions: Vec<Question>,
+    pub(crate) question_draws: Vec<QuestionDraw>,
+    pub(crate) question_revisions: Vec<QuestionRevision>,
+    pub(crate) question_votes: Vec<QuestionVote>,
     pub(crate) answers: Vec<Answer>,
+    pub(crate) answer_edit_tokens: Vec<AnswerEditToken>,
     pub(crate) favorite_question_relations: Vec<FavoriteQuestionRelation>,
+    pub(crate) turns: Vec<Turn>,
+    pub(crate) presences: Vec<Presence>,
+    pub(crate) invites: Vec<Invite>,
+    pub(crate) join_codes: Vec<JoinCode>,
+    pub(crate) audit_events: Vec<AuditEvent>,
+    pub(crate) bans: Vec<Ban>,
+    pub(crate) sessions: Vec<Session>,
+    pub(crate) session_participants: Vec<SessionParticipant>,
+    pub(crate) tags: Vec<Tag>,
+    pub(crate) question_tags: Vec<QuestionTag>,
+    pub(crate) rng: FakeRng,
+}
+
+impl FakeDatabase {
+    /// Creates an empty fake database whose random draws are determined by the provided seed.
+    ///
+    /// This allows tests that depend on randomness to be deterministic.
+    pub fn with_seed(seed: u64) -> Self {
+        FakeDatabase {
+            rng: FakeRng(StdRng::seed_from_u64(seed)),
+            ..Default::default()
+        }
+    }
+}
+
+/// Source of randomness for the fake database.
+///
+/// It is seeded from entropy by default, but can be seeded explicitly via `FakeDatabase::with_seed`.
+#[derive(Clone, Debug)]
+pub struct FakeRng(StdRng);
+
+impl Default for FakeRng {
+    fn default() -> Self {
+        FakeRng(StdRng::from_entropy())
+    }
+}
+
+impl RngCore for FakeRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
 }
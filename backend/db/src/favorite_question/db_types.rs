@@ -6,7 +6,16 @@ use uuid::Uuid;
 
 /// A relation for recording user's favorite questions.
 #[derive(
-    Clone, Copy, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    PartialOrd,
+    Identifiable,
+    Queryable,
+    Insertable,
+    Serialize,
+    Deserialize,
 )]
 #[primary_key(user_uuid, question_uuid)]
 #[table_name = "user_question_favorite_relation"]
@@ -6,8 +6,10 @@ use crate::{
         db_types::{FavoriteQuestionRelation, NewFavoriteQuestionRelation},
         interface::FavoriteQuestionRelationRepository,
     },
+    page::{paginate, Page, Pagination, RecencySort},
     question::db_types::Question,
 };
+use chrono::NaiveDateTime;
 use diesel::result::{DatabaseErrorKind, Error};
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
@@ -51,20 +53,44 @@ impl FavoriteQuestionRelationRepository for Arc<Mutex<FakeDatabase>> {
         Ok(())
     }
 
-    fn get_favorite_questions(&self, user_uuid: Uuid) -> Result<Vec<Question>, Error> {
+    fn get_favorite_questions(
+        &self,
+        user_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Question>, Error> {
         let db = self.lock().unwrap();
-        let question_uuids: Vec<Uuid> = db
+        let favorites: Vec<(NaiveDateTime, Question)> = db
             .favorite_question_relations
             .iter()
             .filter(|f| f.user_uuid == user_uuid)
-            .map(|f| f.question_uuid)
-            .collect();
-        let questions = db
-            .questions
-            .iter()
-            .filter(|q| question_uuids.iter().any(|uuid| &q.uuid == uuid))
-            .cloned()
+            .filter_map(|f| {
+                let question = db.questions.iter().find(|q| q.uuid == f.question_uuid)?;
+                Some((f.created_at, question.clone()))
+            })
             .collect();
-        Ok(questions)
+        let last = match pagination.after {
+            Some(after) => Some(
+                favorites
+                    .iter()
+                    .find(|(_, q)| q.uuid == after)
+                    .cloned()
+                    .ok_or(Error::NotFound)?,
+            ),
+            None => None,
+        };
+        let order =
+            |(a_favorited_at, a): &(NaiveDateTime, Question),
+             (b_favorited_at, b): &(NaiveDateTime, Question)| match pagination.sort {
+                RecencySort::Newest => (b_favorited_at, b.uuid).cmp(&(a_favorited_at, a.uuid)),
+                RecencySort::Oldest => (a_favorited_at, a.uuid).cmp(&(b_favorited_at, b.uuid)),
+            };
+        let page = paginate(
+            favorites,
+            last.as_ref(),
+            pagination.limit,
+            order,
+            |(_, q)| q.uuid,
+        );
+        Ok(page.map(|(_, question)| question))
     }
 }
@@ -1,6 +1,8 @@
 //! Specification of what functions are supported for storing data for favorite questions.
 use crate::{
-    favorite_question::db_types::NewFavoriteQuestionRelation, question::db_types::Question,
+    favorite_question::db_types::NewFavoriteQuestionRelation,
+    page::{Page, Pagination, RecencySort},
+    question::db_types::Question,
 };
 use diesel::QueryResult;
 use uuid::Uuid;
@@ -11,6 +13,12 @@ pub trait FavoriteQuestionRelationRepository {
     fn favorite_question(&self, relation: NewFavoriteQuestionRelation) -> QueryResult<()>;
     /// Removes the relation
     fn unfavorite_question(&self, relation: NewFavoriteQuestionRelation) -> QueryResult<()>;
-    /// Gets the favorite quesitons.
-    fn get_favorite_questions(&self, user_uuid: Uuid) -> QueryResult<Vec<Question>>;
+    /// Gets a page of the favorite quesitons, sorted by when they were favorited.
+    ///
+    /// If the question the page should start after isn't a favorite anymore, this will return a `NotFound` error.
+    fn get_favorite_questions(
+        &self,
+        user_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<Question>>;
 }
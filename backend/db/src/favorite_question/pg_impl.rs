@@ -4,10 +4,12 @@ use crate::{
         db_types::{FavoriteQuestionRelation, NewFavoriteQuestionRelation},
         interface::FavoriteQuestionRelationRepository,
     },
+    page::{Page, Pagination, RecencySort},
     question::db_types::Question,
     schema::{question, user_question_favorite_relation},
     AsConnRef,
 };
+use chrono::NaiveDateTime;
 use diesel::{
     query_dsl::{QueryDsl, RunQueryDsl},
     result::Error,
@@ -37,14 +39,53 @@ where
         diesel::delete(target).execute(self.as_conn()).map(|_| ())
     }
 
-    fn get_favorite_questions(&self, user_uuid: Uuid) -> Result<Vec<Question>, Error> {
+    fn get_favorite_questions(
+        &self,
+        user_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Question>, Error> {
         use user_question_favorite_relation as favorite;
-        favorite::table
-            .filter(favorite::user_uuid.eq(user_uuid))
-            .select(favorite::question_uuid)
+        let conn = self.as_conn();
+        let query = favorite::table
             .inner_join(question::table)
-            .order_by(favorite::updated_at)
+            .filter(favorite::user_uuid.eq(user_uuid))
+            .into_boxed();
+        // Only the questions favorited after the last one of the previous page are on this page.
+        let query = match pagination.after {
+            Some(after) => {
+                let favorited_at: NaiveDateTime = favorite::table
+                    .find((user_uuid, after))
+                    .select(favorite::created_at)
+                    .first(conn)?;
+                match pagination.sort {
+                    RecencySort::Newest => query.filter(
+                        favorite::created_at
+                            .lt(favorited_at)
+                            .or(favorite::created_at
+                                .eq(favorited_at)
+                                .and(favorite::question_uuid.lt(after))),
+                    ),
+                    RecencySort::Oldest => query.filter(
+                        favorite::created_at
+                            .gt(favorited_at)
+                            .or(favorite::created_at
+                                .eq(favorited_at)
+                                .and(favorite::question_uuid.gt(after))),
+                    ),
+                }
+            }
+            None => query,
+        };
+        let query = match pagination.sort {
+            RecencySort::Newest => {
+                query.order_by((favorite::created_at.desc(), favorite::question_uuid.desc()))
+            }
+            RecencySort::Oldest => query.order_by((favorite::created_at, favorite::question_uuid)),
+        };
+        let questions = query
             .select(question::all_columns)
-            .get_results(self.as_conn())
+            .limit(pagination.fetch_limit())
+            .get_results(conn)?;
+        Ok(Page::from_fetched(questions, pagination.limit, |q| q.uuid))
     }
 }
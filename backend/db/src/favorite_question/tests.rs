@@ -1,5 +1,6 @@
 use crate::{
     favorite_question::db_types::NewFavoriteQuestionRelation,
+    page::{Pagination, RecencySort},
     test::{question_fixture::QuestionFixture, util::execute_test},
     BoxedRepository,
 };
@@ -47,8 +48,12 @@ fn get_favorite_questions() {
             .expect("Should be able to favorite question");
 
         let favorites = db
-            .get_favorite_questions(fixture.user.uuid)
-            .expect("Sholud get favorite questions");
+            .get_favorite_questions(
+                fixture.user.uuid,
+                Pagination::first(10, RecencySort::Oldest),
+            )
+            .expect("Sholud get favorite questions")
+            .items;
         assert_eq!(favorites.len(), 1);
         assert_eq!(favorites[0], fixture.question1);
 
@@ -58,11 +63,28 @@ fn get_favorite_questions() {
             .expect("Should be able to favorite question");
 
         let favorites = db
-            .get_favorite_questions(fixture.user.uuid)
-            .expect("Sholud get favorite questions");
+            .get_favorite_questions(
+                fixture.user.uuid,
+                Pagination::first(10, RecencySort::Oldest),
+            )
+            .expect("Sholud get favorite questions")
+            .items;
         assert_eq!(favorites.len(), 2);
-        assert_eq!(favorites[0], fixture.question1); // Oldest - TODO should be other way around.
-        assert_eq!(favorites[1], fixture.question2); // is Ordering guaranteed
-                                                     // TODO order favorite questions by date
+        assert_eq!(favorites[0], fixture.question1);
+        assert_eq!(favorites[1], fixture.question2);
+
+        let newest = Pagination::first(1, RecencySort::Newest);
+        let page = db
+            .get_favorite_questions(fixture.user.uuid, newest)
+            .expect("Should get favorite questions");
+        assert_eq!(page.items, vec![fixture.question2.clone()]);
+        let page = db
+            .get_favorite_questions(
+                fixture.user.uuid,
+                newest.next(&page).expect("Should have another page"),
+            )
+            .expect("Should get favorite questions");
+        assert_eq!(page.items, vec![fixture.question1.clone()]);
+        assert_eq!(page.next_cursor, None);
     });
 }
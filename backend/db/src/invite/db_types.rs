@@ -0,0 +1,133 @@
+//! Database types for invites and join codes.
+use crate::{
+    bucket_user_relation::db_types::NewBucketUserRelation,
+    schema::{bucket_invite, bucket_join_code},
+};
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An invite to a bucket.
+/// Redeeming it lets a user join the bucket, even if it is exclusive.
+#[derive(
+    Clone, Copy, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize,
+)]
+#[primary_key(uuid)]
+#[table_name = "bucket_invite"]
+pub struct Invite {
+    /// The unique identifier of the invite, which is shared with invitees.
+    pub uuid: Uuid,
+    /// The bucket the invite is for.
+    pub bucket_uuid: Uuid,
+    /// The user who created the invite.
+    pub user_uuid: Option<Uuid>,
+    /// When the invite stops being usable, if ever.
+    pub expires_at: Option<NaiveDateTime>,
+    /// How many users can join with the invite, if there is a limit.
+    pub max_uses: Option<i32>,
+    /// How many users have joined with the invite.
+    pub uses: i32,
+    /// Can the joining user set the visibility of the bucket.
+    pub set_public_permission: bool,
+    /// Can the joining user enable drawing from the bucket.
+    pub set_drawing_permission: bool,
+    /// Can the joining user set the bucket to private.
+    pub set_exclusive_permission: bool,
+    /// Can the joining user kick other users.
+    pub kick_permission: bool,
+    /// Can the joining user grant permissions to other users.
+    pub grant_permissions_permission: bool,
+    /// Can the joining user edit any of the bucket's questions.
+    pub moderate_questions_permission: bool,
+    /// Can the joining user delete any of the bucket's questions.
+    pub delete_questions_permission: bool,
+    /// Can the joining user invite others to the bucket.
+    pub invite_permission: bool,
+    /// When the invite was created.
+    pub created_at: NaiveDateTime,
+}
+
+impl Invite {
+    /// Can the invite still be used to join its bucket at the given time.
+    pub fn is_redeemable(&self, now: NaiveDateTime) -> bool {
+        let expired = self
+            .expires_at
+            .map(|expires_at| expires_at <= now)
+            .unwrap_or(false);
+        let used_up = self
+            .max_uses
+            .map(|max_uses| self.uses >= max_uses)
+            .unwrap_or(false);
+        !expired && !used_up
+    }
+
+    /// The relation that is created when the user joins the bucket with this invite.
+    pub fn new_relation(&self, user_uuid: Uuid) -> NewBucketUserRelation {
+        NewBucketUserRelation {
+            user_uuid,
+            bucket_uuid: self.bucket_uuid,
+            set_public_permission: self.set_public_permission,
+            set_drawing_permission: self.set_drawing_permission,
+            set_exclusive_permission: self.set_exclusive_permission,
+            kick_permission: self.kick_permission,
+            grant_permissions_permission: self.grant_permissions_permission,
+            moderate_questions_permission: self.moderate_questions_permission,
+            delete_questions_permission: self.delete_questions_permission,
+            invite_permission: self.invite_permission,
+        }
+    }
+}
+
+/// Structure used to create an invite.
+#[derive(Clone, Copy, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "bucket_invite"]
+pub struct NewInvite {
+    /// The bucket the invite is for.
+    pub bucket_uuid: Uuid,
+    /// The user who created the invite.
+    pub user_uuid: Option<Uuid>,
+    /// When the invite stops being usable, if ever.
+    pub expires_at: Option<NaiveDateTime>,
+    /// How many users can join with the invite, if there is a limit.
+    pub max_uses: Option<i32>,
+    /// Can the joining user set the visibility of the bucket.
+    pub set_public_permission: bool,
+    /// Can the joining user enable drawing from the bucket.
+    pub set_drawing_permission: bool,
+    /// Can the joining user set the bucket to private.
+    pub set_exclusive_permission: bool,
+    /// Can the joining user kick other users.
+    pub kick_permission: bool,
+    /// Can the joining user grant permissions to other users.
+    pub grant_permissions_permission: bool,
+    /// Can the joining user edit any of the bucket's questions.
+    pub moderate_questions_permission: bool,
+    /// Can the joining user delete any of the bucket's questions.
+    pub delete_questions_permission: bool,
+    /// Can the joining user invite others to the bucket.
+    pub invite_permission: bool,
+}
+
+/// A short code that can be typed in to join a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize)]
+#[primary_key(bucket_uuid)]
+#[table_name = "bucket_join_code"]
+pub struct JoinCode {
+    /// The bucket the code is for.
+    pub bucket_uuid: Uuid,
+    /// The code.
+    pub join_code: String,
+    /// When the code was created.
+    pub created_at: NaiveDateTime,
+}
+
+/// Structure used to set the join code of a bucket.
+#[derive(Clone, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "bucket_join_code"]
+pub struct NewJoinCode {
+    /// The bucket the code is for.
+    pub bucket_uuid: Uuid,
+    /// The code.
+    pub join_code: String,
+}
@@ -0,0 +1,151 @@
+//! Mock impl
+use crate::{
+    bucket_user_relation::{db_types::BucketUserRelation, interface::BucketUserRelationRepository},
+    fake::{DummyDbErrorInfo, FakeDatabase},
+    invite::{
+        db_types::{Invite, JoinCode, NewInvite, NewJoinCode},
+        interface::InviteRepository,
+    },
+    page::{paginate, Page, Pagination, RecencySort},
+};
+use diesel::result::{DatabaseErrorKind, Error};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+impl InviteRepository for Arc<Mutex<FakeDatabase>> {
+    fn create_invite(&self, new_invite: NewInvite) -> Result<Invite, Error> {
+        let mut db = self.lock().unwrap();
+        if !db.buckets.iter().any(|b| b.uuid == new_invite.bucket_uuid) {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::ForeignKeyViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+        let invite = Invite {
+            uuid: Uuid::new_v4(),
+            bucket_uuid: new_invite.bucket_uuid,
+            user_uuid: new_invite.user_uuid,
+            expires_at: new_invite.expires_at,
+            max_uses: new_invite.max_uses,
+            uses: 0,
+            set_public_permission: new_invite.set_public_permission,
+            set_drawing_permission: new_invite.set_drawing_permission,
+            set_exclusive_permission: new_invite.set_exclusive_permission,
+            kick_permission: new_invite.kick_permission,
+            grant_permissions_permission: new_invite.grant_permissions_permission,
+            moderate_questions_permission: new_invite.moderate_questions_permission,
+            delete_questions_permission: new_invite.delete_questions_permission,
+            invite_permission: new_invite.invite_permission,
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+        db.invites.push(invite);
+        Ok(invite)
+    }
+
+    fn get_invite(&self, invite_uuid: Uuid) -> Result<Invite, Error> {
+        let db = self.lock().unwrap();
+        db.invites
+            .iter()
+            .find(|i| i.uuid == invite_uuid)
+            .cloned()
+            .ok_or_else(|| Error::NotFound)
+    }
+
+    fn get_invites_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Invite>, Error> {
+        let db = self.lock().unwrap();
+        let last = match pagination.after {
+            Some(after) => Some(
+                db.invites
+                    .iter()
+                    .find(|i| i.uuid == after)
+                    .ok_or(Error::NotFound)?,
+            ),
+            None => None,
+        };
+        let invites = db
+            .invites
+            .iter()
+            .filter(|i| i.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        let order = |a: &Invite, b: &Invite| match pagination.sort {
+            RecencySort::Newest => (b.created_at, b.uuid).cmp(&(a.created_at, a.uuid)),
+            RecencySort::Oldest => (a.created_at, a.uuid).cmp(&(b.created_at, b.uuid)),
+        };
+        Ok(paginate(invites, last, pagination.limit, order, |i| i.uuid))
+    }
+
+    fn delete_invite(&self, invite_uuid: Uuid) -> Result<Invite, Error> {
+        let mut db = self.lock().unwrap();
+        let index = db
+            .invites
+            .iter()
+            .position(|i| i.uuid == invite_uuid)
+            .ok_or_else(|| Error::NotFound)?;
+        Ok(db.invites.remove(index))
+    }
+
+    fn redeem_invite(
+        &self,
+        invite_uuid: Uuid,
+        user_uuid: Uuid,
+    ) -> Result<BucketUserRelation, Error> {
+        let invite = self.get_invite(invite_uuid)?;
+        if !invite.is_redeemable(chrono::Utc::now().naive_utc()) {
+            return Err(Error::NotFound);
+        }
+
+        // The use is only counted if the user could be added.
+        let relation = self.add_user_to_bucket(invite.new_relation(user_uuid))?;
+
+        let mut db = self.lock().unwrap();
+        if let Some(invite) = db.invites.iter_mut().find(|i| i.uuid == invite_uuid) {
+            invite.uses += 1;
+        }
+        Ok(relation)
+    }
+
+    fn set_join_code(&self, new_join_code: NewJoinCode) -> Result<JoinCode, Error> {
+        let mut db = self.lock().unwrap();
+        if db.join_codes.iter().any(|c| {
+            c.join_code == new_join_code.join_code && c.bucket_uuid != new_join_code.bucket_uuid
+        }) {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::UniqueViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+
+        let join_code = JoinCode {
+            bucket_uuid: new_join_code.bucket_uuid,
+            join_code: new_join_code.join_code,
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+        db.join_codes
+            .retain(|c| c.bucket_uuid != join_code.bucket_uuid);
+        db.join_codes.push(join_code.clone());
+        Ok(join_code)
+    }
+
+    fn get_join_code(&self, bucket_uuid: Uuid) -> Result<JoinCode, Error> {
+        let db = self.lock().unwrap();
+        db.join_codes
+            .iter()
+            .find(|c| c.bucket_uuid == bucket_uuid)
+            .cloned()
+            .ok_or_else(|| Error::NotFound)
+    }
+
+    fn get_join_code_by_code(&self, join_code: String) -> Result<JoinCode, Error> {
+        let db = self.lock().unwrap();
+        db.join_codes
+            .iter()
+            .find(|c| c.join_code == join_code)
+            .cloned()
+            .ok_or_else(|| Error::NotFound)
+    }
+}
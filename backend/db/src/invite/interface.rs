@@ -0,0 +1,37 @@
+//! Specification of functions.
+use crate::{
+    bucket_user_relation::db_types::BucketUserRelation,
+    invite::db_types::{Invite, JoinCode, NewInvite, NewJoinCode},
+    page::{Page, Pagination, RecencySort},
+};
+use diesel::QueryResult;
+use uuid::Uuid;
+
+/// Functions for specifically working with invites and join codes.
+pub trait InviteRepository {
+    /// Creates an invite.
+    fn create_invite(&self, new_invite: NewInvite) -> QueryResult<Invite>;
+    /// Gets an invite.
+    fn get_invite(&self, invite_uuid: Uuid) -> QueryResult<Invite>;
+    /// Gets a page of the bucket's invites.
+    ///
+    /// If the invite the page should start after has been deleted, this will return a `NotFound` error.
+    fn get_invites_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<Invite>>;
+    /// Deletes an invite, so it can no longer be used.
+    fn delete_invite(&self, invite_uuid: Uuid) -> QueryResult<Invite>;
+    /// Adds the user to the invite's bucket with the invite's permissions, and counts the use.
+    ///
+    /// If the invite has expired or has been used up, this will return a `NotFound` error.
+    /// If the user is already in the bucket, the use is not counted.
+    fn redeem_invite(&self, invite_uuid: Uuid, user_uuid: Uuid) -> QueryResult<BucketUserRelation>;
+    /// Sets the join code for the bucket, replacing the previous code if there was one.
+    fn set_join_code(&self, new_join_code: NewJoinCode) -> QueryResult<JoinCode>;
+    /// Gets the join code for the bucket.
+    fn get_join_code(&self, bucket_uuid: Uuid) -> QueryResult<JoinCode>;
+    /// Gets the join code that matches the code exactly.
+    fn get_join_code_by_code(&self, join_code: String) -> QueryResult<JoinCode>;
+}
@@ -0,0 +1,132 @@
+//! Implementation of the specified interfaces for PgConnection.
+use crate::{
+    bucket_user_relation::db_types::BucketUserRelation,
+    invite::{
+        db_types::{Invite, JoinCode, NewInvite, NewJoinCode},
+        interface::InviteRepository,
+    },
+    page::{Page, Pagination, RecencySort},
+    schema::{bucket_invite, bucket_join_code, bucket_user_relation},
+    AsConnRef,
+};
+use diesel::{
+    dsl::now,
+    query_dsl::{QueryDsl, RunQueryDsl},
+    result::Error,
+    BoolExpressionMethods, Connection, ExpressionMethods,
+};
+use uuid::Uuid;
+
+impl<T> InviteRepository for T
+where
+    T: AsConnRef,
+{
+    fn create_invite(&self, new_invite: NewInvite) -> Result<Invite, Error> {
+        crate::util::create_row(bucket_invite::table, new_invite, self.as_conn())
+    }
+
+    fn get_invite(&self, invite_uuid: Uuid) -> Result<Invite, Error> {
+        bucket_invite::table
+            .find(invite_uuid)
+            .get_result(self.as_conn())
+    }
+
+    fn get_invites_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Invite>, Error> {
+        let conn = self.as_conn();
+        let query = bucket_invite::table
+            .filter(bucket_invite::bucket_uuid.eq(bucket_uuid))
+            .into_boxed();
+        // Only the invites that sort after the last one of the previous page are on this page.
+        let query = match pagination.after {
+            Some(after) => {
+                let last: Invite = bucket_invite::table.find(after).first(conn)?;
+                match pagination.sort {
+                    RecencySort::Newest => query.filter(
+                        bucket_invite::created_at
+                            .lt(last.created_at)
+                            .or(bucket_invite::created_at
+                                .eq(last.created_at)
+                                .and(bucket_invite::uuid.lt(last.uuid))),
+                    ),
+                    RecencySort::Oldest => query.filter(
+                        bucket_invite::created_at
+                            .gt(last.created_at)
+                            .or(bucket_invite::created_at
+                                .eq(last.created_at)
+                                .and(bucket_invite::uuid.gt(last.uuid))),
+                    ),
+                }
+            }
+            None => query,
+        };
+        let query = match pagination.sort {
+            RecencySort::Newest => {
+                query.order_by((bucket_invite::created_at.desc(), bucket_invite::uuid.desc()))
+            }
+            RecencySort::Oldest => query.order_by((bucket_invite::created_at, bucket_invite::uuid)),
+        };
+        let invites = query.limit(pagination.fetch_limit()).get_results(conn)?;
+        Ok(Page::from_fetched(invites, pagination.limit, |i| i.uuid))
+    }
+
+    fn delete_invite(&self, invite_uuid: Uuid) -> Result<Invite, Error> {
+        diesel::delete(bucket_invite::table.find(invite_uuid)).get_result(self.as_conn())
+    }
+
+    fn redeem_invite(
+        &self,
+        invite_uuid: Uuid,
+        user_uuid: Uuid,
+    ) -> Result<BucketUserRelation, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            // Locking the row keeps concurrent redemptions from exceeding the max uses.
+            let invite: Invite = bucket_invite::table
+                .find(invite_uuid)
+                .for_update()
+                .get_result(conn)?;
+            if !invite.is_redeemable(chrono::Utc::now().naive_utc()) {
+                return Err(Error::NotFound);
+            }
+
+            // If the user is already in the bucket, this fails and rolls back the use.
+            let relation = diesel::insert_into(bucket_user_relation::table)
+                .values(invite.new_relation(user_uuid))
+                .get_result(conn)?;
+
+            diesel::update(bucket_invite::table.find(invite_uuid))
+                .set(bucket_invite::uses.eq(bucket_invite::uses + 1))
+                .execute(conn)?;
+
+            Ok(relation)
+        })
+    }
+
+    fn set_join_code(&self, new_join_code: NewJoinCode) -> Result<JoinCode, Error> {
+        diesel::insert_into(bucket_join_code::table)
+            .values(&new_join_code)
+            .on_conflict(bucket_join_code::bucket_uuid)
+            .do_update()
+            .set((
+                bucket_join_code::join_code.eq(&new_join_code.join_code),
+                bucket_join_code::created_at.eq(now),
+            ))
+            .get_result(self.as_conn())
+    }
+
+    fn get_join_code(&self, bucket_uuid: Uuid) -> Result<JoinCode, Error> {
+        bucket_join_code::table
+            .find(bucket_uuid)
+            .get_result(self.as_conn())
+    }
+
+    fn get_join_code_by_code(&self, join_code: String) -> Result<JoinCode, Error> {
+        bucket_join_code::table
+            .filter(bucket_join_code::join_code.eq(join_code))
+            .get_result(self.as_conn())
+    }
+}
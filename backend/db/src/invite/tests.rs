@@ -0,0 +1,147 @@
+use crate::{
+    invite::db_types::{NewInvite, NewJoinCode},
+    page::{Pagination, RecencySort},
+    test::{bucket_user_relation_fixture::UserBucketRelationFixture, util::execute_test},
+    BoxedRepository,
+};
+use chrono::{Duration, Utc};
+use diesel::result::{DatabaseErrorKind, Error};
+
+fn new_invite(fixture: &UserBucketRelationFixture) -> NewInvite {
+    NewInvite {
+        bucket_uuid: fixture.bucket.uuid,
+        user_uuid: Some(fixture.user1.uuid),
+        expires_at: None,
+        max_uses: None,
+        set_public_permission: false,
+        set_drawing_permission: true,
+        set_exclusive_permission: false,
+        kick_permission: false,
+        grant_permissions_permission: false,
+        moderate_questions_permission: false,
+        delete_questions_permission: false,
+        invite_permission: true,
+    }
+}
+
+#[test]
+fn create_and_list_invites() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let first = db
+            .create_invite(new_invite(fixture))
+            .expect("Should create invite");
+        let second = db
+            .create_invite(new_invite(fixture))
+            .expect("Should create invite");
+        assert_eq!(first.uses, 0);
+
+        let invites = db
+            .get_invites_for_bucket(
+                fixture.bucket.uuid,
+                Pagination::first(10, RecencySort::Newest),
+            )
+            .expect("Should get invites")
+            .items;
+        assert_eq!(invites.len(), 2);
+        assert_eq!(invites[0].uuid, second.uuid);
+
+        db.delete_invite(first.uuid).expect("Should delete invite");
+        let error = db.get_invite(first.uuid).unwrap_err();
+        assert_eq!(error, Error::NotFound);
+    });
+}
+
+#[test]
+fn redeem_invite_grants_preset_permissions() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let invite = db
+            .create_invite(new_invite(fixture))
+            .expect("Should create invite");
+        let relation = db
+            .redeem_invite(invite.uuid, fixture.user2.uuid)
+            .expect("Should redeem invite");
+        assert_eq!(relation.bucket_uuid, fixture.bucket.uuid);
+        assert!(relation.set_drawing_permission);
+        assert!(relation.invite_permission);
+        assert!(!relation.kick_permission);
+
+        let invite = db.get_invite(invite.uuid).expect("Should get invite");
+        assert_eq!(invite.uses, 1);
+    });
+}
+
+#[test]
+fn redeeming_twice_does_not_count_a_use() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let invite = db
+            .create_invite(new_invite(fixture))
+            .expect("Should create invite");
+        let error = db
+            .redeem_invite(invite.uuid, fixture.user1.uuid)
+            .unwrap_err();
+        match error {
+            Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {}
+            other => panic!("Expected a unique violation, got: {:?}", other),
+        }
+
+        let invite = db.get_invite(invite.uuid).expect("Should get invite");
+        assert_eq!(invite.uses, 0);
+    });
+}
+
+#[test]
+fn used_up_and_expired_invites_cant_be_redeemed() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let used_up = db
+            .create_invite(NewInvite {
+                max_uses: Some(0),
+                ..new_invite(fixture)
+            })
+            .expect("Should create invite");
+        let expired = db
+            .create_invite(NewInvite {
+                expires_at: Some(Utc::now().naive_utc() - Duration::minutes(1)),
+                ..new_invite(fixture)
+            })
+            .expect("Should create invite");
+
+        let error = db
+            .redeem_invite(used_up.uuid, fixture.user2.uuid)
+            .unwrap_err();
+        assert_eq!(error, Error::NotFound);
+        let error = db
+            .redeem_invite(expired.uuid, fixture.user2.uuid)
+            .unwrap_err();
+        assert_eq!(error, Error::NotFound);
+    });
+}
+
+#[test]
+fn set_join_code_replaces_previous_code() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        db.set_join_code(NewJoinCode {
+            bucket_uuid: fixture.bucket.uuid,
+            join_code: "ABCD2345".to_string(),
+        })
+        .expect("Should set join code");
+        db.set_join_code(NewJoinCode {
+            bucket_uuid: fixture.bucket.uuid,
+            join_code: "WXYZ6789".to_string(),
+        })
+        .expect("Should replace join code");
+
+        let join_code = db
+            .get_join_code(fixture.bucket.uuid)
+            .expect("Should get join code");
+        assert_eq!(join_code.join_code, "WXYZ6789");
+
+        let found = db
+            .get_join_code_by_code("WXYZ6789".to_string())
+            .expect("Should find join code");
+        assert_eq!(found.bucket_uuid, fixture.bucket.uuid);
+        let error = db
+            .get_join_code_by_code("ABCD2345".to_string())
+            .unwrap_err();
+        assert_eq!(error, Error::NotFound);
+    });
+}
@@ -15,22 +15,39 @@
 extern crate diesel;
 
 pub mod answer;
+pub mod audit;
+pub mod backup;
+pub mod ban;
 pub mod bucket;
 pub mod bucket_user_relation;
 mod conversions;
 pub mod fake;
 pub mod favorite_question;
+pub mod invite;
+pub mod page;
+pub mod presence;
 pub mod question;
+pub mod question_vote;
 mod schema;
+pub mod search;
+pub mod session;
+pub mod tag;
 pub mod test;
+pub mod turn;
 pub mod user;
 mod util;
 
 use crate::{
-    answer::interface::AnswerRepository, bucket::interface::BucketRepository,
+    answer::interface::AnswerRepository, audit::interface::AuditRepository,
+    backup::interface::BackupRepository, ban::interface::BanRepository,
+    bucket::interface::BucketRepository,
     bucket_user_relation::interface::BucketUserRelationRepository, fake::FakeDatabase,
     favorite_question::interface::FavoriteQuestionRelationRepository,
-    question::interface::QuestionRepository, user::interface::UserRepository,
+    invite::interface::InviteRepository, presence::interface::PresenceRepository,
+    question::interface::QuestionRepository,
+    question_vote::interface::QuestionVoteRepository, search::interface::SearchRepository,
+    session::interface::SessionRepository, tag::interface::TagRepository,
+    turn::interface::TurnRepository, user::interface::UserRepository,
 };
 use diesel::PgConnection;
 use pool::{Pool, PooledConn};
@@ -118,6 +135,16 @@ pub trait Repository:
     + AnswerRepository
     + FavoriteQuestionRelationRepository
     + UserRepository
+    + TurnRepository
+    + SessionRepository
+    + QuestionVoteRepository
+    + TagRepository
+    + PresenceRepository
+    + InviteRepository
+    + AuditRepository
+    + BanRepository
+    + BackupRepository
+    + SearchRepository
 {
 }
 
@@ -129,6 +156,16 @@ impl<T> Repository for T where
         + AnswerRepository
         + FavoriteQuestionRelationRepository
         + UserRepository
+        + TurnRepository
+        + SessionRepository
+        + QuestionVoteRepository
+        + TagRepository
+        + PresenceRepository
+        + InviteRepository
+        + AuditRepository
+        + BanRepository
+        + BackupRepository
+        + SearchRepository
 {
 }
 
@@ -0,0 +1,108 @@
+//! Cursor pagination of lists.
+//!
+//! A page is requested with the uuid of the last item of the previous page as its cursor,
+//! and picks up right after where that item sorts.
+//! Because the cursor marks a position in the order rather than a number of items to skip,
+//! items that are added or removed while someone is paging through a list don't shift the pages after them.
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+/// Selects a page of a list.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pagination<S> {
+    /// The last item of the previous page.
+    /// The first page is selected if this isn't specified.
+    pub after: Option<Uuid>,
+    /// How many items to get at most.
+    pub limit: i64,
+    /// The order of the list.
+    pub sort: S,
+}
+
+impl<S> Pagination<S> {
+    /// Selects the first page of the list.
+    pub fn first(limit: i64, sort: S) -> Self {
+        Pagination {
+            after: None,
+            limit,
+            sort,
+        }
+    }
+
+    /// Selects the page that comes after the given one.
+    pub fn next<T>(self, page: &Page<T>) -> Option<Self> {
+        page.next_cursor.map(|after| Pagination {
+            after: Some(after),
+            ..self
+        })
+    }
+
+    /// How many items to fetch for the page.
+    ///
+    /// One more than the limit is fetched, to find out if there is another page after this one.
+    pub(crate) fn fetch_limit(&self) -> i64 {
+        self.limit.max(0) + 1
+    }
+}
+
+/// A page of a list.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// The items on the page.
+    pub items: Vec<T>,
+    /// The cursor to get the next page with.
+    /// This is `None` for the last page.
+    pub next_cursor: Option<Uuid>,
+}
+
+impl<T> Page<T> {
+    /// Makes a page out of items fetched with `Pagination::fetch_limit`.
+    pub(crate) fn from_fetched(mut items: Vec<T>, limit: i64, uuid: impl Fn(&T) -> Uuid) -> Self {
+        let limit = limit.max(0) as usize;
+        let next_cursor = if items.len() > limit {
+            items.truncate(limit);
+            items.last().map(uuid)
+        } else {
+            None
+        };
+        Page { items, next_cursor }
+    }
+
+    /// Converts the items on the page.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+/// The order of lists that are sorted by when their items were made.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecencySort {
+    /// Most recently created first.
+    Newest,
+    /// Least recently created first.
+    Oldest,
+}
+
+/// Sorts the items and picks out the page after the `after` item.
+///
+/// This is how the fake repository pages through lists.
+/// The order must be total, which is achieved by comparing the items' uuids when everything else is equal.
+pub(crate) fn paginate<T>(
+    mut items: Vec<T>,
+    after: Option<&T>,
+    limit: i64,
+    order: impl Fn(&T, &T) -> Ordering,
+    uuid: impl Fn(&T) -> Uuid,
+) -> Page<T> {
+    items.sort_by(|a, b| order(a, b));
+    let items = items
+        .into_iter()
+        .filter(|item| after.map_or(true, |after| order(item, after) == Ordering::Greater))
+        .take(limit.max(0) as usize + 1)
+        .collect();
+    Page::from_fetched(items, limit, uuid)
+}
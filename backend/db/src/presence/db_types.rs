@@ -0,0 +1,31 @@
+//! Database types for tracking which users are currently in a bucket.
+use crate::schema::bucket_presence;
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// When a user was last seen in a bucket.
+#[derive(
+    Clone, Copy, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize,
+)]
+#[primary_key(bucket_uuid, user_uuid)]
+#[table_name = "bucket_presence"]
+pub struct Presence {
+    /// The bucket the user was seen in.
+    pub bucket_uuid: Uuid,
+    /// The user.
+    pub user_uuid: Uuid,
+    /// When the user's last heartbeat was received.
+    pub last_seen_at: NaiveDateTime,
+}
+
+/// Structure used to record a heartbeat from a user in a bucket.
+#[derive(Clone, Copy, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "bucket_presence"]
+pub struct NewPresence {
+    /// The bucket the user is in.
+    pub bucket_uuid: Uuid,
+    /// The user.
+    pub user_uuid: Uuid,
+}
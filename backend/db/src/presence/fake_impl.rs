@@ -0,0 +1,46 @@
+//! Mock impl
+use crate::{
+    bucket_user_relation::{db_types::MemberSort, fake_impl::page_of_members},
+    fake::FakeDatabase,
+    page::{Page, Pagination},
+    presence::{
+        db_types::{NewPresence, Presence},
+        interface::PresenceRepository,
+    },
+    user::db_types::User,
+};
+use chrono::NaiveDateTime;
+use diesel::result::Error;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+impl PresenceRepository for Arc<Mutex<FakeDatabase>> {
+    fn record_heartbeat(&self, new_presence: NewPresence) -> Result<Presence, Error> {
+        let mut db = self.lock().unwrap();
+        let last_seen_at = chrono::Utc::now().naive_utc();
+        if let Some(presence) = db.presences.iter_mut().find(|p| {
+            p.bucket_uuid == new_presence.bucket_uuid && p.user_uuid == new_presence.user_uuid
+        }) {
+            presence.last_seen_at = last_seen_at;
+            return Ok(*presence);
+        }
+
+        let presence = Presence {
+            bucket_uuid: new_presence.bucket_uuid,
+            user_uuid: new_presence.user_uuid,
+            last_seen_at,
+        };
+        db.presences.push(presence);
+        Ok(presence)
+    }
+
+    fn get_present_users(
+        &self,
+        bucket_uuid: Uuid,
+        seen_since: NaiveDateTime,
+        pagination: Pagination<MemberSort>,
+    ) -> Result<Page<User>, Error> {
+        let db = self.lock().unwrap();
+        page_of_members(&db, bucket_uuid, Some(seen_since), pagination)
+    }
+}
@@ -0,0 +1,25 @@
+//! Specification of functions.
+use crate::{
+    bucket_user_relation::db_types::MemberSort,
+    page::{Page, Pagination},
+    presence::db_types::{NewPresence, Presence},
+    user::db_types::User,
+};
+use chrono::NaiveDateTime;
+use diesel::QueryResult;
+use uuid::Uuid;
+
+/// Functions for specifically working with the presence of users in buckets.
+pub trait PresenceRepository {
+    /// Records that the user was just seen in the bucket.
+    fn record_heartbeat(&self, new_presence: NewPresence) -> QueryResult<Presence>;
+    /// Gets a page of the members of the bucket that have been seen in it since the given time.
+    ///
+    /// This pages through the members the same way as `get_users_in_bucket`.
+    fn get_present_users(
+        &self,
+        bucket_uuid: Uuid,
+        seen_since: NaiveDateTime,
+        pagination: Pagination<MemberSort>,
+    ) -> QueryResult<Page<User>>;
+}
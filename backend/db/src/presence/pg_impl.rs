@@ -0,0 +1,39 @@
+//! Implementation of the specified interfaces for PgConnection.
+use crate::{
+    bucket_user_relation::{db_types::MemberSort, pg_impl::page_of_members},
+    page::{Page, Pagination},
+    presence::{
+        db_types::{NewPresence, Presence},
+        interface::PresenceRepository,
+    },
+    schema::bucket_presence,
+    user::db_types::User,
+    AsConnRef,
+};
+use chrono::NaiveDateTime;
+use diesel::{dsl::now, query_dsl::RunQueryDsl, result::Error, ExpressionMethods};
+use uuid::Uuid;
+
+impl<T> PresenceRepository for T
+where
+    T: AsConnRef,
+{
+    fn record_heartbeat(&self, new_presence: NewPresence) -> Result<Presence, Error> {
+        diesel::insert_into(bucket_presence::table)
+            .values(&new_presence)
+            .on_conflict((bucket_presence::bucket_uuid, bucket_presence::user_uuid))
+            .do_update()
+            .set(bucket_presence::last_seen_at.eq(now))
+            .get_result(self.as_conn())
+    }
+
+    fn get_present_users(
+        &self,
+        bucket_uuid: Uuid,
+        seen_since: NaiveDateTime,
+        pagination: Pagination<MemberSort>,
+    ) -> Result<Page<User>, Error> {
+        let members = page_of_members(bucket_uuid, Some(seen_since), pagination, self.as_conn())?;
+        Ok(members.map(|(_, user)| user))
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{
+    bucket_user_relation::db_types::MemberSort,
+    page::Pagination,
+    presence::db_types::NewPresence,
+    test::{
+        bucket_user_relation_fixture::{UserBucketRelationFixture, NO_PERMISSIONS},
+        util::execute_test,
+    },
+    BoxedRepository,
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+
+fn a_minute_ago() -> NaiveDateTime {
+    Utc::now().naive_utc() - Duration::minutes(1)
+}
+
+#[test]
+fn record_heartbeat() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let new_presence = NewPresence {
+            bucket_uuid: fixture.bucket.uuid,
+            user_uuid: fixture.user1.uuid,
+        };
+        let first = db
+            .record_heartbeat(new_presence)
+            .expect("Should record heartbeat");
+        let second = db
+            .record_heartbeat(new_presence)
+            .expect("Should record heartbeat again");
+        assert_eq!(first.user_uuid, second.user_uuid);
+        assert!(second.last_seen_at >= first.last_seen_at);
+    });
+}
+
+#[test]
+fn only_members_with_recent_heartbeats_are_present() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        fixture.add_user2(NO_PERMISSIONS, &db);
+
+        let new_presence = NewPresence {
+            bucket_uuid: fixture.bucket.uuid,
+            user_uuid: fixture.user1.uuid,
+        };
+        db.record_heartbeat(new_presence)
+            .expect("Should record heartbeat");
+
+        let present = db
+            .get_present_users(
+                fixture.bucket.uuid,
+                a_minute_ago(),
+                Pagination::first(10, MemberSort::Joined),
+            )
+            .expect("Should get present users")
+            .items;
+        assert_eq!(present, vec![fixture.user1.clone()]);
+
+        let in_the_future = Utc::now().naive_utc() + Duration::minutes(1);
+        let present = db
+            .get_present_users(
+                fixture.bucket.uuid,
+                in_the_future,
+                Pagination::first(10, MemberSort::Joined),
+            )
+            .expect("Should get present users")
+            .items;
+        assert!(present.is_empty());
+    });
+}
+
+#[test]
+fn users_that_left_are_not_present() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let new_presence = NewPresence {
+            bucket_uuid: fixture.bucket.uuid,
+            user_uuid: fixture.user1.uuid,
+        };
+        db.record_heartbeat(new_presence)
+            .expect("Should record heartbeat");
+        db.remove_user_from_bucket(fixture.user1.uuid, fixture.bucket.uuid)
+            .expect("Should remove user");
+
+        let present = db
+            .get_present_users(
+                fixture.bucket.uuid,
+                a_minute_ago(),
+                Pagination::first(10, MemberSort::Joined),
+            )
+            .expect("Should get present users")
+            .items;
+        assert!(present.is_empty());
+    });
+}
@@ -1,11 +1,13 @@
 //! Module for question related database interactions.
-use crate::schema::question;
+use crate::schema::{question, question_draw, question_revision};
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// A struct representing a question.
-#[derive(Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize)]
+#[derive(
+    Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Insertable, Serialize, Deserialize,
+)]
 #[primary_key(uuid)]
 #[table_name = "question"]
 pub struct Question {
@@ -27,6 +29,26 @@ pub struct Question {
     pub updated_at: NaiveDateTime,
     /// When the row was created.
     pub created_at: NaiveDateTime,
+    /// The number of users that upvoted the question.
+    pub upvotes: i32,
+    /// The number of users that downvoted the question.
+    pub downvotes: i32,
+}
+
+impl Question {
+    /// The question's net votes.
+    pub fn score(&self) -> i32 {
+        self.upvotes - self.downvotes
+    }
+}
+
+/// The order in which lists of questions are returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuestionSort {
+    /// Least recently updated first.
+    Updated,
+    /// Highest score first.
+    Score,
 }
 
 /// A struct for creating new questions.
@@ -40,3 +62,107 @@ pub struct NewQuestion {
     /// The content of the question.
     pub question_text: String,
 }
+
+/// A record of a question being drawn from a bucket.
+#[derive(
+    Clone, Copy, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize,
+)]
+#[primary_key(uuid)]
+#[table_name = "question_draw"]
+pub struct QuestionDraw {
+    /// The draw's unique identifier within the application.
+    pub uuid: Uuid,
+    /// The question that was drawn.
+    pub question_uuid: Uuid,
+    /// The bucket the question was drawn from.
+    pub bucket_uuid: Uuid,
+    /// The user that drew the question.
+    pub user_uuid: Option<Uuid>,
+    /// The session the question was drawn in.
+    pub session_uuid: Option<Uuid>,
+    /// When the question was drawn.
+    pub created_at: NaiveDateTime,
+}
+
+/// A struct for recording new draws.
+#[derive(Clone, Copy, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "question_draw"]
+pub struct NewQuestionDraw {
+    /// The question that was drawn.
+    pub question_uuid: Uuid,
+    /// The bucket the question was drawn from.
+    pub bucket_uuid: Uuid,
+    /// The user that drew the question.
+    pub user_uuid: Option<Uuid>,
+    /// The session the question was drawn in.
+    pub session_uuid: Option<Uuid>,
+}
+
+/// The text a question had before it was edited.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize)]
+#[primary_key(uuid)]
+#[table_name = "question_revision"]
+pub struct QuestionRevision {
+    /// The revision's unique identifier within the application.
+    pub uuid: Uuid,
+    /// The question that was edited.
+    pub question_uuid: Uuid,
+    /// The content of the question before the edit.
+    pub question_text: String,
+    /// The user that made the edit.
+    pub user_uuid: Option<Uuid>,
+    /// When the edit was made.
+    pub created_at: NaiveDateTime,
+}
+
+/// A struct for recording new revisions.
+#[derive(Clone, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "question_revision"]
+pub struct NewQuestionRevision {
+    /// The question that was edited.
+    pub question_uuid: Uuid,
+    /// The content of the question before the edit.
+    pub question_text: String,
+    /// The user that made the edit.
+    pub user_uuid: Option<Uuid>,
+}
+
+/// A question to add to a bucket as part of an import.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuestionImport {
+    /// The content of the question.
+    pub question_text: String,
+    /// The names of the tags to tag the question with.
+    pub tags: Vec<String>,
+}
+
+/// What became of a question that was imported.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportOutcome {
+    /// The question was added to the bucket.
+    Imported(Question),
+    /// The bucket already had the question, or one like it, so it was skipped.
+    Duplicate,
+}
+
+/// A question, along with its tags and how many times it has been answered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuestionExport {
+    /// The question.
+    pub question: Question,
+    /// The names of the question's tags, ordered by name.
+    pub tags: Vec<String>,
+    /// The number of answers given to the question.
+    pub answer_count: i64,
+}
+
+/// A question already in a bucket that is worded the same as, or nearly the same as, another question.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimilarQuestion {
+    /// The question in the bucket.
+    pub question: Question,
+    /// How similar the questions are, from 0.0 to 1.0.
+    pub similarity: f32,
+    /// Do the questions only differ in case, punctuation, or spacing.
+    pub exact: bool,
+}
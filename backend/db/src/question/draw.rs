@@ -0,0 +1,85 @@
+//! Weighted selection of questions according to a bucket's draw strategy.
+//!
+//! Both the Postgres and fake repositories load their candidate questions and then defer to
+//! `choose_question`, so a given strategy picks questions the same way regardless of the backing store.
+//! Both load at most `MAX_DRAW_CANDIDATES` candidates.
+use crate::{bucket::db_types::DrawStrategy, question::db_types::Question};
+use chrono::NaiveDateTime;
+use rand::Rng;
+use uuid::Uuid;
+
+/// The number of recent draws whose authors are considered "recently drawn"
+/// by `DrawStrategy::MembersNotRecentlyDrawn`.
+pub(crate) const RECENT_AUTHOR_DRAWS: i64 = 10;
+
+/// The most questions that are weighed against each other for one draw.
+/// Buckets with more candidates than this draw from a uniformly random sample of them,
+/// so a draw never has to load every question in a large bucket.
+pub(crate) const MAX_DRAW_CANDIDATES: i64 = 1000;
+
+/// Beyond this many net votes in either direction, a question's score stops changing its weight.
+/// This keeps heavily downvoted questions drawable, and stops one popular question from always being drawn.
+const MAX_SCORE_INFLUENCE: i32 = 5;
+
+/// How much more likely a question from a member who hasn't been drawn recently is to be picked.
+const NOT_RECENTLY_DRAWN_WEIGHT: f64 = 4.0;
+
+/// Determines the relative likelihood of a question being drawn.
+///
+/// `newest` is the creation time of the most recently created candidate,
+/// so that age is measured relative to the other candidates instead of the current time.
+pub(crate) fn weight(
+    question: &Question,
+    strategy: DrawStrategy,
+    recent_authors: &[Uuid],
+    newest: NaiveDateTime,
+) -> f64 {
+    match strategy {
+        DrawStrategy::Uniform => 1.0,
+        DrawStrategy::Upvotes => {
+            let score = question
+                .score()
+                .max(-MAX_SCORE_INFLUENCE)
+                .min(MAX_SCORE_INFLUENCE);
+            2f64.powi(score)
+        }
+        DrawStrategy::OlderFirst => {
+            let hours_older = (newest - question.created_at).num_hours().max(0);
+            hours_older as f64 + 1.0
+        }
+        DrawStrategy::MembersNotRecentlyDrawn => match question.user_uuid {
+            Some(author) if !recent_authors.contains(&author) => NOT_RECENTLY_DRAWN_WEIGHT,
+            _ => 1.0,
+        },
+    }
+}
+
+/// Picks one of the candidates, favoring them according to the provided strategy.
+pub(crate) fn choose_question<R: Rng>(
+    candidates: Vec<Question>,
+    strategy: DrawStrategy,
+    recent_authors: &[Uuid],
+    rng: &mut R,
+) -> Option<Question> {
+    let newest = candidates.iter().map(|q| q.created_at).max()?;
+    let weights: Vec<f64> = candidates
+        .iter()
+        .map(|q| weight(q, strategy, recent_authors, newest))
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    let mut target = rng.gen_range(0.0, total);
+    let index = weights
+        .iter()
+        .position(|w| {
+            if target < *w {
+                true
+            } else {
+                target -= w;
+                false
+            }
+        })
+        .unwrap_or(candidates.len() - 1); // Guard against floating point rounding.
+
+    candidates.into_iter().nth(index)
+}
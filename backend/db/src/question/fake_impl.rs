@@ -1,14 +1,26 @@
 //! Mock impl
 
 use crate::{
+    bucket::db_types::{DrawStrategy, DuplicatePolicy},
     fake::{DummyDbErrorInfo, FakeDatabase},
+    page::{paginate, Page, Pagination, RecencySort},
     question::{
-        db_types::{NewQuestion, Question},
+        db_types::{
+            ImportOutcome, NewQuestion, NewQuestionDraw, NewQuestionRevision, Question,
+            QuestionDraw, QuestionExport, QuestionImport, QuestionRevision, QuestionSort,
+            SimilarQuestion,
+        },
+        draw::{choose_question, MAX_DRAW_CANDIDATES, RECENT_AUTHOR_DRAWS},
         interface::QuestionRepository,
+        similarity::{normalize_question_text, trigram_similarity, SIMILARITY_THRESHOLD},
+    },
+    tag::{
+        db_types::{QuestionTag, Tag, TagFilter},
+        fake_impl::tagged_question_uuids,
     },
 };
 use diesel::result::{DatabaseErrorKind, Error};
-use rand::{thread_rng, Rng};
+use rand::seq::SliceRandom;
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
@@ -23,6 +35,8 @@ impl QuestionRepository for Arc<Mutex<FakeDatabase>> {
             archived: false,
             updated_at: chrono::Utc::now().naive_utc(),
             created_at: chrono::Utc::now().naive_utc(),
+            upvotes: 0,
+            downvotes: 0,
         };
         let mut db = self.lock().unwrap();
         if db.questions.iter().find(|q| q.uuid == uuid).is_some() {
@@ -35,6 +49,44 @@ impl QuestionRepository for Arc<Mutex<FakeDatabase>> {
         return Ok(question);
     }
 
+    fn create_tagged_question(
+        &self,
+        question: NewQuestion,
+        tag_names: Vec<String>,
+    ) -> Result<Question, Error> {
+        // Holding the lock keeps anyone from seeing the question before it is tagged.
+        let mut db = self.lock().unwrap();
+        if !db.buckets.iter().any(|b| b.uuid == question.bucket_uuid) {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::ForeignKeyViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+        let question = Question {
+            uuid: Uuid::new_v4(),
+            bucket_uuid: question.bucket_uuid,
+            user_uuid: question.user_uuid,
+            question_text: question.question_text,
+            archived: false,
+            updated_at: chrono::Utc::now().naive_utc(),
+            created_at: chrono::Utc::now().naive_utc(),
+            upvotes: 0,
+            downvotes: 0,
+        };
+        db.questions.push(question.clone());
+        tag_new_question(&mut db, &question, tag_names);
+        Ok(question)
+    }
+
+    fn get_question(&self, uuid: Uuid) -> Result<Question, Error> {
+        let db = self.lock().unwrap();
+        db.questions
+            .iter()
+            .find(|q| q.uuid == uuid)
+            .cloned()
+            .ok_or_else(|| Error::NotFound)
+    }
+
     fn delete_question(&self, uuid: Uuid) -> Result<Question, Error> {
         let mut db = self.lock().unwrap();
         let index = db
@@ -45,19 +97,66 @@ impl QuestionRepository for Arc<Mutex<FakeDatabase>> {
         Ok(db.questions.remove(index))
     }
 
-    fn get_random_question(&self, bucket_uuid: Uuid) -> Result<Option<Question>, Error> {
-        let db = self.lock().unwrap();
-        let bucket_questions: Vec<&Question> = db
+    fn get_random_question(
+        &self,
+        bucket_uuid: Uuid,
+        recent_draws_to_avoid: i64,
+        tags: &TagFilter,
+    ) -> Result<Option<Question>, Error> {
+        let mut db = self.lock().unwrap();
+        let strategy = db
+            .buckets
+            .iter()
+            .find(|b| b.uuid == bucket_uuid)
+            .map(|b| b.draw_strategy)
+            .ok_or(Error::NotFound)?;
+
+        let recently_drawn: Vec<Uuid> = db
+            .question_draws
+            .iter()
+            .rev()
+            .filter(|d| d.bucket_uuid == bucket_uuid)
+            .take(recent_draws_to_avoid.max(0) as usize)
+            .map(|d| d.question_uuid)
+            .collect();
+        let passes_tag_filter = tag_filter_predicate(bucket_uuid, tags, &db);
+        let mut candidates: Vec<Question> = db
             .questions
             .iter()
-            .filter(|q| q.bucket_uuid == bucket_uuid)
+            .filter(|q| {
+                q.bucket_uuid == bucket_uuid && !q.archived && !recently_drawn.contains(&q.uuid)
+            })
+            .filter(|q| passes_tag_filter(q))
+            .cloned()
             .collect();
-        if bucket_questions.len() > 0 {
-            let index: usize = thread_rng().gen_range(0, bucket_questions.len());
-            Ok(bucket_questions.get(index).cloned().cloned())
-        } else {
-            Ok(None)
+        if candidates.len() > MAX_DRAW_CANDIDATES as usize {
+            candidates.shuffle(&mut db.rng);
+            candidates.truncate(MAX_DRAW_CANDIDATES as usize);
         }
+
+        let recent_authors: Vec<Uuid> = if strategy == DrawStrategy::MembersNotRecentlyDrawn {
+            db.question_draws
+                .iter()
+                .rev()
+                .filter(|d| d.bucket_uuid == bucket_uuid)
+                .take(RECENT_AUTHOR_DRAWS as usize)
+                .filter_map(|d| {
+                    db.questions
+                        .iter()
+                        .find(|q| q.uuid == d.question_uuid)
+                        .and_then(|q| q.user_uuid)
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        Ok(choose_question(
+            candidates,
+            strategy,
+            &recent_authors,
+            &mut db.rng,
+        ))
     }
 
     fn get_number_of_active_questions_for_bucket(&self, bucket_uuid: Uuid) -> Result<i64, Error> {
@@ -74,15 +173,36 @@ impl QuestionRepository for Arc<Mutex<FakeDatabase>> {
         &self,
         bucket_uuid: Uuid,
         archived: bool,
-    ) -> Result<Vec<Question>, Error> {
+        tags: &TagFilter,
+        pagination: Pagination<QuestionSort>,
+    ) -> Result<Page<Question>, Error> {
         let db = self.lock().unwrap();
-        let questions = db
+        let last = match pagination.after {
+            Some(after) => Some(
+                db.questions
+                    .iter()
+                    .find(|q| q.uuid == after)
+                    .ok_or(Error::NotFound)?,
+            ),
+            None => None,
+        };
+        let passes_tag_filter = tag_filter_predicate(bucket_uuid, tags, &db);
+        let questions: Vec<Question> = db
             .questions
             .iter()
             .filter(|q| q.archived == archived && q.bucket_uuid == bucket_uuid)
+            .filter(|q| passes_tag_filter(q))
             .cloned()
             .collect();
-        Ok(questions)
+        let order = |a: &Question, b: &Question| match pagination.sort {
+            QuestionSort::Updated => (a.updated_at, a.uuid).cmp(&(b.updated_at, b.uuid)),
+            QuestionSort::Score => {
+                (-a.score(), a.updated_at, a.uuid).cmp(&(-b.score(), b.updated_at, b.uuid))
+            }
+        };
+        Ok(paginate(questions, last, pagination.limit, order, |q| {
+            q.uuid
+        }))
     }
 
     fn set_archive_status_for_question(
@@ -99,4 +219,351 @@ impl QuestionRepository for Arc<Mutex<FakeDatabase>> {
         question.archived = archived;
         Ok(question.clone())
     }
+
+    fn record_draw(&self, draw: NewQuestionDraw) -> Result<QuestionDraw, Error> {
+        let draw = QuestionDraw {
+            uuid: Uuid::new_v4(),
+            question_uuid: draw.question_uuid,
+            bucket_uuid: draw.bucket_uuid,
+            user_uuid: draw.user_uuid,
+            session_uuid: draw.session_uuid,
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+        let mut db = self.lock().unwrap();
+        db.question_draws.push(draw);
+        Ok(draw)
+    }
+
+    fn get_recent_draws(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<QuestionDraw>, Error> {
+        let db = self.lock().unwrap();
+        let last = match pagination.after {
+            Some(after) => Some(
+                db.question_draws
+                    .iter()
+                    .find(|d| d.uuid == after)
+                    .ok_or(Error::NotFound)?,
+            ),
+            None => None,
+        };
+        let draws = db
+            .question_draws
+            .iter()
+            .filter(|d| d.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        let order = |a: &QuestionDraw, b: &QuestionDraw| match pagination.sort {
+            RecencySort::Newest => (b.created_at, b.uuid).cmp(&(a.created_at, a.uuid)),
+            RecencySort::Oldest => (a.created_at, a.uuid).cmp(&(b.created_at, b.uuid)),
+        };
+        Ok(paginate(draws, last, pagination.limit, order, |d| d.uuid))
+    }
+
+    fn edit_question(
+        &self,
+        question_uuid: Uuid,
+        question_text: String,
+        editor_uuid: Option<Uuid>,
+    ) -> Result<Question, Error> {
+        let mut db = self.lock().unwrap();
+        let question = db
+            .questions
+            .iter_mut()
+            .find(|q| q.uuid == question_uuid)
+            .ok_or(Error::NotFound)?;
+        let revision = NewQuestionRevision {
+            question_uuid,
+            question_text: std::mem::replace(&mut question.question_text, question_text),
+            user_uuid: editor_uuid,
+        };
+        question.updated_at = chrono::Utc::now().naive_utc();
+        let question = question.clone();
+
+        db.question_revisions.push(QuestionRevision {
+            uuid: Uuid::new_v4(),
+            question_uuid: revision.question_uuid,
+            question_text: revision.question_text,
+            user_uuid: revision.user_uuid,
+            created_at: chrono::Utc::now().naive_utc(),
+        });
+        Ok(question)
+    }
+
+    fn get_question_revisions(
+        &self,
+        question_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<QuestionRevision>, Error> {
+        let db = self.lock().unwrap();
+        let last = match pagination.after {
+            Some(after) => Some(
+                db.question_revisions
+                    .iter()
+                    .find(|r| r.uuid == after)
+                    .ok_or(Error::NotFound)?,
+            ),
+            None => None,
+        };
+        let revisions = db
+            .question_revisions
+            .iter()
+            .filter(|r| r.question_uuid == question_uuid)
+            .cloned()
+            .collect();
+        let order = |a: &QuestionRevision, b: &QuestionRevision| match pagination.sort {
+            RecencySort::Newest => (b.created_at, b.uuid).cmp(&(a.created_at, a.uuid)),
+            RecencySort::Oldest => (a.created_at, a.uuid).cmp(&(b.created_at, b.uuid)),
+        };
+        Ok(paginate(revisions, last, pagination.limit, order, |r| {
+            r.uuid
+        }))
+    }
+
+    fn get_question_revision(&self, revision_uuid: Uuid) -> Result<QuestionRevision, Error> {
+        let db = self.lock().unwrap();
+        db.question_revisions
+            .iter()
+            .find(|r| r.uuid == revision_uuid)
+            .cloned()
+            .ok_or(Error::NotFound)
+    }
+
+    fn copy_questions(
+        &self,
+        from_bucket_uuid: Uuid,
+        to_bucket_uuid: Uuid,
+        author_uuid: Option<Uuid>,
+    ) -> Result<Vec<(Uuid, Question)>, Error> {
+        let mut db = self.lock().unwrap();
+        if !db.buckets.iter().any(|b| b.uuid == to_bucket_uuid) {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::ForeignKeyViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+        let mut originals: Vec<Question> = db
+            .questions
+            .iter()
+            .filter(|q| q.bucket_uuid == from_bucket_uuid)
+            .cloned()
+            .collect();
+        originals.sort_by_key(|q| q.created_at);
+        let copies: Vec<(Uuid, Question)> = originals
+            .into_iter()
+            .map(|original| {
+                let copy = Question {
+                    uuid: Uuid::new_v4(),
+                    bucket_uuid: to_bucket_uuid,
+                    user_uuid: author_uuid,
+                    question_text: original.question_text,
+                    archived: false,
+                    updated_at: chrono::Utc::now().naive_utc(),
+                    created_at: chrono::Utc::now().naive_utc(),
+                    upvotes: 0,
+                    downvotes: 0,
+                };
+                (original.uuid, copy)
+            })
+            .collect();
+        db.questions
+            .extend(copies.iter().map(|(_, copy)| copy.clone()));
+        Ok(copies)
+    }
+
+    fn import_questions(
+        &self,
+        bucket_uuid: Uuid,
+        user_uuid: Option<Uuid>,
+        questions: Vec<QuestionImport>,
+    ) -> Result<Vec<ImportOutcome>, Error> {
+        // Holding the lock for the whole import keeps it all or nothing.
+        let mut db = self.lock().unwrap();
+        let duplicate_policy = db
+            .buckets
+            .iter()
+            .find(|b| b.uuid == bucket_uuid)
+            .map(|b| b.duplicate_policy)
+            .ok_or(Error::NotFound)?;
+
+        let mut outcomes = Vec::with_capacity(questions.len());
+        for import in questions {
+            let normalized = normalize_question_text(&import.question_text);
+            let is_duplicate = db.questions.iter().any(|q| {
+                q.bucket_uuid == bucket_uuid
+                    && normalize_question_text(&q.question_text) == normalized
+            }) || (duplicate_policy == DuplicatePolicy::Reject
+                && !similar_questions(
+                    &db,
+                    bucket_uuid,
+                    &import.question_text,
+                    SIMILARITY_THRESHOLD,
+                )
+                .is_empty());
+            if is_duplicate {
+                outcomes.push(ImportOutcome::Duplicate);
+                continue;
+            }
+            let question = Question {
+                uuid: Uuid::new_v4(),
+                bucket_uuid,
+                user_uuid,
+                question_text: import.question_text,
+                archived: false,
+                updated_at: chrono::Utc::now().naive_utc(),
+                created_at: chrono::Utc::now().naive_utc(),
+                upvotes: 0,
+                downvotes: 0,
+            };
+            db.questions.push(question.clone());
+            tag_new_question(&mut db, &question, import.tags);
+            outcomes.push(ImportOutcome::Imported(question));
+        }
+        Ok(outcomes)
+    }
+
+    fn export_questions(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<QuestionExport>, Error> {
+        let db = self.lock().unwrap();
+        let last = match pagination.after {
+            Some(after) => Some(
+                db.questions
+                    .iter()
+                    .find(|q| q.uuid == after)
+                    .ok_or(Error::NotFound)?,
+            ),
+            None => None,
+        };
+        let questions = db
+            .questions
+            .iter()
+            .filter(|q| q.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        let order = |a: &Question, b: &Question| match pagination.sort {
+            RecencySort::Newest => (b.created_at, b.uuid).cmp(&(a.created_at, a.uuid)),
+            RecencySort::Oldest => (a.created_at, a.uuid).cmp(&(b.created_at, b.uuid)),
+        };
+        let questions = paginate(questions, last, pagination.limit, order, |q| q.uuid);
+
+        Ok(questions.map(|question| {
+            let mut tags: Vec<String> = db
+                .question_tags
+                .iter()
+                .filter(|qt| qt.question_uuid == question.uuid)
+                .filter_map(|qt| db.tags.iter().find(|t| t.uuid == qt.tag_uuid))
+                .map(|t| t.tag_name.clone())
+                .collect();
+            tags.sort();
+            let answer_count = db
+                .answers
+                .iter()
+                .filter(|a| a.question_uuid == question.uuid)
+                .count() as i64;
+            QuestionExport {
+                question,
+                tags,
+                answer_count,
+            }
+        }))
+    }
+
+    fn find_similar_questions(
+        &self,
+        bucket_uuid: Uuid,
+        question_text: &str,
+        threshold: f32,
+        limit: i64,
+    ) -> Result<Vec<SimilarQuestion>, Error> {
+        let db = self.lock().unwrap();
+        let mut similar = similar_questions(&db, bucket_uuid, question_text, threshold);
+        similar.truncate(limit as usize);
+        Ok(similar)
+    }
+}
+
+/// Creates a predicate that determines if a question in the bucket passes the tag filter.
+fn tag_filter_predicate(
+    bucket_uuid: Uuid,
+    tags: &TagFilter,
+    db: &FakeDatabase,
+) -> impl Fn(&Question) -> bool {
+    let filter_included = !tags.include.is_empty();
+    let included = tagged_question_uuids(bucket_uuid, &tags.include, db);
+    let excluded = tagged_question_uuids(bucket_uuid, &tags.exclude, db);
+    move |question: &Question| {
+        (!filter_included || included.contains(&question.uuid))
+            && !excluded.contains(&question.uuid)
+    }
+}
+
+/// Gets the questions in the bucket whose text is at least `threshold` similar to the given text,
+/// the most similar first.
+fn similar_questions(
+    db: &FakeDatabase,
+    bucket_uuid: Uuid,
+    question_text: &str,
+    threshold: f32,
+) -> Vec<SimilarQuestion> {
+    let normalized = normalize_question_text(question_text);
+    let mut similar: Vec<SimilarQuestion> = db
+        .questions
+        .iter()
+        .filter(|q| q.bucket_uuid == bucket_uuid)
+        .map(|q| SimilarQuestion {
+            question: q.clone(),
+            similarity: trigram_similarity(&q.question_text, question_text),
+            exact: normalize_question_text(&q.question_text) == normalized,
+        })
+        .filter(|s| s.similarity >= threshold)
+        .collect();
+    similar.sort_by(|a, b| {
+        b.similarity
+            .partial_cmp(&a.similarity)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.question.created_at.cmp(&b.question.created_at))
+    });
+    similar
+}
+
+/// Tags the question with the named tags, creating the ones its bucket doesn't have yet.
+fn tag_new_question(db: &mut FakeDatabase, question: &Question, tag_names: Vec<String>) {
+    for tag_name in tag_names {
+        let existing_tag = db
+            .tags
+            .iter()
+            .find(|t| t.bucket_uuid == question.bucket_uuid && t.tag_name == tag_name)
+            .map(|t| t.uuid);
+        let tag_uuid = match existing_tag {
+            Some(tag_uuid) => tag_uuid,
+            None => {
+                let tag = Tag {
+                    uuid: Uuid::new_v4(),
+                    bucket_uuid: question.bucket_uuid,
+                    tag_name,
+                    updated_at: chrono::Utc::now().naive_utc(),
+                    created_at: chrono::Utc::now().naive_utc(),
+                };
+                db.tags.push(tag.clone());
+                tag.uuid
+            }
+        };
+        let already_tagged = db
+            .question_tags
+            .iter()
+            .any(|qt| qt.question_uuid == question.uuid && qt.tag_uuid == tag_uuid);
+        if !already_tagged {
+            db.question_tags.push(QuestionTag {
+                question_uuid: question.uuid,
+                tag_uuid,
+                updated_at: chrono::Utc::now().naive_utc(),
+                created_at: chrono::Utc::now().naive_utc(),
+            });
+        }
+    }
 }
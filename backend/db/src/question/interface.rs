@@ -1,5 +1,12 @@
 //! Specification of functions.
-use crate::question::db_types::{NewQuestion, Question};
+use crate::{
+    page::{Page, Pagination, RecencySort},
+    question::db_types::{
+        ImportOutcome, NewQuestion, NewQuestionDraw, Question, QuestionDraw, QuestionExport,
+        QuestionImport, QuestionRevision, QuestionSort, SimilarQuestion,
+    },
+    tag::db_types::TagFilter,
+};
 use diesel::QueryResult;
 use uuid::Uuid;
 
@@ -7,22 +14,114 @@ use uuid::Uuid;
 pub trait QuestionRepository {
     /// Create a question
     fn create_question(&self, question: NewQuestion) -> QueryResult<Question>;
+    /// Creates a question and tags it, either all of it or none of it.
+    ///
+    /// Tags that the bucket doesn't have yet are created.
+    /// The tag names are expected to be normalized already.
+    fn create_tagged_question(
+        &self,
+        question: NewQuestion,
+        tag_names: Vec<String>,
+    ) -> QueryResult<Question>;
+    /// Gets a question by its uuid.
+    fn get_question(&self, uuid: Uuid) -> QueryResult<Question>;
     /// Delete question
     fn delete_question(&self, uuid: Uuid) -> QueryResult<Question>;
     /// Gets a random question.
-    fn get_random_question(&self, bucket_uuid: Uuid) -> QueryResult<Option<Question>>;
+    ///
+    /// Questions drawn within the bucket's last `recent_draws_to_avoid` draws won't be picked,
+    /// even if they have been put back in the bucket since.
+    /// Only questions that pass the tag filter are considered.
+    /// The remaining questions are weighted according to the bucket's draw strategy.
+    /// Only a random sample of them is weighed when there are very many of them.
+    fn get_random_question(
+        &self,
+        bucket_uuid: Uuid,
+        recent_draws_to_avoid: i64,
+        tags: &TagFilter,
+    ) -> QueryResult<Option<Question>>;
     /// Gets the number of active questions.
     fn get_number_of_active_questions_for_bucket(&self, bucket_uuid: Uuid) -> QueryResult<i64>;
-    /// Gets all questions for a bucket of a specified archived state.
+    /// Gets a page of the questions for a bucket of a specified archived state that pass the tag filter.
+    ///
+    /// If the question the page should start after doesn't exist anymore, this will return a `NotFound` error.
     fn get_all_questions_for_bucket_of_given_archived_status(
         &self,
         bucket_uuid: Uuid,
         archived: bool,
-    ) -> QueryResult<Vec<Question>>;
+        tags: &TagFilter,
+        pagination: Pagination<QuestionSort>,
+    ) -> QueryResult<Page<Question>>;
     /// Disable or Enable the question from drawing eligibility.
     fn set_archive_status_for_question(
         &self,
         question_uuid: Uuid,
         archived: bool,
     ) -> QueryResult<Question>;
+    /// Records that a question was drawn.
+    fn record_draw(&self, draw: NewQuestionDraw) -> QueryResult<QuestionDraw>;
+    /// Gets a page of the bucket's draws.
+    ///
+    /// If the draw the page should start after doesn't exist anymore, this will return a `NotFound` error.
+    fn get_recent_draws(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<QuestionDraw>>;
+    /// Replaces the question's text, keeping its previous text as a revision.
+    fn edit_question(
+        &self,
+        question_uuid: Uuid,
+        question_text: String,
+        editor_uuid: Option<Uuid>,
+    ) -> QueryResult<Question>;
+    /// Gets a page of the revisions of a question.
+    ///
+    /// If the revision the page should start after doesn't exist anymore, this will return a `NotFound` error.
+    fn get_question_revisions(
+        &self,
+        question_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<QuestionRevision>>;
+    /// Gets a revision of a question.
+    fn get_question_revision(&self, revision_uuid: Uuid) -> QueryResult<QuestionRevision>;
+    /// Copies every question in one bucket into another.
+    ///
+    /// The copies keep their text, but are put back in the bucket and start without votes.
+    /// They are attributed to the given author, rather than to whoever asked the original.
+    /// Each copy is paired with the uuid of the question it was copied from, the oldest first.
+    fn copy_questions(
+        &self,
+        from_bucket_uuid: Uuid,
+        to_bucket_uuid: Uuid,
+        author_uuid: Option<Uuid>,
+    ) -> QueryResult<Vec<(Uuid, Question)>>;
+    /// Adds the questions to the bucket, either all of them or none of them.
+    ///
+    /// Questions worded the same as one already in the bucket, ignoring case, punctuation, and spacing, are skipped.
+    /// If the bucket rejects duplicates, questions that are only similar to one already in the bucket are skipped too.
+    /// The tag names are expected to be normalized already.
+    /// There is an outcome for each question, in the order they were given.
+    fn import_questions(
+        &self,
+        bucket_uuid: Uuid,
+        user_uuid: Option<Uuid>,
+        questions: Vec<QuestionImport>,
+    ) -> QueryResult<Vec<ImportOutcome>>;
+    /// Gets a page of the questions in the bucket along with their tags and number of answers.
+    /// If the question the page should start after doesn't exist anymore, this will return a `NotFound` error.
+    fn export_questions(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<QuestionExport>>;
+    /// Gets up to `limit` questions in the bucket whose text is at least `threshold` similar to the given text,
+    /// the most similar first.
+    fn find_similar_questions(
+        &self,
+        bucket_uuid: Uuid,
+        question_text: &str,
+        threshold: f32,
+        limit: i64,
+    ) -> QueryResult<Vec<SimilarQuestion>>;
 }
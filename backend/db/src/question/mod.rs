@@ -1,8 +1,10 @@
 //! Module containing all structures and functions required for question related database functionality.
 pub mod db_types;
+pub(crate) mod draw;
 pub mod fake_impl;
 pub mod interface;
 pub mod pg_impl;
+pub mod similarity;
 #[cfg(test)]
 mod tests;
 pub mod wire_types;
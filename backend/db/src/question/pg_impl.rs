@@ -1,18 +1,41 @@
 //! Implementation of the specified interfaces for PgConnection.
 use crate::{
+    bucket::db_types::{DrawStrategy, DuplicatePolicy},
+    page::{Page, Pagination, RecencySort},
     question::{
-        db_types::{NewQuestion, Question},
+        db_types::{
+            ImportOutcome, NewQuestion, NewQuestionDraw, NewQuestionRevision, Question,
+            QuestionDraw, QuestionExport, QuestionImport, QuestionRevision, QuestionSort,
+            SimilarQuestion,
+        },
+        draw::{choose_question, MAX_DRAW_CANDIDATES, RECENT_AUTHOR_DRAWS},
         interface::QuestionRepository,
+        similarity::{normalize_question_text, SIMILARITY_THRESHOLD},
+    },
+    schema::{answer, bucket, question, question_draw, question_revision, question_tag, tag},
+    tag::{
+        db_types::{NewQuestionTag, NewTag, TagFilter},
+        pg_impl::tagged_question_uuids,
     },
-    schema::question,
     AsConnRef,
 };
 use diesel::{
-    query_dsl::RunQueryDsl, result::Error, BoolExpressionMethods, ExpressionMethods,
-    OptionalExtension, QueryDsl,
+    dsl::sql,
+    pg::Pg,
+    query_dsl::{GroupByDsl, RunQueryDsl},
+    result::Error,
+    sql_types::{BigInt, Text},
+    BoolExpressionMethods, Connection, ExpressionMethods, PgConnection, QueryDsl,
 };
+use rand::thread_rng;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+sql_function! {
+    /// `pg_trgm`'s measure of how similar two texts are, from 0.0 to 1.0.
+    fn similarity(x: Text, y: Text) -> Float;
+}
+
 impl<T> QuestionRepository for T
 where
     T: AsConnRef,
@@ -21,23 +44,83 @@ where
         crate::util::create_row(question::table, question, self.as_conn())
     }
 
+    fn create_tagged_question(
+        &self,
+        question: NewQuestion,
+        tag_names: Vec<String>,
+    ) -> Result<Question, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            let question: Question = diesel::insert_into(question::table)
+                .values(question)
+                .get_result(conn)?;
+            tag_new_question(&question, tag_names, &mut HashMap::new(), conn)?;
+            Ok(question)
+        })
+    }
+
+    fn get_question(&self, uuid: Uuid) -> Result<Question, Error> {
+        crate::util::get_row(question::table, uuid, self.as_conn())
+    }
+
     fn delete_question(&self, uuid: Uuid) -> Result<Question, Error> {
         crate::util::delete_row(question::table, uuid, self.as_conn())
     }
 
-    fn get_random_question(&self, bucket_uuid: Uuid) -> Result<Option<Question>, Error> {
+    fn get_random_question(
+        &self,
+        bucket_uuid: Uuid,
+        recent_draws_to_avoid: i64,
+        tags: &TagFilter,
+    ) -> Result<Option<Question>, Error> {
         no_arg_sql_function!(RANDOM, (), "Represents the sql RANDOM() function");
 
-        // Get a question in the bucket, that isn't on the floor.
+        let strategy: DrawStrategy = bucket::table
+            .find(bucket_uuid)
+            .select(bucket::draw_strategy)
+            .get_result(self.as_conn())?;
+
+        let recently_drawn: Vec<Uuid> = question_draw::table
+            .filter(question_draw::bucket_uuid.eq(bucket_uuid))
+            .order_by(question_draw::created_at.desc())
+            .limit(recent_draws_to_avoid)
+            .select(question_draw::question_uuid)
+            .get_results(self.as_conn())?;
+
+        // Get the questions in the bucket, that aren't on the floor, and weren't drawn recently.
         let condition = question::bucket_uuid
             .eq(bucket_uuid)
-            .and(question::archived.eq(false));
+            .and(question::archived.eq(false))
+            .and(question::uuid.ne_all(recently_drawn));
 
-        question::table
-            .filter(condition)
+        let query = question::table.filter(condition).into_boxed();
+        // Large buckets are sampled, instead of loading every question in them.
+        let candidates: Vec<Question> = filter_by_tags(query, bucket_uuid, tags, self.as_conn())?
             .order(RANDOM)
-            .first(self.as_conn())
-            .optional()
+            .limit(MAX_DRAW_CANDIDATES)
+            .get_results(self.as_conn())?;
+
+        let recent_authors: Vec<Uuid> = if strategy == DrawStrategy::MembersNotRecentlyDrawn {
+            question_draw::table
+                .inner_join(question::table)
+                .filter(question_draw::bucket_uuid.eq(bucket_uuid))
+                .order_by(question_draw::created_at.desc())
+                .limit(RECENT_AUTHOR_DRAWS)
+                .select(question::user_uuid)
+                .get_results::<Option<Uuid>>(self.as_conn())?
+                .into_iter()
+                .filter_map(|author| author)
+                .collect()
+        } else {
+            vec![]
+        };
+
+        Ok(choose_question(
+            candidates,
+            strategy,
+            &recent_authors,
+            &mut thread_rng(),
+        ))
     }
 
     fn get_number_of_active_questions_for_bucket(&self, bucket_uuid: Uuid) -> Result<i64, Error> {
@@ -55,15 +138,48 @@ where
         &self,
         bucket_uuid: Uuid,
         archived: bool,
-    ) -> Result<Vec<Question>, Error> {
-        question::table
+        tags: &TagFilter,
+        pagination: Pagination<QuestionSort>,
+    ) -> Result<Page<Question>, Error> {
+        let conn = self.as_conn();
+        let query = question::table
             .filter(
                 question::bucket_uuid
                     .eq(bucket_uuid)
                     .and(question::archived.eq(archived)),
             )
-            .order_by(question::updated_at)
-            .get_results(self.as_conn())
+            .into_boxed();
+        let query = filter_by_tags(query, bucket_uuid, tags, conn)?;
+        let score = question::upvotes - question::downvotes;
+        // Only the questions that sort after the last one of the previous page are on this page.
+        let query = match pagination.after {
+            Some(after) => {
+                let last: Question = question::table.find(after).first(conn)?;
+                let after_last_update =
+                    question::updated_at
+                        .gt(last.updated_at)
+                        .or(question::updated_at
+                            .eq(last.updated_at)
+                            .and(question::uuid.gt(last.uuid)));
+                match pagination.sort {
+                    QuestionSort::Updated => query.filter(after_last_update),
+                    QuestionSort::Score => query.filter(
+                        score
+                            .lt(last.score())
+                            .or(score.eq(last.score()).and(after_last_update)),
+                    ),
+                }
+            }
+            None => query,
+        };
+        let query = match pagination.sort {
+            QuestionSort::Updated => query.order_by((question::updated_at, question::uuid)),
+            QuestionSort::Score => {
+                query.order_by((score.desc(), question::updated_at, question::uuid))
+            }
+        };
+        let questions = query.limit(pagination.fetch_limit()).get_results(conn)?;
+        Ok(Page::from_fetched(questions, pagination.limit, |q| q.uuid))
     }
 
     fn set_archive_status_for_question(
@@ -77,4 +193,388 @@ where
             .set(question::archived.eq(archived))
             .get_result(self.as_conn())
     }
+
+    fn record_draw(&self, draw: NewQuestionDraw) -> Result<QuestionDraw, Error> {
+        crate::util::create_row(question_draw::table, draw, self.as_conn())
+    }
+
+    fn get_recent_draws(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<QuestionDraw>, Error> {
+        let conn = self.as_conn();
+        let query = question_draw::table
+            .filter(question_draw::bucket_uuid.eq(bucket_uuid))
+            .into_boxed();
+        // Only the draws that sort after the last one of the previous page are on this page.
+        let query = match pagination.after {
+            Some(after) => {
+                let last: QuestionDraw = question_draw::table.find(after).first(conn)?;
+                match pagination.sort {
+                    RecencySort::Newest => query.filter(
+                        question_draw::created_at
+                            .lt(last.created_at)
+                            .or(question_draw::created_at
+                                .eq(last.created_at)
+                                .and(question_draw::uuid.lt(last.uuid))),
+                    ),
+                    RecencySort::Oldest => query.filter(
+                        question_draw::created_at
+                            .gt(last.created_at)
+                            .or(question_draw::created_at
+                                .eq(last.created_at)
+                                .and(question_draw::uuid.gt(last.uuid))),
+                    ),
+                }
+            }
+            None => query,
+        };
+        let query = match pagination.sort {
+            RecencySort::Newest => {
+                query.order_by((question_draw::created_at.desc(), question_draw::uuid.desc()))
+            }
+            RecencySort::Oldest => query.order_by((question_draw::created_at, question_draw::uuid)),
+        };
+        let draws = query.limit(pagination.fetch_limit()).get_results(conn)?;
+        Ok(Page::from_fetched(draws, pagination.limit, |d| d.uuid))
+    }
+
+    fn edit_question(
+        &self,
+        question_uuid: Uuid,
+        question_text: String,
+        editor_uuid: Option<Uuid>,
+    ) -> Result<Question, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            let previous: Question = question::table.find(question_uuid).get_result(conn)?;
+            let revision = NewQuestionRevision {
+                question_uuid,
+                question_text: previous.question_text,
+                user_uuid: editor_uuid,
+            };
+            diesel::insert_into(question_revision::table)
+                .values(revision)
+                .execute(conn)?;
+
+            diesel::update(question::table.find(question_uuid))
+                .set((
+                    question::question_text.eq(question_text),
+                    question::updated_at.eq(diesel::dsl::now),
+                ))
+                .get_result(conn)
+        })
+    }
+
+    fn get_question_revisions(
+        &self,
+        question_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<QuestionRevision>, Error> {
+        let conn = self.as_conn();
+        let query = question_revision::table
+            .filter(question_revision::question_uuid.eq(question_uuid))
+            .into_boxed();
+        // Only the revisions that sort after the last one of the previous page are on this page.
+        let query = match pagination.after {
+            Some(after) => {
+                let last: QuestionRevision = question_revision::table.find(after).first(conn)?;
+                match pagination.sort {
+                    RecencySort::Newest => query.filter(
+                        question_revision::created_at.lt(last.created_at).or(
+                            question_revision::created_at
+                                .eq(last.created_at)
+                                .and(question_revision::uuid.lt(last.uuid)),
+                        ),
+                    ),
+                    RecencySort::Oldest => query.filter(
+                        question_revision::created_at.gt(last.created_at).or(
+                            question_revision::created_at
+                                .eq(last.created_at)
+                                .and(question_revision::uuid.gt(last.uuid)),
+                        ),
+                    ),
+                }
+            }
+            None => query,
+        };
+        let query = match pagination.sort {
+            RecencySort::Newest => query.order_by((
+                question_revision::created_at.desc(),
+                question_revision::uuid.desc(),
+            )),
+            RecencySort::Oldest => {
+                query.order_by((question_revision::created_at, question_revision::uuid))
+            }
+        };
+        let revisions = query.limit(pagination.fetch_limit()).get_results(conn)?;
+        Ok(Page::from_fetched(revisions, pagination.limit, |r| r.uuid))
+    }
+
+    fn get_question_revision(&self, revision_uuid: Uuid) -> Result<QuestionRevision, Error> {
+        crate::util::get_row(question_revision::table, revision_uuid, self.as_conn())
+    }
+
+    fn copy_questions(
+        &self,
+        from_bucket_uuid: Uuid,
+        to_bucket_uuid: Uuid,
+        author_uuid: Option<Uuid>,
+    ) -> Result<Vec<(Uuid, Question)>, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            let originals: Vec<Question> = question::table
+                .filter(question::bucket_uuid.eq(from_bucket_uuid))
+                .order_by(question::created_at)
+                .get_results(conn)?;
+            if originals.is_empty() {
+                return Ok(vec![]);
+            }
+            let copies: Vec<NewQuestion> = originals
+                .iter()
+                .map(|original| NewQuestion {
+                    bucket_uuid: to_bucket_uuid,
+                    user_uuid: author_uuid,
+                    question_text: original.question_text.clone(),
+                })
+                .collect();
+            // Postgres returns the inserted rows in the order they were given.
+            let copies: Vec<Question> = diesel::insert_into(question::table)
+                .values(&copies)
+                .get_results(conn)?;
+            Ok(originals
+                .into_iter()
+                .map(|original| original.uuid)
+                .zip(copies)
+                .collect())
+        })
+    }
+
+    fn import_questions(
+        &self,
+        bucket_uuid: Uuid,
+        user_uuid: Option<Uuid>,
+        questions: Vec<QuestionImport>,
+    ) -> Result<Vec<ImportOutcome>, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            let duplicate_policy: DuplicatePolicy = bucket::table
+                .find(bucket_uuid)
+                .select(bucket::duplicate_policy)
+                .get_result(conn)?;
+            let mut existing: HashSet<String> = question::table
+                .filter(question::bucket_uuid.eq(bucket_uuid))
+                .select(question::question_text)
+                .get_results::<String>(conn)?
+                .iter()
+                .map(|question_text| normalize_question_text(question_text))
+                .collect();
+            let mut tag_uuids: HashMap<String, Uuid> = HashMap::new();
+
+            let mut outcomes = Vec::with_capacity(questions.len());
+            for import in questions {
+                // Questions imported earlier in the loop are already in the bucket,
+                // so they are found to be similar too.
+                let is_duplicate = !existing.insert(normalize_question_text(&import.question_text))
+                    || (duplicate_policy == DuplicatePolicy::Reject
+                        && !self
+                            .find_similar_questions(
+                                bucket_uuid,
+                                &import.question_text,
+                                SIMILARITY_THRESHOLD,
+                                1,
+                            )?
+                            .is_empty());
+                if is_duplicate {
+                    outcomes.push(ImportOutcome::Duplicate);
+                    continue;
+                }
+                let new_question = NewQuestion {
+                    bucket_uuid,
+                    user_uuid,
+                    question_text: import.question_text,
+                };
+                let question: Question = diesel::insert_into(question::table)
+                    .values(new_question)
+                    .get_result(conn)?;
+                tag_new_question(&question, import.tags, &mut tag_uuids, conn)?;
+                outcomes.push(ImportOutcome::Imported(question));
+            }
+            Ok(outcomes)
+        })
+    }
+
+    fn export_questions(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<QuestionExport>, Error> {
+        let conn = self.as_conn();
+        let query = question::table
+            .filter(question::bucket_uuid.eq(bucket_uuid))
+            .into_boxed();
+        // Only the questions that sort after the last one of the previous page are on this page.
+        let query = match pagination.after {
+            Some(after) => {
+                let last: Question = question::table.find(after).first(conn)?;
+                match pagination.sort {
+                    RecencySort::Newest => query.filter(
+                        question::created_at
+                            .lt(last.created_at)
+                            .or(question::created_at
+                                .eq(last.created_at)
+                                .and(question::uuid.lt(last.uuid))),
+                    ),
+                    RecencySort::Oldest => query.filter(
+                        question::created_at
+                            .gt(last.created_at)
+                            .or(question::created_at
+                                .eq(last.created_at)
+                                .and(question::uuid.gt(last.uuid))),
+                    ),
+                }
+            }
+            None => query,
+        };
+        let query = match pagination.sort {
+            RecencySort::Newest => {
+                query.order_by((question::created_at.desc(), question::uuid.desc()))
+            }
+            RecencySort::Oldest => query.order_by((question::created_at, question::uuid)),
+        };
+        let questions: Vec<Question> = query.limit(pagination.fetch_limit()).get_results(conn)?;
+        let questions = Page::from_fetched(questions, pagination.limit, |q| q.uuid);
+        let question_uuids: Vec<Uuid> = questions.items.iter().map(|q| q.uuid).collect();
+
+        let mut tags: HashMap<Uuid, Vec<String>> = HashMap::new();
+        question_tag::table
+            .inner_join(tag::table)
+            .filter(question_tag::question_uuid.eq_any(question_uuids.clone()))
+            .order_by(tag::tag_name)
+            .select((question_tag::question_uuid, tag::tag_name))
+            .get_results::<(Uuid, String)>(conn)?
+            .into_iter()
+            .for_each(|(question_uuid, tag_name)| {
+                tags.entry(question_uuid).or_default().push(tag_name)
+            });
+
+        let answer_counts: HashMap<Uuid, i64> = answer::table
+            .filter(answer::question_uuid.eq_any(question_uuids))
+            .group_by(answer::question_uuid)
+            // Diesel doesn't allow aggregates alongside grouped columns, so the count is written out.
+            .select((answer::question_uuid, sql::<BigInt>("COUNT(*)")))
+            .get_results(conn)?
+            .into_iter()
+            .collect();
+
+        Ok(questions.map(|question| QuestionExport {
+            tags: tags.remove(&question.uuid).unwrap_or_default(),
+            answer_count: answer_counts.get(&question.uuid).cloned().unwrap_or(0),
+            question,
+        }))
+    }
+
+    fn find_similar_questions(
+        &self,
+        bucket_uuid: Uuid,
+        question_text: &str,
+        threshold: f32,
+        limit: i64,
+    ) -> Result<Vec<SimilarQuestion>, Error> {
+        let similar: Vec<(Question, f32)> = question::table
+            .filter(question::bucket_uuid.eq(bucket_uuid))
+            .filter(similarity(question::question_text, question_text).ge(threshold))
+            .order_by(similarity(question::question_text, question_text).desc())
+            .then_order_by(question::created_at)
+            .limit(limit)
+            .select((
+                question::all_columns,
+                similarity(question::question_text, question_text),
+            ))
+            .get_results(self.as_conn())?;
+
+        let normalized = normalize_question_text(question_text);
+        let similar = similar
+            .into_iter()
+            .map(|(question, similarity)| SimilarQuestion {
+                exact: normalize_question_text(&question.question_text) == normalized,
+                question,
+                similarity,
+            })
+            .collect();
+        Ok(similar)
+    }
+}
+
+/// Tags the question with the named tags, creating the ones its bucket doesn't have yet.
+///
+/// The uuids of the tags are remembered, so that questions tagged one after another
+/// don't look the same tags up again.
+fn tag_new_question(
+    question: &Question,
+    tag_names: Vec<String>,
+    tag_uuids: &mut HashMap<String, Uuid>,
+    conn: &PgConnection,
+) -> Result<(), Error> {
+    for tag_name in tag_names {
+        let tag_uuid = match tag_uuids.get(&tag_name) {
+            Some(tag_uuid) => *tag_uuid,
+            None => {
+                let tag_uuid = get_or_create_tag_uuid(question.bucket_uuid, &tag_name, conn)?;
+                tag_uuids.insert(tag_name, tag_uuid);
+                tag_uuid
+            }
+        };
+        diesel::insert_into(question_tag::table)
+            .values(NewQuestionTag {
+                question_uuid: question.uuid,
+                tag_uuid,
+            })
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+/// Gets the uuid of the tag with the given name in the bucket, creating the tag if it doesn't exist yet.
+fn get_or_create_tag_uuid(
+    bucket_uuid: Uuid,
+    tag_name: &str,
+    conn: &PgConnection,
+) -> Result<Uuid, Error> {
+    diesel::insert_into(tag::table)
+        .values(NewTag {
+            bucket_uuid,
+            tag_name: tag_name.to_string(),
+        })
+        .on_conflict((tag::bucket_uuid, tag::tag_name))
+        .do_nothing()
+        .execute(conn)?;
+    tag::table
+        .filter(
+            tag::bucket_uuid
+                .eq(bucket_uuid)
+                .and(tag::tag_name.eq(tag_name)),
+        )
+        .select(tag::uuid)
+        .first(conn)
+}
+
+/// Restricts the query to questions that pass the tag filter.
+fn filter_by_tags<'a>(
+    mut query: question::BoxedQuery<'a, Pg>,
+    bucket_uuid: Uuid,
+    tags: &TagFilter,
+    conn: &PgConnection,
+) -> Result<question::BoxedQuery<'a, Pg>, Error> {
+    if !tags.include.is_empty() {
+        let included = tagged_question_uuids(bucket_uuid, &tags.include, conn)?;
+        query = query.filter(question::uuid.eq_any(included));
+    }
+    if !tags.exclude.is_empty() {
+        let excluded = tagged_question_uuids(bucket_uuid, &tags.exclude, conn)?;
+        query = query.filter(question::uuid.ne_all(excluded));
+    }
+    Ok(query)
 }
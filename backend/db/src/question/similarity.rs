@@ -0,0 +1,63 @@
+//! Comparison of question texts, used to catch questions that are already in a bucket.
+//!
+//! The Postgres repository leans on `pg_trgm` to measure similarity,
+//! while the fake repository uses `trigram_similarity`, which follows the same rules,
+//! so that both find the same questions to be similar.
+use std::collections::HashSet;
+
+/// How similar two questions must be for the second to be considered a near duplicate of the first.
+///
+/// Rewordings like "What's" for "What is", or "colour" for "color", land above this,
+/// while short questions that share an opening but ask about something else,
+/// like "What is your favorite book?" and "What is your favorite band?", land below it.
+pub const SIMILARITY_THRESHOLD: f32 = 0.75;
+
+/// Splits the text into lowercase words, the way `pg_trgm` does.
+///
+/// Anything that isn't a letter or a digit separates words.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Reduces the question to its words, so that questions that only differ in
+/// case, punctuation, or spacing are considered the same.
+pub fn normalize_question_text(text: &str) -> String {
+    words(text).collect::<Vec<_>>().join(" ")
+}
+
+/// The set of trigrams that `pg_trgm` extracts from the text.
+///
+/// Each word is padded with two spaces in front and one behind before it is split into trigrams.
+fn trigrams(text: &str) -> HashSet<[char; 3]> {
+    words(text)
+        .flat_map(|word| {
+            let padded: Vec<char> = "  "
+                .chars()
+                .chain(word.chars())
+                .chain(" ".chars())
+                .collect();
+            padded
+                .windows(3)
+                .map(|w| [w[0], w[1], w[2]])
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// How similar the texts are, from 0.0 for no shared trigrams, to 1.0 for the same words.
+///
+/// This matches `pg_trgm`'s `similarity` function:
+/// the number of trigrams the texts share divided by the number of distinct trigrams across both.
+pub fn trigram_similarity(a: &str, b: &str) -> f32 {
+    let a = trigrams(a);
+    let b = trigrams(b);
+    let shared = a.intersection(&b).count();
+    let total = a.len() + b.len() - shared;
+    if total == 0 {
+        0.0
+    } else {
+        shared as f32 / total as f32
+    }
+}
@@ -1,8 +1,22 @@
 use crate::{
-    question::db_types::{NewQuestion, Question},
+    answer::db_types::NewAnswer,
+    bucket::db_types::{BucketFlagChangeset, DrawStrategy, DuplicatePolicy, NewBucket},
+    page::{Pagination, RecencySort},
+    question::{
+        db_types::{
+            ImportOutcome, NewQuestion, NewQuestionDraw, Question, QuestionImport, QuestionSort,
+        },
+        draw::{choose_question, weight},
+        similarity::{normalize_question_text, trigram_similarity, SIMILARITY_THRESHOLD},
+    },
+    question_vote::db_types::{NewQuestionVote, UPVOTE},
+    tag::db_types::{NewQuestionTag, NewTag, TagFilter},
     test::{bucket_fixture::BucketFixture, question_fixture::QuestionFixture, util::execute_test},
     BoxedRepository,
 };
+use chrono::Duration;
+use rand::{rngs::StdRng, SeedableRng};
+use uuid::Uuid;
 
 #[test]
 fn create_question() {
@@ -32,6 +46,45 @@ fn create_question_without_user() {
     });
 }
 
+#[test]
+fn create_tagged_question() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let new_tag = NewTag {
+            bucket_uuid: fixture.bucket.uuid,
+            tag_name: "existing".to_string(),
+        };
+        let existing = db.get_or_create_tag(new_tag).expect("Should create tag");
+        let new_question = NewQuestion {
+            bucket_uuid: fixture.bucket.uuid,
+            user_uuid: Some(fixture.user.uuid),
+            question_text: "Is this question tagged?".to_string(),
+        };
+
+        let question = db
+            .create_tagged_question(
+                new_question,
+                vec!["existing".to_string(), "new".to_string()],
+            )
+            .expect("Should be able to create question.");
+        let tags = db
+            .get_tags_for_question(question.uuid)
+            .expect("Should get tags");
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains(&existing));
+        assert!(tags.iter().any(|tag| tag.tag_name == "new"));
+    });
+}
+
+#[test]
+fn get_question() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let question = db
+            .get_question(fixture.question1.uuid)
+            .expect("Should be able to get question.");
+        assert_eq!(question, fixture.question1);
+    });
+}
+
 #[test]
 fn delete_question() {
     execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
@@ -57,7 +110,7 @@ fn get_random_question_single() {
             .expect("Should be able to create question.");
 
         let random_question: Option<Question> = db
-            .get_random_question(fixture.bucket.uuid)
+            .get_random_question(fixture.bucket.uuid, 0, &TagFilter::default())
             .expect("should get random question");
         let random_question = random_question.expect("Should be one question in bucket");
         assert_eq!(question, random_question);
@@ -68,7 +121,7 @@ fn get_random_question_single() {
 fn get_random_question_none() {
     execute_test(|fixture: &BucketFixture, db: BoxedRepository| {
         let random_question: Option<Question> = db
-            .get_random_question(fixture.bucket.uuid)
+            .get_random_question(fixture.bucket.uuid, 0, &TagFilter::default())
             .expect("should get random question");
         assert_eq!(random_question, None);
     });
@@ -115,15 +168,623 @@ fn get_questions_depending_on_achived_status() {
         assert!(question.archived);
 
         let archived_questions = db
-            .get_all_questions_for_bucket_of_given_archived_status(fixture.bucket.uuid, true)
-            .expect("Should get archived questions");
+            .get_all_questions_for_bucket_of_given_archived_status(
+                fixture.bucket.uuid,
+                true,
+                &TagFilter::default(),
+                Pagination::first(10, QuestionSort::Updated),
+            )
+            .expect("Should get archived questions")
+            .items;
         assert_eq!(archived_questions.len(), 1);
         assert_eq!(archived_questions[0].uuid, fixture.question1.uuid);
 
         let active_questions = db
-            .get_all_questions_for_bucket_of_given_archived_status(fixture.bucket.uuid, false)
-            .expect("Should get active questions");
+            .get_all_questions_for_bucket_of_given_archived_status(
+                fixture.bucket.uuid,
+                false,
+                &TagFilter::default(),
+                Pagination::first(10, QuestionSort::Updated),
+            )
+            .expect("Should get active questions")
+            .items;
         assert_eq!(active_questions.len(), 1);
         assert_eq!(active_questions[0].uuid, fixture.question2.uuid);
     })
 }
+
+#[test]
+fn get_questions_sorted_by_score() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let vote = NewQuestionVote {
+            user_uuid: fixture.user.uuid,
+            question_uuid: fixture.question2.uuid,
+            vote: UPVOTE,
+        };
+        db.set_vote(vote).expect("Should vote");
+
+        let questions = db
+            .get_all_questions_for_bucket_of_given_archived_status(
+                fixture.bucket.uuid,
+                false,
+                &TagFilter::default(),
+                Pagination::first(10, QuestionSort::Score),
+            )
+            .expect("Should get active questions")
+            .items;
+        assert_eq!(questions.len(), 2);
+        assert_eq!(questions[0].uuid, fixture.question2.uuid);
+        assert_eq!(questions[0].score(), 1);
+        assert_eq!(questions[1].uuid, fixture.question1.uuid);
+    })
+}
+
+#[test]
+fn page_through_questions_sorted_by_score() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let vote = NewQuestionVote {
+            user_uuid: fixture.user.uuid,
+            question_uuid: fixture.question2.uuid,
+            vote: UPVOTE,
+        };
+        db.set_vote(vote).expect("Should vote");
+
+        let first = Pagination::first(1, QuestionSort::Score);
+        let page = db
+            .get_all_questions_for_bucket_of_given_archived_status(
+                fixture.bucket.uuid,
+                false,
+                &TagFilter::default(),
+                first,
+            )
+            .expect("Should get first page");
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].uuid, fixture.question2.uuid);
+        assert_eq!(page.next_cursor, Some(fixture.question2.uuid));
+
+        let next = first.next(&page).expect("Should have another page");
+        let page = db
+            .get_all_questions_for_bucket_of_given_archived_status(
+                fixture.bucket.uuid,
+                false,
+                &TagFilter::default(),
+                next,
+            )
+            .expect("Should get second page");
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].uuid, fixture.question1.uuid);
+        assert_eq!(page.next_cursor, None);
+    })
+}
+
+#[test]
+fn get_random_question_ignores_archived() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        db.set_archive_status_for_question(fixture.question1.uuid, true)
+            .expect("Should set archived question");
+
+        for _ in 0..10 {
+            let random_question = db
+                .get_random_question(fixture.bucket.uuid, 0, &TagFilter::default())
+                .expect("should get random question")
+                .expect("Should be one question in bucket");
+            assert_eq!(random_question, fixture.question2);
+        }
+    });
+}
+
+#[test]
+fn record_draw() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let new_draw = NewQuestionDraw {
+            question_uuid: fixture.question1.uuid,
+            bucket_uuid: fixture.bucket.uuid,
+            user_uuid: Some(fixture.user.uuid),
+            session_uuid: None,
+        };
+        let draw = db.record_draw(new_draw).expect("Should record draw");
+        assert_eq!(draw.question_uuid, fixture.question1.uuid);
+
+        let draws = db
+            .get_recent_draws(
+                fixture.bucket.uuid,
+                Pagination::first(10, RecencySort::Newest),
+            )
+            .expect("Should get recent draws");
+        assert_eq!(draws.items, vec![draw]);
+    });
+}
+
+#[test]
+fn get_recent_draws_most_recent_first() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let mut new_draw = NewQuestionDraw {
+            question_uuid: fixture.question1.uuid,
+            bucket_uuid: fixture.bucket.uuid,
+            user_uuid: None,
+            session_uuid: None,
+        };
+        db.record_draw(new_draw).expect("Should record draw");
+        new_draw.question_uuid = fixture.question2.uuid;
+        db.record_draw(new_draw).expect("Should record draw");
+
+        let draws = db
+            .get_recent_draws(
+                fixture.bucket.uuid,
+                Pagination::first(1, RecencySort::Newest),
+            )
+            .expect("Should get recent draws")
+            .items;
+        assert_eq!(draws.len(), 1);
+        assert_eq!(draws[0].question_uuid, fixture.question2.uuid);
+    });
+}
+
+#[test]
+fn edit_question() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let question = db
+            .edit_question(
+                fixture.question1.uuid,
+                "Is this the edited question?".to_string(),
+                Some(fixture.user.uuid),
+            )
+            .expect("Should edit question");
+        assert_eq!(question.uuid, fixture.question1.uuid);
+        assert_eq!(question.question_text, "Is this the edited question?");
+
+        let revisions = db
+            .get_question_revisions(
+                fixture.question1.uuid,
+                Pagination::first(10, RecencySort::Newest),
+            )
+            .expect("Should get revisions")
+            .items;
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].question_text, fixture.question1.question_text);
+        assert_eq!(revisions[0].user_uuid, Some(fixture.user.uuid));
+        assert_eq!(
+            db.get_question_revision(revisions[0].uuid),
+            Ok(revisions[0].clone())
+        );
+    });
+}
+
+#[test]
+fn get_question_revisions_most_recent_first() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        db.edit_question(fixture.question1.uuid, "Second?".to_string(), None)
+            .expect("Should edit question");
+        db.edit_question(fixture.question1.uuid, "Third?".to_string(), None)
+            .expect("Should edit question");
+
+        let revisions = db
+            .get_question_revisions(
+                fixture.question1.uuid,
+                Pagination::first(10, RecencySort::Newest),
+            )
+            .expect("Should get revisions")
+            .items;
+        let texts: Vec<&str> = revisions.iter().map(|r| r.question_text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec!["Second?", fixture.question1.question_text.as_str()]
+        );
+
+        let other_revisions = db
+            .get_question_revisions(
+                fixture.question2.uuid,
+                Pagination::first(10, RecencySort::Newest),
+            )
+            .expect("Should get revisions");
+        assert!(other_revisions.items.is_empty());
+    });
+}
+
+#[test]
+fn edit_nonexistent_question() {
+    execute_test(|_fixture: &QuestionFixture, db: BoxedRepository| {
+        db.edit_question(Uuid::new_v4(), "Nobody home?".to_string(), None)
+            .expect_err("Should not edit a question that doesn't exist");
+    });
+}
+
+#[test]
+fn get_random_question_avoids_recent_draws() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let new_draw = NewQuestionDraw {
+            question_uuid: fixture.question1.uuid,
+            bucket_uuid: fixture.bucket.uuid,
+            user_uuid: None,
+            session_uuid: None,
+        };
+        db.record_draw(new_draw).expect("Should record draw");
+
+        for _ in 0..10 {
+            let random_question = db
+                .get_random_question(fixture.bucket.uuid, 1, &TagFilter::default())
+                .expect("should get random question")
+                .expect("Should be one question that wasn't drawn recently");
+            assert_eq!(random_question, fixture.question2);
+        }
+
+        let new_draw = NewQuestionDraw {
+            question_uuid: fixture.question2.uuid,
+            ..new_draw
+        };
+        db.record_draw(new_draw).expect("Should record draw");
+        let random_question = db
+            .get_random_question(fixture.bucket.uuid, 2, &TagFilter::default())
+            .expect("should get random question");
+        assert_eq!(random_question, None);
+    });
+}
+
+#[test]
+fn get_random_question_with_each_draw_strategy() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let strategies = [
+            DrawStrategy::Uniform,
+            DrawStrategy::Upvotes,
+            DrawStrategy::OlderFirst,
+            DrawStrategy::MembersNotRecentlyDrawn,
+        ];
+        for strategy in strategies.iter() {
+            let changeset = BucketFlagChangeset {
+                uuid: fixture.bucket.uuid,
+                public_viewable: None,
+                drawing_enabled: None,
+                exclusive: None,
+                draw_strategy: Some(*strategy),
+                duplicate_policy: None,
+            };
+            db.change_bucket_flags(changeset)
+                .expect("Should change draw strategy");
+
+            let question = db
+                .get_random_question(fixture.bucket.uuid, 0, &TagFilter::default())
+                .expect("Should get random question")
+                .expect("Question should be present");
+            assert_eq!(question.bucket_uuid, fixture.bucket.uuid);
+        }
+    });
+}
+
+#[test]
+fn older_first_weights_older_questions_more() {
+    execute_test(|fixture: &QuestionFixture, _db: BoxedRepository| {
+        let mut older = fixture.question1.clone();
+        older.created_at = fixture.question2.created_at - Duration::hours(10);
+        let newest = fixture.question2.created_at;
+
+        let older_weight = weight(&older, DrawStrategy::OlderFirst, &[], newest);
+        let newer_weight = weight(&fixture.question2, DrawStrategy::OlderFirst, &[], newest);
+        assert!(older_weight > newer_weight);
+        assert_eq!(
+            weight(&older, DrawStrategy::Uniform, &[], newest),
+            weight(&fixture.question2, DrawStrategy::Uniform, &[], newest)
+        );
+    });
+}
+
+#[test]
+fn upvotes_weights_higher_scores_more() {
+    execute_test(|fixture: &QuestionFixture, _db: BoxedRepository| {
+        let mut upvoted = fixture.question1.clone();
+        upvoted.upvotes = 3;
+        let mut downvoted = fixture.question2.clone();
+        downvoted.downvotes = 3;
+        let newest = fixture.question2.created_at;
+
+        let upvoted_weight = weight(&upvoted, DrawStrategy::Upvotes, &[], newest);
+        let unvoted_weight = weight(&fixture.question1, DrawStrategy::Upvotes, &[], newest);
+        let downvoted_weight = weight(&downvoted, DrawStrategy::Upvotes, &[], newest);
+        assert!(upvoted_weight > unvoted_weight);
+        assert!(unvoted_weight > downvoted_weight);
+        assert!(downvoted_weight > 0.0);
+    });
+}
+
+#[test]
+fn members_not_recently_drawn_weights_other_authors_more() {
+    execute_test(|fixture: &QuestionFixture, _db: BoxedRepository| {
+        let mut other_author = fixture.question1.clone();
+        other_author.user_uuid = Some(Uuid::new_v4());
+        let recent_authors = [fixture.user.uuid];
+        let newest = fixture.question2.created_at;
+        let strategy = DrawStrategy::MembersNotRecentlyDrawn;
+
+        assert!(
+            weight(&other_author, strategy, &recent_authors, newest)
+                > weight(&fixture.question2, strategy, &recent_authors, newest)
+        );
+    });
+}
+
+#[test]
+fn choose_question_is_deterministic_for_a_seed() {
+    execute_test(|fixture: &QuestionFixture, _db: BoxedRepository| {
+        let candidates = vec![fixture.question1.clone(), fixture.question2.clone()];
+        let draw = |seed: u64| -> Vec<Uuid> {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..10)
+                .filter_map(|_| {
+                    choose_question(candidates.clone(), DrawStrategy::Uniform, &[], &mut rng)
+                })
+                .map(|q| q.uuid)
+                .collect()
+        };
+        assert_eq!(draw(7), draw(7));
+        assert_eq!(draw(7).len(), 10);
+
+        let none = choose_question(
+            vec![],
+            DrawStrategy::Uniform,
+            &[],
+            &mut StdRng::seed_from_u64(7),
+        );
+        assert_eq!(none, None);
+    });
+}
+
+#[test]
+fn copy_questions_into_another_bucket() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let new_bucket = NewBucket {
+            bucket_name: "copy".to_string(),
+            bucket_slug: "copy".to_string(),
+        };
+        let copy_bucket = db.create_bucket(new_bucket).expect("Should create bucket");
+        db.set_archive_status_for_question(fixture.question1.uuid, true)
+            .expect("Should archive question");
+
+        let copies = db
+            .copy_questions(fixture.bucket.uuid, copy_bucket.uuid, None)
+            .expect("Should copy questions");
+        let originals: Vec<Uuid> = copies.iter().map(|(original, _)| *original).collect();
+        assert_eq!(
+            originals,
+            vec![fixture.question1.uuid, fixture.question2.uuid]
+        );
+        for ((_, copy), original) in copies.iter().zip(&[&fixture.question1, &fixture.question2]) {
+            assert_ne!(copy.uuid, original.uuid);
+            assert_eq!(copy.bucket_uuid, copy_bucket.uuid);
+            assert_eq!(copy.user_uuid, None);
+            assert_eq!(copy.question_text, original.question_text);
+            assert!(!copy.archived);
+        }
+
+        // The questions in the original bucket are left alone.
+        let archived = db
+            .get_all_questions_for_bucket_of_given_archived_status(
+                fixture.bucket.uuid,
+                true,
+                &TagFilter::default(),
+                Pagination::first(10, QuestionSort::Updated),
+            )
+            .expect("Should get questions")
+            .items;
+        assert_eq!(archived.len(), 1);
+        assert_eq!(
+            db.get_number_of_active_questions_for_bucket(copy_bucket.uuid),
+            Ok(2)
+        );
+    });
+}
+
+#[test]
+fn import_questions_skips_duplicates() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let import = |question_text: &str, tags: &[&str]| QuestionImport {
+            question_text: question_text.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        };
+        let questions = vec![
+            import("Is this an imported question?", &["imported", "new"]),
+            import("is this the FIRST question", &[]),
+            import("Is this an imported question?", &["imported"]),
+            import("Is this another imported question?", &["imported"]),
+        ];
+        let outcomes = db
+            .import_questions(fixture.bucket.uuid, Some(fixture.user.uuid), questions)
+            .expect("Should import questions");
+        assert_eq!(outcomes.len(), 4);
+        assert_eq!(outcomes[1], ImportOutcome::Duplicate);
+        assert_eq!(outcomes[2], ImportOutcome::Duplicate);
+
+        let imported = match &outcomes[0] {
+            ImportOutcome::Imported(question) => question.clone(),
+            ImportOutcome::Duplicate => panic!("The first question should be imported"),
+        };
+        assert_eq!(imported.bucket_uuid, fixture.bucket.uuid);
+        assert_eq!(imported.user_uuid, Some(fixture.user.uuid));
+        let tags: Vec<String> = db
+            .get_tags_for_question(imported.uuid)
+            .expect("Should get tags")
+            .into_iter()
+            .map(|tag| tag.tag_name)
+            .collect();
+        assert_eq!(tags, vec!["imported".to_string(), "new".to_string()]);
+        assert_eq!(
+            db.get_number_of_active_questions_for_bucket(fixture.bucket.uuid),
+            Ok(4)
+        );
+    });
+}
+
+#[test]
+fn import_questions_skips_similar_questions_when_rejecting_duplicates() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let changeset = BucketFlagChangeset {
+            uuid: fixture.bucket.uuid,
+            public_viewable: None,
+            drawing_enabled: None,
+            exclusive: None,
+            draw_strategy: None,
+            duplicate_policy: Some(DuplicatePolicy::Reject),
+        };
+        db.change_bucket_flags(changeset)
+            .expect("Should reject duplicates");
+
+        let questions = vec![
+            "Is this the very first question?",
+            "What is your favorite color?",
+            "What is your favourite colour?",
+        ]
+        .into_iter()
+        .map(|question_text| QuestionImport {
+            question_text: question_text.to_string(),
+            tags: vec![],
+        })
+        .collect();
+        let outcomes = db
+            .import_questions(fixture.bucket.uuid, None, questions)
+            .expect("Should import questions");
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes[0], ImportOutcome::Duplicate);
+        match &outcomes[1] {
+            ImportOutcome::Imported(question) => {
+                assert_eq!(question.question_text, "What is your favorite color?")
+            }
+            ImportOutcome::Duplicate => panic!("The unrelated question should be imported"),
+        }
+        assert_eq!(outcomes[2], ImportOutcome::Duplicate);
+    });
+}
+
+#[test]
+fn export_questions_includes_tags_and_answer_counts() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let new_tag = NewTag {
+            bucket_uuid: fixture.bucket.uuid,
+            tag_name: "first".to_string(),
+        };
+        let tag = db.get_or_create_tag(new_tag).expect("Should create tag");
+        db.tag_question(NewQuestionTag {
+            question_uuid: fixture.question1.uuid,
+            tag_uuid: tag.uuid,
+        })
+        .expect("Should tag question");
+        for answer_text in &["Yes", "Probably"] {
+            let new_answer = NewAnswer {
+                user_uuid: Some(fixture.user.uuid),
+                question_uuid: fixture.question1.uuid,
+                publicly_visible: true,
+                answer_text: answer_text.to_string(),
+                session_uuid: None,
+            };
+            db.create_answer(new_answer).expect("Should create answer");
+        }
+        db.set_archive_status_for_question(fixture.question2.uuid, true)
+            .expect("Should archive question");
+
+        let pagination = Pagination::first(1, RecencySort::Oldest);
+        let first_page = db
+            .export_questions(fixture.bucket.uuid, pagination)
+            .expect("Should export questions");
+        let next = pagination
+            .next(&first_page)
+            .expect("Should have another page");
+        let second_page = db
+            .export_questions(fixture.bucket.uuid, next)
+            .expect("Should export questions");
+        assert_eq!(second_page.next_cursor, None);
+        let exports: Vec<_> = first_page
+            .items
+            .into_iter()
+            .chain(second_page.items)
+            .collect();
+        assert_eq!(exports.len(), 2);
+        assert_eq!(exports[0].question.uuid, fixture.question1.uuid);
+        assert_eq!(exports[0].tags, vec!["first".to_string()]);
+        assert_eq!(exports[0].answer_count, 2);
+        assert_eq!(exports[1].question.uuid, fixture.question2.uuid);
+        assert!(exports[1].question.archived);
+        assert!(exports[1].tags.is_empty());
+        assert_eq!(exports[1].answer_count, 0);
+    });
+}
+
+#[test]
+fn normalized_questions_ignore_case_punctuation_and_spacing() {
+    assert_eq!(
+        normalize_question_text("  What's your   FAVORITE color?!"),
+        "what s your favorite color"
+    );
+    assert_eq!(trigram_similarity("Is it?", "is, IT"), 1.0);
+    assert_eq!(trigram_similarity("", "?"), 0.0);
+    assert!(
+        trigram_similarity("What is your favorite book?", "What is your favorite band?")
+            < SIMILARITY_THRESHOLD
+    );
+    assert!(
+        trigram_similarity(
+            "What's your favorite color?",
+            "What is your favorite color?"
+        ) >= SIMILARITY_THRESHOLD
+    );
+}
+
+#[test]
+fn find_similar_questions_finds_exact_and_near_duplicates() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let exact = db
+            .find_similar_questions(
+                fixture.bucket.uuid,
+                "is this the FIRST question",
+                SIMILARITY_THRESHOLD,
+                5,
+            )
+            .expect("Should find similar questions");
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].question, fixture.question1);
+        assert!(exact[0].exact);
+        assert!((exact[0].similarity - 1.0).abs() < 0.001);
+
+        let near = db
+            .find_similar_questions(
+                fixture.bucket.uuid,
+                "Is this the very first question?",
+                SIMILARITY_THRESHOLD,
+                5,
+            )
+            .expect("Should find similar questions");
+        assert_eq!(near.len(), 1);
+        assert_eq!(near[0].question, fixture.question1);
+        assert!(!near[0].exact);
+        assert!(near[0].similarity < 1.0);
+
+        let unrelated = db
+            .find_similar_questions(
+                fixture.bucket.uuid,
+                "What is your favorite color?",
+                SIMILARITY_THRESHOLD,
+                5,
+            )
+            .expect("Should find similar questions");
+        assert!(unrelated.is_empty());
+    });
+}
+
+#[test]
+fn find_similar_questions_orders_by_similarity() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let similar = db
+            .find_similar_questions(fixture.bucket.uuid, "Is this the second question?", 0.3, 5)
+            .expect("Should find similar questions");
+        assert_eq!(similar.len(), 2);
+        assert_eq!(similar[0].question, fixture.question2);
+        assert_eq!(similar[1].question, fixture.question1);
+        assert!(similar[0].similarity > similar[1].similarity);
+
+        let limited = db
+            .find_similar_questions(fixture.bucket.uuid, "Is this the second question?", 0.3, 1)
+            .expect("Should find similar questions");
+        assert_eq!(limited.len(), 1);
+
+        let elsewhere = db
+            .find_similar_questions(Uuid::new_v4(), "Is this the second question?", 0.3, 5)
+            .expect("Should find similar questions");
+        assert!(elsewhere.is_empty());
+    });
+}
@@ -0,0 +1,41 @@
+//! Module for question vote related database interactions.
+use crate::schema::question_vote;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The value stored for an upvote.
+pub const UPVOTE: i16 = 1;
+/// The value stored for a downvote.
+pub const DOWNVOTE: i16 = -1;
+
+/// A user's vote on a question.
+#[derive(
+    Clone, Copy, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize,
+)]
+#[primary_key(user_uuid, question_uuid)]
+#[table_name = "question_vote"]
+pub struct QuestionVote {
+    /// The user that voted.
+    pub user_uuid: Uuid,
+    /// The question that was voted on.
+    pub question_uuid: Uuid,
+    /// Either `UPVOTE` or `DOWNVOTE`.
+    pub vote: i16,
+    /// When the row was last updated.
+    pub updated_at: NaiveDateTime,
+    /// When the row was created.
+    pub created_at: NaiveDateTime,
+}
+
+/// Structure for casting or changing a vote.
+#[derive(Clone, Copy, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "question_vote"]
+pub struct NewQuestionVote {
+    /// The user that voted.
+    pub user_uuid: Uuid,
+    /// The question that was voted on.
+    pub question_uuid: Uuid,
+    /// Either `UPVOTE` or `DOWNVOTE`.
+    pub vote: i16,
+}
@@ -0,0 +1,100 @@
+//! Mock impl
+
+use crate::{
+    fake::{DummyDbErrorInfo, FakeDatabase},
+    question::db_types::Question,
+    question_vote::{
+        db_types::{NewQuestionVote, QuestionVote, DOWNVOTE, UPVOTE},
+        interface::QuestionVoteRepository,
+    },
+};
+use diesel::result::{DatabaseErrorKind, Error};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+impl QuestionVoteRepository for Arc<Mutex<FakeDatabase>> {
+    fn set_vote(&self, vote: NewQuestionVote) -> Result<Question, Error> {
+        let mut db = self.lock().unwrap();
+        if vote.vote != UPVOTE && vote.vote != DOWNVOTE {
+            // Mirrors the check constraint on the table.
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::__Unknown,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+        if !db.questions.iter().any(|q| q.uuid == vote.question_uuid)
+            || !db.users.iter().any(|u| u.uuid == vote.user_uuid)
+        {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::ForeignKeyViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        match db
+            .question_votes
+            .iter_mut()
+            .find(|v| v.user_uuid == vote.user_uuid && v.question_uuid == vote.question_uuid)
+        {
+            Some(existing) => {
+                existing.vote = vote.vote;
+                existing.updated_at = now;
+            }
+            None => db.question_votes.push(QuestionVote {
+                user_uuid: vote.user_uuid,
+                question_uuid: vote.question_uuid,
+                vote: vote.vote,
+                updated_at: now,
+                created_at: now,
+            }),
+        }
+        update_vote_totals(vote.question_uuid, &mut db)
+    }
+
+    fn remove_vote(&self, user_uuid: Uuid, question_uuid: Uuid) -> Result<Question, Error> {
+        let mut db = self.lock().unwrap();
+        let index = db
+            .question_votes
+            .iter()
+            .position(|v| v.user_uuid == user_uuid && v.question_uuid == question_uuid)
+            .ok_or(Error::NotFound)?;
+        db.question_votes.remove(index);
+        update_vote_totals(question_uuid, &mut db)
+    }
+
+    fn get_vote(
+        &self,
+        user_uuid: Uuid,
+        question_uuid: Uuid,
+    ) -> Result<Option<QuestionVote>, Error> {
+        let db = self.lock().unwrap();
+        let vote = db
+            .question_votes
+            .iter()
+            .find(|v| v.user_uuid == user_uuid && v.question_uuid == question_uuid)
+            .cloned();
+        Ok(vote)
+    }
+}
+
+/// Recounts the votes for a question, and stores the totals on the question.
+fn update_vote_totals(question_uuid: Uuid, db: &mut FakeDatabase) -> Result<Question, Error> {
+    let count_votes = |value: i16| {
+        db.question_votes
+            .iter()
+            .filter(|v| v.question_uuid == question_uuid && v.vote == value)
+            .count() as i32
+    };
+    let upvotes = count_votes(UPVOTE);
+    let downvotes = count_votes(DOWNVOTE);
+
+    let question = db
+        .questions
+        .iter_mut()
+        .find(|q| q.uuid == question_uuid)
+        .ok_or(Error::NotFound)?;
+    question.upvotes = upvotes;
+    question.downvotes = downvotes;
+    Ok(question.clone())
+}
@@ -0,0 +1,21 @@
+//! Specification of what functions are supported for storing data for question votes.
+use crate::{
+    question::db_types::Question,
+    question_vote::db_types::{NewQuestionVote, QuestionVote},
+};
+use diesel::QueryResult;
+use uuid::Uuid;
+
+/// Functions for specifically working with votes on questions.
+pub trait QuestionVoteRepository {
+    /// Casts a vote, replacing the user's previous vote on the question if there was one.
+    ///
+    /// Returns the question with its updated vote totals.
+    fn set_vote(&self, vote: NewQuestionVote) -> QueryResult<Question>;
+    /// Removes the user's vote on the question.
+    ///
+    /// Returns the question with its updated vote totals.
+    fn remove_vote(&self, user_uuid: Uuid, question_uuid: Uuid) -> QueryResult<Question>;
+    /// Gets the user's vote on the question, if they have voted on it.
+    fn get_vote(&self, user_uuid: Uuid, question_uuid: Uuid) -> QueryResult<Option<QuestionVote>>;
+}
@@ -0,0 +1,80 @@
+//! Implementation of the specified interfaces for PgConnection.
+use crate::{
+    question::db_types::Question,
+    question_vote::{
+        db_types::{NewQuestionVote, QuestionVote, DOWNVOTE, UPVOTE},
+        interface::QuestionVoteRepository,
+    },
+    schema::{question, question_vote},
+    AsConnRef,
+};
+use diesel::{
+    query_dsl::{QueryDsl, RunQueryDsl},
+    result::Error,
+    BoolExpressionMethods, Connection, ExpressionMethods, OptionalExtension, PgConnection,
+};
+use uuid::Uuid;
+
+impl<T> QuestionVoteRepository for T
+where
+    T: AsConnRef,
+{
+    fn set_vote(&self, vote: NewQuestionVote) -> Result<Question, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            diesel::insert_into(question_vote::table)
+                .values(&vote)
+                .on_conflict((question_vote::user_uuid, question_vote::question_uuid))
+                .do_update()
+                .set(question_vote::vote.eq(vote.vote))
+                .execute(conn)?;
+            update_vote_totals(vote.question_uuid, conn)
+        })
+    }
+
+    fn remove_vote(&self, user_uuid: Uuid, question_uuid: Uuid) -> Result<Question, Error> {
+        let conn = self.as_conn();
+        conn.transaction(|| {
+            let target = question_vote::table.find((user_uuid, question_uuid));
+            let deleted = diesel::delete(target).execute(conn)?;
+            if deleted == 0 {
+                return Err(Error::NotFound);
+            }
+            update_vote_totals(question_uuid, conn)
+        })
+    }
+
+    fn get_vote(
+        &self,
+        user_uuid: Uuid,
+        question_uuid: Uuid,
+    ) -> Result<Option<QuestionVote>, Error> {
+        question_vote::table
+            .find((user_uuid, question_uuid))
+            .get_result(self.as_conn())
+            .optional()
+    }
+}
+
+/// Recounts the votes for a question, and stores the totals on the question.
+fn update_vote_totals(question_uuid: Uuid, conn: &PgConnection) -> Result<Question, Error> {
+    let count_votes = |value: i16| -> Result<i64, Error> {
+        question_vote::table
+            .filter(
+                question_vote::question_uuid
+                    .eq(question_uuid)
+                    .and(question_vote::vote.eq(value)),
+            )
+            .count()
+            .get_result(conn)
+    };
+    let upvotes = count_votes(UPVOTE)? as i32;
+    let downvotes = count_votes(DOWNVOTE)? as i32;
+
+    diesel::update(question::table.find(question_uuid))
+        .set((
+            question::upvotes.eq(upvotes),
+            question::downvotes.eq(downvotes),
+        ))
+        .get_result(conn)
+}
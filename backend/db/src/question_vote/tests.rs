@@ -0,0 +1,81 @@
+use crate::{
+    question_vote::db_types::{NewQuestionVote, DOWNVOTE, UPVOTE},
+    test::{question_fixture::QuestionFixture, util::execute_test},
+    BoxedRepository,
+};
+use diesel::result::Error;
+
+#[test]
+fn upvote_question() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let vote = NewQuestionVote {
+            user_uuid: fixture.user.uuid,
+            question_uuid: fixture.question1.uuid,
+            vote: UPVOTE,
+        };
+        let question = db.set_vote(vote).expect("Should be able to vote");
+        assert_eq!(question.upvotes, 1);
+        assert_eq!(question.downvotes, 0);
+        assert_eq!(question.score(), 1);
+    });
+}
+
+#[test]
+fn changing_vote_replaces_previous_vote() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let mut vote = NewQuestionVote {
+            user_uuid: fixture.user.uuid,
+            question_uuid: fixture.question1.uuid,
+            vote: UPVOTE,
+        };
+        db.set_vote(vote).expect("Should be able to vote");
+
+        vote.vote = DOWNVOTE;
+        let question = db.set_vote(vote).expect("Should be able to change vote");
+        assert_eq!(question.upvotes, 0);
+        assert_eq!(question.downvotes, 1);
+
+        let stored = db
+            .get_vote(fixture.user.uuid, fixture.question1.uuid)
+            .expect("Should get vote")
+            .expect("Vote should exist");
+        assert_eq!(stored.vote, DOWNVOTE);
+    });
+}
+
+#[test]
+fn remove_vote() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let vote = NewQuestionVote {
+            user_uuid: fixture.user.uuid,
+            question_uuid: fixture.question1.uuid,
+            vote: UPVOTE,
+        };
+        db.set_vote(vote).expect("Should be able to vote");
+
+        let question = db
+            .remove_vote(fixture.user.uuid, fixture.question1.uuid)
+            .expect("Should remove vote");
+        assert_eq!(question.upvotes, 0);
+        assert_eq!(
+            db.get_vote(fixture.user.uuid, fixture.question1.uuid),
+            Ok(None)
+        );
+        assert_eq!(
+            db.remove_vote(fixture.user.uuid, fixture.question1.uuid),
+            Err(Error::NotFound)
+        );
+    });
+}
+
+#[test]
+fn invalid_vote_is_rejected() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let vote = NewQuestionVote {
+            user_uuid: fixture.user.uuid,
+            question_uuid: fixture.question1.uuid,
+            vote: 2,
+        };
+        db.set_vote(vote).expect_err("Votes can only be up or down");
+    });
+}
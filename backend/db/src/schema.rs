@@ -7,6 +7,15 @@ table! {
         answer_text -> Varchar,
         updated_at -> Timestamp,
         created_at -> Timestamp,
+        session_uuid -> Nullable<Uuid>,
+    }
+}
+
+table! {
+    answer_edit_token (answer_uuid) {
+        answer_uuid -> Uuid,
+        edit_token -> Uuid,
+        created_at -> Timestamp,
     }
 }
 
@@ -28,6 +37,100 @@ table! {
         exclusive -> Bool,
         updated_at -> Timestamp,
         created_at -> Timestamp,
+        draw_strategy -> Varchar,
+        owner_uuid -> Nullable<Uuid>,
+        is_template -> Bool,
+        duplicate_policy -> Varchar,
+    }
+}
+
+table! {
+    bucket_audit_event (uuid) {
+        uuid -> Uuid,
+        bucket_uuid -> Uuid,
+        actor_uuid -> Nullable<Uuid>,
+        target_uuid -> Nullable<Uuid>,
+        action -> Varchar,
+        diff -> Jsonb,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    bucket_ban (bucket_uuid, user_uuid) {
+        bucket_uuid -> Uuid,
+        user_uuid -> Uuid,
+        banned_by_uuid -> Nullable<Uuid>,
+        reason -> Nullable<Varchar>,
+        expires_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    bucket_invite (uuid) {
+        uuid -> Uuid,
+        bucket_uuid -> Uuid,
+        user_uuid -> Nullable<Uuid>,
+        expires_at -> Nullable<Timestamp>,
+        max_uses -> Nullable<Int4>,
+        uses -> Int4,
+        set_public_permission -> Bool,
+        set_drawing_permission -> Bool,
+        set_exclusive_permission -> Bool,
+        kick_permission -> Bool,
+        grant_permissions_permission -> Bool,
+        moderate_questions_permission -> Bool,
+        delete_questions_permission -> Bool,
+        invite_permission -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    bucket_join_code (bucket_uuid) {
+        bucket_uuid -> Uuid,
+        join_code -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    bucket_permission_grant (uuid) {
+        uuid -> Uuid,
+        bucket_uuid -> Uuid,
+        granter_uuid -> Nullable<Uuid>,
+        target_uuid -> Uuid,
+        permission -> Varchar,
+        granted -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    bucket_presence (bucket_uuid, user_uuid) {
+        bucket_uuid -> Uuid,
+        user_uuid -> Uuid,
+        last_seen_at -> Timestamp,
+    }
+}
+
+table! {
+    bucket_slug_redirect (slug) {
+        slug -> Varchar,
+        bucket_uuid -> Uuid,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    bucket_turn (bucket_uuid) {
+        bucket_uuid -> Uuid,
+        asker_uuid -> Uuid,
+        answerer_uuid -> Uuid,
+        turn_number -> Int4,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
     }
 }
 
@@ -42,6 +145,9 @@ table! {
         grant_permissions_permission -> Bool,
         updated_at -> Timestamp,
         created_at -> Timestamp,
+        moderate_questions_permission -> Bool,
+        delete_questions_permission -> Bool,
+        invite_permission -> Bool,
     }
 }
 
@@ -54,6 +160,78 @@ table! {
         archived -> Bool,
         updated_at -> Timestamp,
         created_at -> Timestamp,
+        upvotes -> Int4,
+        downvotes -> Int4,
+    }
+}
+
+table! {
+    question_draw (uuid) {
+        uuid -> Uuid,
+        question_uuid -> Uuid,
+        bucket_uuid -> Uuid,
+        user_uuid -> Nullable<Uuid>,
+        session_uuid -> Nullable<Uuid>,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    question_revision (uuid) {
+        uuid -> Uuid,
+        question_uuid -> Uuid,
+        question_text -> Varchar,
+        user_uuid -> Nullable<Uuid>,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    question_tag (question_uuid, tag_uuid) {
+        question_uuid -> Uuid,
+        tag_uuid -> Uuid,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    question_vote (user_uuid, question_uuid) {
+        user_uuid -> Uuid,
+        question_uuid -> Uuid,
+        vote -> Int2,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    session (uuid) {
+        uuid -> Uuid,
+        bucket_uuid -> Uuid,
+        started_at -> Timestamp,
+        ended_at -> Nullable<Timestamp>,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    session_participant (session_uuid, user_uuid) {
+        session_uuid -> Uuid,
+        user_uuid -> Uuid,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    tag (uuid) {
+        uuid -> Uuid,
+        bucket_uuid -> Uuid,
+        tag_name -> Varchar,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
     }
 }
 
@@ -68,18 +246,61 @@ table! {
 
 joinable!(answer -> bq_user (user_uuid));
 joinable!(answer -> question (question_uuid));
+joinable!(answer -> session (session_uuid));
+joinable!(answer_edit_token -> answer (answer_uuid));
+joinable!(bucket_audit_event -> bq_user (actor_uuid));
+joinable!(bucket_audit_event -> bucket (bucket_uuid));
+joinable!(bucket_ban -> bucket (bucket_uuid));
+joinable!(bucket_invite -> bq_user (user_uuid));
+joinable!(bucket_invite -> bucket (bucket_uuid));
+joinable!(bucket_join_code -> bucket (bucket_uuid));
+joinable!(bucket_permission_grant -> bucket (bucket_uuid));
+joinable!(bucket_presence -> bq_user (user_uuid));
+joinable!(bucket_presence -> bucket (bucket_uuid));
+joinable!(bucket_slug_redirect -> bucket (bucket_uuid));
+joinable!(bucket_turn -> bucket (bucket_uuid));
 joinable!(bucket_user_relation -> bq_user (user_uuid));
 joinable!(bucket_user_relation -> bucket (bucket_uuid));
 joinable!(question -> bq_user (user_uuid));
 joinable!(question -> bucket (bucket_uuid));
+joinable!(question_draw -> bq_user (user_uuid));
+joinable!(question_draw -> bucket (bucket_uuid));
+joinable!(question_draw -> question (question_uuid));
+joinable!(question_draw -> session (session_uuid));
+joinable!(question_revision -> bq_user (user_uuid));
+joinable!(question_revision -> question (question_uuid));
+joinable!(question_tag -> question (question_uuid));
+joinable!(question_tag -> tag (tag_uuid));
+joinable!(question_vote -> bq_user (user_uuid));
+joinable!(question_vote -> question (question_uuid));
+joinable!(session -> bucket (bucket_uuid));
+joinable!(session_participant -> bq_user (user_uuid));
+joinable!(session_participant -> session (session_uuid));
+joinable!(tag -> bucket (bucket_uuid));
 joinable!(user_question_favorite_relation -> bq_user (user_uuid));
 joinable!(user_question_favorite_relation -> question (question_uuid));
 
 allow_tables_to_appear_in_same_query!(
     answer,
+    answer_edit_token,
     bq_user,
     bucket,
+    bucket_audit_event,
+    bucket_ban,
+    bucket_invite,
+    bucket_join_code,
+    bucket_permission_grant,
+    bucket_presence,
+    bucket_slug_redirect,
+    bucket_turn,
     bucket_user_relation,
     question,
+    question_draw,
+    question_revision,
+    question_tag,
+    question_vote,
+    session,
+    session_participant,
+    tag,
     user_question_favorite_relation,
 );
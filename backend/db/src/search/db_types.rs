@@ -0,0 +1,54 @@
+//! Database types for searching.
+use crate::{answer::db_types::Answer, question::db_types::Question};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Marks the start of a matched word in a snippet.
+///
+/// The markers match the ones the database's `search_headline` function uses.
+pub const HIGHLIGHT_START: &str = "<b>";
+/// Marks the end of a matched word in a snippet.
+pub const HIGHLIGHT_END: &str = "</b>";
+
+/// Postgres' `tsvector`, the searchable form of a document.
+#[derive(Clone, Copy, Debug, SqlType, QueryId)]
+#[postgres(type_name = "tsvector")]
+pub struct TsVector;
+
+/// Postgres' `tsquery`, a search query that can be matched against a `TsVector`.
+#[derive(Clone, Copy, Debug, SqlType, QueryId)]
+#[postgres(type_name = "tsquery")]
+pub struct TsQuery;
+
+/// A question that matched a search.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuestionMatch {
+    /// The question.
+    pub question: Question,
+    /// The parts of the question that matched, HTML-escaped, with the matched words highlighted.
+    pub snippet: String,
+    /// How well the question matched, relative to the other matches.
+    pub rank: f32,
+}
+
+/// An answer that matched a search.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnswerMatch {
+    /// The answer.
+    pub answer: Answer,
+    /// The bucket the answered question is in.
+    pub bucket_uuid: Uuid,
+    /// The parts of the answer that matched, HTML-escaped, with the matched words highlighted.
+    pub snippet: String,
+    /// How well the answer matched, relative to the other matches.
+    pub rank: f32,
+}
+
+/// Everything that matched a search.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchResults {
+    /// The matching questions, the best match first.
+    pub questions: Vec<QuestionMatch>,
+    /// The matching answers, the best match first.
+    pub answers: Vec<AnswerMatch>,
+}
@@ -0,0 +1,170 @@
+//! Mock impl
+//!
+//! Instead of Postgres' text search, a document matches if each word of the query
+//! starts one of the document's words, ignoring case.
+use crate::{
+    fake::FakeDatabase,
+    search::{
+        db_types::{AnswerMatch, QuestionMatch, SearchResults, HIGHLIGHT_END, HIGHLIGHT_START},
+        interface::SearchRepository,
+    },
+};
+use diesel::result::Error;
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+use uuid::Uuid;
+
+impl SearchRepository for Arc<Mutex<FakeDatabase>> {
+    fn search(
+        &self,
+        query: &str,
+        user_uuid: Option<Uuid>,
+        limit: i64,
+    ) -> Result<SearchResults, Error> {
+        let db = self.lock().unwrap();
+        let query_words: Vec<String> = words(query).map(|(_, word)| word.to_lowercase()).collect();
+        let visible_buckets: HashSet<Uuid> = db
+            .buckets
+            .iter()
+            .filter(|bucket| {
+                bucket.public_viewable
+                    || db
+                        .user_bucket_relations
+                        .iter()
+                        .any(|r| Some(r.user_uuid) == user_uuid && r.bucket_uuid == bucket.uuid)
+            })
+            .map(|bucket| bucket.uuid)
+            .collect();
+
+        let mut questions: Vec<QuestionMatch> = db
+            .questions
+            .iter()
+            .filter(|question| visible_buckets.contains(&question.bucket_uuid))
+            .filter_map(|question| {
+                rank(&question.question_text, &query_words).map(|rank| QuestionMatch {
+                    question: question.clone(),
+                    snippet: highlight(&question.question_text, &query_words),
+                    rank,
+                })
+            })
+            .collect();
+        questions.sort_by(|a, b| {
+            b.rank
+                .partial_cmp(&a.rank)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| b.question.created_at.cmp(&a.question.created_at))
+        });
+        questions.truncate(limit as usize);
+
+        let mut answers: Vec<AnswerMatch> = db
+            .answers
+            .iter()
+            .filter(|answer| answer.publicly_visible)
+            .filter_map(|answer| {
+                let bucket_uuid = db
+                    .questions
+                    .iter()
+                    .find(|question| question.uuid == answer.question_uuid)
+                    .map(|question| question.bucket_uuid)
+                    .filter(|bucket_uuid| visible_buckets.contains(bucket_uuid))?;
+                rank(&answer.answer_text, &query_words).map(|rank| AnswerMatch {
+                    answer: answer.clone(),
+                    bucket_uuid,
+                    snippet: highlight(&answer.answer_text, &query_words),
+                    rank,
+                })
+            })
+            .collect();
+        answers.sort_by(|a, b| {
+            b.rank
+                .partial_cmp(&a.rank)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| b.answer.created_at.cmp(&a.answer.created_at))
+        });
+        answers.truncate(limit as usize);
+
+        Ok(SearchResults { questions, answers })
+    }
+}
+
+/// The words in the text, along with where they start.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = None;
+    text.char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+        .filter_map(move |(index, c)| match (start, c.is_alphanumeric()) {
+            (None, true) => {
+                start = Some(index);
+                None
+            }
+            (Some(word_start), false) => {
+                start = None;
+                Some((word_start, &text[word_start..index]))
+            }
+            _ => None,
+        })
+}
+
+/// Does the word match one of the query's words.
+fn is_match(word: &str, query_words: &[String]) -> bool {
+    let word = word.to_lowercase();
+    query_words
+        .iter()
+        .any(|query_word| word.starts_with(query_word.as_str()))
+}
+
+/// The share of the text's words that match the query,
+/// or nothing if the text doesn't match every word of the query.
+fn rank(text: &str, query_words: &[String]) -> Option<f32> {
+    if query_words.is_empty() {
+        return None;
+    }
+    let text_words: Vec<String> = words(text).map(|(_, word)| word.to_lowercase()).collect();
+    let matches_every_word = query_words.iter().all(|query_word| {
+        text_words
+            .iter()
+            .any(|word| word.starts_with(query_word.as_str()))
+    });
+    if !matches_every_word {
+        return None;
+    }
+    let matched = text_words
+        .iter()
+        .filter(|word| is_match(word, query_words))
+        .count();
+    Some(matched as f32 / text_words.len() as f32)
+}
+
+/// The text, HTML-escaped, with the words that match the query highlighted.
+fn highlight(text: &str, query_words: &[String]) -> String {
+    let mut snippet = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (start, word) in words(text) {
+        if is_match(word, query_words) {
+            push_escaped(&mut snippet, &text[copied..start]);
+            snippet.push_str(HIGHLIGHT_START);
+            snippet.push_str(word);
+            snippet.push_str(HIGHLIGHT_END);
+            copied = start + word.len();
+        }
+    }
+    push_escaped(&mut snippet, &text[copied..]);
+    snippet
+}
+
+/// Appends the text, escaped the same way the database's `search_headline` function escapes documents.
+fn push_escaped(snippet: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => snippet.push_str("&amp;"),
+            '<' => snippet.push_str("&lt;"),
+            '>' => snippet.push_str("&gt;"),
+            '"' => snippet.push_str("&quot;"),
+            '\'' => snippet.push_str("&#39;"),
+            c => snippet.push(c),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! Specification of functions.
+use crate::search::db_types::SearchResults;
+use diesel::QueryResult;
+use uuid::Uuid;
+
+/// Functions for searching the text of questions and answers.
+pub trait SearchRepository {
+    /// Searches the questions, and the publicly visible answers,
+    /// in the buckets that are publicly visible or that the user is a member of.
+    ///
+    /// Up to `limit` questions and `limit` answers are found, the best matches first.
+    fn search(
+        &self,
+        query: &str,
+        user_uuid: Option<Uuid>,
+        limit: i64,
+    ) -> QueryResult<SearchResults>;
+}
@@ -0,0 +1,143 @@
+//! Implementation of the specified interfaces for PgConnection.
+//!
+//! The search vectors aren't part of the `question` and `answer` tables in the schema,
+//! so that loading questions and answers doesn't load them too.
+//! Instead, they are referred to by name where they are needed.
+//! `schema.patch` keeps them out when the schema is regenerated.
+use crate::{
+    answer::db_types::Answer,
+    question::db_types::Question,
+    schema::{answer, bucket, bucket_user_relation, question},
+    search::{
+        db_types::{AnswerMatch, QuestionMatch, SearchResults, TsQuery, TsVector},
+        interface::SearchRepository,
+    },
+    AsConnRef,
+};
+use diesel::{
+    dsl::sql,
+    expression::SqlLiteral,
+    query_dsl::RunQueryDsl,
+    result::Error,
+    sql_types::{Float, Text},
+    BoolExpressionMethods, ExpressionMethods, QueryDsl,
+};
+use operators::Matches;
+use uuid::Uuid;
+
+mod operators {
+    use diesel::pg::Pg;
+    diesel_infix_operator!(Matches, " @@ ", backend: Pg);
+}
+
+sql_function! {
+    /// Parses the text as a search query, using the configuration the search vectors were made with.
+    fn search_query(query: Text) -> TsQuery;
+}
+
+sql_function! {
+    /// Gets the parts of the document that match the query, HTML-escaped, with the matched words highlighted.
+    fn search_headline(document: Text, query: TsQuery) -> Text;
+}
+
+sql_function! {
+    /// How well the search vector matches the query.
+    fn ts_rank(vector: TsVector, query: TsQuery) -> Float;
+}
+
+/// The search vector of the question's text.
+fn question_search_vector() -> SqlLiteral<TsVector> {
+    sql("question.search_vector")
+}
+
+/// The search vector of the answer's text.
+fn answer_search_vector() -> SqlLiteral<TsVector> {
+    sql("answer.search_vector")
+}
+
+impl<T> SearchRepository for T
+where
+    T: AsConnRef,
+{
+    fn search(
+        &self,
+        query: &str,
+        user_uuid: Option<Uuid>,
+        limit: i64,
+    ) -> Result<SearchResults, Error> {
+        let conn = self.as_conn();
+        let public = bucket::public_viewable.eq(true);
+
+        let questions = question::table
+            .inner_join(bucket::table)
+            .filter(Matches::new(question_search_vector(), search_query(query)))
+            .into_boxed();
+        let questions = match user_uuid {
+            Some(user_uuid) => questions.filter(
+                public.or(bucket::uuid.eq_any(
+                    bucket_user_relation::table
+                        .filter(bucket_user_relation::user_uuid.eq(user_uuid))
+                        .select(bucket_user_relation::bucket_uuid),
+                )),
+            ),
+            None => questions.filter(public),
+        };
+        let questions: Vec<(Question, f32, String)> = questions
+            .select((
+                question::all_columns,
+                ts_rank(question_search_vector(), search_query(query)),
+                search_headline(question::question_text, search_query(query)),
+            ))
+            .order_by(ts_rank(question_search_vector(), search_query(query)).desc())
+            .then_order_by(question::created_at.desc())
+            .limit(limit)
+            .get_results(conn)?;
+
+        let answers = answer::table
+            .inner_join(question::table.inner_join(bucket::table))
+            .filter(answer::publicly_visible.eq(true))
+            .filter(Matches::new(answer_search_vector(), search_query(query)))
+            .into_boxed();
+        let answers = match user_uuid {
+            Some(user_uuid) => answers.filter(
+                public.or(bucket::uuid.eq_any(
+                    bucket_user_relation::table
+                        .filter(bucket_user_relation::user_uuid.eq(user_uuid))
+                        .select(bucket_user_relation::bucket_uuid),
+                )),
+            ),
+            None => answers.filter(public),
+        };
+        let answers: Vec<(Answer, Uuid, f32, String)> = answers
+            .select((
+                answer::all_columns,
+                question::bucket_uuid,
+                ts_rank(answer_search_vector(), search_query(query)),
+                search_headline(answer::answer_text, search_query(query)),
+            ))
+            .order_by(ts_rank(answer_search_vector(), search_query(query)).desc())
+            .then_order_by(answer::created_at.desc())
+            .limit(limit)
+            .get_results(conn)?;
+
+        Ok(SearchResults {
+            questions: questions
+                .into_iter()
+                .map(|(question, rank, snippet)| QuestionMatch {
+                    question,
+                    snippet,
+                    rank,
+                })
+                .collect(),
+            answers: answers
+                .into_iter()
+                .map(|(answer, bucket_uuid, rank, snippet)| AnswerMatch {
+                    answer,
+                    bucket_uuid,
+                    snippet,
+                    rank,
+                })
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    answer::db_types::NewAnswer,
+    bucket::db_types::BucketFlagChangeset,
+    question::db_types::{NewQuestion, Question},
+    search::db_types::{HIGHLIGHT_END, HIGHLIGHT_START},
+    test::{bucket_user_relation_fixture::UserBucketRelationFixture, util::execute_test},
+    BoxedRepository,
+};
+
+/// Gives the fixture's bucket a question about penguins, answered publicly and privately by user1.
+fn fill_bucket(fixture: &UserBucketRelationFixture, db: &BoxedRepository) -> Question {
+    let new_question = NewQuestion {
+        bucket_uuid: fixture.bucket.uuid,
+        user_uuid: Some(fixture.user1.uuid),
+        question_text: "Where do penguins live?".to_string(),
+    };
+    let question = db
+        .create_question(new_question)
+        .expect("Should create question");
+    for (answer_text, publicly_visible) in &[
+        ("The penguins I know live at the zoo.", true),
+        ("Penguins live in my freezer.", false),
+    ] {
+        let new_answer = NewAnswer {
+            user_uuid: Some(fixture.user1.uuid),
+            question_uuid: question.uuid,
+            publicly_visible: *publicly_visible,
+            answer_text: answer_text.to_string(),
+            session_uuid: None,
+        };
+        db.create_answer(new_answer).expect("Should create answer");
+    }
+    question
+}
+
+/// Makes the fixture's bucket publicly visible, or not.
+fn set_public(fixture: &UserBucketRelationFixture, db: &BoxedRepository, public: bool) {
+    let changeset = BucketFlagChangeset {
+        uuid: fixture.bucket.uuid,
+        public_viewable: Some(public),
+        drawing_enabled: None,
+        exclusive: None,
+        draw_strategy: None,
+        duplicate_policy: None,
+    };
+    db.change_bucket_flags(changeset)
+        .expect("Should change visibility");
+}
+
+#[test]
+fn search_finds_questions_and_public_answers() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let question = fill_bucket(fixture, &db);
+        set_public(fixture, &db, true);
+
+        let results = db.search("penguins", None, 10).expect("Should search");
+        assert_eq!(results.questions.len(), 1);
+        assert_eq!(results.questions[0].question, question);
+        assert!(results.questions[0].snippet.contains(HIGHLIGHT_START));
+        assert!(results.questions[0].snippet.contains(HIGHLIGHT_END));
+
+        assert_eq!(results.answers.len(), 1);
+        let answer_match = &results.answers[0];
+        assert_eq!(answer_match.answer.question_uuid, question.uuid);
+        assert!(answer_match.answer.publicly_visible);
+        assert_eq!(answer_match.bucket_uuid, fixture.bucket.uuid);
+    });
+}
+
+#[test]
+fn search_snippets_escape_the_text() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let new_question = NewQuestion {
+            bucket_uuid: fixture.bucket.uuid,
+            user_uuid: Some(fixture.user1.uuid),
+            question_text: "Do penguins eat <script>fish</script>?".to_string(),
+        };
+        db.create_question(new_question)
+            .expect("Should create question");
+        set_public(fixture, &db, true);
+
+        let results = db.search("penguins", None, 10).expect("Should search");
+        assert_eq!(results.questions.len(), 1);
+        let snippet = &results.questions[0].snippet;
+        assert!(snippet.contains(HIGHLIGHT_START));
+        assert!(!snippet.contains("<script>"));
+        assert!(snippet.contains("&lt;script&gt;"));
+    });
+}
+
+#[test]
+fn search_only_finds_matching_text() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        fill_bucket(fixture, &db);
+        set_public(fixture, &db, true);
+
+        let results = db.search("zoo", None, 10).expect("Should search");
+        assert!(results.questions.is_empty());
+        assert_eq!(results.answers.len(), 1);
+
+        let results = db.search("giraffes", None, 10).expect("Should search");
+        assert!(results.questions.is_empty());
+        assert!(results.answers.is_empty());
+    });
+}
+
+#[test]
+fn search_excludes_buckets_the_user_cant_see() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        fill_bucket(fixture, &db);
+        set_public(fixture, &db, false);
+
+        let results = db.search("penguins", None, 10).expect("Should search");
+        assert!(results.questions.is_empty());
+        assert!(results.answers.is_empty());
+
+        let results = db
+            .search("penguins", Some(fixture.user2.uuid), 10)
+            .expect("Should search");
+        assert!(results.questions.is_empty());
+        assert!(results.answers.is_empty());
+
+        // user1 is a member of the bucket.
+        let results = db
+            .search("penguins", Some(fixture.user1.uuid), 10)
+            .expect("Should search");
+        assert_eq!(results.questions.len(), 1);
+        assert_eq!(results.answers.len(), 1);
+    });
+}
@@ -0,0 +1,65 @@
+//! Module for session related database interactions.
+use crate::schema::{session, session_participant};
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A struct representing a session.
+/// A session is a single round of play for a bucket,
+/// during which questions are drawn and answered.
+#[derive(
+    Clone, Copy, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize,
+)]
+#[primary_key(uuid)]
+#[table_name = "session"]
+pub struct Session {
+    /// The session's unique identifier within the application.
+    pub uuid: Uuid,
+    /// The bucket the session is played in.
+    pub bucket_uuid: Uuid,
+    /// When the session started.
+    pub started_at: NaiveDateTime,
+    /// When the session ended.
+    /// If this is None, the session is still going on.
+    pub ended_at: Option<NaiveDateTime>,
+    /// When the row was last updated.
+    pub updated_at: NaiveDateTime,
+    /// When the row was created.
+    pub created_at: NaiveDateTime,
+}
+
+/// A struct for starting new sessions.
+#[derive(Clone, Copy, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "session"]
+pub struct NewSession {
+    /// The bucket the session is played in.
+    pub bucket_uuid: Uuid,
+}
+
+/// A relation between sessions and the users that took part in them.
+#[derive(
+    Clone, Copy, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize,
+)]
+#[primary_key(session_uuid, user_uuid)]
+#[table_name = "session_participant"]
+pub struct SessionParticipant {
+    /// The uuid of the session.
+    pub session_uuid: Uuid,
+    /// The uuid of the user.
+    pub user_uuid: Uuid,
+    /// When the row was last updated.
+    pub updated_at: NaiveDateTime,
+    /// When the row was created.
+    pub created_at: NaiveDateTime,
+}
+
+/// Structure used to add users to sessions.
+#[derive(Clone, Copy, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "session_participant"]
+pub struct NewSessionParticipant {
+    /// The uuid of the session.
+    pub session_uuid: Uuid,
+    /// The uuid of the user.
+    pub user_uuid: Uuid,
+}
@@ -0,0 +1,155 @@
+//! Mock impl
+use crate::{
+    answer::db_types::Answer,
+    fake::{DummyDbErrorInfo, FakeDatabase},
+    page::{paginate, Page, Pagination, RecencySort},
+    question::db_types::Question,
+    session::{
+        db_types::{NewSession, NewSessionParticipant, Session, SessionParticipant},
+        interface::SessionRepository,
+        transcript::assemble_transcript,
+    },
+    user::db_types::User,
+};
+use diesel::result::{DatabaseErrorKind, Error};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+impl SessionRepository for Arc<Mutex<FakeDatabase>> {
+    fn create_session(&self, new_session: NewSession) -> Result<Session, Error> {
+        let mut db = self.lock().unwrap();
+        if db
+            .sessions
+            .iter()
+            .any(|s| s.bucket_uuid == new_session.bucket_uuid && s.ended_at.is_none())
+        {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::UniqueViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+        let session = Session {
+            uuid: Uuid::new_v4(),
+            bucket_uuid: new_session.bucket_uuid,
+            started_at: chrono::Utc::now().naive_utc(),
+            ended_at: None,
+            updated_at: chrono::Utc::now().naive_utc(),
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+        db.sessions.push(session);
+        Ok(session)
+    }
+
+    fn end_session(&self, session_uuid: Uuid) -> Result<Session, Error> {
+        let mut db = self.lock().unwrap();
+        let session = db
+            .sessions
+            .iter_mut()
+            .find(|s| s.uuid == session_uuid)
+            .ok_or_else(|| Error::NotFound)?;
+        session.ended_at = Some(chrono::Utc::now().naive_utc());
+        session.updated_at = chrono::Utc::now().naive_utc();
+        Ok(*session)
+    }
+
+    fn get_session(&self, session_uuid: Uuid) -> Result<Session, Error> {
+        let db = self.lock().unwrap();
+        db.sessions
+            .iter()
+            .find(|s| s.uuid == session_uuid)
+            .cloned()
+            .ok_or_else(|| Error::NotFound)
+    }
+
+    fn get_active_session(&self, bucket_uuid: Uuid) -> Result<Option<Session>, Error> {
+        let db = self.lock().unwrap();
+        let session = db
+            .sessions
+            .iter()
+            .find(|s| s.bucket_uuid == bucket_uuid && s.ended_at.is_none())
+            .cloned();
+        Ok(session)
+    }
+
+    fn get_sessions_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Session>, Error> {
+        let db = self.lock().unwrap();
+        let last = match pagination.after {
+            Some(after) => Some(
+                db.sessions
+                    .iter()
+                    .find(|s| s.uuid == after)
+                    .ok_or(Error::NotFound)?,
+            ),
+            None => None,
+        };
+        let sessions = db
+            .sessions
+            .iter()
+            .filter(|s| s.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        let order = |a: &Session, b: &Session| match pagination.sort {
+            RecencySort::Newest => (b.started_at, b.uuid).cmp(&(a.started_at, a.uuid)),
+            RecencySort::Oldest => (a.started_at, a.uuid).cmp(&(b.started_at, b.uuid)),
+        };
+        Ok(paginate(sessions, last, pagination.limit, order, |s| {
+            s.uuid
+        }))
+    }
+
+    fn add_participant(&self, participant: NewSessionParticipant) -> Result<(), Error> {
+        let mut db = self.lock().unwrap();
+        if db.session_participants.iter().any(|p| {
+            p.session_uuid == participant.session_uuid && p.user_uuid == participant.user_uuid
+        }) {
+            return Ok(());
+        }
+        let participant = SessionParticipant {
+            session_uuid: participant.session_uuid,
+            user_uuid: participant.user_uuid,
+            updated_at: chrono::Utc::now().naive_utc(),
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+        db.session_participants.push(participant);
+        Ok(())
+    }
+
+    fn get_participants(&self, session_uuid: Uuid) -> Result<Vec<User>, Error> {
+        let db = self.lock().unwrap();
+        let users = db
+            .session_participants
+            .iter()
+            .filter(|p| p.session_uuid == session_uuid)
+            .filter_map(|p| db.users.iter().find(|u| u.uuid == p.user_uuid))
+            .cloned()
+            .collect();
+        Ok(users)
+    }
+
+    fn get_transcript(&self, session_uuid: Uuid) -> Result<Vec<(Question, Option<Answer>)>, Error> {
+        let db = self.lock().unwrap();
+        let question = |question_uuid: Uuid| {
+            db.questions
+                .iter()
+                .find(|q| q.uuid == question_uuid)
+                .cloned()
+        };
+        let answers = db
+            .answers
+            .iter()
+            .filter(|a| a.session_uuid == Some(session_uuid))
+            .filter_map(|a| question(a.question_uuid).map(|q| (q, a.clone())))
+            .collect();
+        let draws = db
+            .question_draws
+            .iter()
+            .filter(|d| d.session_uuid == Some(session_uuid))
+            .filter_map(|d| question(d.question_uuid).map(|q| (q, *d)))
+            .collect();
+        Ok(assemble_transcript(answers, draws))
+    }
+}
@@ -0,0 +1,40 @@
+//! Specification of functions.
+use crate::{
+    answer::db_types::Answer,
+    page::{Page, Pagination, RecencySort},
+    question::db_types::Question,
+    session::db_types::{NewSession, NewSessionParticipant, Session},
+    user::db_types::User,
+};
+use diesel::QueryResult;
+use uuid::Uuid;
+
+/// Functions for specifically working with sessions.
+pub trait SessionRepository {
+    /// Starts a session.
+    /// Only one session may be going on at a time for a bucket.
+    fn create_session(&self, new_session: NewSession) -> QueryResult<Session>;
+    /// Ends the session.
+    fn end_session(&self, session_uuid: Uuid) -> QueryResult<Session>;
+    /// Gets the session.
+    fn get_session(&self, session_uuid: Uuid) -> QueryResult<Session>;
+    /// Gets the session that is currently going on for the bucket, if there is one.
+    fn get_active_session(&self, bucket_uuid: Uuid) -> QueryResult<Option<Session>>;
+    /// Gets a page of the sessions that have been played in the bucket, sorted by when they started.
+    ///
+    /// If the session the page should start after doesn't exist anymore, this will return a `NotFound` error.
+    fn get_sessions_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> QueryResult<Page<Session>>;
+    /// Adds a user to the session.
+    /// Adding a user that is already a participant does nothing.
+    fn add_participant(&self, participant: NewSessionParticipant) -> QueryResult<()>;
+    /// Gets the users that took part in the session.
+    fn get_participants(&self, session_uuid: Uuid) -> QueryResult<Vec<User>>;
+    /// Gets the answers given in the session alongside the questions they answer,
+    /// and the questions that were drawn in the session without being answered in it,
+    /// in the order they happened.
+    fn get_transcript(&self, session_uuid: Uuid) -> QueryResult<Vec<(Question, Option<Answer>)>>;
+}
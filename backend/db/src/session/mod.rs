@@ -0,0 +1,8 @@
+//! Module containing all structures and functions required for session related database functionality.
+pub mod db_types;
+pub mod fake_impl;
+pub mod interface;
+pub mod pg_impl;
+#[cfg(test)]
+mod tests;
+pub(crate) mod transcript;
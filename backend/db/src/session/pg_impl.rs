@@ -0,0 +1,125 @@
+//! Implementation of the specified interfaces for PgConnection.
+use crate::{
+    answer::db_types::Answer,
+    page::{Page, Pagination, RecencySort},
+    question::db_types::Question,
+    schema::{answer, bq_user, question, question_draw, session, session_participant},
+    session::{
+        db_types::{NewSession, NewSessionParticipant, Session},
+        interface::SessionRepository,
+        transcript::assemble_transcript,
+    },
+    user::db_types::User,
+    AsConnRef,
+};
+use diesel::{
+    dsl::now,
+    query_dsl::{QueryDsl, RunQueryDsl},
+    result::Error,
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension,
+};
+use uuid::Uuid;
+
+impl<T> SessionRepository for T
+where
+    T: AsConnRef,
+{
+    fn create_session(&self, new_session: NewSession) -> Result<Session, Error> {
+        crate::util::create_row(session::table, new_session, self.as_conn())
+    }
+
+    fn end_session(&self, session_uuid: Uuid) -> Result<Session, Error> {
+        let target = session::table.find(session_uuid);
+        diesel::update(target)
+            .set((session::ended_at.eq(now), session::updated_at.eq(now)))
+            .get_result(self.as_conn())
+    }
+
+    fn get_session(&self, session_uuid: Uuid) -> Result<Session, Error> {
+        crate::util::get_row(session::table, session_uuid, self.as_conn())
+    }
+
+    fn get_active_session(&self, bucket_uuid: Uuid) -> Result<Option<Session>, Error> {
+        session::table
+            .filter(
+                session::bucket_uuid
+                    .eq(bucket_uuid)
+                    .and(session::ended_at.is_null()),
+            )
+            .first(self.as_conn())
+            .optional()
+    }
+
+    fn get_sessions_for_bucket(
+        &self,
+        bucket_uuid: Uuid,
+        pagination: Pagination<RecencySort>,
+    ) -> Result<Page<Session>, Error> {
+        let conn = self.as_conn();
+        let query = session::table
+            .filter(session::bucket_uuid.eq(bucket_uuid))
+            .into_boxed();
+        // Only the sessions that sort after the last one of the previous page are on this page.
+        let query = match pagination.after {
+            Some(after) => {
+                let last: Session = session::table.find(after).first(conn)?;
+                match pagination.sort {
+                    RecencySort::Newest => query.filter(
+                        session::started_at
+                            .lt(last.started_at)
+                            .or(session::started_at
+                                .eq(last.started_at)
+                                .and(session::uuid.lt(last.uuid))),
+                    ),
+                    RecencySort::Oldest => query.filter(
+                        session::started_at
+                            .gt(last.started_at)
+                            .or(session::started_at
+                                .eq(last.started_at)
+                                .and(session::uuid.gt(last.uuid))),
+                    ),
+                }
+            }
+            None => query,
+        };
+        let query = match pagination.sort {
+            RecencySort::Newest => {
+                query.order_by((session::started_at.desc(), session::uuid.desc()))
+            }
+            RecencySort::Oldest => query.order_by((session::started_at, session::uuid)),
+        };
+        let sessions = query.limit(pagination.fetch_limit()).get_results(conn)?;
+        Ok(Page::from_fetched(sessions, pagination.limit, |s| s.uuid))
+    }
+
+    fn add_participant(&self, participant: NewSessionParticipant) -> Result<(), Error> {
+        diesel::insert_into(session_participant::table)
+            .values(&participant)
+            .on_conflict_do_nothing()
+            .execute(self.as_conn())
+            .map(|_| ())
+    }
+
+    fn get_participants(&self, session_uuid: Uuid) -> Result<Vec<User>, Error> {
+        session_participant::table
+            .filter(session_participant::session_uuid.eq(session_uuid))
+            .inner_join(bq_user::table)
+            .order_by(session_participant::created_at)
+            .select(bq_user::all_columns)
+            .get_results(self.as_conn())
+    }
+
+    fn get_transcript(&self, session_uuid: Uuid) -> Result<Vec<(Question, Option<Answer>)>, Error> {
+        let answers = answer::table
+            .filter(answer::session_uuid.eq(session_uuid))
+            .inner_join(question::table)
+            .select((question::all_columns, answer::all_columns))
+            .get_results(self.as_conn())?;
+        let draws = question_draw::table
+            .filter(question_draw::session_uuid.eq(session_uuid))
+            .inner_join(question::table)
+            .select((question::all_columns, question_draw::all_columns))
+            .get_results(self.as_conn())?;
+        Ok(assemble_transcript(answers, draws))
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    answer::db_types::NewAnswer,
+    page::{Pagination, RecencySort},
+    question::db_types::{NewQuestion, NewQuestionDraw},
+    session::db_types::{NewSession, NewSessionParticipant},
+    test::{answer_fixture::AnswerFixture, util::execute_test},
+    BoxedRepository,
+};
+
+#[test]
+fn create_session() {
+    execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+        let new_session = NewSession {
+            bucket_uuid: fixture.bucket.uuid,
+        };
+        let session = db
+            .create_session(new_session)
+            .expect("Should create session");
+        assert_eq!(session.ended_at, None);
+
+        let active_session = db
+            .get_active_session(fixture.bucket.uuid)
+            .expect("Should get active session");
+        assert_eq!(active_session, Some(session));
+    });
+}
+
+#[test]
+fn cant_create_two_active_sessions() {
+    execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+        let new_session = NewSession {
+            bucket_uuid: fixture.bucket.uuid,
+        };
+        db.create_session(new_session)
+            .expect("Should create session");
+        db.create_session(new_session)
+            .expect_err("Should not create a second session while the first is going on");
+    });
+}
+
+#[test]
+fn end_session() {
+    execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+        let new_session = NewSession {
+            bucket_uuid: fixture.bucket.uuid,
+        };
+        let session = db
+            .create_session(new_session)
+            .expect("Should create session");
+        let session = db.end_session(session.uuid).expect("Should end session");
+        assert!(session.ended_at.is_some());
+
+        let active_session = db
+            .get_active_session(fixture.bucket.uuid)
+            .expect("Should get active session");
+        assert_eq!(active_session, None);
+
+        db.create_session(new_session)
+            .expect("Should create a new session after the first ended");
+        let sessions = db
+            .get_sessions_for_bucket(
+                fixture.bucket.uuid,
+                Pagination::first(1, RecencySort::Newest),
+            )
+            .expect("Should get sessions");
+        assert_eq!(sessions.items.len(), 1);
+        assert!(sessions.items[0].ended_at.is_none());
+        assert!(sessions.next_cursor.is_some());
+    });
+}
+
+#[test]
+fn add_participant() {
+    execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+        let new_session = NewSession {
+            bucket_uuid: fixture.bucket.uuid,
+        };
+        let session = db
+            .create_session(new_session)
+            .expect("Should create session");
+
+        let participant = NewSessionParticipant {
+            session_uuid: session.uuid,
+            user_uuid: fixture.user.uuid,
+        };
+        db.add_participant(participant)
+            .expect("Should add participant");
+        db.add_participant(participant)
+            .expect("Adding a participant twice should do nothing");
+
+        let participants = db
+            .get_participants(session.uuid)
+            .expect("Should get participants");
+        assert_eq!(participants, vec![fixture.user.clone()]);
+    });
+}
+
+#[test]
+fn get_transcript() {
+    execute_test(|fixture: &AnswerFixture, db: BoxedRepository| {
+        let new_session = NewSession {
+            bucket_uuid: fixture.bucket.uuid,
+        };
+        let session = db
+            .create_session(new_session)
+            .expect("Should create session");
+        let new_question = NewQuestion {
+            bucket_uuid: fixture.bucket.uuid,
+            user_uuid: Some(fixture.user.uuid),
+            question_text: "A question nobody answered".to_string(),
+        };
+        let unanswered = db
+            .create_question(new_question)
+            .expect("Should create question");
+
+        for question_uuid in &[fixture.question.uuid, unanswered.uuid] {
+            let new_draw = NewQuestionDraw {
+                question_uuid: *question_uuid,
+                bucket_uuid: fixture.bucket.uuid,
+                user_uuid: Some(fixture.user.uuid),
+                session_uuid: Some(session.uuid),
+            };
+            db.record_draw(new_draw).expect("Should record draw");
+        }
+        let new_answer = NewAnswer {
+            user_uuid: Some(fixture.user.uuid),
+            question_uuid: fixture.question.uuid,
+            publicly_visible: true,
+            answer_text: "An answer given during the session".to_string(),
+            session_uuid: Some(session.uuid),
+        };
+        let answer = db
+            .create_answer(new_answer)
+            .expect("Should create new answer");
+
+        let transcript = db
+            .get_transcript(session.uuid)
+            .expect("Should get transcript");
+        // The fixture's answer wasn't given during the session,
+        // and the answer given during it stands in for the draw of its question.
+        assert_eq!(
+            transcript,
+            vec![(unanswered, None), (fixture.question.clone(), Some(answer)),]
+        );
+    });
+}
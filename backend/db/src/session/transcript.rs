@@ -0,0 +1,36 @@
+//! Putting together what happened during a session.
+//!
+//! Both the Postgres and fake repositories load the session's answers and draws and then defer to
+//! `assemble_transcript`, so a transcript comes out in the same order regardless of the backing store.
+use crate::{
+    answer::db_types::Answer,
+    question::db_types::{Question, QuestionDraw},
+};
+use chrono::NaiveDateTime;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Orders the session's answers, and the draws of questions that weren't answered in it, by when they happened.
+///
+/// Draws of questions that were answered during the session are left out, as their answers stand in for them.
+pub(crate) fn assemble_transcript(
+    answers: Vec<(Question, Answer)>,
+    draws: Vec<(Question, QuestionDraw)>,
+) -> Vec<(Question, Option<Answer>)> {
+    let answered: HashSet<Uuid> = answers.iter().map(|(question, _)| question.uuid).collect();
+    let unanswered_draws = draws
+        .into_iter()
+        .filter(|(question, _)| !answered.contains(&question.uuid))
+        .map(|(question, draw)| (draw.created_at, draw.uuid, question, None));
+    let mut entries: Vec<(NaiveDateTime, Uuid, Question, Option<Answer>)> = answers
+        .into_iter()
+        .map(|(question, answer)| (answer.created_at, answer.uuid, question, Some(answer)))
+        .chain(unanswered_draws)
+        .collect();
+    // The uuid breaks ties, so entries made at the same time are always in the same order.
+    entries.sort_by_key(|(happened_at, uuid, _, _)| (*happened_at, *uuid));
+    entries
+        .into_iter()
+        .map(|(_, _, question, answer)| (question, answer))
+        .collect()
+}
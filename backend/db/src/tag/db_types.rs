@@ -0,0 +1,81 @@
+//! Module for tag related database interactions.
+use crate::schema::{question_tag, tag};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A tag used to categorize questions within a bucket.
+#[derive(
+    Clone, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Insertable, Serialize, Deserialize,
+)]
+#[primary_key(uuid)]
+#[table_name = "tag"]
+pub struct Tag {
+    /// The tag's unique identifier within the application.
+    pub uuid: Uuid,
+    /// The bucket the tag belongs to.
+    pub bucket_uuid: Uuid,
+    /// The name of the tag. This is unique within a bucket.
+    pub tag_name: String,
+    /// When the row was last updated.
+    pub updated_at: NaiveDateTime,
+    /// When the row was created.
+    pub created_at: NaiveDateTime,
+}
+
+/// Structure for creating a new tag.
+#[derive(Clone, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "tag"]
+pub struct NewTag {
+    /// The bucket the tag belongs to.
+    pub bucket_uuid: Uuid,
+    /// The name of the tag.
+    pub tag_name: String,
+}
+
+/// A relation recording that a question has been tagged.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    PartialOrd,
+    Identifiable,
+    Queryable,
+    Insertable,
+    Serialize,
+    Deserialize,
+)]
+#[primary_key(question_uuid, tag_uuid)]
+#[table_name = "question_tag"]
+pub struct QuestionTag {
+    /// The question that is tagged.
+    pub question_uuid: Uuid,
+    /// The tag.
+    pub tag_uuid: Uuid,
+    /// When the row was last updated.
+    pub updated_at: NaiveDateTime,
+    /// When the row was created.
+    pub created_at: NaiveDateTime,
+}
+
+/// Structure for tagging a question.
+#[derive(Clone, Copy, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "question_tag"]
+pub struct NewQuestionTag {
+    /// The question that is tagged.
+    pub question_uuid: Uuid,
+    /// The tag.
+    pub tag_uuid: Uuid,
+}
+
+/// Restricts which questions are considered, based on their tags.
+///
+/// Tags are referred to by name, and are looked up within the bucket the questions belong to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TagFilter {
+    /// If not empty, only questions with at least one of these tags are considered.
+    pub include: Vec<String>,
+    /// Questions with any of these tags aren't considered.
+    pub exclude: Vec<String>,
+}
@@ -0,0 +1,131 @@
+//! Mock impl
+
+use crate::{
+    fake::{DummyDbErrorInfo, FakeDatabase},
+    tag::{
+        db_types::{NewQuestionTag, NewTag, QuestionTag, Tag},
+        interface::TagRepository,
+    },
+};
+use diesel::result::{DatabaseErrorKind, Error};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+impl TagRepository for Arc<Mutex<FakeDatabase>> {
+    fn get_or_create_tag(&self, new_tag: NewTag) -> Result<Tag, Error> {
+        let mut db = self.lock().unwrap();
+        if let Some(tag) = db
+            .tags
+            .iter()
+            .find(|t| t.bucket_uuid == new_tag.bucket_uuid && t.tag_name == new_tag.tag_name)
+        {
+            return Ok(tag.clone());
+        }
+        if !db.buckets.iter().any(|b| b.uuid == new_tag.bucket_uuid) {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::ForeignKeyViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+
+        let tag = Tag {
+            uuid: Uuid::new_v4(),
+            bucket_uuid: new_tag.bucket_uuid,
+            tag_name: new_tag.tag_name,
+            updated_at: chrono::Utc::now().naive_utc(),
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+        db.tags.push(tag.clone());
+        Ok(tag)
+    }
+
+    fn get_tags_in_bucket(&self, bucket_uuid: Uuid) -> Result<Vec<Tag>, Error> {
+        let db = self.lock().unwrap();
+        let mut tags: Vec<Tag> = db
+            .tags
+            .iter()
+            .filter(|t| t.bucket_uuid == bucket_uuid)
+            .cloned()
+            .collect();
+        tags.sort_by(|a, b| a.tag_name.cmp(&b.tag_name));
+        Ok(tags)
+    }
+
+    fn get_tags_for_question(&self, question_uuid: Uuid) -> Result<Vec<Tag>, Error> {
+        let db = self.lock().unwrap();
+        let tag_uuids: Vec<Uuid> = db
+            .question_tags
+            .iter()
+            .filter(|qt| qt.question_uuid == question_uuid)
+            .map(|qt| qt.tag_uuid)
+            .collect();
+        let mut tags: Vec<Tag> = db
+            .tags
+            .iter()
+            .filter(|t| tag_uuids.contains(&t.uuid))
+            .cloned()
+            .collect();
+        tags.sort_by(|a, b| a.tag_name.cmp(&b.tag_name));
+        Ok(tags)
+    }
+
+    fn tag_question(&self, relation: NewQuestionTag) -> Result<(), Error> {
+        let mut db = self.lock().unwrap();
+        if db.question_tags.iter().any(|qt| {
+            qt.question_uuid == relation.question_uuid && qt.tag_uuid == relation.tag_uuid
+        }) {
+            return Ok(());
+        }
+        if !db
+            .questions
+            .iter()
+            .any(|q| q.uuid == relation.question_uuid)
+            || !db.tags.iter().any(|t| t.uuid == relation.tag_uuid)
+        {
+            return Err(Error::DatabaseError(
+                DatabaseErrorKind::ForeignKeyViolation,
+                Box::new(DummyDbErrorInfo::new()),
+            ));
+        }
+
+        db.question_tags.push(QuestionTag {
+            question_uuid: relation.question_uuid,
+            tag_uuid: relation.tag_uuid,
+            updated_at: chrono::Utc::now().naive_utc(),
+            created_at: chrono::Utc::now().naive_utc(),
+        });
+        Ok(())
+    }
+
+    fn untag_question(&self, relation: NewQuestionTag) -> Result<(), Error> {
+        let mut db = self.lock().unwrap();
+        let index = db
+            .question_tags
+            .iter()
+            .position(|qt| {
+                qt.question_uuid == relation.question_uuid && qt.tag_uuid == relation.tag_uuid
+            })
+            .ok_or(Error::NotFound)?;
+        db.question_tags.remove(index);
+        Ok(())
+    }
+}
+
+/// Gets the uuids of the questions in the bucket that are tagged with any of the named tags.
+pub(crate) fn tagged_question_uuids(
+    bucket_uuid: Uuid,
+    tag_names: &[String],
+    db: &FakeDatabase,
+) -> Vec<Uuid> {
+    let tag_uuids: Vec<Uuid> = db
+        .tags
+        .iter()
+        .filter(|t| t.bucket_uuid == bucket_uuid && tag_names.contains(&t.tag_name))
+        .map(|t| t.uuid)
+        .collect();
+    db.question_tags
+        .iter()
+        .filter(|qt| tag_uuids.contains(&qt.tag_uuid))
+        .map(|qt| qt.question_uuid)
+        .collect()
+}
@@ -0,0 +1,18 @@
+//! Specification of what functions are supported for storing data for tags.
+use crate::tag::db_types::{NewQuestionTag, NewTag, Tag};
+use diesel::QueryResult;
+use uuid::Uuid;
+
+/// Functions for specifically working with tags.
+pub trait TagRepository {
+    /// Gets the tag with the given name in the bucket, creating it if it doesn't exist yet.
+    fn get_or_create_tag(&self, tag: NewTag) -> QueryResult<Tag>;
+    /// Gets all of the tags in the bucket, ordered by name.
+    fn get_tags_in_bucket(&self, bucket_uuid: Uuid) -> QueryResult<Vec<Tag>>;
+    /// Gets the tags the question has been tagged with, ordered by name.
+    fn get_tags_for_question(&self, question_uuid: Uuid) -> QueryResult<Vec<Tag>>;
+    /// Tags the question. Tagging a question with a tag it already has does nothing.
+    fn tag_question(&self, relation: NewQuestionTag) -> QueryResult<()>;
+    /// Removes the tag from the question.
+    fn untag_question(&self, relation: NewQuestionTag) -> QueryResult<()>;
+}
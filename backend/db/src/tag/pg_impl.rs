@@ -0,0 +1,88 @@
+//! Implementation of the specified interfaces for PgConnection.
+use crate::{
+    schema::{question_tag, tag},
+    tag::{
+        db_types::{NewQuestionTag, NewTag, Tag},
+        interface::TagRepository,
+    },
+    AsConnRef,
+};
+use diesel::{
+    query_dsl::{QueryDsl, RunQueryDsl},
+    result::Error,
+    BoolExpressionMethods, ExpressionMethods, PgConnection,
+};
+use uuid::Uuid;
+
+impl<T> TagRepository for T
+where
+    T: AsConnRef,
+{
+    fn get_or_create_tag(&self, new_tag: NewTag) -> Result<Tag, Error> {
+        diesel::insert_into(tag::table)
+            .values(&new_tag)
+            .on_conflict((tag::bucket_uuid, tag::tag_name))
+            .do_nothing()
+            .execute(self.as_conn())?;
+
+        tag::table
+            .filter(
+                tag::bucket_uuid
+                    .eq(new_tag.bucket_uuid)
+                    .and(tag::tag_name.eq(new_tag.tag_name)),
+            )
+            .first(self.as_conn())
+    }
+
+    fn get_tags_in_bucket(&self, bucket_uuid: Uuid) -> Result<Vec<Tag>, Error> {
+        tag::table
+            .filter(tag::bucket_uuid.eq(bucket_uuid))
+            .order_by(tag::tag_name)
+            .get_results(self.as_conn())
+    }
+
+    fn get_tags_for_question(&self, question_uuid: Uuid) -> Result<Vec<Tag>, Error> {
+        question_tag::table
+            .inner_join(tag::table)
+            .filter(question_tag::question_uuid.eq(question_uuid))
+            .order_by(tag::tag_name)
+            .select(tag::all_columns)
+            .get_results(self.as_conn())
+    }
+
+    fn tag_question(&self, relation: NewQuestionTag) -> Result<(), Error> {
+        diesel::insert_into(question_tag::table)
+            .values(&relation)
+            .on_conflict_do_nothing()
+            .execute(self.as_conn())
+            .map(|_| ())
+    }
+
+    fn untag_question(&self, relation: NewQuestionTag) -> Result<(), Error> {
+        let target = question_tag::table.find((relation.question_uuid, relation.tag_uuid));
+        let deleted = diesel::delete(target).execute(self.as_conn())?;
+        if deleted == 0 {
+            Err(Error::NotFound)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Gets the uuids of the questions in the bucket that are tagged with any of the named tags.
+pub(crate) fn tagged_question_uuids(
+    bucket_uuid: Uuid,
+    tag_names: &[String],
+    conn: &PgConnection,
+) -> Result<Vec<Uuid>, Error> {
+    question_tag::table
+        .inner_join(tag::table)
+        .filter(
+            tag::bucket_uuid
+                .eq(bucket_uuid)
+                .and(tag::tag_name.eq_any(tag_names)),
+        )
+        .select(question_tag::question_uuid)
+        .distinct()
+        .get_results(conn)
+}
@@ -0,0 +1,110 @@
+use crate::{
+    page::Pagination,
+    question::db_types::QuestionSort,
+    tag::db_types::{NewQuestionTag, NewTag, TagFilter},
+    test::{question_fixture::QuestionFixture, util::execute_test},
+    BoxedRepository,
+};
+use diesel::result::Error;
+
+#[test]
+fn get_or_create_tag() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let new_tag = NewTag {
+            bucket_uuid: fixture.bucket.uuid,
+            tag_name: "silly".to_string(),
+        };
+        let created = db
+            .get_or_create_tag(new_tag.clone())
+            .expect("Should create tag");
+        let gotten = db.get_or_create_tag(new_tag).expect("Should get tag");
+        assert_eq!(created, gotten);
+
+        let tags = db
+            .get_tags_in_bucket(fixture.bucket.uuid)
+            .expect("Should get tags");
+        assert_eq!(tags, vec![created]);
+    });
+}
+
+#[test]
+fn tag_and_untag_question() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let new_tag = NewTag {
+            bucket_uuid: fixture.bucket.uuid,
+            tag_name: "serious".to_string(),
+        };
+        let tag = db.get_or_create_tag(new_tag).expect("Should create tag");
+        let relation = NewQuestionTag {
+            question_uuid: fixture.question1.uuid,
+            tag_uuid: tag.uuid,
+        };
+        db.tag_question(relation).expect("Should tag question");
+        db.tag_question(relation)
+            .expect("Tagging a question twice should do nothing");
+
+        let tags = db
+            .get_tags_for_question(fixture.question1.uuid)
+            .expect("Should get tags");
+        assert_eq!(tags, vec![tag]);
+
+        db.untag_question(relation).expect("Should untag question");
+        let tags = db
+            .get_tags_for_question(fixture.question1.uuid)
+            .expect("Should get tags");
+        assert!(tags.is_empty());
+        assert_eq!(db.untag_question(relation), Err(Error::NotFound));
+    });
+}
+
+#[test]
+fn filter_questions_by_tag() {
+    execute_test(|fixture: &QuestionFixture, db: BoxedRepository| {
+        let new_tag = NewTag {
+            bucket_uuid: fixture.bucket.uuid,
+            tag_name: "silly".to_string(),
+        };
+        let tag = db.get_or_create_tag(new_tag).expect("Should create tag");
+        let relation = NewQuestionTag {
+            question_uuid: fixture.question1.uuid,
+            tag_uuid: tag.uuid,
+        };
+        db.tag_question(relation).expect("Should tag question");
+
+        let include = TagFilter {
+            include: vec!["silly".to_string()],
+            exclude: vec![],
+        };
+        let questions = db
+            .get_all_questions_for_bucket_of_given_archived_status(
+                fixture.bucket.uuid,
+                false,
+                &include,
+                Pagination::first(10, QuestionSort::Updated),
+            )
+            .expect("Should get questions")
+            .items;
+        assert_eq!(questions, vec![fixture.question1.clone()]);
+
+        let exclude = TagFilter {
+            include: vec![],
+            exclude: vec!["silly".to_string()],
+        };
+        for _ in 0..10 {
+            let question = db
+                .get_random_question(fixture.bucket.uuid, 0, &exclude)
+                .expect("Should get random question")
+                .expect("A question should remain after excluding the tag");
+            assert_eq!(question, fixture.question2);
+        }
+
+        let unknown = TagFilter {
+            include: vec!["unknown".to_string()],
+            exclude: vec![],
+        };
+        let question = db
+            .get_random_question(fixture.bucket.uuid, 0, &unknown)
+            .expect("Should get random question");
+        assert_eq!(question, None);
+    });
+}
@@ -45,6 +45,9 @@ impl Fixture for AnswerFixture {
             set_exclusive_permission: true,
             kick_permission: true,
             grant_permissions_permission: true,
+            moderate_questions_permission: true,
+            delete_questions_permission: true,
+            invite_permission: true,
         };
 
         let relation = conn
@@ -66,6 +69,7 @@ impl Fixture for AnswerFixture {
             question_uuid: question.uuid,
             publicly_visible: false,
             answer_text: "I think this is an answer".to_string(),
+            session_uuid: None,
         };
         let answer = conn
             .create_answer(new_answer)
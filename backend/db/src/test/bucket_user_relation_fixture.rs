@@ -1,12 +1,26 @@
 //! A fixture for testing against configurations related to bucket user relations.
 use crate::{
     bucket::db_types::{Bucket, NewBucket},
-    bucket_user_relation::db_types::{BucketUserRelation, NewBucketUserRelation},
+    bucket_user_relation::db_types::{
+        BucketUserPermissions, BucketUserRelation, NewBucketUserRelation,
+    },
     test::{fixture::Fixture, user_fixture::UserFixture},
     user::db_types::{NewUser, User},
     BoxedRepository,
 };
 
+/// The permissions of a member who can't do anything beyond what every member can.
+pub const NO_PERMISSIONS: BucketUserPermissions = BucketUserPermissions {
+    set_public_permission: false,
+    set_drawing_permission: false,
+    set_exclusive_permission: false,
+    kick_permission: false,
+    grant_permissions_permission: false,
+    moderate_questions_permission: false,
+    delete_questions_permission: false,
+    invite_permission: false,
+};
+
 /// Fixture that creates 2 users, 1 bucket, and one relation record in the repository.
 /// user1 is joined to the bucket.
 #[derive(Clone, Debug)]
@@ -48,6 +62,9 @@ impl Fixture for UserBucketRelationFixture {
             set_exclusive_permission: true,
             kick_permission: true,
             grant_permissions_permission: true,
+            moderate_questions_permission: true,
+            delete_questions_permission: true,
+            invite_permission: true,
         };
 
         let relation = conn
@@ -62,3 +79,27 @@ impl Fixture for UserBucketRelationFixture {
         }
     }
 }
+
+impl UserBucketRelationFixture {
+    /// Joins the second user to the bucket with the provided permissions.
+    pub fn add_user2(
+        &self,
+        permissions: BucketUserPermissions,
+        conn: &BoxedRepository,
+    ) -> BucketUserRelation {
+        let new_relation = NewBucketUserRelation {
+            user_uuid: self.user2.uuid,
+            bucket_uuid: self.bucket.uuid,
+            set_public_permission: permissions.set_public_permission,
+            set_drawing_permission: permissions.set_drawing_permission,
+            set_exclusive_permission: permissions.set_exclusive_permission,
+            kick_permission: permissions.kick_permission,
+            grant_permissions_permission: permissions.grant_permissions_permission,
+            moderate_questions_permission: permissions.moderate_questions_permission,
+            delete_questions_permission: permissions.delete_questions_permission,
+            invite_permission: permissions.invite_permission,
+        };
+        conn.add_user_to_bucket(new_relation)
+            .expect("Should add user 2 to bucket")
+    }
+}
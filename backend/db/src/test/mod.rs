@@ -79,7 +79,8 @@ pub mod util {
     where
         Fix: Fixture,
     {
-        let db = Arc::new(Mutex::new(FakeDatabase::default()));
+        // A fixed seed keeps random draws reproducible across test runs.
+        let db = Arc::new(Mutex::new(FakeDatabase::with_seed(0)));
         let db_clone: BoxedRepository = Box::new(db.clone());
         let fixture = Fix::generate(&db_clone);
         (fixture, db)
@@ -46,6 +46,9 @@ impl Fixture for QuestionFixture {
             set_exclusive_permission: true,
             kick_permission: true,
             grant_permissions_permission: true,
+            moderate_questions_permission: true,
+            delete_questions_permission: true,
+            invite_permission: true,
         };
 
         let relation = conn
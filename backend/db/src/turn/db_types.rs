@@ -0,0 +1,40 @@
+//! Database types for tracking whose turn it is within a bucket.
+use crate::schema::bucket_turn;
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The current turn for a bucket.
+/// One user asks the question that is drawn, and another answers it.
+#[derive(
+    Clone, Copy, Debug, PartialEq, PartialOrd, Identifiable, Queryable, Serialize, Deserialize,
+)]
+#[primary_key(bucket_uuid)]
+#[table_name = "bucket_turn"]
+pub struct Turn {
+    /// The bucket the turn belongs to.
+    pub bucket_uuid: Uuid,
+    /// The user that draws and asks the question.
+    pub asker_uuid: Uuid,
+    /// The user that has been chosen to answer the question.
+    pub answerer_uuid: Uuid,
+    /// How many times the rotation has advanced for this bucket.
+    pub turn_number: i32,
+    /// When the row was last updated.
+    pub updated_at: NaiveDateTime,
+    /// When the row was created.
+    pub created_at: NaiveDateTime,
+}
+
+/// Structure used to set the turn for a bucket.
+#[derive(Clone, Copy, Insertable, Debug, Serialize, Deserialize)]
+#[table_name = "bucket_turn"]
+pub struct NewTurn {
+    /// The bucket the turn belongs to.
+    pub bucket_uuid: Uuid,
+    /// The user that draws and asks the question.
+    pub asker_uuid: Uuid,
+    /// The user that has been chosen to answer the question.
+    pub answerer_uuid: Uuid,
+}
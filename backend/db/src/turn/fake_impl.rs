@@ -0,0 +1,48 @@
+//! Mock impl
+use crate::{
+    fake::FakeDatabase,
+    turn::{
+        db_types::{NewTurn, Turn},
+        interface::TurnRepository,
+    },
+};
+use diesel::result::Error;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+impl TurnRepository for Arc<Mutex<FakeDatabase>> {
+    fn get_turn(&self, bucket_uuid: Uuid) -> Result<Turn, Error> {
+        let db = self.lock().unwrap();
+        db.turns
+            .iter()
+            .find(|t| t.bucket_uuid == bucket_uuid)
+            .cloned()
+            .ok_or_else(|| Error::NotFound)
+    }
+
+    fn set_turn(&self, new_turn: NewTurn) -> Result<Turn, Error> {
+        let mut db = self.lock().unwrap();
+        if let Some(turn) = db
+            .turns
+            .iter_mut()
+            .find(|t| t.bucket_uuid == new_turn.bucket_uuid)
+        {
+            turn.asker_uuid = new_turn.asker_uuid;
+            turn.answerer_uuid = new_turn.answerer_uuid;
+            turn.turn_number += 1;
+            turn.updated_at = chrono::Utc::now().naive_utc();
+            return Ok(turn.clone());
+        }
+
+        let turn = Turn {
+            bucket_uuid: new_turn.bucket_uuid,
+            asker_uuid: new_turn.asker_uuid,
+            answerer_uuid: new_turn.answerer_uuid,
+            turn_number: 0,
+            updated_at: chrono::Utc::now().naive_utc(),
+            created_at: chrono::Utc::now().naive_utc(),
+        };
+        db.turns.push(turn);
+        Ok(turn)
+    }
+}
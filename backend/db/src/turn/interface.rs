@@ -0,0 +1,14 @@
+//! Specification of functions.
+use crate::turn::db_types::{NewTurn, Turn};
+use diesel::QueryResult;
+use uuid::Uuid;
+
+/// Functions for specifically working with the turn rotation of buckets.
+pub trait TurnRepository {
+    /// Gets the current turn for the bucket.
+    /// If the rotation hasn't started yet, this will return a `NotFound` error.
+    fn get_turn(&self, bucket_uuid: Uuid) -> QueryResult<Turn>;
+    /// Sets the current turn for the bucket, replacing the previous turn if there was one.
+    /// The turn number is incremented every time the turn is replaced.
+    fn set_turn(&self, new_turn: NewTurn) -> QueryResult<Turn>;
+}
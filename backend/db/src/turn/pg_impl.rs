@@ -0,0 +1,41 @@
+//! Implementation of the specified interfaces for PgConnection.
+use crate::{
+    schema::bucket_turn,
+    turn::{
+        db_types::{NewTurn, Turn},
+        interface::TurnRepository,
+    },
+    AsConnRef,
+};
+use diesel::{
+    dsl::now,
+    query_dsl::{QueryDsl, RunQueryDsl},
+    result::Error,
+    ExpressionMethods,
+};
+use uuid::Uuid;
+
+impl<T> TurnRepository for T
+where
+    T: AsConnRef,
+{
+    fn get_turn(&self, bucket_uuid: Uuid) -> Result<Turn, Error> {
+        bucket_turn::table
+            .find(bucket_uuid)
+            .get_result(self.as_conn())
+    }
+
+    fn set_turn(&self, new_turn: NewTurn) -> Result<Turn, Error> {
+        diesel::insert_into(bucket_turn::table)
+            .values(&new_turn)
+            .on_conflict(bucket_turn::bucket_uuid)
+            .do_update()
+            .set((
+                bucket_turn::asker_uuid.eq(new_turn.asker_uuid),
+                bucket_turn::answerer_uuid.eq(new_turn.answerer_uuid),
+                bucket_turn::turn_number.eq(bucket_turn::turn_number + 1),
+                bucket_turn::updated_at.eq(now),
+            ))
+            .get_result(self.as_conn())
+    }
+}
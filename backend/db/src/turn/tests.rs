@@ -0,0 +1,57 @@
+use crate::{
+    test::{
+        bucket_user_relation_fixture::{UserBucketRelationFixture, NO_PERMISSIONS},
+        util::execute_test,
+    },
+    turn::db_types::NewTurn,
+    BoxedRepository,
+};
+
+#[test]
+fn no_turn_before_rotation_starts() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        db.get_turn(fixture.bucket.uuid)
+            .expect_err("There should not be a turn yet");
+    });
+}
+
+#[test]
+fn set_turn() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        let new_turn = NewTurn {
+            bucket_uuid: fixture.bucket.uuid,
+            asker_uuid: fixture.user1.uuid,
+            answerer_uuid: fixture.user1.uuid,
+        };
+        let turn = db.set_turn(new_turn).expect("Should set turn");
+        assert_eq!(turn.turn_number, 0);
+        assert_eq!(turn.asker_uuid, fixture.user1.uuid);
+
+        let fetched_turn = db.get_turn(fixture.bucket.uuid).expect("Should get turn");
+        assert_eq!(turn, fetched_turn);
+    });
+}
+
+#[test]
+fn replace_turn() {
+    execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+        fixture.add_user2(NO_PERMISSIONS, &db);
+
+        let new_turn = NewTurn {
+            bucket_uuid: fixture.bucket.uuid,
+            asker_uuid: fixture.user1.uuid,
+            answerer_uuid: fixture.user2.uuid,
+        };
+        db.set_turn(new_turn).expect("Should set turn");
+
+        let next_turn = NewTurn {
+            bucket_uuid: fixture.bucket.uuid,
+            asker_uuid: fixture.user2.uuid,
+            answerer_uuid: fixture.user1.uuid,
+        };
+        let turn = db.set_turn(next_turn).expect("Should replace turn");
+        assert_eq!(turn.turn_number, 1);
+        assert_eq!(turn.asker_uuid, fixture.user2.uuid);
+        assert_eq!(turn.answerer_uuid, fixture.user1.uuid);
+    });
+}
@@ -0,0 +1,10 @@
+-- Tracks whose turn it is within a bucket.
+-- There is at most one turn per bucket, it is replaced every time the rotation advances.
+CREATE TABLE bucket_turn (
+  bucket_uuid UUID PRIMARY KEY NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE,
+  asker_uuid UUID NOT NULL REFERENCES bq_user(uuid) ON DELETE CASCADE, -- The user that draws the question and asks it.
+  answerer_uuid UUID NOT NULL REFERENCES bq_user(uuid) ON DELETE CASCADE, -- The randomly assigned user that has to answer the question.
+  turn_number INTEGER NOT NULL DEFAULT 0, -- How many times the rotation has advanced for the bucket.
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE answer DROP COLUMN session_uuid;
+DROP TABLE session_participant;
+DROP TABLE session;
@@ -0,0 +1,23 @@
+-- A session is a single round of play for a bucket, during which questions are drawn and answered.
+CREATE TABLE session (
+  uuid UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+  bucket_uuid UUID NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE, -- The bucket the session is played in.
+  started_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  ended_at TIMESTAMP, -- Is NULL while the session is still going on.
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Only one session can be going on at a time for a given bucket.
+CREATE UNIQUE INDEX session_one_active_per_bucket ON session (bucket_uuid) WHERE ended_at IS NULL;
+
+-- The users that took part in a session.
+CREATE TABLE session_participant (
+  session_uuid UUID NOT NULL REFERENCES session(uuid) ON DELETE CASCADE,
+  user_uuid UUID NOT NULL REFERENCES bq_user(uuid) ON DELETE CASCADE,
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (session_uuid, user_uuid)
+);
+
+ALTER TABLE answer ADD COLUMN session_uuid UUID REFERENCES session(uuid) ON DELETE SET NULL; -- The session the answer was given in, if one was going on.
@@ -0,0 +1,11 @@
+-- A record of every time a question was drawn from a bucket.
+CREATE TABLE question_draw (
+  uuid UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+  question_uuid UUID NOT NULL REFERENCES question(uuid) ON DELETE CASCADE, -- The question that was drawn.
+  bucket_uuid UUID NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE, -- The bucket the question was drawn from.
+  user_uuid UUID REFERENCES bq_user(uuid) ON DELETE CASCADE, -- The user that drew the question. Users don't have to be logged in to draw questions.
+  session_uuid UUID REFERENCES session(uuid) ON DELETE SET NULL, -- The session the question was drawn in, if one was going on.
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX question_draw_bucket_created_at ON question_draw (bucket_uuid, created_at DESC);
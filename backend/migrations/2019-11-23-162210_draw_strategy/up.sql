@@ -0,0 +1,3 @@
+-- How questions are picked when drawing from the bucket.
+ALTER TABLE bucket ADD COLUMN draw_strategy VARCHAR NOT NULL DEFAULT 'uniform'
+  CHECK (draw_strategy IN ('uniform', 'upvotes', 'older_first', 'members_not_recently_drawn'));
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE question DROP COLUMN downvotes;
+ALTER TABLE question DROP COLUMN upvotes;
+DROP TABLE question_vote;
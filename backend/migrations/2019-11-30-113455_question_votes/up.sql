@@ -0,0 +1,15 @@
+-- Users can vote on questions to signal which ones the group wants to see drawn.
+-- A user gets a single vote per question, which is either an upvote (1) or a downvote (-1).
+CREATE TABLE question_vote (
+  user_uuid UUID NOT NULL REFERENCES bq_user(uuid) ON DELETE CASCADE,
+  question_uuid UUID NOT NULL REFERENCES question(uuid) ON DELETE CASCADE,
+  vote SMALLINT NOT NULL CHECK (vote IN (-1, 1)),
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (user_uuid, question_uuid)
+);
+
+-- Vote totals are kept on the question, so that questions can be cheaply sorted and weighted by their score.
+-- These are recalculated from question_vote whenever a vote changes.
+ALTER TABLE question ADD COLUMN upvotes INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE question ADD COLUMN downvotes INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,19 @@
+-- Tags are used to categorize the questions within a bucket.
+-- Each bucket has its own set of tags.
+CREATE TABLE tag (
+  uuid UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+  bucket_uuid UUID NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE,
+  tag_name VARCHAR NOT NULL,
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  UNIQUE (bucket_uuid, tag_name)
+);
+
+-- Associates tags with questions.
+CREATE TABLE question_tag (
+  question_uuid UUID NOT NULL REFERENCES question(uuid) ON DELETE CASCADE,
+  tag_uuid UUID NOT NULL REFERENCES tag(uuid) ON DELETE CASCADE,
+  updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (question_uuid, tag_uuid)
+);
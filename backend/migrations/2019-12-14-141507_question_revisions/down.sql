@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE question_revision;
+ALTER TABLE bucket_user_relation DROP COLUMN moderate_questions_permission;
@@ -0,0 +1,15 @@
+-- Lets a member edit any question in the bucket, not just the ones they wrote.
+-- Members who could already grant permissions are trusted with this as well.
+ALTER TABLE bucket_user_relation ADD COLUMN moderate_questions_permission BOOLEAN NOT NULL DEFAULT FALSE;
+UPDATE bucket_user_relation SET moderate_questions_permission = grant_permissions_permission;
+
+-- Every time a question is edited, the text it had before the edit is kept here,
+-- so that edits can be reviewed and reverted.
+CREATE TABLE question_revision (
+  uuid UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+  question_uuid UUID NOT NULL REFERENCES question(uuid) ON DELETE CASCADE,
+  question_text VARCHAR NOT NULL,
+  -- The user who made the edit that replaced this text.
+  user_uuid UUID REFERENCES bq_user(uuid) ON DELETE SET NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
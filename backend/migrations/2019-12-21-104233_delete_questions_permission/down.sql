@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE bucket_user_relation DROP COLUMN delete_questions_permission;
@@ -0,0 +1,4 @@
+-- Lets a member delete any question in the bucket, not just the ones they wrote.
+-- Members who could already grant permissions are trusted with this as well.
+ALTER TABLE bucket_user_relation ADD COLUMN delete_questions_permission BOOLEAN NOT NULL DEFAULT FALSE;
+UPDATE bucket_user_relation SET delete_questions_permission = grant_permissions_permission;
@@ -0,0 +1,7 @@
+-- Answers given without logging in can't be tied to a user,
+-- so a secret token is handed out when they are created, which allows them to be edited or deleted later.
+CREATE TABLE answer_edit_token (
+  answer_uuid UUID PRIMARY KEY NOT NULL REFERENCES answer(uuid) ON DELETE CASCADE,
+  edit_token UUID NOT NULL DEFAULT gen_random_uuid(),
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,9 @@
+-- Tracks when users in a bucket were last seen playing in it.
+-- Clients send heartbeats while they have the bucket open,
+-- so users whose heartbeat is recent enough are considered present.
+CREATE TABLE bucket_presence (
+  bucket_uuid UUID NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE,
+  user_uuid UUID NOT NULL REFERENCES bq_user(uuid) ON DELETE CASCADE,
+  last_seen_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (bucket_uuid, user_uuid)
+);
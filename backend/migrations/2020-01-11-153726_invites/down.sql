@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE bucket_join_code;
+DROP TABLE bucket_invite;
+ALTER TABLE bucket_user_relation DROP COLUMN invite_permission;
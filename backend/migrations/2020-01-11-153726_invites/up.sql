@@ -0,0 +1,33 @@
+-- Lets a member hand out invites and see the bucket's join code.
+-- Members who could already grant permissions are trusted with this as well.
+ALTER TABLE bucket_user_relation ADD COLUMN invite_permission BOOLEAN NOT NULL DEFAULT FALSE;
+UPDATE bucket_user_relation SET invite_permission = grant_permissions_permission;
+
+-- An invite to a bucket, which lets users join even if the bucket is exclusive.
+-- The uuid of the invite doubles as the secret that is shared with the invitees.
+CREATE TABLE bucket_invite (
+  uuid UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+  bucket_uuid UUID NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE,
+  user_uuid UUID REFERENCES bq_user(uuid) ON DELETE SET NULL, -- The user who created the invite.
+  expires_at TIMESTAMP, -- The invite can't be used after this time. Null invites never expire.
+  max_uses INTEGER, -- How many users can join with this invite. Null invites can be used any number of times.
+  uses INTEGER NOT NULL DEFAULT 0,
+  -- The permissions that users joining with this invite are given.
+  set_public_permission BOOLEAN NOT NULL DEFAULT FALSE,
+  set_drawing_permission BOOLEAN NOT NULL DEFAULT FALSE,
+  set_exclusive_permission BOOLEAN NOT NULL DEFAULT FALSE,
+  kick_permission BOOLEAN NOT NULL DEFAULT FALSE,
+  grant_permissions_permission BOOLEAN NOT NULL DEFAULT FALSE,
+  moderate_questions_permission BOOLEAN NOT NULL DEFAULT FALSE,
+  delete_questions_permission BOOLEAN NOT NULL DEFAULT FALSE,
+  invite_permission BOOLEAN NOT NULL DEFAULT FALSE,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- A short code that can be typed in to join a bucket, even if the bucket is exclusive.
+-- There is at most one per bucket, it is replaced when it is regenerated.
+CREATE TABLE bucket_join_code (
+  bucket_uuid UUID PRIMARY KEY NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE,
+  join_code VARCHAR NOT NULL UNIQUE,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
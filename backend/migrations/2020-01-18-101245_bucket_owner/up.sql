@@ -0,0 +1,26 @@
+-- The owner of a bucket always holds every permission, and can't be kicked or have permissions revoked.
+-- This guarantees that there is always someone who can grant permissions in the bucket.
+ALTER TABLE bucket ADD COLUMN owner_uuid UUID REFERENCES bq_user(uuid) ON DELETE SET NULL;
+
+-- Existing buckets are owned by their longest standing member that could grant permissions,
+-- or failing that, their longest standing member.
+UPDATE bucket SET owner_uuid = (
+  SELECT r.user_uuid
+  FROM bucket_user_relation r
+  WHERE r.bucket_uuid = bucket.uuid
+  ORDER BY r.grant_permissions_permission DESC, r.created_at ASC
+  LIMIT 1
+);
+
+UPDATE bucket_user_relation r
+SET
+  set_public_permission = TRUE,
+  set_drawing_permission = TRUE,
+  set_exclusive_permission = TRUE,
+  kick_permission = TRUE,
+  grant_permissions_permission = TRUE,
+  moderate_questions_permission = TRUE,
+  delete_questions_permission = TRUE,
+  invite_permission = TRUE
+FROM bucket b
+WHERE b.uuid = r.bucket_uuid AND b.owner_uuid = r.user_uuid;
@@ -0,0 +1,10 @@
+-- A record of every permission that was granted to or revoked from a member of a bucket.
+CREATE TABLE bucket_permission_grant (
+  uuid UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+  bucket_uuid UUID NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE,
+  granter_uuid UUID REFERENCES bq_user(uuid) ON DELETE SET NULL, -- The user who made the change.
+  target_uuid UUID NOT NULL REFERENCES bq_user(uuid) ON DELETE CASCADE, -- The user whose permission changed.
+  permission VARCHAR NOT NULL, -- The name of the permission's column in bucket_user_relation.
+  granted BOOLEAN NOT NULL, -- True if the permission was granted, false if it was revoked.
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
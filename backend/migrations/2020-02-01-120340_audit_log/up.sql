@@ -0,0 +1,12 @@
+-- An append-only record of the changes made within a bucket.
+CREATE TABLE bucket_audit_event (
+  uuid UUID PRIMARY KEY NOT NULL DEFAULT gen_random_uuid(),
+  bucket_uuid UUID NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE,
+  actor_uuid UUID REFERENCES bq_user(uuid) ON DELETE SET NULL, -- Null if the change was made without logging in.
+  target_uuid UUID, -- The user, question or answer that was acted upon, if any.
+  action VARCHAR NOT NULL,
+  diff JSONB NOT NULL DEFAULT '{}',
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX bucket_audit_event_bucket_created_at_idx ON bucket_audit_event (bucket_uuid, created_at);
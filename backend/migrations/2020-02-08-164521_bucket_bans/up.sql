@@ -0,0 +1,11 @@
+-- Keeps users that were removed from a bucket from joining it again.
+-- A user has at most one ban per bucket, banning them again replaces it.
+CREATE TABLE bucket_ban (
+  bucket_uuid UUID NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE,
+  user_uuid UUID NOT NULL REFERENCES bq_user(uuid) ON DELETE CASCADE, -- The user who is banned.
+  banned_by_uuid UUID REFERENCES bq_user(uuid) ON DELETE SET NULL, -- The user who banned them.
+  reason VARCHAR, -- Why the user was banned, shown to the members who manage the bucket.
+  expires_at TIMESTAMP, -- The ban stops applying after this time. Null bans last until they are lifted.
+  created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (bucket_uuid, user_uuid)
+);
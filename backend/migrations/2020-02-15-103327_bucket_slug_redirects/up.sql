@@ -0,0 +1,7 @@
+-- Slugs that buckets had before they were re-slugged.
+-- They are kept so that links to a bucket keep working after its slug changes.
+CREATE TABLE bucket_slug_redirect (
+  slug VARCHAR PRIMARY KEY NOT NULL,
+  bucket_uuid UUID NOT NULL REFERENCES bucket(uuid) ON DELETE CASCADE,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
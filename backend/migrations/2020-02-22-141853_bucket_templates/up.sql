@@ -0,0 +1,3 @@
+-- Templates are buckets that aren't played in, but that new buckets are made from.
+-- Making a bucket from a template copies the template's questions and tags into the new bucket.
+ALTER TABLE bucket ADD COLUMN is_template BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,5 @@
+-- Trigram similarity is used to find questions that are worded almost the same as a new question.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+-- What happens when a question is asked that already is in the bucket.
+ALTER TABLE bucket ADD COLUMN duplicate_policy VARCHAR NOT NULL DEFAULT 'warn'
+  CHECK (duplicate_policy IN ('warn', 'reject'));
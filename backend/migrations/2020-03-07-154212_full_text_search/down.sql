@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+
+DROP FUNCTION search_headline(TEXT, TSQUERY);
+DROP FUNCTION search_query(TEXT);
+
+DROP TRIGGER answer_search_vector_update ON answer;
+ALTER TABLE answer DROP COLUMN search_vector;
+
+DROP TRIGGER question_search_vector_update ON question;
+ALTER TABLE question DROP COLUMN search_vector;
@@ -0,0 +1,30 @@
+-- Full text search over questions and answers.
+-- The search vectors are kept up to date by triggers whenever the text changes.
+ALTER TABLE question ADD COLUMN search_vector TSVECTOR;
+UPDATE question SET search_vector = to_tsvector('pg_catalog.english', question_text);
+CREATE INDEX question_search_vector_idx ON question USING GIN (search_vector);
+CREATE TRIGGER question_search_vector_update BEFORE INSERT OR UPDATE OF question_text ON question
+    FOR EACH ROW EXECUTE PROCEDURE tsvector_update_trigger(search_vector, 'pg_catalog.english', question_text);
+
+ALTER TABLE answer ADD COLUMN search_vector TSVECTOR;
+UPDATE answer SET search_vector = to_tsvector('pg_catalog.english', answer_text);
+CREATE INDEX answer_search_vector_idx ON answer USING GIN (search_vector);
+CREATE TRIGGER answer_search_vector_update BEFORE INSERT OR UPDATE OF answer_text ON answer
+    FOR EACH ROW EXECUTE PROCEDURE tsvector_update_trigger(search_vector, 'pg_catalog.english', answer_text);
+
+-- Searches go through these, so that the search configuration is only named in this file.
+-- Queries are parsed like a web search: quoted phrases, "or", and "-" to exclude words.
+CREATE FUNCTION search_query(query TEXT) RETURNS TSQUERY AS $$
+    SELECT websearch_to_tsquery('pg_catalog.english', query)
+$$ LANGUAGE SQL STABLE STRICT;
+
+-- Matched words are wrapped in <b> and </b>.
+-- The document is HTML-escaped first, so that the markers are the only markup in the headline.
+CREATE FUNCTION search_headline(document TEXT, query TSQUERY) RETURNS TEXT AS $$
+    SELECT ts_headline(
+        'pg_catalog.english',
+        replace(replace(replace(replace(replace(document, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;'),
+        query,
+        'StartSel=<b>, StopSel=</b>, MaxFragments=2'
+    )
+$$ LANGUAGE SQL STABLE STRICT;
@@ -1,50 +1,256 @@
 use crate::{
+    api::{
+        audit::{diff, record_audit_event},
+        bucket::advance_turn,
+        question::can_archive_questions,
+    },
     error::Error,
-    server_auth::optional_user_filter,
-    state::State,
-    util::{json_or_reject, sized_body_json},
+    server_auth::{optional_user_filter, user_filter},
+    state::{hub::Hub, State},
+    util::{json_or_reject, pagination, sized_body_json},
 };
 use db::{
-    answer::db_types::{Answer, NewAnswer},
+    answer::db_types::{Answer, AnswerChangeset, NewAnswer},
+    audit::db_types::AuditAction,
+    page::Page,
+    session::db_types::NewSessionParticipant,
     BoxedRepository,
 };
 use uuid::Uuid;
-use warp::{filters::BoxedFilter, path, Filter, Reply};
-use wire::answer::NewAnswerRequest;
+use log::info;
+use serde_json::Value;
+use warp::{filters::BoxedFilter, path, query, Filter, Reply};
+use wire::answer::{CreatedAnswer, EditAnswerRequest, NewAnswerRequest};
+use wire::live::BucketEvent;
+use wire::page::{PageQuery, RecencySort};
 
 
 pub const ANSWER_PATH: &str = "answer";
+
+/// The header used to provide the edit token of an answer given without logging in.
+pub const ANSWER_EDIT_TOKEN_HEADER: &str = "answer-edit-token";
+
 pub fn answer_api(state: &State) -> BoxedFilter<(impl Reply,)> {
     let answer_question = warp::path::end()
         .and(warp::post2())
         .and(sized_body_json(30))
         .and(optional_user_filter(state))
+        .and(state.hub())
         .and(state.db())
         .map(answer_question_handler)
         .and_then(json_or_reject);
 
+    let get_own_answers = path!("mine")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(
+            |page: PageQuery<RecencySort>,
+             user_uuid: Uuid,
+             conn: BoxedRepository|
+             -> Result<Page<Answer>, Error> {
+                let pagination = pagination(page, RecencySort::Newest)?;
+                conn.get_answers_for_user(user_uuid, pagination)
+                    .map_err(Error::from)
+            },
+        )
+        .and_then(json_or_reject);
+
+    let edit_answer = path!(Uuid)
+        .and(warp::path::end())
+        .and(warp::put2())
+        .and(sized_body_json(30))
+        .and(optional_user_filter(state))
+        .and(edit_token_filter())
+        .and(state.db())
+        .map(edit_answer_handler)
+        .and_then(json_or_reject);
 
-    // TODO need a get answers?
-    // Put that under this subpath or questions?
+    let delete_answer = path!(Uuid)
+        .and(warp::path::end())
+        .and(warp::delete2())
+        .and(optional_user_filter(state))
+        .and(edit_token_filter())
+        .and(state.db())
+        .map(delete_answer_handler)
+        .and_then(json_or_reject);
 
-    path(ANSWER_PATH).and(answer_question).boxed()
+    path(ANSWER_PATH)
+        .and(
+            answer_question
+                .or(get_own_answers)
+                .or(edit_answer)
+                .or(delete_answer),
+        )
+        .boxed()
 }
 
-/// Will set the associated question to archived if the archived field of the request is set to true.
+/// Gets the answer edit token from the request, if it has one.
+fn edit_token_filter() -> BoxedFilter<(Option<Uuid>,)> {
+    warp::header::header::<Uuid>(ANSWER_EDIT_TOKEN_HEADER)
+        .map(Some)
+        .or(warp::any().map(|| None))
+        .unify::<(Option<Uuid>,)>()
+        .boxed()
+}
+
+/// Will set the associated question to archived if the archived field of the request is set to true,
+/// and the user could archive it themselves. Otherwise, the field is ignored.
+///
+/// Answering a question ends the current turn for the question's bucket.
+/// If a session is going on in the bucket, the answer is recorded as part of it.
+///
+/// Answers given without logging in come with an edit token, which is the only way to edit or delete them later.
 fn answer_question_handler(
     request: NewAnswerRequest,
     user_uuid: Option<Uuid>,
+    hub: Hub,
     conn: BoxedRepository,
-) -> Result<Answer, Error> {
+) -> Result<CreatedAnswer, Error> {
+    let question = conn.get_question(request.question_uuid)?;
+    let session = conn.get_active_session(question.bucket_uuid)?;
+
     let new_answer = NewAnswer {
         user_uuid,
         question_uuid: request.question_uuid,
         publicly_visible: request.publicly_visible,
         answer_text: request.answer_text,
+        session_uuid: session.map(|session| session.uuid),
     };
     let answer = conn.create_answer(new_answer).map_err(Error::from)?;
-    if request.archive_question {
+    if request.archive_question && can_archive_questions(user_uuid, question.bucket_uuid, &conn)? {
         conn.set_archive_status_for_question(request.question_uuid, true)?;
     }
-    Ok(answer)
+    // Anyone who answers during a session took part in it, even if they joined late.
+    if let (Some(session), Some(user_uuid)) = (session, user_uuid) {
+        let participant = NewSessionParticipant {
+            session_uuid: session.uuid,
+            user_uuid,
+        };
+        conn.add_participant(participant)?;
+    }
+    advance_turn(question.bucket_uuid, &conn)?;
+    record_audit_event(
+        question.bucket_uuid,
+        user_uuid,
+        Some(answer.uuid),
+        AuditAction::AnswerPosted,
+        answer_diff(None, Some(&answer)),
+        &conn,
+    )?;
+
+    // Private answers are announced, but their contents are not shared.
+    let event = BucketEvent::AnswerPosted {
+        question_uuid: question.uuid,
+        answer: if answer.publicly_visible {
+            Some(answer.clone().into())
+        } else {
+            None
+        },
+    };
+    hub.publish(question.bucket_uuid, event);
+
+    let edit_token = if user_uuid.is_none() {
+        Some(conn.create_edit_token(answer.uuid)?.edit_token)
+    } else {
+        None
+    };
+    Ok(CreatedAnswer {
+        answer: answer.into(),
+        edit_token,
+    })
+}
+
+/// Changes the answer's text or visibility.
+fn edit_answer_handler(
+    answer_uuid: Uuid,
+    request: EditAnswerRequest,
+    user_uuid: Option<Uuid>,
+    edit_token: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<Answer, Error> {
+    info!("edit_answer_handler");
+    let answer = conn.get_answer(answer_uuid)?;
+    verify_can_manage_answer(&answer, user_uuid, edit_token, &conn)?;
+
+    let changeset = AnswerChangeset {
+        uuid: answer_uuid,
+        publicly_visible: request.publicly_visible,
+        answer_text: request.answer_text,
+    };
+    let edited = conn.update_answer(changeset)?;
+    let question = conn.get_question(answer.question_uuid)?;
+    record_audit_event(
+        question.bucket_uuid,
+        user_uuid,
+        Some(answer_uuid),
+        AuditAction::AnswerEdited,
+        answer_diff(Some(&answer), Some(&edited)),
+        &conn,
+    )?;
+    Ok(edited)
+}
+
+fn delete_answer_handler(
+    answer_uuid: Uuid,
+    user_uuid: Option<Uuid>,
+    edit_token: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<Answer, Error> {
+    info!("delete_answer_handler");
+    let answer = conn.get_answer(answer_uuid)?;
+    verify_can_manage_answer(&answer, user_uuid, edit_token, &conn)?;
+    let deleted = conn.delete_answer(answer_uuid)?;
+    let question = conn.get_question(answer.question_uuid)?;
+    record_audit_event(
+        question.bucket_uuid,
+        user_uuid,
+        Some(answer_uuid),
+        AuditAction::AnswerDeleted,
+        answer_diff(Some(&deleted), None),
+        &conn,
+    )?;
+    Ok(deleted)
+}
+
+/// The fields of the answer that changed, for the audit log.
+///
+/// Private answers are kept private from the log as well, so their text is left out.
+fn answer_diff(old: Option<&Answer>, new: Option<&Answer>) -> Value {
+    let mut changes = diff(old, new);
+    let is_private = old
+        .iter()
+        .chain(new.iter())
+        .any(|answer| !answer.publicly_visible);
+    if let (true, Value::Object(fields)) = (is_private, &mut changes) {
+        fields.remove("answer_text");
+    }
+    changes
+}
+
+/// Rejects anyone but the answer's author.
+///
+/// Answers given without logging in can only be managed with the edit token handed out when they were created.
+fn verify_can_manage_answer(
+    answer: &Answer,
+    user_uuid: Option<Uuid>,
+    edit_token: Option<Uuid>,
+    conn: &BoxedRepository,
+) -> Result<(), Error> {
+    let is_author = match (answer.user_uuid, user_uuid, edit_token) {
+        (Some(author), Some(user_uuid), _) => author == user_uuid,
+        (None, _, Some(edit_token)) => match conn.get_edit_token(answer.uuid) {
+            Ok(token) => token.edit_token == edit_token,
+            Err(diesel::result::Error::NotFound) => false,
+            Err(e) => return Err(Error::from(e)),
+        },
+        _ => false,
+    };
+    if is_author {
+        Ok(())
+    } else {
+        Err(Error::not_authorized("Only the answer's author can change it."))
+    }
 }
@@ -0,0 +1,144 @@
+//! Records what changes within buckets, so the members that manage a bucket can see who did what.
+use crate::{
+    api::bucket::BUCKET_PATH,
+    error::Error,
+    server_auth::user_filter,
+    state::State,
+    util::{json_or_reject, pagination},
+};
+use db::{
+    audit::db_types::{AuditAction, AuditEvent, NewAuditEvent},
+    page::Page,
+    BoxedRepository,
+};
+use log::info;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+use warp::{filters::BoxedFilter, path, query, Filter, Reply};
+use wire::page::{PageQuery, RecencySort};
+
+pub fn audit_api(state: &State) -> BoxedFilter<(impl Reply,)> {
+    let get_audit_log = path!(Uuid / "audit")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(get_audit_log_handler)
+        .and_then(json_or_reject);
+
+    path(BUCKET_PATH).and(get_audit_log).boxed()
+}
+
+/// Gets a page of the events in the bucket, the most recent first unless the request says otherwise.
+///
+/// Only members that can manage other members, by kicking them or granting them permissions, can see the log.
+fn get_audit_log_handler(
+    bucket_uuid: Uuid,
+    page: PageQuery<RecencySort>,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Page<AuditEvent>, Error> {
+    info!("get_audit_log_handler");
+    let reason = "Only members who can kick or grant permissions can see the audit log.";
+    match conn.get_permissions(user_uuid, bucket_uuid) {
+        Ok(permissions)
+            if permissions.grant_permissions_permission || permissions.kick_permission => {}
+        Ok(_) | Err(diesel::result::Error::NotFound) => return Err(Error::not_authorized(reason)),
+        Err(e) => return Err(Error::from(e)),
+    }
+    let pagination = pagination(page, RecencySort::Newest)?;
+    conn.get_audit_events(bucket_uuid, pagination)
+        .map_err(Error::from)
+}
+
+/// Adds an event to the bucket's audit log.
+pub fn record_audit_event(
+    bucket_uuid: Uuid,
+    actor_uuid: Option<Uuid>,
+    target_uuid: Option<Uuid>,
+    action: AuditAction,
+    diff: Value,
+    conn: &BoxedRepository,
+) -> Result<(), Error> {
+    let new_event = NewAuditEvent {
+        bucket_uuid,
+        actor_uuid,
+        target_uuid,
+        action,
+        diff,
+    };
+    conn.record_audit_event(new_event)?;
+    Ok(())
+}
+
+/// The fields that differ between two versions of something, as `{"field": {"old": .., "new": ..}}`.
+///
+/// Something that was created has no old version, and something that was deleted has no new version,
+/// in which case every one of its fields is included.
+pub fn diff<T: Serialize>(old: Option<&T>, new: Option<&T>) -> Value {
+    fn fields<T: Serialize>(value: Option<&T>) -> Map<String, Value> {
+        match value.map(serde_json::to_value) {
+            Some(Ok(Value::Object(fields))) => fields,
+            _ => Map::new(),
+        }
+    }
+    let old = fields(old);
+    let new = fields(new);
+    let changes = old
+        .keys()
+        .chain(new.keys())
+        .filter(|field| old.get(*field) != new.get(*field))
+        .map(|field| {
+            let change = json!({
+                "old": old.get(field).cloned().unwrap_or(Value::Null),
+                "new": new.get(field).cloned().unwrap_or(Value::Null),
+            });
+            (field.clone(), change)
+        })
+        .collect();
+    Value::Object(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Thing {
+        name: &'static str,
+        count: i32,
+    }
+
+    #[test]
+    fn diff_only_includes_changed_fields() {
+        let old = Thing {
+            name: "thing",
+            count: 1,
+        };
+        let new = Thing {
+            name: "thing",
+            count: 2,
+        };
+        assert_eq!(
+            diff(Some(&old), Some(&new)),
+            json!({"count": {"old": 1, "new": 2}})
+        );
+    }
+
+    #[test]
+    fn diff_of_created_includes_every_field() {
+        let new = Thing {
+            name: "thing",
+            count: 1,
+        };
+        assert_eq!(
+            diff(None, Some(&new)),
+            json!({
+                "name": {"old": null, "new": "thing"},
+                "count": {"old": null, "new": 1},
+            })
+        );
+    }
+}
@@ -287,6 +287,15 @@ pub mod test {
         create_jwt(user, secret).expect("Should create JWT.")
     }
 
+    /// Gets a JWT for a user that already exists, for tests that act as someone other than the fixture's main user.
+    ///
+    /// # Panics
+    /// Panics if the JWT can't be encoded.
+    pub fn get_jwt_for_user(state: &State, user: User) -> String {
+        let secret: Secret = warp::test::request().filter(&state.secret()).unwrap();
+        create_jwt(user, secret).expect("Should create JWT.")
+    }
+
     /// Test for testing infrastructure
     #[test]
     fn get_jwt_util_creates_user() {
@@ -0,0 +1,243 @@
+//! Backs up whole buckets, and restores them as new buckets.
+//!
+//! Anyone can write a backup by hand, so restoring one here doesn't trust the users in it.
+//! The restored bucket only has the restorer as a member, and what everyone else wrote is kept without an author.
+//! Restoring a backup along with its users, like when moving a bucket to another instance, is left to the backup cli.
+use crate::{
+    api::{
+        audit::{diff, record_audit_event},
+        bucket::{unused_slug, BUCKET_PATH},
+    },
+    backup::{read_backup, write_backup},
+    error::Error,
+    server_auth::user_filter,
+    state::State,
+    util::{json_or_reject, reject, sized_body_bytes},
+};
+use db::{
+    audit::db_types::AuditAction, backup::db_types::RestoreOptions, bucket::db_types::Bucket,
+    BoxedRepository,
+};
+use hyper::Body;
+use log::info;
+use uuid::Uuid;
+use warp::{filters::BoxedFilter, http::Response, path, Filter, Reply};
+
+/// The largest backup that will be restored, in kilobytes.
+const BACKUP_SIZE_LIMIT_KB: u64 = 10 * 1024;
+
+pub fn backup_api(state: &State) -> BoxedFilter<(impl Reply,)> {
+    let backup_bucket = path!(Uuid / "backup")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(user_filter(state))
+        .and(state.db())
+        .and_then(
+            |bucket_uuid: Uuid, user_uuid: Uuid, conn: BoxedRepository| {
+                reject(backup_bucket_handler(bucket_uuid, user_uuid, conn))
+            },
+        );
+
+    let restore_bucket = path!("restore")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(sized_body_bytes(BACKUP_SIZE_LIMIT_KB))
+        .and(user_filter(state))
+        .and(state.db())
+        .map(restore_bucket_handler)
+        .and_then(json_or_reject);
+
+    path(BUCKET_PATH)
+        .and(backup_bucket.or(restore_bucket))
+        .boxed()
+}
+
+/// Gets a tarball holding everything needed to recreate the bucket.
+///
+/// Only the bucket's owner can back it up, because the backup includes every member's answers.
+fn backup_bucket_handler(
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Response<Body>, Error> {
+    info!("backup_bucket_handler");
+    let bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    if bucket.owner_uuid != Some(user_uuid) {
+        return Err(Error::not_authorized(
+            "Only the owner can back up the bucket.",
+        ));
+    }
+    let archive = conn.export_bucket(bucket_uuid)?;
+    let backup = write_backup(&archive)?;
+
+    Response::builder()
+        .header("content-type", "application/x-tar")
+        .header(
+            "content-disposition",
+            format!("attachment; filename=\"{}.tar\"", bucket.bucket_slug),
+        )
+        .body(Body::from(backup))
+        .map_err(|_| Error::internal_server_error("Could not create the backup."))
+}
+
+/// Restores the backup as a new bucket that is owned by the user.
+///
+/// The backup's users aren't restored.
+fn restore_bucket_handler(
+    backup: Vec<u8>,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Bucket, Error> {
+    info!("restore_bucket_handler");
+    let archive = read_backup(&backup)?;
+    let options = RestoreOptions {
+        bucket_slug: unused_slug(&archive.bucket.bucket_name, None, &conn)?,
+        owner_uuid: Some(user_uuid),
+        keep_users: false,
+    };
+    let bucket = conn.restore_bucket(archive, options)?;
+    record_audit_event(
+        bucket.uuid,
+        Some(user_uuid),
+        None,
+        AuditAction::BucketRestored,
+        diff(None, Some(&bucket)),
+        &conn,
+    )?;
+    Ok(bucket)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_util::execute_test_on_repository;
+    use chrono::Utc;
+    use db::{
+        answer::db_types::Answer, bucket_user_relation::db_types::BucketUserRelation,
+        question::db_types::NewQuestion,
+        test::bucket_user_relation_fixture::UserBucketRelationFixture, user::db_types::User,
+        RepositoryProvider,
+    };
+
+    #[test]
+    fn restored_bucket_is_owned_by_restorer() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let new_question = NewQuestion {
+                    bucket_uuid: fixture.bucket.uuid,
+                    user_uuid: Some(fixture.user1.uuid),
+                    question_text: "What's your favorite color?".to_string(),
+                };
+                repo.create_question(new_question)
+                    .expect("Should create question");
+                let archive = repo
+                    .export_bucket(fixture.bucket.uuid)
+                    .expect("Should export bucket");
+                let backup = write_backup(&archive).expect("Should write backup");
+
+                let bucket = restore_bucket_handler(backup, fixture.user2.uuid, repo)
+                    .expect("Should restore bucket");
+                assert_ne!(bucket.uuid, fixture.bucket.uuid);
+                assert_ne!(bucket.bucket_slug, fixture.bucket.bucket_slug);
+                assert_eq!(bucket.bucket_name, fixture.bucket.bucket_name);
+                assert_eq!(bucket.owner_uuid, Some(fixture.user2.uuid));
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let permissions = repo
+                    .get_permissions(fixture.user2.uuid, bucket.uuid)
+                    .expect("Restorer should be a member");
+                assert!(permissions.grant_permissions_permission);
+                let count = repo
+                    .get_number_of_active_questions_for_bucket(bucket.uuid)
+                    .expect("Should count questions");
+                assert_eq!(count, 1);
+            },
+        )
+    }
+
+    #[test]
+    fn restore_does_not_trust_the_backups_users() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let new_question = NewQuestion {
+                    bucket_uuid: fixture.bucket.uuid,
+                    user_uuid: Some(fixture.user1.uuid),
+                    question_text: "What's your favorite color?".to_string(),
+                };
+                let question = repo
+                    .create_question(new_question)
+                    .expect("Should create question");
+                let mut archive = repo
+                    .export_bucket(fixture.bucket.uuid)
+                    .expect("Should export bucket");
+                // user1 forges a backup that says user2 is a member, and has answered,
+                // and that someone else who hasn't signed up yet is a member too.
+                let forged_user = User {
+                    uuid: Uuid::new_v4(),
+                    google_user_id: "forged".to_string(),
+                    google_name: Some("Forged".to_string()),
+                };
+                archive.users.push(fixture.user2.clone());
+                archive.users.push(forged_user.clone());
+                for user_uuid in &[fixture.user2.uuid, forged_user.uuid] {
+                    archive.members.push(BucketUserRelation {
+                        user_uuid: *user_uuid,
+                        ..fixture.relation.clone()
+                    });
+                }
+                let now = Utc::now().naive_utc();
+                archive.answers.push(Answer {
+                    uuid: Uuid::new_v4(),
+                    user_uuid: Some(fixture.user2.uuid),
+                    question_uuid: question.uuid,
+                    publicly_visible: true,
+                    answer_text: "Whatever user1 wants".to_string(),
+                    updated_at: now,
+                    created_at: now,
+                    session_uuid: None,
+                });
+                let backup = write_backup(&archive).expect("Should write backup");
+
+                let bucket = restore_bucket_handler(backup, fixture.user1.uuid, repo)
+                    .expect("Should restore bucket");
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                repo.get_permissions(fixture.user2.uuid, bucket.uuid)
+                    .expect_err("user2 should not be a member");
+                repo.get_user_by_google_id(forged_user.google_user_id)
+                    .expect_err("Should not create the forged user");
+                let restored = repo
+                    .export_bucket(bucket.uuid)
+                    .expect("Should export restored bucket");
+                assert_eq!(restored.users, vec![fixture.user1.clone()]);
+                assert_eq!(restored.members.len(), 1);
+                assert_eq!(restored.answers.len(), 1);
+                assert_eq!(restored.answers[0].user_uuid, None);
+            },
+        )
+    }
+
+    #[test]
+    fn only_owner_can_back_up() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                // Being a member with every permission isn't enough.
+                let error = backup_bucket_handler(fixture.bucket.uuid, fixture.user1.uuid, repo)
+                    .unwrap_err();
+                match error {
+                    Error::NotAuthorized { .. } => {}
+                    other => panic!("Expected not authorized, got: {:?}", other),
+                }
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                repo.set_bucket_owner(fixture.bucket.uuid, fixture.user1.uuid)
+                    .expect("Should set owner");
+                backup_bucket_handler(fixture.bucket.uuid, fixture.user1.uuid, repo)
+                    .expect("Owner should back up the bucket");
+            },
+        )
+    }
+}
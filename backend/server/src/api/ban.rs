@@ -0,0 +1,208 @@
+//! Keeps users that were removed from a bucket from joining it again.
+//!
+//! Banning a member removes them from the bucket, and the ban is checked whenever a user joins,
+//! whether they join directly, by invite, or with the bucket's join code.
+use crate::{
+    api::{
+        audit::{diff, record_audit_event},
+        bucket::{ensure_other_granter_remains, BUCKET_PATH},
+    },
+    error::Error,
+    server_auth::user_filter,
+    state::{hub::Hub, State},
+    util::{json_or_reject, pagination, sized_body_json},
+};
+use chrono::Utc;
+use db::{
+    audit::db_types::AuditAction,
+    ban::db_types::{Ban, NewBan},
+    page::Page,
+    BoxedRepository,
+};
+use log::info;
+use uuid::Uuid;
+use warp::{filters::BoxedFilter, path, query, Filter, Reply};
+use wire::{
+    ban::NewBanRequest,
+    live::BucketEvent,
+    page::{PageQuery, RecencySort},
+};
+
+pub fn ban_api(state: &State) -> BoxedFilter<(impl Reply,)> {
+    let ban_user = path!(Uuid / "bans")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(sized_body_json(1))
+        .and(user_filter(state))
+        .and(state.hub())
+        .and(state.db())
+        .map(ban_user_handler)
+        .and_then(json_or_reject);
+
+    let get_bans = path!(Uuid / "bans")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(get_bans_handler)
+        .and_then(json_or_reject);
+
+    let lift_ban = path!(Uuid / "bans" / Uuid)
+        .and(warp::path::end())
+        .and(warp::delete2())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(lift_ban_handler)
+        .and_then(json_or_reject);
+
+    path(BUCKET_PATH)
+        .and(ban_user.or(get_bans).or(lift_ban))
+        .boxed()
+}
+
+/// Rejects the request if the user can't kick others from the bucket.
+fn ensure_can_kick(
+    user_uuid: Uuid,
+    bucket_uuid: Uuid,
+    conn: &BoxedRepository,
+) -> Result<(), Error> {
+    let reason = "User does not have privileges to ban others from this bucket.";
+    match conn.get_permissions(user_uuid, bucket_uuid) {
+        Ok(permissions) if permissions.kick_permission => Ok(()),
+        Ok(_) | Err(diesel::result::Error::NotFound) => Err(Error::not_authorized(reason)),
+        Err(e) => Err(Error::from(e)),
+    }
+}
+
+/// Rejects the request if the user is banned from the bucket.
+///
+/// Bans that have expired don't count.
+pub fn ensure_not_banned(
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: &BoxedRepository,
+) -> Result<(), Error> {
+    match conn.get_ban(bucket_uuid, user_uuid) {
+        Ok(ban) if ban.is_active(Utc::now().naive_utc()) => {
+            Err(Error::not_authorized("User is banned from this bucket."))
+        }
+        Ok(_) | Err(diesel::result::Error::NotFound) => Ok(()),
+        Err(e) => Err(Error::from(e)),
+    }
+}
+
+/// Bans the user from the bucket, removing them from it if they are a member.
+fn ban_user_handler(
+    bucket_uuid: Uuid,
+    request: NewBanRequest,
+    user_uuid: Uuid,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<Ban, Error> {
+    info!("ban_user_handler");
+    ensure_can_kick(user_uuid, bucket_uuid, &conn)?;
+    let target_uuid = request.user_uuid;
+    if target_uuid == user_uuid {
+        return Err(Error::BadRequest("Users can't ban themselves.".to_string()));
+    }
+    if request
+        .expires_at
+        .map(|expires_at| expires_at <= Utc::now().naive_utc())
+        .unwrap_or(false)
+    {
+        return Err(Error::BadRequest(
+            "A ban must expire in the future.".to_string(),
+        ));
+    }
+    let bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    if bucket.owner_uuid == Some(target_uuid) {
+        return Err(Error::PreconditionNotMet(
+            "The owner can't be banned from the bucket.".to_string(),
+        ));
+    }
+
+    // Users who aren't members can be banned too, which keeps them from ever joining.
+    let membership = match conn.get_user_bucket_relation(target_uuid, bucket_uuid) {
+        Ok(relation) => Some(relation),
+        Err(diesel::result::Error::NotFound) => None,
+        Err(e) => return Err(Error::from(e)),
+    };
+    if let Some(relation) = &membership {
+        if relation.grant_permissions_permission {
+            ensure_other_granter_remains(bucket_uuid, target_uuid, &conn)?;
+        }
+    }
+
+    let new_ban = NewBan {
+        bucket_uuid,
+        user_uuid: target_uuid,
+        banned_by_uuid: Some(user_uuid),
+        reason: request.reason,
+        expires_at: request.expires_at,
+    };
+    // Members are removed from the bucket as they are banned.
+    let ban = conn.ban_user(new_ban)?;
+    if let Some(relation) = membership {
+        record_audit_event(
+            bucket_uuid,
+            Some(user_uuid),
+            Some(target_uuid),
+            AuditAction::MemberRemoved,
+            diff(Some(&relation), None),
+            &conn,
+        )?;
+        hub.publish(
+            bucket_uuid,
+            BucketEvent::MemberKicked {
+                user_uuid: target_uuid,
+            },
+        );
+    }
+    record_audit_event(
+        bucket_uuid,
+        Some(user_uuid),
+        Some(target_uuid),
+        AuditAction::MemberBanned,
+        diff(None, Some(&ban)),
+        &conn,
+    )?;
+    Ok(ban)
+}
+
+/// Gets a page of the bucket's bans, the most recent first unless the request says otherwise.
+///
+/// The cursor of a page of bans is the uuid of the last banned user on the page before it.
+fn get_bans_handler(
+    bucket_uuid: Uuid,
+    page: PageQuery<RecencySort>,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Page<Ban>, Error> {
+    info!("get_bans_handler");
+    ensure_can_kick(user_uuid, bucket_uuid, &conn)?;
+    let pagination = pagination(page, RecencySort::Newest)?;
+    conn.get_bans_for_bucket(bucket_uuid, pagination)
+        .map_err(Error::from)
+}
+
+/// Lifts the user's ban, so they can join the bucket again.
+fn lift_ban_handler(
+    bucket_uuid: Uuid,
+    target_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Ban, Error> {
+    info!("lift_ban_handler");
+    ensure_can_kick(user_uuid, bucket_uuid, &conn)?;
+    let ban = conn.lift_ban(bucket_uuid, target_uuid)?;
+    record_audit_event(
+        bucket_uuid,
+        Some(user_uuid),
+        Some(target_uuid),
+        AuditAction::BanLifted,
+        diff(Some(&ban), None),
+        &conn,
+    )?;
+    Ok(ban)
+}
@@ -1,27 +1,48 @@
 use crate::{
+    api::{
+        audit::{diff, record_audit_event},
+        ban::ensure_not_banned,
+    },
     error::Error,
-    server_auth::user_filter,
-    state::State,
-    util::{json_or_reject, sized_body_json},
+    server_auth::{optional_user_filter, user_filter},
+    state::{hub::Hub, State},
+    util::{json_or_reject, pagination, sized_body_json},
 };
 use db::{
-    bucket::db_types::{Bucket, BucketFlagChangeset, NewBucket},
+    answer::db_types::Answer,
+    audit::db_types::AuditAction,
+    bucket::db_types::{Bucket, BucketFlagChangeset, BucketNameChangeset, NewBucket},
     bucket_user_relation::db_types::{
         BucketUserPermissions, BucketUserPermissionsChangeset, BucketUserRelation,
-        NewBucketUserRelation,
+        NewBucketUserRelation, NewPermissionGrant,
     },
+    page::{Page, Pagination},
+    presence::db_types::{NewPresence, Presence},
+    turn::db_types::{NewTurn, Turn},
     user::db_types::User,
     BoxedRepository,
 };
+use authorization::{JwtPayload, Secret};
+use chrono::{Duration, NaiveDateTime, Utc};
 use diesel::result::DatabaseErrorKind;
 use log::info;
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use warp::{filters::BoxedFilter, path, Filter, Reply};
+use warp::{filters::BoxedFilter, path, query, Filter, Reply};
 
 pub const BUCKET_PATH: &str = "bucket";
 
-use wire::bucket::{ChangeBucketFlagsRequest, UserUuidQueryParam, SetPermissionsRequest, NewBucketRequest};
-use wire::bucket_user_relation::UserAndPermissions;
+/// How long a user is considered to be present in a bucket after their last heartbeat.
+const PRESENCE_TIMEOUT_SECONDS: i64 = 60;
+
+/// How long the owner has to confirm the deletion of a bucket.
+const DELETION_TOKEN_LIFETIME_MINUTES: i64 = 5;
+
+use wire::bucket::{ChangeBucketFlagsRequest, UserUuidQueryParam, SetPermissionsRequest, NewBucketRequest, SetRoleRequest, TransferOwnershipRequest, RenameBucketRequest, BucketDeletionToken, DeleteBucketQueryParam, BucketSort};
+use wire::bucket_user_relation::{BucketRole, MemberSort, UserAndPermissions};
+use wire::live::BucketEvent;
+use wire::page::{PageQuery, RecencySort};
 
 pub fn bucket_api(state: &State) -> BoxedFilter<(impl Reply,)> {
     //impl Filter<Extract=(impl Reply,), Error=Rejection> + Clone{
@@ -55,9 +76,41 @@ pub fn bucket_api(state: &State) -> BoxedFilter<(impl Reply,)> {
         .map(get_bucket_by_uuid_handler)
         .and_then(json_or_reject);
 
+    let rename_bucket = path!(Uuid)
+        .and(warp::path::end())
+        .and(warp::patch())
+        .and(sized_body_json(1))
+        .and(user_filter(state))
+        .and(state.hub())
+        .and(state.db())
+        .map(rename_bucket_handler)
+        .and_then(json_or_reject);
+
+    // The owner has to get a token before they can delete the bucket.
+    let get_deletion_token = path!(Uuid / "deletion_token")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(user_filter(state))
+        .and(state.secret())
+        .and(state.db())
+        .map(get_deletion_token_handler)
+        .and_then(json_or_reject);
+
+    let delete_bucket = path!(Uuid)
+        .and(warp::path::end())
+        .and(warp::delete2())
+        .and(user_filter(state))
+        .and(warp::query())
+        .and(state.secret())
+        .and(state.hub())
+        .and(state.db())
+        .map(delete_bucket_handler)
+        .and_then(json_or_reject);
+
     let get_buckets_user_is_in = path("in")
         .and(warp::path::end())
         .and(warp::get2())
+        .and(query())
         .and(user_filter(state))
         .and(state.db())
         .map(get_buckets_user_is_in_handler)
@@ -66,6 +119,7 @@ pub fn bucket_api(state: &State) -> BoxedFilter<(impl Reply,)> {
     let get_public_buckets = path!("public")
         .and(warp::path::end())
         .and(warp::get2())
+        .and(query())
         .and(state.db())
         .map(get_public_buckets_handler)
         .and_then(json_or_reject);
@@ -75,6 +129,7 @@ pub fn bucket_api(state: &State) -> BoxedFilter<(impl Reply,)> {
         .and(warp::path::end())
         .and(warp::post2())
         .and(user_filter(state))
+        .and(state.hub())
         .and(state.db())
         .map(add_self_to_bucket_handler)
         .and_then(json_or_reject);
@@ -84,6 +139,7 @@ pub fn bucket_api(state: &State) -> BoxedFilter<(impl Reply,)> {
         .and(warp::delete2())
         .and(user_filter(state))
         .and(warp::query())
+        .and(state.hub())
         .and(state.db())
         .map(remove_user_from_bucket_handler)
         .and_then(json_or_reject);
@@ -102,15 +158,37 @@ pub fn bucket_api(state: &State) -> BoxedFilter<(impl Reply,)> {
         .and(warp::put2())
         .and(sized_body_json(2))
         .and(user_filter(state))
+        .and(state.hub())
         .and(state.db())
         .map(set_permissions_handler)
         .and_then(json_or_reject);
 
+    let set_role = path!(Uuid / "user" / "role")
+        .and(warp::path::end())
+        .and(warp::put2())
+        .and(sized_body_json(1))
+        .and(user_filter(state))
+        .and(state.hub())
+        .and(state.db())
+        .map(set_role_handler)
+        .and_then(json_or_reject);
+
+    let transfer_ownership = path!(Uuid / "owner")
+        .and(warp::path::end())
+        .and(warp::put2())
+        .and(sized_body_json(1))
+        .and(user_filter(state))
+        .and(state.hub())
+        .and(state.db())
+        .map(transfer_ownership_handler)
+        .and_then(json_or_reject);
+
     let set_bucket_flags = path!(Uuid)
         .and(warp::path::end())
         .and(warp::put2())
         .and(sized_body_json(1))
         .and(user_filter(state))
+        .and(state.hub())
         .and(state.db())
         .map(set_bucket_flags_handler)
         .and_then(json_or_reject);
@@ -118,6 +196,7 @@ pub fn bucket_api(state: &State) -> BoxedFilter<(impl Reply,)> {
     let get_users_in_bucket = path!(Uuid / "users")
         .and(warp::path::end())
         .and(warp::get2())
+        .and(query())
         .and(state.db())
         .map(get_users_in_bucket_handler)
         .and_then(json_or_reject);
@@ -126,23 +205,77 @@ pub fn bucket_api(state: &State) -> BoxedFilter<(impl Reply,)> {
     let get_users_and_permissions_in_bucket = path!(Uuid / "all_user_permissions")
         .and(warp::path::end())
         .and(warp::get2())
+        .and(query())
         .and(state.db())
         .map(get_all_user_permissions_for_bucket_handler)
         .and_then(json_or_reject);
 
+    let get_turn = path!(Uuid / "turn")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .map(get_turn_handler)
+        .and_then(json_or_reject);
+
+    // Skips the current turn, moving the rotation along without an answer being given.
+    let skip_turn = path!(Uuid / "turn")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(skip_turn_handler)
+        .and_then(json_or_reject);
+
+    let heartbeat = path!(Uuid / "heartbeat")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(heartbeat_handler)
+        .and_then(json_or_reject);
+
+    let get_present_users = path!(Uuid / "present")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .map(get_present_users_handler)
+        .and_then(json_or_reject);
+
+    let get_answers_in_bucket = path!(Uuid / "answers")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .map(get_answers_in_bucket_handler)
+        .and_then(json_or_reject);
+
     path(BUCKET_PATH)
         .and(
             create_bucket
                 .or(get_bucket_by_uuid)
+                .or(rename_bucket)
+                .or(get_deletion_token)
+                .or(delete_bucket)
                 .or(get_buckets_user_is_in)
                 .or(get_public_buckets)
                 .or(add_self_to_bucket)
                 .or(remove_user_from_bucket)
                 .or(get_permissions_for_self)
                 .or(set_permissions)
+                .or(set_role)
+                .or(transfer_ownership)
                 .or(set_bucket_flags)
                 .or(get_users_in_bucket)
                 .or(get_users_and_permissions_in_bucket)
+                .or(get_turn)
+                .or(skip_turn)
+                .or(heartbeat)
+                .or(get_present_users)
+                .or(get_answers_in_bucket)
                 .or(get_bucket),
         )
         .boxed()
@@ -155,12 +288,49 @@ fn create_bucket_handler(
 ) -> Result<Bucket, Error> {
     info!("create_bucket_handler");
 
-    fn bucket_already_exists(
-        slug: &String,
-        conn: &BoxedRepository,
-    ) -> Result<bool, diesel::result::Error> {
+    let new_bucket = NewBucket {
+        bucket_slug: unused_slug(&request.bucket_name, None, &conn)?,
+        bucket_name: request.bucket_name,
+    };
+
+    let bucket = conn.create_bucket(new_bucket)?;
+    // By default, the user has full permissions when they create a bucket.
+    let new_relation = NewBucketUserRelation {
+        user_uuid,
+        bucket_uuid: bucket.uuid,
+        set_public_permission: true,
+        set_drawing_permission: true,
+        set_exclusive_permission: true,
+        kick_permission: true,
+        grant_permissions_permission: true,
+        moderate_questions_permission: true,
+        delete_questions_permission: true,
+        invite_permission: true,
+    };
+    conn.add_user_to_bucket(new_relation)?;
+    let bucket = conn.set_bucket_owner(bucket.uuid, user_uuid)?;
+    record_audit_event(
+        bucket.uuid,
+        Some(user_uuid),
+        None,
+        AuditAction::BucketCreated,
+        diff(None, Some(&bucket)),
+        &conn,
+    )?;
+    Ok(bucket)
+}
+
+/// Makes a slug out of the bucket name that no other bucket uses, or has used.
+///
+/// When renaming a bucket, the slugs that the bucket itself has used are available to it.
+pub fn unused_slug(
+    bucket_name: &str,
+    bucket_uuid: Option<Uuid>,
+    conn: &BoxedRepository,
+) -> Result<String, Error> {
+    let slug_is_taken = |slug: &String| -> Result<bool, diesel::result::Error> {
         conn.get_bucket_by_slug(slug.clone())
-            .map(|_| true)
+            .map(|bucket| Some(bucket.uuid) != bucket_uuid)
             .or_else(|e| {
                 if let diesel::result::Error::NotFound = e {
                     Ok(false)
@@ -168,33 +338,132 @@ fn create_bucket_handler(
                     Err(e)
                 }
             })
-    }
-    let slug = slug::slugify(&request.bucket_name);
+    };
+    let slug = slug::slugify(bucket_name);
     let mut candidate_slug = slug.clone();
     let mut id = 0;
 
-    while bucket_already_exists(&candidate_slug, &conn)? {
+    while slug_is_taken(&candidate_slug)? {
         candidate_slug = format!("{}-{}", slug, id);
         id += 1;
     }
+    Ok(candidate_slug)
+}
 
-    let new_bucket = NewBucket {
+/// Changes the bucket's name, and its slug if asked to.
+///
+/// Only the owner can rename the bucket.
+fn rename_bucket_handler(
+    bucket_uuid: Uuid,
+    request: RenameBucketRequest,
+    user_uuid: Uuid,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<Bucket, Error> {
+    info!("rename_bucket_handler");
+    let old_bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    if old_bucket.owner_uuid != Some(user_uuid) {
+        return Err(Error::not_authorized("Only the owner can rename the bucket."));
+    }
+    if request.bucket_name.trim().is_empty() {
+        return Err(Error::BadRequest(
+            "The bucket's name can't be empty.".to_string(),
+        ));
+    }
+
+    let bucket_slug = if request.regenerate_slug {
+        Some(unused_slug(&request.bucket_name, Some(bucket_uuid), &conn)?)
+    } else {
+        None
+    };
+    let changeset = BucketNameChangeset {
+        uuid: bucket_uuid,
         bucket_name: request.bucket_name,
-        bucket_slug: candidate_slug,
+        bucket_slug,
     };
+    let bucket = conn.rename_bucket(changeset)?;
+    record_audit_event(
+        bucket_uuid,
+        Some(user_uuid),
+        None,
+        AuditAction::BucketRenamed,
+        diff(Some(&old_bucket), Some(&bucket)),
+        &conn,
+    )?;
+    let event = BucketEvent::BucketRenamed {
+        bucket: bucket.clone().into(),
+    };
+    hub.publish(bucket_uuid, event);
+    Ok(bucket)
+}
 
-    let bucket = conn.create_bucket(new_bucket)?;
-    // By default, the user has full permissions when they create a bucket.
-    let new_relation = NewBucketUserRelation {
+/// What a deletion token confirms.
+#[derive(Serialize, Deserialize)]
+struct BucketDeletion {
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+}
+
+/// Gets a token that confirms that the owner wants to delete the bucket.
+fn get_deletion_token_handler(
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+    secret: Secret,
+    conn: BoxedRepository,
+) -> Result<BucketDeletionToken, Error> {
+    info!("get_deletion_token_handler");
+    let bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    if bucket.owner_uuid != Some(user_uuid) {
+        return Err(Error::not_authorized("Only the owner can delete the bucket."));
+    }
+
+    let subject = BucketDeletion {
+        bucket_uuid,
         user_uuid,
-        bucket_uuid: bucket.uuid,
-        set_public_permission: true,
-        set_drawing_permission: true,
-        set_exclusive_permission: true,
-        kick_permission: true,
-        grant_permissions_permission: true,
     };
-    conn.add_user_to_bucket(new_relation)?;
+    let payload = JwtPayload::new(subject, Duration::minutes(DELETION_TOKEN_LIFETIME_MINUTES));
+    let token = payload
+        .encode_jwt_string(&secret)
+        .map_err(|_| Error::internal_server_error("Could not create a deletion token."))?;
+    Ok(BucketDeletionToken {
+        token,
+        expires_at: payload.exp,
+    })
+}
+
+/// Deletes the bucket, along with everything in it.
+///
+/// Only the owner can delete the bucket, and they must send a token gotten from `get_deletion_token_handler`.
+fn delete_bucket_handler(
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+    query: DeleteBucketQueryParam,
+    secret: Secret,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<Bucket, Error> {
+    info!("delete_bucket_handler");
+    let bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    if bucket.owner_uuid != Some(user_uuid) {
+        return Err(Error::not_authorized("Only the owner can delete the bucket."));
+    }
+
+    let confirmed = JwtPayload::<BucketDeletion>::decode_jwt_string(&query.token, &secret)
+        .and_then(|payload| payload.validate_dates())
+        .map(|payload| {
+            let deletion = payload.subject();
+            deletion.bucket_uuid == bucket_uuid && deletion.user_uuid == user_uuid
+        })
+        .unwrap_or(false);
+    if !confirmed {
+        return Err(Error::PreconditionNotMet(
+            "The deletion token is invalid or has expired.".to_string(),
+        ));
+    }
+
+    let bucket = conn.delete_bucket(bucket_uuid)?;
+    hub.publish(bucket_uuid, BucketEvent::BucketDeleted);
+    hub.remove_bucket(bucket_uuid);
     Ok(bucket)
 }
 
@@ -203,6 +472,7 @@ fn create_bucket_handler(
 fn add_self_to_bucket_handler(
     bucket_uuid: Uuid,
     user_uuid: Uuid,
+    hub: Hub,
     conn: BoxedRepository,
 ) -> Result<BucketUserRelation, Error> {
     let bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
@@ -211,6 +481,12 @@ fn add_self_to_bucket_handler(
             "Bucket is set to exclusive. Users are not allowed to join.".to_string(),
         ));
     }
+    if bucket.is_template {
+        return Err(Error::PreconditionNotMet(
+            "Templates can't be joined. Make a bucket from the template instead.".to_string(),
+        ));
+    }
+    ensure_not_banned(bucket_uuid, user_uuid, &conn)?;
 
     info!("add_self_to_bucket_handler");
     // By default, users don't have any permissions.
@@ -222,8 +498,11 @@ fn add_self_to_bucket_handler(
         set_exclusive_permission: false,
         kick_permission: false,
         grant_permissions_permission: false,
+        moderate_questions_permission: false,
+        delete_questions_permission: false,
+        invite_permission: false,
     };
-    conn.add_user_to_bucket(new_relation).map_err(|e| {
+    let relation = conn.add_user_to_bucket(new_relation).map_err(|e| {
         if let diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) = e {
             Error::PreconditionNotMet(
                 "There is already a relation between this user and the bucket".to_string(),
@@ -231,7 +510,17 @@ fn add_self_to_bucket_handler(
         } else {
             Error::from(e)
         }
-    })
+    })?;
+    record_audit_event(
+        bucket_uuid,
+        Some(user_uuid),
+        Some(user_uuid),
+        AuditAction::MemberJoined,
+        diff(None, Some(&relation)),
+        &conn,
+    )?;
+    hub.publish(bucket_uuid, BucketEvent::MemberJoined { user_uuid });
+    Ok(relation)
 }
 
 /// Won't reject a request, but will just drop requests to change settings that the user isn't authorized to do.
@@ -239,13 +528,14 @@ fn set_bucket_flags_handler(
     bucket_uuid: Uuid,
     request: ChangeBucketFlagsRequest,
     user_uuid: Uuid,
+    hub: Hub,
     conn: BoxedRepository,
 ) -> Result<Bucket, Error> {
     info!("set_bucket_flags_handler");
     let permissions_for_acting_user = conn
         .get_permissions(user_uuid, bucket_uuid)
         .map_err(Error::from)?;
-    fn verify_permission(permission: bool, flag: Option<bool>) -> Option<bool> {
+    fn verify_permission<T>(permission: bool, flag: Option<T>) -> Option<T> {
         if permission {
             flag
         } else {
@@ -266,14 +556,37 @@ fn set_bucket_flags_handler(
             permissions_for_acting_user.set_exclusive_permission,
             request.exclusive,
         ),
+        draw_strategy: verify_permission(
+            permissions_for_acting_user.set_drawing_permission,
+            request.draw_strategy,
+        ),
+        duplicate_policy: verify_permission(
+            permissions_for_acting_user.moderate_questions_permission,
+            request.duplicate_policy,
+        ),
+    };
+    let old_bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    let bucket = conn.change_bucket_flags(changeset)?;
+    record_audit_event(
+        bucket_uuid,
+        Some(user_uuid),
+        None,
+        AuditAction::FlagsChanged,
+        diff(Some(&old_bucket), Some(&bucket)),
+        &conn,
+    )?;
+    let event = BucketEvent::FlagsChanged {
+        bucket: bucket.clone().into(),
     };
-    conn.change_bucket_flags(changeset).map_err(Error::from)
+    hub.publish(bucket_uuid, event);
+    Ok(bucket)
 }
 
 fn remove_user_from_bucket_handler(
     bucket_uuid: Uuid,
     account_user_uuid: Uuid,
     target_user_uuid: UserUuidQueryParam,
+    hub: Hub,
     db: BoxedRepository,
 ) -> Result<BucketUserRelation, Error> {
     info!("remove_user_from_bucket_handler");
@@ -282,8 +595,31 @@ fn remove_user_from_bucket_handler(
 
     // Does user have permission to remove user
     if relation.kick_permission || account_user_uuid == target_user_uuid.user_uuid {
-        db.remove_user_from_bucket(target_user_uuid.user_uuid, bucket_uuid)
-            .map_err(Error::from)
+        let bucket = db.get_bucket_by_uuid(bucket_uuid)?;
+        if bucket.owner_uuid == Some(target_user_uuid.user_uuid) {
+            return Err(Error::PreconditionNotMet(
+                "The owner can't leave the bucket until they transfer ownership.".to_string(),
+            ));
+        }
+        let target_permissions = db.get_permissions(target_user_uuid.user_uuid, bucket_uuid)?;
+        if target_permissions.grant_permissions_permission {
+            ensure_other_granter_remains(bucket_uuid, target_user_uuid.user_uuid, &db)?;
+        }
+
+        let relation = db.remove_user_from_bucket(target_user_uuid.user_uuid, bucket_uuid)?;
+        record_audit_event(
+            bucket_uuid,
+            Some(account_user_uuid),
+            Some(relation.user_uuid),
+            AuditAction::MemberRemoved,
+            diff(Some(&relation), None),
+            &db,
+        )?;
+        let event = BucketEvent::MemberKicked {
+            user_uuid: relation.user_uuid,
+        };
+        hub.publish(bucket_uuid, event);
+        Ok(relation)
     } else {
         Err(Error::PreconditionNotMet(
             "User does not have permission to remove another user from this bucket.".to_string(),
@@ -291,62 +627,363 @@ fn remove_user_from_bucket_handler(
     }
 }
 
+/// Gets a page of the users in the bucket, in the order they joined unless the request says otherwise.
 fn get_users_in_bucket_handler(
     bucket_uuid: Uuid,
+    page: PageQuery<MemberSort>,
     conn: BoxedRepository,
-) -> Result<Vec<User>, Error> {
+) -> Result<Page<User>, Error> {
     info!("get_users_in_bucket_handler");
-    conn.get_users_in_bucket(bucket_uuid).map_err(Error::from)
+    let pagination = pagination(page, MemberSort::Joined)?;
+    conn.get_users_in_bucket(bucket_uuid, pagination)
+        .map_err(Error::from)
 }
 
+/// Gets a page of the users in the bucket along with their permissions,
+/// in the order they joined unless the request says otherwise.
 fn get_all_user_permissions_for_bucket_handler(
     bucket_uuid: Uuid,
+    page: PageQuery<MemberSort>,
     conn: BoxedRepository
-) -> Result<Vec<UserAndPermissions>, Error> {
+) -> Result<Page<UserAndPermissions>, Error> {
     info!("get_all_user_permissions_for_bucket_handler");
-    conn.get_permissions_all_users_in_bucket(bucket_uuid)
-        .map(|list| {
-            list.into_iter()
-                .map(|(permissions, user)|{
-                    UserAndPermissions {
-                        user: user.into(),
-                        permissions: permissions.into()
-                    }
-                })
-                .collect()
+    let pagination = pagination(page, MemberSort::Joined)?;
+    conn.get_permissions_of_users_in_bucket(bucket_uuid, pagination)
+        .map(|page| {
+            page.map(|(permissions, user)|{
+                UserAndPermissions {
+                    user: user.into(),
+                    permissions: permissions.into()
+                }
+            })
         } )
         .map_err(Error::from)
 }
 
+/// Gets a page of the users that have been in the bucket recently,
+/// in the order they joined unless the request says otherwise.
+///
+/// Only users who can see the bucket can see who is in it.
+fn get_present_users_handler(
+    bucket_uuid: Uuid,
+    page: PageQuery<MemberSort>,
+    user_uuid: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<Page<User>, Error> {
+    info!("get_present_users_handler");
+    ensure_can_view_bucket(bucket_uuid, user_uuid, &conn)?;
+    let pagination = pagination(page, MemberSort::Joined)?;
+    conn.get_present_users(bucket_uuid, present_since(), pagination)
+        .map_err(Error::from)
+}
+
 fn set_permissions_handler(
     bucket_uuid: Uuid,
     permissions_request: SetPermissionsRequest,
     user_uuid: Uuid,
+    hub: Hub,
     conn: BoxedRepository,
 ) -> Result<BucketUserRelation, Error> {
     info!("set_permissions_handler");
     let permissions_for_acting_user = conn
         .get_permissions(user_uuid, bucket_uuid)
         .map_err(Error::from)?;
-    if permissions_for_acting_user.grant_permissions_permission {
-        let permissions_changeset = BucketUserPermissionsChangeset {
-            user_uuid,
+    if !permissions_for_acting_user.grant_permissions_permission {
+        return Err(Error::not_authorized(
+            "User does not have privileges to set permissions for other users.",
+        ));
+    }
+
+    let permissions_changeset = BucketUserPermissionsChangeset {
+        user_uuid: permissions_request.target_user_uuid,
+        bucket_uuid,
+        set_public_permission: permissions_request.set_public_permission,
+        set_drawing_permission: permissions_request.set_drawing_permission,
+        set_exclusive_permission: permissions_request.set_exclusive_permission,
+        kick_permission: permissions_request.kick_permission,
+        grant_permissions_permission: permissions_request.grant_permissions_permission,
+        moderate_questions_permission: permissions_request.moderate_questions_permission,
+        delete_questions_permission: permissions_request.delete_questions_permission,
+        invite_permission: permissions_request.invite_permission,
+    };
+    let granted = BucketUserPermissions {
+        set_public_permission: permissions_changeset.set_public_permission == Some(true),
+        set_drawing_permission: permissions_changeset.set_drawing_permission == Some(true),
+        set_exclusive_permission: permissions_changeset.set_exclusive_permission == Some(true),
+        kick_permission: permissions_changeset.kick_permission == Some(true),
+        grant_permissions_permission: permissions_changeset.grant_permissions_permission
+            == Some(true),
+        moderate_questions_permission: permissions_changeset.moderate_questions_permission
+            == Some(true),
+        delete_questions_permission: permissions_changeset.delete_questions_permission
+            == Some(true),
+        invite_permission: permissions_changeset.invite_permission == Some(true),
+    };
+    if !permissions_for_acting_user.includes(&granted) {
+        return Err(Error::not_authorized(
+            "User can't grant permissions they don't have.",
+        ));
+    }
+
+    apply_permissions_changeset(
+        permissions_changeset,
+        user_uuid,
+        AuditAction::PermissionsChanged,
+        &hub,
+        &conn,
+    )
+}
+
+/// Gives the user every permission of the role at once.
+///
+/// Ownership can't be given this way, it has to be transferred by the owner.
+fn set_role_handler(
+    bucket_uuid: Uuid,
+    request: SetRoleRequest,
+    user_uuid: Uuid,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<BucketUserRelation, Error> {
+    info!("set_role_handler");
+    let permissions_for_acting_user = conn.get_permissions(user_uuid, bucket_uuid)?;
+    if !permissions_for_acting_user.grant_permissions_permission {
+        return Err(Error::not_authorized(
+            "User does not have privileges to set permissions for other users.",
+        ));
+    }
+    if request.role == BucketRole::Owner {
+        return Err(Error::PreconditionNotMet(
+            "Ownership can only be given by transferring it.".to_string(),
+        ));
+    }
+    let role_permissions: BucketUserPermissions = request.role.permissions().into();
+    if !permissions_for_acting_user.includes(&role_permissions) {
+        return Err(Error::not_authorized(
+            "User can't give out a role with permissions they don't have.",
+        ));
+    }
+
+    let permissions_changeset = BucketUserPermissionsChangeset {
+        user_uuid: request.target_user_uuid,
+        bucket_uuid,
+        set_public_permission: Some(role_permissions.set_public_permission),
+        set_drawing_permission: Some(role_permissions.set_drawing_permission),
+        set_exclusive_permission: Some(role_permissions.set_exclusive_permission),
+        kick_permission: Some(role_permissions.kick_permission),
+        grant_permissions_permission: Some(role_permissions.grant_permissions_permission),
+        moderate_questions_permission: Some(role_permissions.moderate_questions_permission),
+        delete_questions_permission: Some(role_permissions.delete_questions_permission),
+        invite_permission: Some(role_permissions.invite_permission),
+    };
+    apply_permissions_changeset(
+        permissions_changeset,
+        user_uuid,
+        AuditAction::RoleSet,
+        &hub,
+        &conn,
+    )
+}
+
+/// Makes another member the owner of the bucket.
+/// The previous owner keeps their permissions, but can now have them revoked.
+fn transfer_ownership_handler(
+    bucket_uuid: Uuid,
+    request: TransferOwnershipRequest,
+    user_uuid: Uuid,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<Bucket, Error> {
+    info!("transfer_ownership_handler");
+    let old_bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    if old_bucket.owner_uuid != Some(user_uuid) {
+        return Err(Error::not_authorized(
+            "Only the owner can transfer ownership of the bucket.",
+        ));
+    }
+
+    let not_a_member = |e: diesel::result::Error| match e {
+        diesel::result::Error::NotFound => Error::PreconditionNotMet(
+            "The new owner must be a member of the bucket.".to_string(),
+        ),
+        other => Error::from(other),
+    };
+    let before = conn
+        .get_permissions(request.new_owner_uuid, bucket_uuid)
+        .map_err(not_a_member)?;
+    let bucket = conn
+        .set_bucket_owner(bucket_uuid, request.new_owner_uuid)
+        .map_err(not_a_member)?;
+
+    let new_owner = conn.get_user_bucket_relation(request.new_owner_uuid, bucket_uuid)?;
+    let after = conn.get_permissions(request.new_owner_uuid, bucket_uuid)?;
+    record_permission_changes(
+        bucket_uuid,
+        Some(user_uuid),
+        request.new_owner_uuid,
+        &before,
+        &after,
+        &conn,
+    )?;
+    record_audit_event(
+        bucket_uuid,
+        Some(user_uuid),
+        Some(request.new_owner_uuid),
+        AuditAction::OwnershipTransferred,
+        diff(Some(&old_bucket), Some(&bucket)),
+        &conn,
+    )?;
+    hub.publish(
+        bucket_uuid,
+        BucketEvent::PermissionsChanged {
+            user_uuid: new_owner.user_uuid,
+            permissions: new_owner.into(),
+        },
+    );
+    hub.publish(
+        bucket_uuid,
+        BucketEvent::FlagsChanged {
+            bucket: bucket.clone().into(),
+        },
+    );
+    Ok(bucket)
+}
+
+/// Sets the permissions, records each one that was granted or revoked, and tells the bucket about it.
+fn apply_permissions_changeset(
+    changeset: BucketUserPermissionsChangeset,
+    granter_uuid: Uuid,
+    action: AuditAction,
+    hub: &Hub,
+    conn: &BoxedRepository,
+) -> Result<BucketUserRelation, Error> {
+    let before = conn.get_permissions(changeset.user_uuid, changeset.bucket_uuid)?;
+    protect_bucket_invariants(&changeset, conn)?;
+    let relation = conn.set_permissions(changeset)?;
+    let after = conn.get_permissions(changeset.user_uuid, changeset.bucket_uuid)?;
+    record_permission_changes(
+        changeset.bucket_uuid,
+        Some(granter_uuid),
+        changeset.user_uuid,
+        &before,
+        &after,
+        conn,
+    )?;
+    record_audit_event(
+        changeset.bucket_uuid,
+        Some(granter_uuid),
+        Some(changeset.user_uuid),
+        action,
+        diff(Some(&before), Some(&after)),
+        conn,
+    )?;
+    let event = BucketEvent::PermissionsChanged {
+        user_uuid: relation.user_uuid,
+        permissions: relation.into(),
+    };
+    hub.publish(changeset.bucket_uuid, event);
+    Ok(relation)
+}
+
+/// Records every permission of the target that differs from what they had before.
+pub fn record_permission_changes(
+    bucket_uuid: Uuid,
+    granter_uuid: Option<Uuid>,
+    target_uuid: Uuid,
+    before: &BucketUserPermissions,
+    after: &BucketUserPermissions,
+    conn: &BoxedRepository,
+) -> Result<(), Error> {
+    let grants = before
+        .changes_to(after)
+        .into_iter()
+        .map(|(permission, granted)| NewPermissionGrant {
             bucket_uuid,
-            set_public_permission: permissions_request.set_public_permission,
-            set_drawing_permission: permissions_request.set_drawing_permission,
-            set_exclusive_permission: permissions_request.set_exclusive_permission,
-            kick_permission: permissions_request.kick_permission,
-            grant_permissions_permission: permissions_request.grant_permissions_permission,
-        };
-        conn.set_permissions(permissions_changeset)
-            .map_err(Error::from)
+            granter_uuid,
+            target_uuid,
+            permission: permission.to_string(),
+            granted,
+        })
+        .collect::<Vec<_>>();
+    if !grants.is_empty() {
+        conn.record_permission_grants(grants)?;
+    }
+    Ok(())
+}
+
+/// Rejects permission changes that would leave the bucket without anyone who can manage it.
+///
+/// The owner's permissions can't be revoked,
+/// and the last member who can grant permissions can't lose that ability.
+fn protect_bucket_invariants(
+    changeset: &BucketUserPermissionsChangeset,
+    conn: &BoxedRepository,
+) -> Result<(), Error> {
+    let bucket = conn.get_bucket_by_uuid(changeset.bucket_uuid)?;
+    let revokes_any = [
+        changeset.set_public_permission,
+        changeset.set_drawing_permission,
+        changeset.set_exclusive_permission,
+        changeset.kick_permission,
+        changeset.grant_permissions_permission,
+        changeset.moderate_questions_permission,
+        changeset.delete_questions_permission,
+        changeset.invite_permission,
+    ]
+    .iter()
+    .any(|permission| *permission == Some(false));
+
+    if bucket.owner_uuid == Some(changeset.user_uuid) && revokes_any {
+        return Err(Error::PreconditionNotMet(
+            "The owner's permissions can't be revoked.".to_string(),
+        ));
+    }
+    if changeset.grant_permissions_permission == Some(false) {
+        ensure_other_granter_remains(changeset.bucket_uuid, changeset.user_uuid, conn)?;
+    }
+    Ok(())
+}
+
+/// Ensures that someone other than the user will still be able to grant permissions in the bucket.
+pub fn ensure_other_granter_remains(
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: &BoxedRepository,
+) -> Result<(), Error> {
+    let other_granter_exists = conn
+        .get_permissions_all_users_in_bucket(bucket_uuid)?
+        .into_iter()
+        .any(|(permissions, user)| {
+            user.uuid != user_uuid && permissions.grant_permissions_permission
+        });
+    if other_granter_exists {
+        Ok(())
     } else {
-        Err(Error::not_authorized(
-            "User does not have privileges to set permissions for other users.",
+        Err(Error::PreconditionNotMet(
+            "The bucket must keep at least one member who can grant permissions.".to_string(),
         ))
     }
 }
 
+/// Rejects the request if the user can't see the bucket.
+///
+/// Anyone can see a public bucket, but only its members can see any other bucket.
+pub fn ensure_can_view_bucket(
+    bucket_uuid: Uuid,
+    user_uuid: Option<Uuid>,
+    conn: &BoxedRepository,
+) -> Result<(), Error> {
+    let bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    if bucket.public_viewable {
+        return Ok(());
+    }
+    let reason = "Only members can see a bucket that isn't public.";
+    match user_uuid.map(|user_uuid| conn.get_permissions(user_uuid, bucket_uuid)) {
+        Some(Ok(_)) => Ok(()),
+        None | Some(Err(diesel::result::Error::NotFound)) => Err(Error::not_authorized(reason)),
+        Some(Err(e)) => Err(Error::from(e)),
+    }
+}
+
 fn get_permissions_for_self_handler(
     bucket_uuid: Uuid,
     user_uuid: Uuid,
@@ -357,17 +994,26 @@ fn get_permissions_for_self_handler(
         .map_err(Error::from)
 }
 
-fn get_public_buckets_handler(conn: BoxedRepository) -> Result<Vec<Bucket>, Error> {
+/// Gets a page of the public buckets, the least recently updated first unless the request says otherwise.
+fn get_public_buckets_handler(
+    page: PageQuery<BucketSort>,
+    conn: BoxedRepository,
+) -> Result<Page<Bucket>, Error> {
     info!("get_public_buckets_handler");
-    conn.get_publicly_visible_buckets().map_err(Error::from)
+    let pagination = pagination(page, BucketSort::Updated)?;
+    conn.get_publicly_visible_buckets(pagination)
+        .map_err(Error::from)
 }
 
+/// Gets a page of the buckets the user is in, the least recently updated first unless the request says otherwise.
 fn get_buckets_user_is_in_handler(
+    page: PageQuery<BucketSort>,
     user_uuid: Uuid,
     conn: BoxedRepository,
-) -> Result<Vec<Bucket>, Error> {
+) -> Result<Page<Bucket>, Error> {
     info!("get_buckets_user_is_in_handler");
-    conn.get_buckets_user_is_a_part_of(user_uuid)
+    let pagination = pagination(page, BucketSort::Updated)?;
+    conn.get_buckets_user_is_a_part_of(user_uuid, pagination)
         .map_err(Error::from)
 }
 
@@ -376,13 +1022,162 @@ fn get_bucket_by_uuid_handler(uuid: Uuid, conn: BoxedRepository) -> Result<Bucke
     conn.get_bucket_by_uuid(uuid).map_err(Error::from)
 }
 
+/// Gets whose turn it is to draw a question from the bucket.
+///
+/// Only those who can see the bucket can see whose turn it is.
+fn get_turn_handler(
+    bucket_uuid: Uuid,
+    user_uuid: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<Turn, Error> {
+    info!("get_turn_handler");
+    ensure_can_view_bucket(bucket_uuid, user_uuid, &conn)?;
+    conn.get_turn(bucket_uuid).map_err(Error::from)
+}
+
+/// Gets a page of the answers given in the bucket, the most recent first unless the request says otherwise.
+///
+/// Private answers are only included for the user who gave them.
+fn get_answers_in_bucket_handler(
+    bucket_uuid: Uuid,
+    page: PageQuery<RecencySort>,
+    user_uuid: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<Page<Answer>, Error> {
+    info!("get_answers_in_bucket_handler");
+    ensure_can_view_bucket(bucket_uuid, user_uuid, &conn)?;
+    let pagination = pagination(page, RecencySort::Newest)?;
+    conn.get_visible_answers_for_bucket(bucket_uuid, user_uuid, pagination)
+        .map_err(Error::from)
+}
+
+fn skip_turn_handler(
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Option<Turn>, Error> {
+    info!("skip_turn_handler");
+    let permissions_for_acting_user = conn
+        .get_permissions(user_uuid, bucket_uuid)
+        .map_err(Error::from)?;
+    if !permissions_for_acting_user.set_drawing_permission {
+        return Err(Error::not_authorized(
+            "User does not have privileges to change the turn for this bucket.",
+        ));
+    }
+    let old_turn = match conn.get_turn(bucket_uuid) {
+        Ok(turn) => Some(turn),
+        Err(diesel::result::Error::NotFound) => None,
+        Err(e) => return Err(Error::from(e)),
+    };
+    let turn = advance_turn(bucket_uuid, &conn)?;
+    record_audit_event(
+        bucket_uuid,
+        Some(user_uuid),
+        None,
+        AuditAction::TurnSkipped,
+        diff(old_turn.as_ref(), turn.as_ref()),
+        &conn,
+    )?;
+    Ok(turn)
+}
+
+/// Moves the bucket's rotation along to the next turn.
+///
+/// The asker role is passed along to the next user in the order they joined the bucket,
+/// and the answerer is picked at random from everyone else in the bucket.
+/// If the bucket has no users, there is no one to take a turn, and `None` is returned.
+pub fn advance_turn(bucket_uuid: Uuid, conn: &BoxedRepository) -> Result<Option<Turn>, Error> {
+    let users: Vec<User> = conn
+        .get_permissions_all_users_in_bucket(bucket_uuid)?
+        .into_iter()
+        .map(|(_, user)| user)
+        .collect();
+    if users.is_empty() {
+        return Ok(None);
+    }
+    // Dormant members are passed over,
+    // unless nobody has been seen recently, in which case everyone takes part.
+    // Present users are members, so they all fit on a page as long as the members do.
+    let present = conn
+        .get_present_users(
+            bucket_uuid,
+            present_since(),
+            Pagination::first(users.len() as i64, MemberSort::Joined.into()),
+        )?
+        .items;
+    let is_active =
+        |user: &User| present.is_empty() || present.iter().any(|p| p.uuid == user.uuid);
+
+    let start_index = match conn.get_turn(bucket_uuid) {
+        Ok(turn) => users
+            .iter()
+            .position(|user| user.uuid == turn.asker_uuid)
+            .map(|index| index + 1)
+            // The previous asker left the bucket, so start from the beginning.
+            .unwrap_or(0),
+        Err(diesel::result::Error::NotFound) => 0,
+        Err(e) => return Err(Error::from(e)),
+    };
+    let asker = users
+        .iter()
+        .cycle()
+        .skip(start_index)
+        .take(users.len())
+        .find(|user| is_active(user))
+        // Someone seen recently may have left the bucket since,
+        // in which case nobody left in it is active, so the rotation carries on as usual.
+        .unwrap_or(&users[start_index % users.len()]);
+
+    let candidates: Vec<&User> = users
+        .iter()
+        .filter(|user| user.uuid != asker.uuid && is_active(user))
+        .collect();
+    // A user alone in a bucket will have to answer their own questions.
+    let answerer = if candidates.is_empty() {
+        asker
+    } else {
+        candidates[thread_rng().gen_range(0, candidates.len())]
+    };
+
+    let new_turn = NewTurn {
+        bucket_uuid,
+        asker_uuid: asker.uuid,
+        answerer_uuid: answerer.uuid,
+    };
+    conn.set_turn(new_turn).map(Some).map_err(Error::from)
+}
+
+/// Records that the user is currently in the bucket.
+fn heartbeat_handler(
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Presence, Error> {
+    info!("heartbeat_handler");
+    // Only members can be present in a bucket.
+    conn.get_user_bucket_relation(user_uuid, bucket_uuid)?;
+    let new_presence = NewPresence {
+        bucket_uuid,
+        user_uuid,
+    };
+    conn.record_heartbeat(new_presence).map_err(Error::from)
+}
+
+/// The earliest time a heartbeat can have been received for its user to still count as present.
+fn present_since() -> NaiveDateTime {
+    Utc::now().naive_utc() - Duration::seconds(PRESENCE_TIMEOUT_SECONDS)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use db::{
         test::{
-            bucket_fixture::BucketFixture, bucket_user_relation_fixture::UserBucketRelationFixture,
-            user_fixture::UserFixture, util::execute_test,
+            bucket_fixture::BucketFixture,
+            bucket_user_relation_fixture::{UserBucketRelationFixture, NO_PERMISSIONS},
+            user_fixture::UserFixture,
+            util::execute_test,
         },
         user::db_types::NewUser,
     };
@@ -396,8 +1191,9 @@ mod tests {
             };
             let user = db.create_user(new_user).expect("Should create new user");
 
-            let relation = add_self_to_bucket_handler(fixture.bucket.uuid, user.uuid, db)
-                .expect("Should add user to bucket");
+            let relation =
+                add_self_to_bucket_handler(fixture.bucket.uuid, user.uuid, Hub::default(), db)
+                    .expect("Should add user to bucket");
             assert!(!relation.grant_permissions_permission);
             assert!(!relation.set_public_permission);
             assert!(!relation.set_exclusive_permission);
@@ -405,6 +1201,32 @@ mod tests {
         });
     }
 
+    #[test]
+    fn only_members_can_view_private_buckets() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            ensure_can_view_bucket(fixture.bucket.uuid, None, &db)
+                .expect("Anyone can view a public bucket");
+
+            let changeset = BucketFlagChangeset {
+                uuid: fixture.bucket.uuid,
+                public_viewable: Some(false),
+                drawing_enabled: None,
+                exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: None,
+            };
+            db.change_bucket_flags(changeset)
+                .expect("Should make bucket private");
+
+            ensure_can_view_bucket(fixture.bucket.uuid, None, &db)
+                .expect_err("Anonymous users can't view a private bucket");
+            ensure_can_view_bucket(fixture.bucket.uuid, Some(fixture.user2.uuid), &db)
+                .expect_err("Non-members can't view a private bucket");
+            ensure_can_view_bucket(fixture.bucket.uuid, Some(fixture.user1.uuid), &db)
+                .expect("Members can view a private bucket");
+        });
+    }
+
     #[test]
     fn set_bucket_flags() {
         execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
@@ -412,11 +1234,18 @@ mod tests {
                 publicly_visible: None,
                 drawing_enabled: None,
                 exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: None,
             };
 
-            let bucket =
-                set_bucket_flags_handler(fixture.bucket.uuid, request, fixture.user1.uuid, db)
-                    .expect("Bucket should be returned after changing flags.");
+            let bucket = set_bucket_flags_handler(
+                fixture.bucket.uuid,
+                request,
+                fixture.user1.uuid,
+                Hub::default(),
+                db,
+            )
+            .expect("Bucket should be returned after changing flags.");
             assert_eq!(fixture.bucket, bucket);
         });
     }
@@ -440,74 +1269,294 @@ mod tests {
     #[test]
     fn remove_self_from_bucket() {
         execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
-            let new_relation = NewBucketUserRelation {
-                user_uuid: fixture.user2.uuid,
-                bucket_uuid: fixture.bucket.uuid,
-                set_public_permission: false,
-                set_drawing_permission: false,
-                set_exclusive_permission: false,
-                kick_permission: false,
-                grant_permissions_permission: false,
-            };
-            db.add_user_to_bucket(new_relation)
-                .expect("Should add user to bucket");
+            fixture.add_user2(NO_PERMISSIONS, &db);
 
             let query = UserUuidQueryParam {
                 user_uuid: fixture.user2.uuid,
             };
 
-            remove_user_from_bucket_handler(fixture.bucket.uuid, fixture.user2.uuid, query, db)
-                .expect("User should be removed");
+            remove_user_from_bucket_handler(
+                fixture.bucket.uuid,
+                fixture.user2.uuid,
+                query,
+                Hub::default(),
+                db,
+            )
+            .expect("User should be removed");
         })
     }
 
     #[test]
     fn remove_other_from_bucket() {
         execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
-            let new_relation = NewBucketUserRelation {
-                user_uuid: fixture.user2.uuid,
-                bucket_uuid: fixture.bucket.uuid,
-                set_public_permission: false,
-                set_drawing_permission: false,
-                set_exclusive_permission: false,
-                kick_permission: false,
-                grant_permissions_permission: false,
-            };
-            db.add_user_to_bucket(new_relation)
-                .expect("Should add user to bucket");
+            fixture.add_user2(NO_PERMISSIONS, &db);
 
             let query = UserUuidQueryParam {
                 user_uuid: fixture.user2.uuid,
             };
 
             // User 1, who has permissions, should remove user 2.
-            remove_user_from_bucket_handler(fixture.bucket.uuid, fixture.user1.uuid, query, db)
-                .expect("User should be removed");
+            remove_user_from_bucket_handler(
+                fixture.bucket.uuid,
+                fixture.user1.uuid,
+                query,
+                Hub::default(),
+                db,
+            )
+            .expect("User should be removed");
         })
     }
 
     #[test]
-    fn cant_remove_other_from_bucket() {
+    fn advance_turn_rotates_asker() {
         execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
-            let new_relation = NewBucketUserRelation {
-                user_uuid: fixture.user2.uuid,
+            fixture.add_user2(NO_PERMISSIONS, &db);
+
+            let first_turn = advance_turn(fixture.bucket.uuid, &db)
+                .expect("Should advance turn")
+                .expect("Bucket has users, so there should be a turn");
+            assert_ne!(first_turn.asker_uuid, first_turn.answerer_uuid);
+
+            let second_turn = advance_turn(fixture.bucket.uuid, &db)
+                .expect("Should advance turn")
+                .expect("Bucket has users, so there should be a turn");
+            assert_ne!(first_turn.asker_uuid, second_turn.asker_uuid);
+            assert_ne!(second_turn.asker_uuid, second_turn.answerer_uuid);
+            assert_eq!(second_turn.turn_number, first_turn.turn_number + 1);
+        })
+    }
+
+    #[test]
+    fn advance_turn_passes_over_dormant_members() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            fixture.add_user2(NO_PERMISSIONS, &db);
+            // Only user 2 is around, so user 1 shouldn't be picked.
+            let new_presence = NewPresence {
                 bucket_uuid: fixture.bucket.uuid,
-                set_public_permission: false,
-                set_drawing_permission: false,
-                set_exclusive_permission: false,
-                kick_permission: false,
-                grant_permissions_permission: false,
+                user_uuid: fixture.user2.uuid,
             };
-            db.add_user_to_bucket(new_relation)
-                .expect("Should add user to bucket");
+            db.record_heartbeat(new_presence)
+                .expect("Should record heartbeat");
+
+            for _ in 0..3 {
+                let turn = advance_turn(fixture.bucket.uuid, &db)
+                    .expect("Should advance turn")
+                    .expect("Bucket has users, so there should be a turn");
+                assert_eq!(turn.asker_uuid, fixture.user2.uuid);
+                assert_eq!(turn.answerer_uuid, fixture.user2.uuid);
+            }
+        })
+    }
+
+    #[test]
+    fn advance_turn_without_users() {
+        execute_test(|fixture: &BucketFixture, db: BoxedRepository| {
+            let turn = advance_turn(fixture.bucket.uuid, &db).expect("Should advance turn");
+            assert_eq!(turn, None);
+        })
+    }
+
+    #[test]
+    fn cant_skip_turn_without_permission() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            fixture.add_user2(NO_PERMISSIONS, &db);
+
+            skip_turn_handler(fixture.bucket.uuid, fixture.user2.uuid, db)
+                .expect_err("User without drawing permission should not skip the turn.");
+        })
+    }
+
+    #[test]
+    fn cant_remove_other_from_bucket() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            fixture.add_user2(NO_PERMISSIONS, &db);
 
             let query = UserUuidQueryParam {
                 user_uuid: fixture.user1.uuid,
             };
 
             // User 2, who does not have kick permissions, should not be able to remove user 1.
-            remove_user_from_bucket_handler(fixture.bucket.uuid, fixture.user2.uuid, query, db)
-                .expect_err("User should not be removed.");
+            remove_user_from_bucket_handler(
+                fixture.bucket.uuid,
+                fixture.user2.uuid,
+                query,
+                Hub::default(),
+                db,
+            )
+            .expect_err("User should not be removed.");
+        })
+    }
+
+    #[test]
+    fn creator_owns_bucket() {
+        execute_test(|fixture: &UserFixture, db: BoxedRepository| {
+            let new_bucket = NewBucketRequest {
+                bucket_name: "Bucket".to_string(),
+            };
+            let bucket = create_bucket_handler(new_bucket, fixture.user.uuid, db)
+                .expect("Should create bucket");
+            assert_eq!(bucket.owner_uuid, Some(fixture.user.uuid));
+        })
+    }
+
+    #[test]
+    fn owner_cant_be_kicked() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            db.set_bucket_owner(fixture.bucket.uuid, fixture.user1.uuid)
+                .expect("Should set owner");
+            fixture.add_user2(BucketRole::Moderator.permissions().into(), &db);
+
+            let query = UserUuidQueryParam {
+                user_uuid: fixture.user1.uuid,
+            };
+            let error = remove_user_from_bucket_handler(
+                fixture.bucket.uuid,
+                fixture.user2.uuid,
+                query,
+                Hub::default(),
+                db,
+            )
+            .expect_err("The owner should not be removed.");
+            match error {
+                Error::PreconditionNotMet(_) => {}
+                other => panic!("Expected precondition not met, got: {:?}", other),
+            }
+        })
+    }
+
+    #[test]
+    fn last_granter_cant_leave() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            fixture.add_user2(BucketRole::Player.permissions().into(), &db);
+
+            // User 1 is the only one who can grant permissions.
+            let query = UserUuidQueryParam {
+                user_uuid: fixture.user1.uuid,
+            };
+            remove_user_from_bucket_handler(
+                fixture.bucket.uuid,
+                fixture.user1.uuid,
+                query,
+                Hub::default(),
+                db,
+            )
+            .expect_err("The last member who can grant permissions should not leave.");
+        })
+    }
+
+    #[test]
+    fn owner_permissions_cant_be_revoked() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            db.set_bucket_owner(fixture.bucket.uuid, fixture.user1.uuid)
+                .expect("Should set owner");
+            let request = SetPermissionsRequest {
+                target_user_uuid: fixture.user1.uuid,
+                set_public_permission: None,
+                set_drawing_permission: None,
+                set_exclusive_permission: None,
+                kick_permission: Some(false),
+                grant_permissions_permission: None,
+                moderate_questions_permission: None,
+                delete_questions_permission: None,
+                invite_permission: None,
+            };
+            set_permissions_handler(
+                fixture.bucket.uuid,
+                request,
+                fixture.user1.uuid,
+                Hub::default(),
+                db,
+            )
+            .expect_err("The owner's permissions should not be revoked.");
+        })
+    }
+
+    #[test]
+    fn set_role_gives_role_permissions() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            fixture.add_user2(BucketRole::Spectator.permissions().into(), &db);
+
+            let request = SetRoleRequest {
+                target_user_uuid: fixture.user2.uuid,
+                role: BucketRole::Moderator,
+            };
+            let relation = set_role_handler(
+                fixture.bucket.uuid,
+                request,
+                fixture.user1.uuid,
+                Hub::default(),
+                db,
+            )
+            .expect("Should set role");
+            assert_eq!(relation.user_uuid, fixture.user2.uuid);
+            let permissions: wire::bucket_user_relation::BucketUserPermissions = relation.into();
+            assert_eq!(
+                BucketRole::from_permissions(&permissions),
+                Some(BucketRole::Moderator)
+            );
+        })
+    }
+
+    #[test]
+    fn cant_set_owner_role() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            fixture.add_user2(BucketRole::Spectator.permissions().into(), &db);
+
+            let request = SetRoleRequest {
+                target_user_uuid: fixture.user2.uuid,
+                role: BucketRole::Owner,
+            };
+            set_role_handler(
+                fixture.bucket.uuid,
+                request,
+                fixture.user1.uuid,
+                Hub::default(),
+                db,
+            )
+            .expect_err("Ownership should only be transferred.");
+        })
+    }
+
+    #[test]
+    fn transfer_ownership() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            db.set_bucket_owner(fixture.bucket.uuid, fixture.user1.uuid)
+                .expect("Should set owner");
+            fixture.add_user2(BucketRole::Player.permissions().into(), &db);
+
+            let request = TransferOwnershipRequest {
+                new_owner_uuid: fixture.user2.uuid,
+            };
+            let bucket = transfer_ownership_handler(
+                fixture.bucket.uuid,
+                request,
+                fixture.user1.uuid,
+                Hub::default(),
+                db,
+            )
+            .expect("Should transfer ownership");
+            assert_eq!(bucket.owner_uuid, Some(fixture.user2.uuid));
+        })
+    }
+
+    #[test]
+    fn only_owner_can_transfer_ownership() {
+        execute_test(|fixture: &UserBucketRelationFixture, db: BoxedRepository| {
+            db.set_bucket_owner(fixture.bucket.uuid, fixture.user1.uuid)
+                .expect("Should set owner");
+            fixture.add_user2(BucketRole::Moderator.permissions().into(), &db);
+
+            let request = TransferOwnershipRequest {
+                new_owner_uuid: fixture.user2.uuid,
+            };
+            transfer_ownership_handler(
+                fixture.bucket.uuid,
+                request,
+                fixture.user2.uuid,
+                Hub::default(),
+                db,
+            )
+            .expect_err("Only the owner should transfer ownership.");
         })
     }
 }
@@ -0,0 +1,497 @@
+//! Adds questions to a bucket, and gets them back out, in bulk.
+//!
+//! Imports can be plain text with one question per line, CSV with an optional column of tags,
+//! or the same JSON that is used to make questions one at a time.
+use crate::{
+    api::{
+        audit::{diff, record_audit_event},
+        bucket::BUCKET_PATH,
+        question::normalized_tag_names,
+    },
+    error::Error,
+    server_auth::user_filter,
+    state::{hub::Hub, State},
+    util::{json_or_reject, reject, sized_body_json},
+};
+use db::{
+    audit::db_types::AuditAction,
+    page::{Page, Pagination, RecencySort},
+    question::db_types::{ImportOutcome, QuestionExport, QuestionImport},
+    BoxedRepository,
+};
+use futures::{stream, Async, Poll, Stream};
+use hyper::Body;
+use log::info;
+use serde_json::json;
+use uuid::Uuid;
+use warp::{filters::BoxedFilter, http::Response, path, Filter, Reply};
+use wire::{
+    live::BucketEvent,
+    question::{
+        ExportedQuestion, ImportLineResult, ImportQuestionsRequest, ImportQuestionsResponse,
+        NewQuestionRequest,
+    },
+    tag::split_tag_names,
+};
+
+/// The largest import that will be accepted, in kilobytes.
+const IMPORT_SIZE_LIMIT_KB: u64 = 256;
+
+/// The most questions that can be imported at once.
+const MAX_IMPORTED_QUESTIONS: usize = 1000;
+
+/// How many questions are read from the database at a time while exporting.
+const EXPORT_PAGE_SIZE: i64 = 100;
+
+/// Why an export was cut short.
+type ExportError = Box<dyn std::error::Error + Send + Sync>;
+
+pub fn bulk_api(state: &State) -> BoxedFilter<(impl Reply,)> {
+    let import_questions = path!(Uuid / "questions" / "import")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(sized_body_json(IMPORT_SIZE_LIMIT_KB))
+        .and(user_filter(state))
+        .and(state.hub())
+        .and(state.db())
+        .map(import_questions_handler)
+        .and_then(json_or_reject);
+
+    let export_questions = path!(Uuid / "questions" / "export")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(user_filter(state))
+        .and(state.db())
+        .and_then(
+            |bucket_uuid: Uuid, user_uuid: Uuid, conn: BoxedRepository| {
+                reject(export_questions_handler(bucket_uuid, user_uuid, conn))
+            },
+        );
+
+    path(BUCKET_PATH)
+        .and(import_questions.or(export_questions))
+        .boxed()
+}
+
+/// A question read from an import, or the reason it couldn't be read, along with the line it came from.
+type ParsedLine = (usize, Result<QuestionImport, String>);
+
+/// Adds the questions to the bucket.
+///
+/// Only members who can moderate the bucket's questions can import them.
+/// Lines that can't be read, and questions the bucket already has, are skipped,
+/// but the rest of the questions are still added.
+/// Questions are duplicates when they are worded the same as one in the bucket,
+/// or are only similar to one if the bucket rejects duplicates.
+fn import_questions_handler(
+    bucket_uuid: Uuid,
+    request: ImportQuestionsRequest,
+    user_uuid: Uuid,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<ImportQuestionsResponse, Error> {
+    info!("import_questions_handler");
+    let reason = "Only members who can moderate questions can import them.";
+    match conn.get_permissions(user_uuid, bucket_uuid) {
+        Ok(permissions) if permissions.moderate_questions_permission => {}
+        Ok(_) | Err(diesel::result::Error::NotFound) => return Err(Error::not_authorized(reason)),
+        Err(e) => return Err(Error::from(e)),
+    }
+
+    let parsed = match request {
+        ImportQuestionsRequest::Text(content) => parse_text(&content),
+        ImportQuestionsRequest::Csv(content) => parse_csv(&content),
+        ImportQuestionsRequest::Json(questions) => parse_json(bucket_uuid, questions),
+    };
+    if parsed.len() > MAX_IMPORTED_QUESTIONS {
+        return Err(Error::BadRequest(format!(
+            "At most {} questions can be imported at once.",
+            MAX_IMPORTED_QUESTIONS
+        )));
+    }
+
+    let imports: Vec<QuestionImport> = parsed
+        .iter()
+        .filter_map(|(_, import)| import.as_ref().ok().cloned())
+        .collect();
+    let mut outcomes = conn
+        .import_questions(bucket_uuid, Some(user_uuid), imports)?
+        .into_iter();
+
+    let mut lines = Vec::with_capacity(parsed.len());
+    let mut imported = 0;
+    for (line, import) in parsed {
+        let outcome = match import {
+            Ok(_) => match outcomes.next() {
+                Some(ImportOutcome::Imported(question)) => {
+                    imported += 1;
+                    let question_uuid = question.uuid;
+                    let event = BucketEvent::QuestionAdded {
+                        question: question.into(),
+                    };
+                    hub.publish(bucket_uuid, event);
+                    wire::question::ImportOutcome::Imported { question_uuid }
+                }
+                Some(ImportOutcome::Duplicate) => wire::question::ImportOutcome::Duplicate,
+                None => {
+                    return Err(Error::internal_server_error(
+                        "Not every imported question had an outcome.",
+                    ))
+                }
+            },
+            Err(reason) => wire::question::ImportOutcome::Invalid { reason },
+        };
+        lines.push(ImportLineResult { line, outcome });
+    }
+    let skipped = lines.len() - imported;
+
+    if imported > 0 {
+        record_audit_event(
+            bucket_uuid,
+            Some(user_uuid),
+            None,
+            AuditAction::QuestionsImported,
+            diff(
+                None,
+                Some(&json!({ "imported": imported, "skipped": skipped })),
+            ),
+            &conn,
+        )?;
+    }
+    Ok(ImportQuestionsResponse {
+        imported,
+        skipped,
+        lines,
+    })
+}
+
+/// Checks that the question has some text, and normalizes its text and tags.
+fn question_import(question_text: &str, tags: &[String]) -> Result<QuestionImport, String> {
+    let question_text = question_text.trim();
+    if question_text.is_empty() {
+        return Err("The question is empty.".to_string());
+    }
+    Ok(QuestionImport {
+        question_text: question_text.to_string(),
+        tags: normalized_tag_names(tags),
+    })
+}
+
+/// Reads one question from each line. Blank lines are left out.
+fn parse_text(content: &str) -> Vec<ParsedLine> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .map(|(index, text)| (index + 1, question_import(text, &[])))
+        .collect()
+}
+
+/// Reads one question from each row, where the first column is the question,
+/// and the optional second column holds comma separated tag names.
+///
+/// A first row with "question" or "question_text" as its first column is taken to be a header,
+/// and is left out.
+fn parse_csv(content: &str) -> Vec<ParsedLine> {
+    csv_records(content)
+        .into_iter()
+        .filter(|(_, record)| match record {
+            Ok(fields) => fields.iter().any(|field| !field.trim().is_empty()),
+            Err(_) => true,
+        })
+        .enumerate()
+        .filter(|(index, (_, record))| match record {
+            Ok(fields) => *index != 0 || !is_header(&fields[0]),
+            Err(_) => true,
+        })
+        .map(|(_, (line, record))| {
+            let import = record.and_then(|fields| match fields.as_slice() {
+                [question_text] => question_import(question_text, &[]),
+                [question_text, tags] => question_import(question_text, &split_tag_names(tags)),
+                _ => Err("Expected a question, optionally followed by its tags.".to_string()),
+            });
+            (line, import)
+        })
+        .collect()
+}
+
+fn is_header(field: &str) -> bool {
+    let field = field.trim();
+    field.eq_ignore_ascii_case("question") || field.eq_ignore_ascii_case("question_text")
+}
+
+/// Splits CSV content into records of fields, along with the line each record starts on.
+///
+/// Quoted fields can hold commas, line breaks, and quotes, which are written as two quotes.
+fn csv_records(content: &str) -> Vec<(usize, Result<Vec<String>, String>)> {
+    let mut records = vec![];
+    let mut chars = content.chars().peekable();
+    let mut line = 1;
+    while chars.peek().is_some() {
+        let start_line = line;
+        let mut fields = vec![];
+        let mut field = String::new();
+        let mut in_quotes = false;
+        loop {
+            match chars.next() {
+                None => break,
+                Some('"') if in_quotes => {
+                    if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        in_quotes = false;
+                    }
+                }
+                Some('"') if field.is_empty() => in_quotes = true,
+                Some(',') if !in_quotes => {
+                    fields.push(std::mem::replace(&mut field, String::new()))
+                }
+                Some('\n') => {
+                    line += 1;
+                    if in_quotes {
+                        field.push('\n');
+                    } else {
+                        break;
+                    }
+                }
+                Some('\r') if !in_quotes => {}
+                Some(c) => field.push(c),
+            }
+        }
+        fields.push(field);
+        if in_quotes {
+            records.push((
+                start_line,
+                Err("A quoted field is never closed.".to_string()),
+            ));
+        } else {
+            records.push((start_line, Ok(fields)));
+        }
+    }
+    records
+}
+
+/// Reads the questions from the same requests that are used to make questions one at a time.
+fn parse_json(bucket_uuid: Uuid, questions: Vec<NewQuestionRequest>) -> Vec<ParsedLine> {
+    questions
+        .into_iter()
+        .enumerate()
+        .map(|(index, request)| {
+            let import = if request.bucket_uuid != bucket_uuid {
+                Err("The question is for another bucket.".to_string())
+            } else {
+                question_import(&request.question_text, &request.tags)
+            };
+            (index + 1, import)
+        })
+        .collect()
+}
+
+/// Gets every question in the bucket as a JSON array, the oldest first,
+/// along with whether it is on the floor, its tags, and how many times it has been answered.
+///
+/// The questions are read from the database a page at a time as the response is sent,
+/// so that large buckets aren't held in memory all at once.
+/// If reading a page fails, the response is cut short.
+/// Only members of the bucket can export its questions.
+fn export_questions_handler(
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Response<Body>, Error> {
+    info!("export_questions_handler");
+    match conn.get_permissions(user_uuid, bucket_uuid) {
+        Ok(_) => {}
+        Err(diesel::result::Error::NotFound) => {
+            return Err(Error::not_authorized(
+                "Only members can export the bucket's questions.",
+            ))
+        }
+        Err(e) => return Err(Error::from(e)),
+    }
+
+    let mut next_page = Some(Pagination::first(EXPORT_PAGE_SIZE, RecencySort::Oldest));
+    let pages = stream::poll_fn(move || -> Poll<Option<Page<QuestionExport>>, ExportError> {
+        match next_page {
+            Some(pagination) => {
+                let page = conn.export_questions(bucket_uuid, pagination)?;
+                next_page = pagination.next(&page);
+                Ok(Async::Ready(Some(page)))
+            }
+            None => Ok(Async::Ready(None)),
+        }
+    });
+    let mut is_first = true;
+    let questions = pages
+        .map(|page| stream::iter_ok(page.items))
+        .flatten()
+        .and_then(move |export| -> Result<String, ExportError> {
+            let question: ExportedQuestion = export.into();
+            let separator = if is_first { "" } else { "," };
+            is_first = false;
+            Ok(format!(
+                "{}{}",
+                separator,
+                serde_json::to_string(&question)?
+            ))
+        });
+    let body = stream::once(Ok("[".to_string()))
+        .chain(questions)
+        .chain(stream::once(Ok("]".to_string())));
+
+    Response::builder()
+        .header("content-type", "application/json")
+        .body(Body::wrap_stream(body))
+        .map_err(|_| Error::internal_server_error("Could not create the export."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_util::execute_test_on_repository;
+    use db::{
+        question::db_types::NewQuestion,
+        test::bucket_user_relation_fixture::UserBucketRelationFixture, RepositoryProvider,
+    };
+
+    fn texts(parsed: &[ParsedLine]) -> Vec<(usize, Option<&str>)> {
+        parsed
+            .iter()
+            .map(|(line, import)| {
+                let text = import
+                    .as_ref()
+                    .ok()
+                    .map(|import| import.question_text.as_str());
+                (*line, text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn text_skips_blank_lines() {
+        let parsed = parse_text("First?\n\n  Second?  \r\n");
+        assert_eq!(
+            texts(&parsed),
+            vec![(1, Some("First?")), (3, Some("Second?"))]
+        );
+    }
+
+    #[test]
+    fn csv_reads_quoted_fields_and_tags() {
+        let content =
+            "question,tags\n\"Why, though?\",\"Deep, Silly\"\n\"Say \"\"hi\"\"\nback\"\nPlain?,";
+        let parsed = parse_csv(content);
+        assert_eq!(
+            texts(&parsed),
+            vec![
+                (2, Some("Why, though?")),
+                (3, Some("Say \"hi\"\nback")),
+                (5, Some("Plain?")),
+            ]
+        );
+        let tags = &parsed[0].1.as_ref().unwrap().tags;
+        assert_eq!(tags, &vec!["deep".to_string(), "silly".to_string()]);
+        assert!(parsed[2].1.as_ref().unwrap().tags.is_empty());
+    }
+
+    #[test]
+    fn csv_reports_bad_rows() {
+        let parsed = parse_csv("One?,a,b\n,tag\n\"Never closed?");
+        assert_eq!(texts(&parsed), vec![(1, None), (2, None), (3, None)]);
+    }
+
+    #[test]
+    fn json_rejects_questions_for_other_buckets() {
+        let bucket_uuid = Uuid::new_v4();
+        let request = |bucket_uuid: Uuid| NewQuestionRequest {
+            bucket_uuid,
+            question_text: "Which bucket?".to_string(),
+            tags: vec![],
+        };
+        let parsed = parse_json(
+            bucket_uuid,
+            vec![request(bucket_uuid), request(Uuid::new_v4())],
+        );
+        assert_eq!(texts(&parsed), vec![(1, Some("Which bucket?")), (2, None)]);
+    }
+
+    #[test]
+    fn import_skips_duplicates_and_bad_lines() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let new_question = NewQuestion {
+                    bucket_uuid: fixture.bucket.uuid,
+                    user_uuid: Some(fixture.user1.uuid),
+                    question_text: "Already asked?".to_string(),
+                };
+                repo.create_question(new_question)
+                    .expect("Should create question");
+
+                let request = ImportQuestionsRequest::Csv(
+                    "Already asked?\nNew?,fun\nNew?\n\"Unclosed".to_string(),
+                );
+                let response = import_questions_handler(
+                    fixture.bucket.uuid,
+                    request,
+                    fixture.user1.uuid,
+                    Hub::default(),
+                    repo,
+                )
+                .expect("Should import questions");
+                assert_eq!(response.imported, 1);
+                assert_eq!(response.skipped, 3);
+                let outcomes: Vec<(usize, &str)> = response
+                    .lines
+                    .iter()
+                    .map(|result| {
+                        let outcome = match result.outcome {
+                            wire::question::ImportOutcome::Imported { .. } => "imported",
+                            wire::question::ImportOutcome::Duplicate => "duplicate",
+                            wire::question::ImportOutcome::Invalid { .. } => "invalid",
+                        };
+                        (result.line, outcome)
+                    })
+                    .collect();
+                assert_eq!(
+                    outcomes,
+                    vec![
+                        (1, "duplicate"),
+                        (2, "imported"),
+                        (3, "duplicate"),
+                        (4, "invalid")
+                    ]
+                );
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let count = repo
+                    .get_number_of_active_questions_for_bucket(fixture.bucket.uuid)
+                    .expect("Should count questions");
+                assert_eq!(count, 2);
+            },
+        )
+    }
+
+    #[test]
+    fn import_requires_moderation_permission() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let request = ImportQuestionsRequest::Text("Let me in?".to_string());
+                let error = import_questions_handler(
+                    fixture.bucket.uuid,
+                    request,
+                    fixture.user2.uuid,
+                    Hub::default(),
+                    repo,
+                )
+                .unwrap_err();
+                match error {
+                    Error::NotAuthorized { .. } => {}
+                    other => panic!("Expected not authorized, got: {:?}", other),
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,659 @@
+//! Lets users into buckets by invitation, even if the bucket is exclusive.
+//!
+//! Members with the invite permission can create invites that carry preset permissions,
+//! or share the bucket's join code, which joins users with no permissions.
+use crate::{
+    api::{
+        audit::{diff, record_audit_event},
+        ban::ensure_not_banned,
+        bucket::{record_permission_changes, BUCKET_PATH},
+    },
+    error::Error,
+    server_auth::user_filter,
+    state::{hub::Hub, State},
+    util::{json_or_reject, pagination, sized_body_json},
+};
+use db::{
+    audit::db_types::AuditAction,
+    bucket::db_types::Bucket,
+    bucket_user_relation::db_types::{BucketUserPermissions, NewBucketUserRelation},
+    invite::db_types::{Invite, JoinCode, NewInvite, NewJoinCode},
+    page::Page,
+    BoxedRepository,
+};
+use diesel::result::DatabaseErrorKind;
+use log::info;
+use rand::{thread_rng, Rng};
+use uuid::Uuid;
+use warp::{filters::BoxedFilter, path, query, Filter, Reply};
+use wire::{
+    invite::NewInviteRequest,
+    live::BucketEvent,
+    page::{PageQuery, RecencySort},
+};
+
+/// The characters join codes are made of.
+/// Characters that are easily confused with each other (0/O, 1/I/L) are left out.
+const JOIN_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+
+/// How many characters are in a join code.
+const JOIN_CODE_LENGTH: usize = 8;
+
+/// How many times generating a join code is attempted before giving up on finding an unused one.
+const JOIN_CODE_ATTEMPTS: usize = 5;
+
+pub fn invite_api(state: &State) -> BoxedFilter<(impl Reply,)> {
+    let create_invite = path!(Uuid / "invites")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(sized_body_json(1))
+        .and(user_filter(state))
+        .and(state.db())
+        .map(create_invite_handler)
+        .and_then(json_or_reject);
+
+    let get_invites = path!(Uuid / "invites")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(get_invites_handler)
+        .and_then(json_or_reject);
+
+    let delete_invite = path!(Uuid / "invites" / Uuid)
+        .and(warp::path::end())
+        .and(warp::delete2())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(delete_invite_handler)
+        .and_then(json_or_reject);
+
+    let redeem_invite = path!("invites" / Uuid / "redeem")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(user_filter(state))
+        .and(state.hub())
+        .and(state.db())
+        .map(redeem_invite_handler)
+        .and_then(json_or_reject);
+
+    let get_join_code = path!(Uuid / "join_code")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(get_join_code_handler)
+        .and_then(json_or_reject);
+
+    let regenerate_join_code = path!(Uuid / "join_code")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(regenerate_join_code_handler)
+        .and_then(json_or_reject);
+
+    let join_with_code = path!("join" / String)
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(user_filter(state))
+        .and(state.hub())
+        .and(state.db())
+        .map(join_with_code_handler)
+        .and_then(json_or_reject);
+
+    path(BUCKET_PATH)
+        .and(
+            create_invite
+                .or(get_invites)
+                .or(delete_invite)
+                .or(redeem_invite)
+                .or(get_join_code)
+                .or(regenerate_join_code)
+                .or(join_with_code),
+        )
+        .boxed()
+}
+
+/// Gets the user's permissions for the bucket, rejecting the request if they can't invite others.
+fn permissions_to_invite(
+    user_uuid: Uuid,
+    bucket_uuid: Uuid,
+    conn: &BoxedRepository,
+) -> Result<BucketUserPermissions, Error> {
+    let permissions = conn.get_permissions(user_uuid, bucket_uuid)?;
+    if !permissions.invite_permission {
+        return Err(Error::not_authorized(
+            "User does not have privileges to invite others to this bucket.",
+        ));
+    }
+    Ok(permissions)
+}
+
+/// Rejects joining a template, which only serves to make buckets from.
+fn ensure_not_template(bucket: &Bucket) -> Result<(), Error> {
+    if bucket.is_template {
+        return Err(Error::PreconditionNotMet(
+            "Templates can't be joined. Make a bucket from the template instead.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Turns the error for a user that is already in the bucket into one that explains that.
+fn already_joined_error(error: diesel::result::Error) -> Error {
+    if let diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) = error {
+        Error::PreconditionNotMet(
+            "There is already a relation between this user and the bucket".to_string(),
+        )
+    } else {
+        Error::from(error)
+    }
+}
+
+fn create_invite_handler(
+    bucket_uuid: Uuid,
+    request: NewInviteRequest,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Invite, Error> {
+    info!("create_invite_handler");
+    let held = permissions_to_invite(user_uuid, bucket_uuid, &conn)?;
+
+    let preset: BucketUserPermissions = request.permissions.into();
+    if !held.includes(&preset) {
+        return Err(Error::not_authorized(
+            "An invite can't grant permissions that its creator doesn't have.",
+        ));
+    }
+    if request
+        .max_uses
+        .map(|max_uses| max_uses < 1)
+        .unwrap_or(false)
+    {
+        return Err(Error::BadRequest(
+            "An invite must be usable at least once.".to_string(),
+        ));
+    }
+
+    let new_invite = NewInvite {
+        bucket_uuid,
+        user_uuid: Some(user_uuid),
+        expires_at: request.expires_at,
+        max_uses: request.max_uses,
+        set_public_permission: preset.set_public_permission,
+        set_drawing_permission: preset.set_drawing_permission,
+        set_exclusive_permission: preset.set_exclusive_permission,
+        kick_permission: preset.kick_permission,
+        grant_permissions_permission: preset.grant_permissions_permission,
+        moderate_questions_permission: preset.moderate_questions_permission,
+        delete_questions_permission: preset.delete_questions_permission,
+        invite_permission: preset.invite_permission,
+    };
+    let invite = conn.create_invite(new_invite)?;
+    record_audit_event(
+        bucket_uuid,
+        Some(user_uuid),
+        Some(invite.uuid),
+        AuditAction::InviteCreated,
+        diff(None, Some(&invite)),
+        &conn,
+    )?;
+    Ok(invite)
+}
+
+/// Gets a page of the bucket's invites, the most recent first unless the request says otherwise.
+fn get_invites_handler(
+    bucket_uuid: Uuid,
+    page: PageQuery<RecencySort>,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Page<Invite>, Error> {
+    info!("get_invites_handler");
+    permissions_to_invite(user_uuid, bucket_uuid, &conn)?;
+    let pagination = pagination(page, RecencySort::Newest)?;
+    conn.get_invites_for_bucket(bucket_uuid, pagination)
+        .map_err(Error::from)
+}
+
+fn delete_invite_handler(
+    bucket_uuid: Uuid,
+    invite_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Invite, Error> {
+    info!("delete_invite_handler");
+    permissions_to_invite(user_uuid, bucket_uuid, &conn)?;
+    let invite = conn.get_invite(invite_uuid)?;
+    if invite.bucket_uuid != bucket_uuid {
+        return Err(Error::NotFound {
+            type_name: "Invite".to_string(),
+        });
+    }
+    let invite = conn.delete_invite(invite_uuid)?;
+    record_audit_event(
+        bucket_uuid,
+        Some(user_uuid),
+        Some(invite.uuid),
+        AuditAction::InviteDeleted,
+        diff(Some(&invite), None),
+        &conn,
+    )?;
+    Ok(invite)
+}
+
+/// Joins the bucket the invite is for, with the invite's permissions.
+/// Returns the bucket, so the client can navigate to it.
+fn redeem_invite_handler(
+    invite_uuid: Uuid,
+    user_uuid: Uuid,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<Bucket, Error> {
+    info!("redeem_invite_handler");
+    // Invites that have expired or been used up are treated as if they don't exist.
+    let invite_not_found = || Error::NotFound {
+        type_name: "Invite".to_string(),
+    };
+    let invite = conn.get_invite(invite_uuid).map_err(|e| match e {
+        diesel::result::Error::NotFound => invite_not_found(),
+        other => Error::from(other),
+    })?;
+    let bucket = conn.get_bucket_by_uuid(invite.bucket_uuid)?;
+    ensure_not_template(&bucket)?;
+    ensure_not_banned(invite.bucket_uuid, user_uuid, &conn)?;
+    let relation = conn
+        .redeem_invite(invite_uuid, user_uuid)
+        .map_err(|e| match e {
+            diesel::result::Error::NotFound => invite_not_found(),
+            other => already_joined_error(other),
+        })?;
+    // The invitee starts out with no permissions, and is granted the invite's by its creator.
+    let before = BucketUserPermissions {
+        set_public_permission: false,
+        set_drawing_permission: false,
+        set_exclusive_permission: false,
+        kick_permission: false,
+        grant_permissions_permission: false,
+        moderate_questions_permission: false,
+        delete_questions_permission: false,
+        invite_permission: false,
+    };
+    record_permission_changes(
+        relation.bucket_uuid,
+        invite.user_uuid,
+        user_uuid,
+        &before,
+        &relation.permissions(),
+        &conn,
+    )?;
+    record_audit_event(
+        relation.bucket_uuid,
+        Some(user_uuid),
+        Some(user_uuid),
+        AuditAction::MemberJoined,
+        diff(None, Some(&relation)),
+        &conn,
+    )?;
+    hub.publish(
+        relation.bucket_uuid,
+        BucketEvent::MemberJoined { user_uuid },
+    );
+    Ok(bucket)
+}
+
+fn get_join_code_handler(
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<JoinCode, Error> {
+    info!("get_join_code_handler");
+    permissions_to_invite(user_uuid, bucket_uuid, &conn)?;
+    conn.get_join_code(bucket_uuid).map_err(Error::from)
+}
+
+/// Generates a new join code for the bucket.
+/// The previous code, if there was one, stops working.
+fn regenerate_join_code_handler(
+    bucket_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<JoinCode, Error> {
+    info!("regenerate_join_code_handler");
+    permissions_to_invite(user_uuid, bucket_uuid, &conn)?;
+    let old_join_code = match conn.get_join_code(bucket_uuid) {
+        Ok(join_code) => Some(join_code),
+        Err(diesel::result::Error::NotFound) => None,
+        Err(e) => return Err(Error::from(e)),
+    };
+
+    let mut rng = thread_rng();
+    for _ in 0..JOIN_CODE_ATTEMPTS {
+        let join_code = (0..JOIN_CODE_LENGTH)
+            .map(|_| JOIN_CODE_ALPHABET[rng.gen_range(0, JOIN_CODE_ALPHABET.len())] as char)
+            .collect();
+        let new_join_code = NewJoinCode {
+            bucket_uuid,
+            join_code,
+        };
+        match conn.set_join_code(new_join_code) {
+            // Another bucket has the code already, try a different one.
+            Err(diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                continue
+            }
+            result => {
+                let join_code = result?;
+                record_audit_event(
+                    bucket_uuid,
+                    Some(user_uuid),
+                    None,
+                    AuditAction::JoinCodeRegenerated,
+                    diff(old_join_code.as_ref(), Some(&join_code)),
+                    &conn,
+                )?;
+                return Ok(join_code);
+            }
+        }
+    }
+    Err(Error::InternalServerError(Some(
+        "Could not find an unused join code.".to_string(),
+    )))
+}
+
+/// Joins the bucket the code is for, with no permissions.
+/// Returns the bucket, so the client can navigate to it.
+fn join_with_code_handler(
+    join_code: String,
+    user_uuid: Uuid,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<Bucket, Error> {
+    info!("join_with_code_handler");
+    // Codes are forgiving of how they are typed.
+    let join_code: String = join_code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let join_code = conn.get_join_code_by_code(join_code)?;
+    let bucket_uuid = join_code.bucket_uuid;
+    let bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    ensure_not_template(&bucket)?;
+    ensure_not_banned(bucket_uuid, user_uuid, &conn)?;
+
+    // By default, users don't have any permissions.
+    let new_relation = NewBucketUserRelation {
+        user_uuid,
+        bucket_uuid,
+        set_public_permission: false,
+        set_drawing_permission: false,
+        set_exclusive_permission: false,
+        kick_permission: false,
+        grant_permissions_permission: false,
+        moderate_questions_permission: false,
+        delete_questions_permission: false,
+        invite_permission: false,
+    };
+    let relation = conn
+        .add_user_to_bucket(new_relation)
+        .map_err(already_joined_error)?;
+    record_audit_event(
+        bucket_uuid,
+        Some(user_uuid),
+        Some(user_uuid),
+        AuditAction::MemberJoined,
+        diff(None, Some(&relation)),
+        &conn,
+    )?;
+    hub.publish(bucket_uuid, BucketEvent::MemberJoined { user_uuid });
+    Ok(bucket)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_util::execute_test_on_repository;
+    use db::{
+        ban::db_types::NewBan,
+        bucket::db_types::BucketFlagChangeset,
+        page::Pagination,
+        test::bucket_user_relation_fixture::{UserBucketRelationFixture, NO_PERMISSIONS},
+        RepositoryProvider,
+    };
+    use wire::bucket_user_relation::BucketUserPermissions as WireBucketUserPermissions;
+
+    fn no_permissions() -> WireBucketUserPermissions {
+        WireBucketUserPermissions {
+            set_public_permission: false,
+            set_drawing_permission: false,
+            set_exclusive_permission: false,
+            grant_permissions_permission: false,
+            moderate_questions_permission: false,
+            delete_questions_permission: false,
+            invite_permission: false,
+            kick_permission: false,
+        }
+    }
+
+    fn make_exclusive(fixture: &UserBucketRelationFixture, conn: &BoxedRepository) {
+        let changeset = BucketFlagChangeset {
+            uuid: fixture.bucket.uuid,
+            public_viewable: None,
+            drawing_enabled: None,
+            exclusive: Some(true),
+            draw_strategy: None,
+            duplicate_policy: None,
+        };
+        conn.change_bucket_flags(changeset)
+            .expect("Should make bucket exclusive");
+    }
+
+    #[test]
+    fn invite_lets_user_into_exclusive_bucket() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                make_exclusive(fixture, &repo);
+
+                let request = NewInviteRequest {
+                    expires_at: None,
+                    max_uses: Some(1),
+                    permissions: WireBucketUserPermissions {
+                        set_drawing_permission: true,
+                        ..no_permissions()
+                    },
+                };
+                let invite =
+                    create_invite_handler(fixture.bucket.uuid, request, fixture.user1.uuid, repo)
+                        .expect("Should create invite");
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let bucket =
+                    redeem_invite_handler(invite.uuid, fixture.user2.uuid, Hub::default(), repo)
+                        .expect("Should redeem invite");
+                assert_eq!(bucket.uuid, fixture.bucket.uuid);
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let permissions = repo
+                    .get_permissions(fixture.user2.uuid, fixture.bucket.uuid)
+                    .expect("User should be in the bucket");
+                assert!(permissions.set_drawing_permission);
+                assert!(!permissions.kick_permission);
+
+                let invite = repo.get_invite(invite.uuid).expect("Should get invite");
+                assert_eq!(invite.uses, 1);
+
+                let events = repo
+                    .get_audit_events(
+                        fixture.bucket.uuid,
+                        Pagination::first(10, RecencySort::Newest.into()),
+                    )
+                    .expect("Should get audit events");
+                let actions: Vec<AuditAction> = events.items.iter().map(|e| e.action).collect();
+                assert!(actions.contains(&AuditAction::InviteCreated));
+                assert!(actions.contains(&AuditAction::MemberJoined));
+            },
+        )
+    }
+
+    #[test]
+    fn invite_cant_exceed_creators_permissions() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                fixture.add_user2(
+                    BucketUserPermissions {
+                        invite_permission: true,
+                        ..NO_PERMISSIONS
+                    },
+                    &repo,
+                );
+
+                let request = NewInviteRequest {
+                    expires_at: None,
+                    max_uses: None,
+                    permissions: WireBucketUserPermissions {
+                        kick_permission: true,
+                        ..no_permissions()
+                    },
+                };
+                let error =
+                    create_invite_handler(fixture.bucket.uuid, request, fixture.user2.uuid, repo)
+                        .unwrap_err();
+                match error {
+                    Error::NotAuthorized { .. } => {}
+                    other => panic!("Expected not authorized, got: {:?}", other),
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn join_code_lets_user_into_exclusive_bucket() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                make_exclusive(fixture, &repo);
+
+                let join_code =
+                    regenerate_join_code_handler(fixture.bucket.uuid, fixture.user1.uuid, repo)
+                        .expect("Should generate join code");
+                assert_eq!(join_code.join_code.len(), JOIN_CODE_LENGTH);
+
+                // Codes may be typed in lower case.
+                let typed = join_code.join_code.to_lowercase();
+                let repo = provider.get_repo().expect("Should get repo.");
+                let bucket =
+                    join_with_code_handler(typed, fixture.user2.uuid, Hub::default(), repo)
+                        .expect("Should join with code");
+                assert_eq!(bucket.uuid, fixture.bucket.uuid);
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let permissions = repo
+                    .get_permissions(fixture.user2.uuid, fixture.bucket.uuid)
+                    .expect("User should be in the bucket");
+                assert!(!permissions.invite_permission);
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let error = join_with_code_handler(
+                    join_code.join_code,
+                    fixture.user2.uuid,
+                    Hub::default(),
+                    repo,
+                )
+                .unwrap_err();
+                match error {
+                    Error::PreconditionNotMet(_) => {}
+                    other => panic!("Expected precondition not met, got: {:?}", other),
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn templates_cant_be_joined() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let request = NewInviteRequest {
+                    expires_at: None,
+                    max_uses: None,
+                    permissions: no_permissions(),
+                };
+                let invite =
+                    create_invite_handler(fixture.bucket.uuid, request, fixture.user1.uuid, repo)
+                        .expect("Should create invite");
+                let repo = provider.get_repo().expect("Should get repo.");
+                let join_code =
+                    regenerate_join_code_handler(fixture.bucket.uuid, fixture.user1.uuid, repo)
+                        .expect("Should generate join code");
+                let repo = provider.get_repo().expect("Should get repo.");
+                repo.set_bucket_template(fixture.bucket.uuid, true)
+                    .expect("Should make bucket a template");
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let error =
+                    redeem_invite_handler(invite.uuid, fixture.user2.uuid, Hub::default(), repo)
+                        .unwrap_err();
+                match error {
+                    Error::PreconditionNotMet(_) => {}
+                    other => panic!("Expected precondition not met, got: {:?}", other),
+                }
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let error = join_with_code_handler(
+                    join_code.join_code,
+                    fixture.user2.uuid,
+                    Hub::default(),
+                    repo,
+                )
+                .unwrap_err();
+                match error {
+                    Error::PreconditionNotMet(_) => {}
+                    other => panic!("Expected precondition not met, got: {:?}", other),
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn banned_user_cant_redeem_invite() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let new_ban = NewBan {
+                    bucket_uuid: fixture.bucket.uuid,
+                    user_uuid: fixture.user2.uuid,
+                    banned_by_uuid: Some(fixture.user1.uuid),
+                    reason: None,
+                    expires_at: None,
+                };
+                repo.ban_user(new_ban).expect("Should ban user");
+
+                let request = NewInviteRequest {
+                    expires_at: None,
+                    max_uses: None,
+                    permissions: no_permissions(),
+                };
+                let invite =
+                    create_invite_handler(fixture.bucket.uuid, request, fixture.user1.uuid, repo)
+                        .expect("Should create invite");
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let error =
+                    redeem_invite_handler(invite.uuid, fixture.user2.uuid, Hub::default(), repo)
+                        .unwrap_err();
+                match error {
+                    Error::NotAuthorized { .. } => {}
+                    other => panic!("Expected not authorized, got: {:?}", other),
+                }
+
+                // The invite isn't used up by the rejected redemption.
+                let repo = provider.get_repo().expect("Should get repo.");
+                let invite = repo.get_invite(invite.uuid).expect("Should get invite");
+                assert_eq!(invite.uses, 0);
+            },
+        )
+    }
+}
@@ -0,0 +1,284 @@
+//! Pushes the events of a bucket to clients as they happen.
+//!
+//! Events are sent over a websocket, or as server-sent events for clients that can't use websockets.
+use crate::{
+    api::bucket::{ensure_can_view_bucket, BUCKET_PATH},
+    server_auth::optional_user_filter,
+    state::{
+        hub::{Hub, SequencedEvent},
+        State,
+    },
+    util::reject,
+};
+use db::{BoxedRepository, RepositoryProvider};
+use futures::{Future, Stream};
+use log::{info, warn};
+use uuid::Uuid;
+use warp::{
+    filters::{
+        sse::{self, Sse},
+        ws::{Message, WebSocket, Ws2},
+        BoxedFilter,
+    },
+    path, Filter, Rejection, Reply,
+};
+use wire::live::BucketEvent;
+
+pub fn live_api(state: &State) -> BoxedFilter<(impl Reply,)> {
+    let live_socket = path!(Uuid / "live")
+        .and(warp::path::end())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .and_then(can_follow_bucket)
+        .and(warp::ws2())
+        .and(state.hub())
+        .and(state.repository_provider())
+        .map(
+            |follower: Follower, ws: Ws2, hub: Hub, provider: RepositoryProvider| {
+                ws.on_upgrade(move |socket| live_connection(follower, socket, hub, provider))
+            },
+        );
+
+    let event_stream = path!(Uuid / "events")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .and_then(can_follow_bucket)
+        .and(warp::sse())
+        .and(sse::last_event_id::<u64>())
+        .and(state.hub())
+        .and(state.repository_provider())
+        .map(event_stream_handler);
+
+    path(BUCKET_PATH)
+        .and(live_socket.or(event_stream))
+        .boxed()
+}
+
+/// Someone following the events of a bucket.
+#[derive(Clone, Copy, Debug)]
+struct Follower {
+    bucket_uuid: Uuid,
+    /// The user following the bucket, if they are logged in.
+    user_uuid: Option<Uuid>,
+}
+
+/// Rejects users that can't see the bucket, before they start following it.
+fn can_follow_bucket(
+    bucket_uuid: Uuid,
+    user_uuid: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<Follower, Rejection> {
+    let follower = Follower {
+        bucket_uuid,
+        user_uuid,
+    };
+    reject(ensure_can_view_bucket(bucket_uuid, user_uuid, &conn).map(|()| follower))
+}
+
+/// Passes the events on for as long as the follower can still see the bucket.
+///
+/// Whether they can is checked again for every event,
+/// so followers that are removed, banned, or shut out of a bucket that stopped being public stop getting its events.
+/// The bucket being deleted is passed on regardless, the hub ends the subscription after it.
+fn while_visible<S>(
+    events: S,
+    follower: Follower,
+    provider: RepositoryProvider,
+) -> impl Stream<Item = SequencedEvent, Error = ()>
+where
+    S: Stream<Item = SequencedEvent, Error = ()>,
+{
+    events.take_while(move |event| {
+        if let BucketEvent::BucketDeleted = event.event {
+            return Ok(true);
+        }
+        let conn = match provider.get_repo() {
+            Ok(conn) => conn,
+            Err(_) => {
+                warn!("Could not get a database connection to check a follower of a bucket.");
+                return Ok(false);
+            }
+        };
+        Ok(ensure_can_view_bucket(follower.bucket_uuid, follower.user_uuid, &conn).is_ok())
+    })
+}
+
+/// Streams the bucket's events as server-sent events,
+/// starting with any the client missed if it is reconnecting.
+///
+/// Users that can't see the bucket are turned away before any events, including the missed ones, are sent,
+/// and the stream ends as soon as the user can't see the bucket anymore.
+fn event_stream_handler(
+    follower: Follower,
+    sse: Sse,
+    last_event_id: Option<u64>,
+    hub: Hub,
+    provider: RepositoryProvider,
+) -> impl Reply {
+    info!("event_stream_handler");
+    let events = hub.subscribe(follower.bucket_uuid, last_event_id);
+    let events = while_visible(events, follower, provider)
+        .map(|event: SequencedEvent| {
+            let data = serde_json::to_string(&event.event).expect("Events should serialize.");
+            (sse::id(event.id.to_string()), sse::data(data))
+        })
+        .map_err(|()| -> warp::Error { unreachable!("unbounded receivers never error") });
+    sse.reply(sse::keep_alive().stream(events))
+}
+
+/// Forwards the bucket's events to the socket until the client disconnects,
+/// or can't see the bucket anymore.
+fn live_connection(
+    follower: Follower,
+    socket: WebSocket,
+    hub: Hub,
+    provider: RepositoryProvider,
+) -> impl Future<Item = (), Error = ()> {
+    info!("live_connection");
+    let (ws_tx, ws_rx) = socket.split();
+
+    let events = hub.subscribe(follower.bucket_uuid, None);
+    let outgoing = while_visible(events, follower, provider)
+        .map(|event| {
+            let text = serde_json::to_string(&event.event).expect("Events should serialize.");
+            Message::text(text)
+        })
+        .map_err(|()| -> warp::Error { unreachable!("unbounded receivers never error") })
+        .forward(ws_tx)
+        .map(|_| ())
+        .map_err(|e| warn!("Failed to send live event: {}", e));
+
+    // The client isn't expected to say anything,
+    // but the socket has to be read in order to notice when it is closed.
+    let incoming = ws_rx
+        .take_while(|message| Ok(!message.is_close()))
+        .for_each(|_| Ok(()))
+        .map_err(|e| warn!("Live connection closed with an error: {}", e));
+
+    // Dropping the subscription when the client leaves lets the hub forget about it.
+    outgoing.select(incoming).map(|_| ()).map_err(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_util::execute_test_on_repository;
+    use db::{
+        bucket::db_types::BucketFlagChangeset,
+        test::bucket_user_relation_fixture::UserBucketRelationFixture,
+    };
+    use futures::stream;
+
+    fn events(fixture: &UserBucketRelationFixture) -> Vec<SequencedEvent> {
+        vec![
+            SequencedEvent {
+                id: 1,
+                event: BucketEvent::MemberJoined {
+                    user_uuid: fixture.user1.uuid,
+                },
+            },
+            SequencedEvent {
+                id: 2,
+                event: BucketEvent::BucketDeleted,
+            },
+        ]
+    }
+
+    #[test]
+    fn only_those_who_can_see_the_bucket_keep_following_it() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                {
+                    let repo = provider.get_repo().expect("Should get repo.");
+                    let changeset = BucketFlagChangeset {
+                        uuid: fixture.bucket.uuid,
+                        public_viewable: Some(false),
+                        drawing_enabled: None,
+                        exclusive: None,
+                        draw_strategy: None,
+                        duplicate_policy: None,
+                    };
+                    repo.change_bucket_flags(changeset)
+                        .expect("Should hide bucket");
+                }
+
+                let member = Follower {
+                    bucket_uuid: fixture.bucket.uuid,
+                    user_uuid: Some(fixture.user1.uuid),
+                };
+                let received =
+                    while_visible(stream::iter_ok(events(fixture)), member, provider.clone())
+                        .collect()
+                        .wait()
+                        .unwrap();
+                assert_eq!(received, events(fixture));
+
+                // Someone who can't see the bucket gets nothing, not even the bucket being deleted.
+                let outsider = Follower {
+                    bucket_uuid: fixture.bucket.uuid,
+                    user_uuid: Some(fixture.user2.uuid),
+                };
+                let received = while_visible(stream::iter_ok(events(fixture)), outsider, provider)
+                    .collect()
+                    .wait()
+                    .unwrap();
+                assert!(received.is_empty());
+            },
+        )
+    }
+
+    #[test]
+    fn replayed_events_are_only_sent_to_those_who_can_see_the_bucket() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                {
+                    let repo = provider.get_repo().expect("Should get repo.");
+                    let changeset = BucketFlagChangeset {
+                        uuid: fixture.bucket.uuid,
+                        public_viewable: Some(false),
+                        drawing_enabled: None,
+                        exclusive: None,
+                        draw_strategy: None,
+                        duplicate_policy: None,
+                    };
+                    repo.change_bucket_flags(changeset)
+                        .expect("Should hide bucket");
+                }
+                let hub = Hub::default();
+                let event = BucketEvent::MemberJoined {
+                    user_uuid: fixture.user1.uuid,
+                };
+                hub.publish(fixture.bucket.uuid, event.clone());
+
+                let member = Follower {
+                    bucket_uuid: fixture.bucket.uuid,
+                    user_uuid: Some(fixture.user1.uuid),
+                };
+                let outsider = Follower {
+                    bucket_uuid: fixture.bucket.uuid,
+                    user_uuid: None,
+                };
+                let member_events = hub.subscribe(fixture.bucket.uuid, Some(0));
+                let outsider_events = hub.subscribe(fixture.bucket.uuid, Some(0));
+                // Ends the subscriptions once the replayed event has been received.
+                hub.remove_bucket(fixture.bucket.uuid);
+
+                let received: Vec<BucketEvent> =
+                    while_visible(member_events, member, provider.clone())
+                        .map(|event| event.event)
+                        .collect()
+                        .wait()
+                        .unwrap();
+                assert_eq!(received, vec![event]);
+
+                let received = while_visible(outsider_events, outsider, provider)
+                    .collect()
+                    .wait()
+                    .unwrap();
+                assert!(received.is_empty());
+            },
+        )
+    }
+}
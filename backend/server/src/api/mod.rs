@@ -1,8 +1,17 @@
 //! The api defines all of the routes that are supported for the server.
 mod answer;
+mod audit;
 mod auth;
+mod backup;
+mod ban;
 mod bucket;
+mod bulk;
+mod invite;
+mod live;
 mod question;
+mod search;
+mod session;
+mod template;
 #[cfg(test)]
 mod test;
 mod user;
@@ -13,8 +22,10 @@ use warp::{path, Filter};
 
 use crate::{
     api::{
-        answer::answer_api, auth::auth_api, bucket::bucket_api, question::question_api,
-        user::user_api,
+        answer::answer_api, audit::audit_api, auth::auth_api, backup::backup_api, ban::ban_api,
+        bucket::bucket_api, bulk::bulk_api, invite::invite_api, live::live_api,
+        question::question_api, search::search_api, session::session_api,
+        template::template_api, user::user_api,
     },
     state::State,
     static_files::{static_files_handler, FileConfig},
@@ -33,8 +44,17 @@ pub fn api(state: &State) -> BoxedFilter<(impl Reply,)> {
             bucket_api(state)
                 .or(answer_api(state))
                 .or(question_api(state))
+                .or(session_api(state))
                 .or(auth_api(state))
-                .or(user_api(state)),
+                .or(user_api(state))
+                .or(live_api(state))
+                .or(invite_api(state))
+                .or(audit_api(state))
+                .or(ban_api(state))
+                .or(template_api(state))
+                .or(bulk_api(state))
+                .or(backup_api(state))
+                .or(search_api(state)),
         )
         .boxed()
 }
@@ -55,9 +75,10 @@ pub fn routes(state: &State) -> impl Filter<Extract = (impl Reply,), Error = Rej
             "Access-Control-Allow-Origin",
             "content-type",
             "Authorization",
+            "Last-Event-ID",
         ])
         .allow_any_origin()
-        .allow_methods(vec!["GET", "POST", "PUT", "DELETE"]);
+        .allow_methods(vec!["GET", "POST", "PUT", "PATCH", "DELETE"]);
 
     let file_config = FileConfig::new(state.server_lib_root());
 
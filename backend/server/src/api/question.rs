@@ -1,20 +1,49 @@
 use crate::{
+    api::{
+        audit::{diff, record_audit_event},
+        bucket::ensure_can_view_bucket,
+    },
     error::Error,
     server_auth::{optional_user_filter, user_filter},
-    state::State,
-    util::{json_or_reject, sized_body_json},
+    state::{hub::Hub, State},
+    util::{json_or_reject, pagination, sized_body_json},
 };
 use db::{
+    answer::db_types::Answer,
+    audit::db_types::AuditAction,
+    bucket::db_types::DuplicatePolicy,
     favorite_question::db_types::NewFavoriteQuestionRelation,
-    question::db_types::{NewQuestion, Question},
+    page::Page,
+    question::{
+        db_types::{NewQuestion, NewQuestionDraw, Question, QuestionDraw, QuestionRevision},
+        similarity::SIMILARITY_THRESHOLD,
+    },
+    question_vote::db_types::{NewQuestionVote, QuestionVote, DOWNVOTE, UPVOTE},
+    tag::db_types::{NewQuestionTag, NewTag, Tag, TagFilter},
     BoxedRepository,
 };
+use log::info;
+use serde_json::{json, Value};
 use uuid::Uuid;
 use warp::{filters::BoxedFilter, path, query, Filter, Reply};
 
 pub const QUESTION_PATH: &str = "question";
 
-use wire::question::{NewQuestionRequest, BucketUuidQueryParam, SetArchivedRequest};
+/// How many of a bucket's most recent draws are avoided when drawing a question,
+/// unless the request specifies otherwise.
+const DEFAULT_RECENT_DRAWS_TO_AVOID: i64 = 5;
+/// The most draws that can be avoided when drawing a question.
+const RECENT_DRAWS_LIMIT: i64 = 50;
+/// How many similar questions are reported when a question is created.
+const SIMILAR_QUESTIONS_LIMIT: i64 = 5;
+
+use wire::question::{
+    BucketUuidQueryParam, CreatedQuestion, EditQuestionRequest, NewQuestionRequest, QuestionListQueryParam, QuestionSort, RandomQuestionQueryParam,
+    SetArchivedRequest, Vote, VoteRequest,
+};
+use wire::live::BucketEvent;
+use wire::page::{PageQuery, RecencySort};
+use wire::tag::{normalize_tag_name, split_tag_names, TagQuestionRequest};
 
 pub fn question_api(state: &State) -> BoxedFilter<(impl Reply,)> {
     // impl Filter<Extract=(impl Reply,), Error=Rejection> + Clone{
@@ -23,52 +52,63 @@ pub fn question_api(state: &State) -> BoxedFilter<(impl Reply,)> {
         .and(warp::post2())
         .and(sized_body_json(10))
         .and(optional_user_filter(state))
+        .and(state.hub())
         .and(state.db())
-        .map(
-            |request: NewQuestionRequest,
-             user_uuid: Option<Uuid>,
-             conn: BoxedRepository|
-             -> Result<Question, Error> {
-                let new_question = NewQuestion {
-                    bucket_uuid: request.bucket_uuid,
-                    user_uuid,
-                    question_text: request.question_text,
-                };
-                conn.create_question(new_question).map_err(Error::from)
-            },
-        )
+        .map(create_question_handler)
+        .and_then(json_or_reject);
+
+    let edit_question = path!(Uuid)
+        .and(warp::path::end())
+        .and(warp::put2())
+        .and(sized_body_json(10))
+        .and(user_filter(state))
+        .and(state.db())
+        .map(edit_question_handler)
+        .and_then(json_or_reject);
+
+    let question_revisions = path!(Uuid / "revisions")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .map(get_question_revisions_handler)
         .and_then(json_or_reject);
 
-    // TODO need a modify question endpoint
+    let revert_question = path!(Uuid / "revisions" / Uuid / "revert")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(revert_question_handler)
+        .and_then(json_or_reject);
 
-    // TODO Not sure of the value of this endpoint
-    // At the very least, you should be able to only delete questions that you either
-    // * created yourself
-    // * or have a permission to delete.
     let delete_question = path!(Uuid)
         .and(warp::path::end())
         .and(warp::delete2())
+        .and(user_filter(state))
         .and(state.db())
-        .map(
-            |question_uuid: Uuid, conn: BoxedRepository| -> Result<Question, Error> {
-                conn.delete_question(question_uuid).map_err(Error::from)
-            },
-        )
+        .map(delete_question_handler)
         .and_then(json_or_reject);
 
     let random_question = path!("random")
         .and(warp::path::end())
         .and(warp::get2())
         .and(query())
+        .and(optional_user_filter(state))
+        .and(state.hub())
         .and(state.db())
-        .map(
-            |query: BucketUuidQueryParam,
-             conn: BoxedRepository|
-             -> Result<Option<Question>, Error> {
-                conn.get_random_question(query.bucket_uuid)
-                    .map_err(Error::from)
-            },
-        )
+        .map(get_random_question_handler)
+        .and_then(json_or_reject);
+
+    let recent_draws = path!("draws")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(query())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .map(get_recent_draws_handler)
         .and_then(json_or_reject);
 
     let num_questions_in_bucket = path!("number")
@@ -88,11 +128,20 @@ pub fn question_api(state: &State) -> BoxedFilter<(impl Reply,)> {
         .and(warp::path::end())
         .and(warp::get2())
         .and(query())
+        .and(query())
         .and(state.db())
         .map(
-            |query: BucketUuidQueryParam, conn: BoxedRepository| -> Result<Vec<Question>, Error> {
-                conn.get_all_questions_for_bucket_of_given_archived_status(query.bucket_uuid, false)
-                    .map_err(Error::from)
+            |query: QuestionListQueryParam,
+             page: PageQuery<QuestionSort>,
+             conn: BoxedRepository|
+             -> Result<Page<Question>, Error> {
+                conn.get_all_questions_for_bucket_of_given_archived_status(
+                    query.bucket_uuid,
+                    false,
+                    &tag_filter(&query.include_tags, &query.exclude_tags),
+                    pagination(page, QuestionSort::default())?,
+                )
+                .map_err(Error::from)
             },
         )
         .and_then(json_or_reject);
@@ -101,27 +150,31 @@ pub fn question_api(state: &State) -> BoxedFilter<(impl Reply,)> {
         .and(warp::path::end())
         .and(warp::get2())
         .and(query())
+        .and(query())
         .and(state.db())
         .map(
-            |query: BucketUuidQueryParam, conn: BoxedRepository| -> Result<Vec<Question>, Error> {
-                conn.get_all_questions_for_bucket_of_given_archived_status(query.bucket_uuid, true)
-                    .map_err(Error::from)
+            |query: QuestionListQueryParam,
+             page: PageQuery<QuestionSort>,
+             conn: BoxedRepository|
+             -> Result<Page<Question>, Error> {
+                conn.get_all_questions_for_bucket_of_given_archived_status(
+                    query.bucket_uuid,
+                    true,
+                    &tag_filter(&query.include_tags, &query.exclude_tags),
+                    pagination(page, QuestionSort::default())?,
+                )
+                .map_err(Error::from)
             },
         )
         .and_then(json_or_reject);
 
-    // TODO, this may make sense to remove, or constrain to only putting the question back in the bucket.
     let set_question_archived_state = path!("archive")
         .and(warp::path::end())
         .and(warp::put2())
         .and(sized_body_json(2))
+        .and(user_filter(state))
         .and(state.db())
-        .map(
-            |request: SetArchivedRequest, conn: BoxedRepository| -> Result<Question, Error> {
-                conn.set_archive_status_for_question(request.question_uuid, request.archived)
-                    .map_err(Error::from)
-            },
-        )
+        .map(set_question_archived_state_handler)
         .and_then(json_or_reject);
 
     let favorite_question = path!(Uuid / "favorite")
@@ -159,27 +212,620 @@ pub fn question_api(state: &State) -> BoxedFilter<(impl Reply,)> {
     let get_favorite_questions = path!("favorites")
         .and(warp::path::end())
         .and(warp::get2())
+        .and(query())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(
+            |page: PageQuery<RecencySort>,
+             user_uuid: Uuid,
+             conn: BoxedRepository|
+             -> Result<Page<Question>, Error> {
+                let pagination = pagination(page, RecencySort::Oldest)?;
+                conn.get_favorite_questions(user_uuid, pagination)
+                    .map_err(Error::from)
+            },
+        )
+        .and_then(json_or_reject);
+
+    let vote_on_question = path!(Uuid / "vote")
+        .and(warp::path::end())
+        .and(warp::put2())
+        .and(sized_body_json(1))
+        .and(user_filter(state))
+        .and(state.db())
+        .map(vote_on_question_handler)
+        .and_then(json_or_reject);
+
+    let remove_vote_on_question = path!(Uuid / "vote")
+        .and(warp::path::end())
+        .and(warp::delete2())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(remove_vote_on_question_handler)
+        .and_then(json_or_reject);
+
+    let get_own_vote_on_question = path!(Uuid / "vote")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(
+            |question_uuid: Uuid,
+             user_uuid: Uuid,
+             conn: BoxedRepository|
+             -> Result<Option<QuestionVote>, Error> {
+                conn.get_vote(user_uuid, question_uuid)
+                    .map_err(Error::from)
+            },
+        )
+        .and_then(json_or_reject);
+
+    let tags_in_bucket = path!("tags")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(state.db())
+        .map(
+            |query: BucketUuidQueryParam, conn: BoxedRepository| -> Result<Vec<Tag>, Error> {
+                conn.get_tags_in_bucket(query.bucket_uuid)
+                    .map_err(Error::from)
+            },
+        )
+        .and_then(json_or_reject);
+
+    let tags_for_question = path!(Uuid / "tags")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(state.db())
+        .map(
+            |question_uuid: Uuid, conn: BoxedRepository| -> Result<Vec<Tag>, Error> {
+                conn.get_tags_for_question(question_uuid)
+                    .map_err(Error::from)
+            },
+        )
+        .and_then(json_or_reject);
+
+    let tag_question = path!(Uuid / "tags")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(sized_body_json(1))
         .and(user_filter(state))
         .and(state.db())
-        .map(|user_uuid: Uuid, conn: BoxedRepository| -> Result<Vec<Question>, Error> {
-            conn.get_favorite_questions(user_uuid).map_err(Error::from)
-        })
+        .map(tag_question_handler)
         .and_then(json_or_reject);
 
-    // TODO get answers for question: api/question/<uuid>/answers
+    let untag_question = path!(Uuid / "tags" / Uuid)
+        .and(warp::path::end())
+        .and(warp::delete2())
+        .and(user_filter(state))
+        .and(state.db())
+        .map(untag_question_handler)
+        .and_then(json_or_reject);
+
+    let answers_for_question = path!(Uuid / "answers")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .map(get_answers_for_question_handler)
+        .and_then(json_or_reject);
 
     path(QUESTION_PATH)
         .and(
             random_question
+                .or(recent_draws)
                 .or(create_question)
                 .or(delete_question)
+                .or(edit_question)
+                .or(question_revisions)
+                .or(revert_question)
                 .or(num_questions_in_bucket)
                 .or(all_questions_in_bucket)
                 .or(all_questions_on_floor)
                 .or(set_question_archived_state)
                 .or(favorite_question)
                 .or(unfavorite_question)
-                .or(get_favorite_questions),
+                .or(get_favorite_questions)
+                .or(vote_on_question)
+                .or(remove_vote_on_question)
+                .or(get_own_vote_on_question)
+                .or(tags_in_bucket)
+                .or(tags_for_question)
+                .or(tag_question)
+                .or(untag_question)
+                .or(answers_for_question),
         )
         .boxed()
 }
+
+/// Draws a random question from the bucket, and records who drew it.
+///
+/// Questions drawn recently are avoided, unless every question in the bucket was drawn recently.
+fn get_random_question_handler(
+    query: RandomQuestionQueryParam,
+    user_uuid: Option<Uuid>,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<Option<Question>, Error> {
+    info!("get_random_question_handler");
+    let recent_draws_to_avoid = query
+        .avoid_recent_draws
+        .unwrap_or(DEFAULT_RECENT_DRAWS_TO_AVOID)
+        .max(0)
+        .min(RECENT_DRAWS_LIMIT);
+    let tags = tag_filter(&query.include_tags, &query.exclude_tags);
+    let question =
+        match conn.get_random_question(query.bucket_uuid, recent_draws_to_avoid, &tags)? {
+            Some(question) => Some(question),
+            None => conn.get_random_question(query.bucket_uuid, 0, &tags)?,
+        };
+
+    if let Some(question) = &question {
+        let session = conn.get_active_session(query.bucket_uuid)?;
+        let new_draw = NewQuestionDraw {
+            question_uuid: question.uuid,
+            bucket_uuid: query.bucket_uuid,
+            user_uuid,
+            session_uuid: session.map(|session| session.uuid),
+        };
+        let draw = conn.record_draw(new_draw)?;
+        record_audit_event(
+            query.bucket_uuid,
+            user_uuid,
+            Some(question.uuid),
+            AuditAction::QuestionDrawn,
+            diff(None, Some(&draw)),
+            &conn,
+        )?;
+        let event = BucketEvent::QuestionDrawn {
+            question: question.clone().into(),
+        };
+        hub.publish(query.bucket_uuid, event);
+    }
+    Ok(question)
+}
+
+/// Casts the user's vote on a question, replacing any vote they previously cast on it.
+///
+/// Only members of the question's bucket can vote on it.
+fn vote_on_question_handler(
+    question_uuid: Uuid,
+    request: VoteRequest,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Question, Error> {
+    info!("vote_on_question_handler");
+    let question = conn.get_question(question_uuid)?;
+    verify_bucket_membership(
+        user_uuid,
+        question.bucket_uuid,
+        &conn,
+        "Only members of the bucket can vote on its questions.",
+    )?;
+
+    let vote = NewQuestionVote {
+        user_uuid,
+        question_uuid,
+        vote: match request.vote {
+            Vote::Up => UPVOTE,
+            Vote::Down => DOWNVOTE,
+        },
+    };
+    let voted = conn.set_vote(vote)?;
+    record_audit_event(
+        question.bucket_uuid,
+        Some(user_uuid),
+        Some(question_uuid),
+        AuditAction::QuestionVoted,
+        diff(Some(&question), Some(&voted)),
+        &conn,
+    )?;
+    Ok(voted)
+}
+
+/// Takes back the user's vote on a question.
+fn remove_vote_on_question_handler(
+    question_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Question, Error> {
+    info!("remove_vote_on_question_handler");
+    let question = conn.get_question(question_uuid)?;
+    let unvoted = conn.remove_vote(user_uuid, question_uuid)?;
+    record_audit_event(
+        question.bucket_uuid,
+        Some(user_uuid),
+        Some(question_uuid),
+        AuditAction::QuestionVoteRemoved,
+        diff(Some(&question), Some(&unvoted)),
+        &conn,
+    )?;
+    Ok(unvoted)
+}
+
+/// Creates the question, and tags it with the requested tags.
+///
+/// Questions already in the bucket that are like the new one are returned alongside it,
+/// unless the bucket rejects duplicates, in which case the question isn't created at all.
+fn create_question_handler(
+    request: NewQuestionRequest,
+    user_uuid: Option<Uuid>,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<CreatedQuestion, Error> {
+    info!("create_question_handler");
+    let bucket_uuid = request.bucket_uuid;
+    let bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    let similar_questions = conn.find_similar_questions(
+        bucket_uuid,
+        &request.question_text,
+        SIMILARITY_THRESHOLD,
+        SIMILAR_QUESTIONS_LIMIT,
+    )?;
+    if bucket.duplicate_policy == DuplicatePolicy::Reject {
+        if let Some(closest) = similar_questions.first() {
+            return Err(Error::PreconditionNotMet(format!(
+                "The bucket already has a question like this one: \"{}\"",
+                closest.question.question_text
+            )));
+        }
+    }
+
+    let new_question = NewQuestion {
+        bucket_uuid,
+        user_uuid,
+        question_text: request.question_text,
+    };
+    let question =
+        conn.create_tagged_question(new_question, normalized_tag_names(&request.tags))?;
+
+    record_audit_event(
+        bucket_uuid,
+        user_uuid,
+        Some(question.uuid),
+        AuditAction::QuestionCreated,
+        diff(None, Some(&question)),
+        &conn,
+    )?;
+    let event = BucketEvent::QuestionAdded {
+        question: question.clone().into(),
+    };
+    hub.publish(bucket_uuid, event);
+    Ok(CreatedQuestion {
+        question: question.into(),
+        similar_questions: similar_questions.into_iter().map(Into::into).collect(),
+    })
+}
+
+/// Normalizes the tag names, leaving out any that are empty or repeated.
+pub fn normalized_tag_names(tags: &[String]) -> Vec<String> {
+    let mut tag_names: Vec<String> = tags
+        .iter()
+        .map(|tag| normalize_tag_name(tag))
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tag_names.sort();
+    tag_names.dedup();
+    tag_names
+}
+
+/// Deletes the question, if the user wrote it, or can delete the questions in its bucket.
+fn delete_question_handler(
+    question_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Question, Error> {
+    info!("delete_question_handler");
+    let question = conn.get_question(question_uuid)?;
+    if question.user_uuid != Some(user_uuid) {
+        let reason = "Only the question's author, or users with permission to delete questions, can delete it.";
+        match conn.get_permissions(user_uuid, question.bucket_uuid) {
+            Ok(permissions) if permissions.delete_questions_permission => {}
+            Ok(_) | Err(diesel::result::Error::NotFound) => {
+                return Err(Error::not_authorized(reason))
+            }
+            Err(e) => return Err(Error::from(e)),
+        }
+    }
+    let deleted = conn.delete_question(question_uuid)?;
+    record_audit_event(
+        question.bucket_uuid,
+        Some(user_uuid),
+        Some(question_uuid),
+        AuditAction::QuestionDeleted,
+        diff(Some(&deleted), None),
+        &conn,
+    )?;
+    Ok(deleted)
+}
+
+/// Moves the question onto or off of the floor.
+///
+/// Members of the bucket can always do this, while anyone else can only do so while the bucket is being drawn from.
+fn set_question_archived_state_handler(
+    request: SetArchivedRequest,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Question, Error> {
+    info!("set_question_archived_state_handler");
+    let question = conn.get_question(request.question_uuid)?;
+    if !can_archive_questions(Some(user_uuid), question.bucket_uuid, &conn)? {
+        return Err(Error::not_authorized(
+            "Only members of the bucket can archive its questions while drawing is disabled.",
+        ));
+    }
+    let archived = conn.set_archive_status_for_question(request.question_uuid, request.archived)?;
+    record_audit_event(
+        question.bucket_uuid,
+        Some(user_uuid),
+        Some(question.uuid),
+        AuditAction::QuestionArchiveStateSet,
+        diff(Some(&question), Some(&archived)),
+        &conn,
+    )?;
+    Ok(archived)
+}
+
+/// Replaces the question's text, keeping the previous text as a revision.
+fn edit_question_handler(
+    question_uuid: Uuid,
+    request: EditQuestionRequest,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Question, Error> {
+    info!("edit_question_handler");
+    let question = conn.get_question(question_uuid)?;
+    verify_can_edit_question(user_uuid, &question, &conn)?;
+
+    if request.question_text.trim().is_empty() {
+        return Err(Error::BadRequest("Questions can't be empty.".to_string()));
+    }
+    let edited = conn.edit_question(question_uuid, request.question_text, Some(user_uuid))?;
+    record_audit_event(
+        question.bucket_uuid,
+        Some(user_uuid),
+        Some(question_uuid),
+        AuditAction::QuestionEdited,
+        diff(Some(&question), Some(&edited)),
+        &conn,
+    )?;
+    Ok(edited)
+}
+
+/// Restores the text the question had in the revision.
+///
+/// The text being replaced is kept as a revision as well, so reverts can themselves be reverted.
+fn revert_question_handler(
+    question_uuid: Uuid,
+    revision_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Question, Error> {
+    info!("revert_question_handler");
+    let question = conn.get_question(question_uuid)?;
+    verify_can_edit_question(user_uuid, &question, &conn)?;
+
+    let revision = match conn.get_question_revision(revision_uuid) {
+        Ok(revision) if revision.question_uuid == question_uuid => revision,
+        Ok(_) | Err(diesel::result::Error::NotFound) => {
+            return Err(Error::BadRequest(
+                "The revision doesn't belong to the question.".to_string(),
+            ))
+        }
+        Err(e) => return Err(Error::from(e)),
+    };
+    let reverted = conn.edit_question(question_uuid, revision.question_text, Some(user_uuid))?;
+    record_audit_event(
+        question.bucket_uuid,
+        Some(user_uuid),
+        Some(question_uuid),
+        AuditAction::QuestionReverted,
+        diff(Some(&question), Some(&reverted)),
+        &conn,
+    )?;
+    Ok(reverted)
+}
+
+/// Gets a page of the question's revisions, the most recent first unless the request says otherwise.
+///
+/// Only those who can see the question's bucket can see its revisions.
+fn get_question_revisions_handler(
+    question_uuid: Uuid,
+    page: PageQuery<RecencySort>,
+    user_uuid: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<Page<QuestionRevision>, Error> {
+    info!("get_question_revisions_handler");
+    let question = conn.get_question(question_uuid)?;
+    ensure_can_view_bucket(question.bucket_uuid, user_uuid, &conn)?;
+    let pagination = pagination(page, RecencySort::Newest)?;
+    conn.get_question_revisions(question_uuid, pagination)
+        .map_err(Error::from)
+}
+
+/// Gets a page of the answers given to the question, the oldest first unless the request says otherwise.
+///
+/// Private answers are only included for the user who gave them.
+fn get_answers_for_question_handler(
+    question_uuid: Uuid,
+    page: PageQuery<RecencySort>,
+    user_uuid: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<Page<Answer>, Error> {
+    info!("get_answers_for_question_handler");
+    let question = conn.get_question(question_uuid)?;
+    ensure_can_view_bucket(question.bucket_uuid, user_uuid, &conn)?;
+    let pagination = pagination(page, RecencySort::Oldest)?;
+    conn.get_visible_answers_for_question(question_uuid, user_uuid, pagination)
+        .map_err(Error::from)
+}
+
+/// Gets a page of the questions drawn from the bucket, the most recent first unless the request says otherwise.
+fn get_recent_draws_handler(
+    query: BucketUuidQueryParam,
+    page: PageQuery<RecencySort>,
+    user_uuid: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<Page<QuestionDraw>, Error> {
+    info!("get_recent_draws_handler");
+    ensure_can_view_bucket(query.bucket_uuid, user_uuid, &conn)?;
+    let pagination = pagination(page, RecencySort::Newest)?;
+    conn.get_recent_draws(query.bucket_uuid, pagination)
+        .map_err(Error::from)
+}
+
+/// Rejects users that neither wrote the question, nor can moderate the questions in its bucket.
+fn verify_can_edit_question(
+    user_uuid: Uuid,
+    question: &Question,
+    conn: &BoxedRepository,
+) -> Result<(), Error> {
+    if question.user_uuid == Some(user_uuid) {
+        return Ok(());
+    }
+    let reason = "Only the question's author, or users with permission to moderate questions, can edit it.";
+    match conn.get_permissions(user_uuid, question.bucket_uuid) {
+        Ok(permissions) if permissions.moderate_questions_permission => Ok(()),
+        Ok(_) | Err(diesel::result::Error::NotFound) => Err(Error::not_authorized(reason)),
+        Err(e) => Err(Error::from(e)),
+    }
+}
+
+/// Tags the question, creating the tag if the bucket doesn't have it yet.
+///
+/// Returns the question's tags.
+fn tag_question_handler(
+    question_uuid: Uuid,
+    request: TagQuestionRequest,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Vec<Tag>, Error> {
+    info!("tag_question_handler");
+    let question = conn.get_question(question_uuid)?;
+    verify_bucket_membership(
+        user_uuid,
+        question.bucket_uuid,
+        &conn,
+        "Only members of the bucket can tag its questions.",
+    )?;
+
+    let tag_name = normalize_tag_name(&request.tag_name);
+    if tag_name.is_empty() {
+        return Err(Error::BadRequest("Tag names can't be empty.".to_string()));
+    }
+    let old_tags = conn.get_tags_for_question(question_uuid)?;
+    let tag = conn.get_or_create_tag(NewTag {
+        bucket_uuid: question.bucket_uuid,
+        tag_name,
+    })?;
+    conn.tag_question(NewQuestionTag {
+        question_uuid,
+        tag_uuid: tag.uuid,
+    })?;
+    let tags = conn.get_tags_for_question(question_uuid)?;
+    record_audit_event(
+        question.bucket_uuid,
+        Some(user_uuid),
+        Some(question_uuid),
+        AuditAction::QuestionTagged,
+        tags_diff(&old_tags, &tags),
+        &conn,
+    )?;
+    Ok(tags)
+}
+
+/// Removes the tag from the question.
+///
+/// Returns the question's remaining tags.
+fn untag_question_handler(
+    question_uuid: Uuid,
+    tag_uuid: Uuid,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Vec<Tag>, Error> {
+    info!("untag_question_handler");
+    let question = conn.get_question(question_uuid)?;
+    verify_bucket_membership(
+        user_uuid,
+        question.bucket_uuid,
+        &conn,
+        "Only members of the bucket can untag its questions.",
+    )?;
+
+    let old_tags = conn.get_tags_for_question(question_uuid)?;
+    conn.untag_question(NewQuestionTag {
+        question_uuid,
+        tag_uuid,
+    })?;
+    let tags = conn.get_tags_for_question(question_uuid)?;
+    record_audit_event(
+        question.bucket_uuid,
+        Some(user_uuid),
+        Some(question_uuid),
+        AuditAction::QuestionUntagged,
+        tags_diff(&old_tags, &tags),
+        &conn,
+    )?;
+    Ok(tags)
+}
+
+/// The change in the names of a question's tags, for the audit log.
+fn tags_diff(old_tags: &[Tag], new_tags: &[Tag]) -> Value {
+    let names = |tags: &[Tag]| -> Vec<String> { tags.iter().map(|tag| tag.tag_name.clone()).collect() };
+    diff(
+        Some(&json!({ "tags": names(old_tags) })),
+        Some(&json!({ "tags": names(new_tags) })),
+    )
+}
+
+/// Whether the user can archive the bucket's questions.
+///
+/// Anyone can while drawing is enabled, otherwise only the bucket's members can.
+pub(crate) fn can_archive_questions(
+    user_uuid: Option<Uuid>,
+    bucket_uuid: Uuid,
+    conn: &BoxedRepository,
+) -> Result<bool, Error> {
+    let bucket = conn.get_bucket_by_uuid(bucket_uuid)?;
+    if bucket.drawing_enabled {
+        return Ok(true);
+    }
+    let user_uuid = match user_uuid {
+        Some(user_uuid) => user_uuid,
+        None => return Ok(false),
+    };
+    match conn.get_permissions(user_uuid, bucket_uuid) {
+        Ok(_) => Ok(true),
+        Err(diesel::result::Error::NotFound) => Ok(false),
+        Err(e) => Err(Error::from(e)),
+    }
+}
+
+/// Rejects users that aren't members of the bucket.
+fn verify_bucket_membership(
+    user_uuid: Uuid,
+    bucket_uuid: Uuid,
+    conn: &BoxedRepository,
+    reason: &str,
+) -> Result<(), Error> {
+    match conn.get_permissions(user_uuid, bucket_uuid) {
+        Ok(_) => Ok(()),
+        Err(diesel::result::Error::NotFound) => Err(Error::not_authorized(reason)),
+        Err(e) => Err(Error::from(e)),
+    }
+}
+
+/// Creates a tag filter from comma separated lists of tag names.
+fn tag_filter(include_tags: &Option<String>, exclude_tags: &Option<String>) -> TagFilter {
+    TagFilter {
+        include: include_tags
+            .as_ref()
+            .map(|tags| split_tag_names(tags))
+            .unwrap_or_default(),
+        exclude: exclude_tags
+            .as_ref()
+            .map(|tags| split_tag_names(tags))
+            .unwrap_or_default(),
+    }
+}
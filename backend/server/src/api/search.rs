@@ -0,0 +1,104 @@
+//! Searches the questions and answers in the buckets a user can see.
+use crate::{error::Error, server_auth::optional_user_filter, state::State, util::json_or_reject};
+use db::{search::db_types::SearchResults, BoxedRepository};
+use log::info;
+use uuid::Uuid;
+use warp::{filters::BoxedFilter, path, query, Filter, Reply};
+use wire::search::SearchQueryParam;
+
+pub const SEARCH_PATH: &str = "search";
+
+/// How many questions, and how many answers, are found by a search.
+const SEARCH_RESULTS_LIMIT: i64 = 25;
+
+pub fn search_api(state: &State) -> BoxedFilter<(impl Reply,)> {
+    path(SEARCH_PATH)
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .map(search_handler)
+        .and_then(json_or_reject)
+        .boxed()
+}
+
+/// Searches the publicly visible buckets, along with the buckets the user is a member of.
+fn search_handler(
+    query: SearchQueryParam,
+    user_uuid: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<SearchResults, Error> {
+    info!("search_handler");
+    let search = query.q.trim();
+    if search.is_empty() {
+        return Err(Error::BadRequest(
+            "Nothing was given to search for.".to_string(),
+        ));
+    }
+    conn.search(search, user_uuid, SEARCH_RESULTS_LIMIT)
+        .map_err(Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_util::execute_test_on_repository;
+    use db::{
+        bucket::db_types::BucketFlagChangeset, question::db_types::NewQuestion,
+        test::bucket_user_relation_fixture::UserBucketRelationFixture, RepositoryProvider,
+    };
+
+    #[test]
+    fn search_only_finds_visible_buckets() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let new_question = NewQuestion {
+                    bucket_uuid: fixture.bucket.uuid,
+                    user_uuid: Some(fixture.user1.uuid),
+                    question_text: "What's your favorite penguin?".to_string(),
+                };
+                repo.create_question(new_question)
+                    .expect("Should create question");
+                let changeset = BucketFlagChangeset {
+                    uuid: fixture.bucket.uuid,
+                    public_viewable: Some(false),
+                    drawing_enabled: None,
+                    exclusive: None,
+                    draw_strategy: None,
+                    duplicate_policy: None,
+                };
+                repo.change_bucket_flags(changeset)
+                    .expect("Should hide bucket");
+
+                let search = || SearchQueryParam {
+                    q: " penguin ".to_string(),
+                };
+                let results = search_handler(search(), None, repo).expect("Should search");
+                assert!(results.questions.is_empty());
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let results = search_handler(search(), Some(fixture.user1.uuid), repo)
+                    .expect("Should search");
+                assert_eq!(results.questions.len(), 1);
+            },
+        )
+    }
+
+    #[test]
+    fn empty_search_is_rejected() {
+        execute_test_on_repository(
+            |_fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let query = SearchQueryParam {
+                    q: "   ".to_string(),
+                };
+                match search_handler(query, None, repo) {
+                    Err(Error::BadRequest(_)) => {}
+                    other => panic!("Expected a bad request, got: {:?}", other),
+                }
+            },
+        )
+    }
+}
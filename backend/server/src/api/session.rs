@@ -0,0 +1,328 @@
+use crate::{
+    api::{
+        audit::{diff, record_audit_event},
+        bucket::ensure_can_view_bucket,
+    },
+    error::Error,
+    server_auth::{optional_user_filter, user_filter},
+    state::{hub::Hub, State},
+    util::{json_or_reject, pagination, sized_body_json},
+};
+use db::{
+    audit::db_types::AuditAction,
+    bucket::db_types::BucketFlagChangeset,
+    page::Page,
+    session::db_types::{NewSession, NewSessionParticipant, Session},
+    BoxedRepository,
+};
+use log::info;
+use uuid::Uuid;
+use warp::{filters::BoxedFilter, path, query, Filter, Reply};
+use wire::{
+    live::BucketEvent,
+    page::{PageQuery, RecencySort},
+    question::BucketUuidQueryParam,
+    session::{SessionTranscript, StartSessionRequest, TranscriptEntry},
+};
+
+pub const SESSION_PATH: &str = "session";
+
+pub fn session_api(state: &State) -> BoxedFilter<(impl Reply,)> {
+    let start_session = warp::path::end()
+        .and(warp::post2())
+        .and(sized_body_json(1))
+        .and(user_filter(state))
+        .and(state.hub())
+        .and(state.db())
+        .map(start_session_handler)
+        .and_then(json_or_reject);
+
+    let end_session = path!(Uuid / "end")
+        .and(warp::path::end())
+        .and(warp::put2())
+        .and(user_filter(state))
+        .and(state.hub())
+        .and(state.db())
+        .map(end_session_handler)
+        .and_then(json_or_reject);
+
+    let get_sessions_for_bucket = warp::path::end()
+        .and(warp::get2())
+        .and(query())
+        .and(query())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .map(get_sessions_for_bucket_handler)
+        .and_then(json_or_reject);
+
+    let get_transcript = path!(Uuid / "transcript")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(optional_user_filter(state))
+        .and(state.db())
+        .map(get_transcript_handler)
+        .and_then(json_or_reject);
+
+    path(SESSION_PATH)
+        .and(
+            start_session
+                .or(end_session)
+                .or(get_sessions_for_bucket)
+                .or(get_transcript),
+        )
+        .boxed()
+}
+
+/// Starts a session for the bucket.
+/// Everyone in the bucket at the time is recorded as a participant, and drawing is enabled.
+fn start_session_handler(
+    request: StartSessionRequest,
+    user_uuid: Uuid,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<Session, Error> {
+    info!("start_session_handler");
+    let permissions_for_acting_user = conn
+        .get_permissions(user_uuid, request.bucket_uuid)
+        .map_err(Error::from)?;
+    if !permissions_for_acting_user.set_drawing_permission {
+        return Err(Error::not_authorized(
+            "User does not have privileges to start a session for this bucket.",
+        ));
+    }
+    if conn.get_active_session(request.bucket_uuid)?.is_some() {
+        return Err(Error::PreconditionNotMet(
+            "A session is already going on for this bucket.".to_string(),
+        ));
+    }
+
+    let new_session = NewSession {
+        bucket_uuid: request.bucket_uuid,
+    };
+    let session = conn.create_session(new_session)?;
+
+    for (_, user) in conn.get_permissions_all_users_in_bucket(request.bucket_uuid)? {
+        let participant = NewSessionParticipant {
+            session_uuid: session.uuid,
+            user_uuid: user.uuid,
+        };
+        conn.add_participant(participant)?;
+    }
+
+    let changeset = BucketFlagChangeset {
+        uuid: request.bucket_uuid,
+        public_viewable: None,
+        drawing_enabled: Some(true),
+        exclusive: None,
+        draw_strategy: None,
+        duplicate_policy: None,
+    };
+    let bucket = conn.change_bucket_flags(changeset)?;
+    record_audit_event(
+        request.bucket_uuid,
+        Some(user_uuid),
+        Some(session.uuid),
+        AuditAction::SessionStarted,
+        diff(None, Some(&session)),
+        &conn,
+    )?;
+    let event = BucketEvent::FlagsChanged {
+        bucket: bucket.into(),
+    };
+    hub.publish(request.bucket_uuid, event);
+
+    Ok(session)
+}
+
+/// Ends the session, and disables drawing for the bucket.
+fn end_session_handler(
+    session_uuid: Uuid,
+    user_uuid: Uuid,
+    hub: Hub,
+    conn: BoxedRepository,
+) -> Result<Session, Error> {
+    info!("end_session_handler");
+    let session = conn.get_session(session_uuid)?;
+    let permissions_for_acting_user = conn
+        .get_permissions(user_uuid, session.bucket_uuid)
+        .map_err(Error::from)?;
+    if !permissions_for_acting_user.set_drawing_permission {
+        return Err(Error::not_authorized(
+            "User does not have privileges to end a session for this bucket.",
+        ));
+    }
+    if session.ended_at.is_some() {
+        return Err(Error::PreconditionNotMet(
+            "The session has already ended.".to_string(),
+        ));
+    }
+
+    let old_session = session;
+    let session = conn.end_session(session_uuid)?;
+
+    let changeset = BucketFlagChangeset {
+        uuid: session.bucket_uuid,
+        public_viewable: None,
+        drawing_enabled: Some(false),
+        exclusive: None,
+        draw_strategy: None,
+        duplicate_policy: None,
+    };
+    let bucket = conn.change_bucket_flags(changeset)?;
+    record_audit_event(
+        session.bucket_uuid,
+        Some(user_uuid),
+        Some(session.uuid),
+        AuditAction::SessionEnded,
+        diff(Some(&old_session), Some(&session)),
+        &conn,
+    )?;
+    let event = BucketEvent::FlagsChanged {
+        bucket: bucket.into(),
+    };
+    hub.publish(session.bucket_uuid, event);
+
+    Ok(session)
+}
+
+/// Gets a page of the sessions played in the bucket, the most recent first unless the request says otherwise.
+fn get_sessions_for_bucket_handler(
+    query: BucketUuidQueryParam,
+    page: PageQuery<RecencySort>,
+    user_uuid: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<Page<Session>, Error> {
+    info!("get_sessions_for_bucket_handler");
+    ensure_can_view_bucket(query.bucket_uuid, user_uuid, &conn)?;
+    let pagination = pagination(page, RecencySort::Newest)?;
+    conn.get_sessions_for_bucket(query.bucket_uuid, pagination)
+        .map_err(Error::from)
+}
+
+/// Gets everything that happened during a session.
+/// Answers that aren't publicly visible are only included for the user that gave them,
+/// everyone else just sees that the question was drawn.
+fn get_transcript_handler(
+    session_uuid: Uuid,
+    user_uuid: Option<Uuid>,
+    conn: BoxedRepository,
+) -> Result<SessionTranscript, Error> {
+    info!("get_transcript_handler");
+    let session = conn.get_session(session_uuid)?;
+    ensure_can_view_bucket(session.bucket_uuid, user_uuid, &conn)?;
+    let participants = conn.get_participants(session_uuid)?;
+    let entries = conn
+        .get_transcript(session_uuid)?
+        .into_iter()
+        .map(|(question, answer)| TranscriptEntry {
+            question: question.into(),
+            answer: answer
+                .filter(|answer| {
+                    answer.publicly_visible
+                        || (user_uuid.is_some() && answer.user_uuid == user_uuid)
+                })
+                .map(Into::into),
+        })
+        .collect();
+
+    Ok(SessionTranscript {
+        session: session.into(),
+        participants: participants.into_iter().map(Into::into).collect(),
+        entries,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_util::execute_test_on_repository;
+    use db::{
+        answer::db_types::NewAnswer,
+        test::{
+            answer_fixture::AnswerFixture,
+            bucket_user_relation_fixture::{UserBucketRelationFixture, NO_PERMISSIONS},
+        },
+        RepositoryProvider,
+    };
+
+    #[test]
+    fn start_and_end_session() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let request = StartSessionRequest {
+                    bucket_uuid: fixture.bucket.uuid,
+                };
+                let session =
+                    start_session_handler(request, fixture.user1.uuid, Hub::default(), repo)
+                        .expect("Should start session");
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                assert!(repo.get_bucket_by_uuid(fixture.bucket.uuid).unwrap().drawing_enabled);
+                assert_eq!(
+                    repo.get_participants(session.uuid).unwrap(),
+                    vec![fixture.user1.clone()]
+                );
+
+                let session =
+                    end_session_handler(session.uuid, fixture.user1.uuid, Hub::default(), repo)
+                        .expect("Should end session");
+                assert!(session.ended_at.is_some());
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                assert!(!repo.get_bucket_by_uuid(fixture.bucket.uuid).unwrap().drawing_enabled);
+            },
+        )
+    }
+
+    #[test]
+    fn cant_start_session_without_permission() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                fixture.add_user2(NO_PERMISSIONS, &repo);
+
+                let request = StartSessionRequest {
+                    bucket_uuid: fixture.bucket.uuid,
+                };
+                start_session_handler(request, fixture.user2.uuid, Hub::default(), repo)
+                    .expect_err("User without drawing permission should not start a session");
+            },
+        )
+    }
+
+    #[test]
+    fn transcript_hides_private_answers() {
+        execute_test_on_repository(|fixture: &AnswerFixture, provider: RepositoryProvider| {
+            let repo = provider.get_repo().expect("Should get repo.");
+            let request = StartSessionRequest {
+                bucket_uuid: fixture.bucket.uuid,
+            };
+            let session = start_session_handler(request, fixture.user.uuid, Hub::default(), repo)
+                .expect("Should start session");
+
+            let repo = provider.get_repo().expect("Should get repo.");
+            let new_answer = NewAnswer {
+                user_uuid: Some(fixture.user.uuid),
+                question_uuid: fixture.question.uuid,
+                publicly_visible: false,
+                answer_text: "A private answer".to_string(),
+                session_uuid: Some(session.uuid),
+            };
+            repo.create_answer(new_answer)
+                .expect("Should create new answer");
+
+            let repo = provider.get_repo().expect("Should get repo.");
+            let transcript =
+                get_transcript_handler(session.uuid, None, repo).expect("Should get transcript");
+            assert_eq!(transcript.entries.len(), 1);
+            assert_eq!(transcript.entries[0].answer, None);
+
+            let repo = provider.get_repo().expect("Should get repo.");
+            let transcript = get_transcript_handler(session.uuid, Some(fixture.user.uuid), repo)
+                .expect("Should get transcript");
+            assert_eq!(transcript.entries.len(), 1);
+            assert!(transcript.entries[0].answer.is_some());
+        })
+    }
+}
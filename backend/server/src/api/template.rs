@@ -0,0 +1,357 @@
+//! Copies buckets, either into new buckets to be played in, or into templates that new buckets are made from.
+//!
+//! Templates are buckets that can't be joined.
+//! Public templates can be copied by anyone, while other buckets can only be copied by their members.
+use crate::{
+    api::{
+        audit::{diff, record_audit_event},
+        bucket::{unused_slug, BUCKET_PATH},
+    },
+    error::Error,
+    server_auth::user_filter,
+    state::State,
+    util::{json_or_reject, pagination, sized_body_json},
+};
+use db::{
+    audit::db_types::AuditAction,
+    bucket::db_types::{Bucket, BucketCopy, BucketFlagChangeset, NewBucket},
+    bucket_user_relation::db_types::BucketUserPermissions,
+    page::Page,
+    BoxedRepository,
+};
+use log::info;
+use uuid::Uuid;
+use warp::{filters::BoxedFilter, path, query, Filter, Reply};
+use wire::{
+    bucket::{BucketSort, CloneBucketRequest, SaveTemplateRequest},
+    page::PageQuery,
+};
+
+pub fn template_api(state: &State) -> BoxedFilter<(impl Reply,)> {
+    let clone_bucket = path!(Uuid / "clone")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(sized_body_json(1))
+        .and(user_filter(state))
+        .and(state.db())
+        .map(clone_bucket_handler)
+        .and_then(json_or_reject);
+
+    let save_template = path!(Uuid / "template")
+        .and(warp::path::end())
+        .and(warp::post2())
+        .and(sized_body_json(1))
+        .and(user_filter(state))
+        .and(state.db())
+        .map(save_template_handler)
+        .and_then(json_or_reject);
+
+    let get_public_templates = path!("templates")
+        .and(warp::path::end())
+        .and(warp::get2())
+        .and(query())
+        .and(state.db())
+        .map(get_public_templates_handler)
+        .and_then(json_or_reject);
+
+    path(BUCKET_PATH)
+        .and(clone_bucket.or(save_template).or(get_public_templates))
+        .boxed()
+}
+
+/// Gets the user's permissions in the bucket, if they are a member of it.
+fn member_permissions(
+    user_uuid: Uuid,
+    bucket_uuid: Uuid,
+    conn: &BoxedRepository,
+) -> Result<Option<BucketUserPermissions>, Error> {
+    match conn.get_permissions(user_uuid, bucket_uuid) {
+        Ok(permissions) => Ok(Some(permissions)),
+        Err(diesel::result::Error::NotFound) => Ok(None),
+        Err(e) => Err(Error::from(e)),
+    }
+}
+
+/// Makes the name and slug of a bucket copy, as long as the name isn't empty.
+fn new_bucket(bucket_name: String, conn: &BoxedRepository) -> Result<NewBucket, Error> {
+    if bucket_name.trim().is_empty() {
+        return Err(Error::BadRequest(
+            "The bucket's name can't be empty.".to_string(),
+        ));
+    }
+    Ok(NewBucket {
+        bucket_slug: unused_slug(&bucket_name, None, conn)?,
+        bucket_name,
+    })
+}
+
+/// Makes a new bucket out of the questions in another bucket or in a template.
+///
+/// The user owns the new bucket.
+/// Only members who can grant permissions can bring the other members along.
+fn clone_bucket_handler(
+    bucket_uuid: Uuid,
+    request: CloneBucketRequest,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Bucket, Error> {
+    info!("clone_bucket_handler");
+    let source = conn.get_bucket_by_uuid(bucket_uuid)?;
+    let permissions = member_permissions(user_uuid, bucket_uuid, &conn)?;
+    let is_public_template = source.is_template && source.public_viewable;
+    if permissions.is_none() && !is_public_template {
+        return Err(Error::not_authorized("Only members can copy this bucket."));
+    }
+    let can_grant = permissions
+        .map(|permissions| permissions.grant_permissions_permission)
+        .unwrap_or(false);
+    if request.copy_member_roles && !can_grant {
+        return Err(Error::not_authorized(
+            "Only members who can grant permissions can copy the bucket's members.",
+        ));
+    }
+
+    let flags = if request.copy_flags {
+        BucketFlagChangeset {
+            uuid: source.uuid,
+            public_viewable: Some(source.public_viewable),
+            drawing_enabled: Some(source.drawing_enabled),
+            exclusive: Some(source.exclusive),
+            draw_strategy: Some(source.draw_strategy),
+            duplicate_policy: Some(source.duplicate_policy),
+        }
+    } else {
+        BucketFlagChangeset {
+            uuid: source.uuid,
+            public_viewable: None,
+            drawing_enabled: None,
+            exclusive: None,
+            draw_strategy: None,
+            duplicate_policy: None,
+        }
+    };
+    let copy = BucketCopy {
+        source_uuid: source.uuid,
+        new_bucket: new_bucket(request.bucket_name, &conn)?,
+        owner_uuid: user_uuid,
+        copy_tags: request.copy_tags,
+        copy_members: request.copy_member_roles,
+        is_template: false,
+        flags,
+    };
+    let bucket = conn.copy_bucket(copy)?;
+    record_audit_event(
+        bucket.uuid,
+        Some(user_uuid),
+        None,
+        AuditAction::BucketCloned,
+        diff(Some(&source), Some(&bucket)),
+        &conn,
+    )?;
+    Ok(bucket)
+}
+
+/// Saves the bucket's questions and tags as a template owned by the user.
+///
+/// Any member of the bucket can save it as a template.
+fn save_template_handler(
+    bucket_uuid: Uuid,
+    request: SaveTemplateRequest,
+    user_uuid: Uuid,
+    conn: BoxedRepository,
+) -> Result<Bucket, Error> {
+    info!("save_template_handler");
+    let source = conn.get_bucket_by_uuid(bucket_uuid)?;
+    if member_permissions(user_uuid, bucket_uuid, &conn)?.is_none() {
+        return Err(Error::not_authorized(
+            "Only members can save this bucket as a template.",
+        ));
+    }
+
+    let copy = BucketCopy {
+        source_uuid: source.uuid,
+        new_bucket: new_bucket(request.template_name, &conn)?,
+        owner_uuid: user_uuid,
+        copy_tags: true,
+        copy_members: false,
+        is_template: true,
+        // Buckets made from the template can take its draw strategy and duplicate policy.
+        flags: BucketFlagChangeset {
+            uuid: source.uuid,
+            public_viewable: Some(request.public),
+            drawing_enabled: None,
+            exclusive: None,
+            draw_strategy: Some(source.draw_strategy),
+            duplicate_policy: Some(source.duplicate_policy),
+        },
+    };
+    let template = conn.copy_bucket(copy)?;
+    record_audit_event(
+        template.uuid,
+        Some(user_uuid),
+        None,
+        AuditAction::TemplateSaved,
+        diff(Some(&source), Some(&template)),
+        &conn,
+    )?;
+    Ok(template)
+}
+
+/// Gets a page of the public templates, ordered by name unless the request says otherwise.
+fn get_public_templates_handler(
+    page: PageQuery<BucketSort>,
+    conn: BoxedRepository,
+) -> Result<Page<Bucket>, Error> {
+    info!("get_public_templates_handler");
+    let pagination = pagination(page, BucketSort::Name)?;
+    conn.get_public_templates(pagination).map_err(Error::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_util::execute_test_on_repository;
+    use db::{
+        page::Pagination,
+        question::db_types::{NewQuestion, QuestionSort},
+        tag::db_types::{NewQuestionTag, NewTag, TagFilter},
+        test::bucket_user_relation_fixture::{UserBucketRelationFixture, NO_PERMISSIONS},
+        RepositoryProvider,
+    };
+
+    fn clone_request(copy_member_roles: bool) -> CloneBucketRequest {
+        CloneBucketRequest {
+            bucket_name: "Copy".to_string(),
+            copy_tags: true,
+            copy_flags: false,
+            copy_member_roles,
+        }
+    }
+
+    fn expect_not_authorized(result: Result<Bucket, Error>) {
+        match result.unwrap_err() {
+            Error::NotAuthorized { .. } => {}
+            other => panic!("Expected not authorized, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn clone_copies_questions_tags_and_members() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let new_question = NewQuestion {
+                    bucket_uuid: fixture.bucket.uuid,
+                    user_uuid: Some(fixture.user1.uuid),
+                    question_text: "What's your favorite color?".to_string(),
+                };
+                let question = repo
+                    .create_question(new_question)
+                    .expect("Should create question");
+                let new_tag = NewTag {
+                    bucket_uuid: fixture.bucket.uuid,
+                    tag_name: "icebreaker".to_string(),
+                };
+                let tag = repo.get_or_create_tag(new_tag).expect("Should create tag");
+                let relation = NewQuestionTag {
+                    question_uuid: question.uuid,
+                    tag_uuid: tag.uuid,
+                };
+                repo.tag_question(relation).expect("Should tag question");
+                fixture.add_user2(
+                    BucketUserPermissions {
+                        kick_permission: true,
+                        ..NO_PERMISSIONS
+                    },
+                    &repo,
+                );
+
+                let bucket = clone_bucket_handler(
+                    fixture.bucket.uuid,
+                    clone_request(true),
+                    fixture.user1.uuid,
+                    repo,
+                )
+                .expect("Should clone bucket");
+                assert_ne!(bucket.uuid, fixture.bucket.uuid);
+                assert_eq!(bucket.owner_uuid, Some(fixture.user1.uuid));
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let questions = repo
+                    .get_all_questions_for_bucket_of_given_archived_status(
+                        bucket.uuid,
+                        false,
+                        &TagFilter::default(),
+                        Pagination::first(10, QuestionSort::Updated),
+                    )
+                    .expect("Should get questions")
+                    .items;
+                assert_eq!(questions.len(), 1);
+                assert_eq!(questions[0].question_text, question.question_text);
+                let tags = repo
+                    .get_tags_for_question(questions[0].uuid)
+                    .expect("Should get tags");
+                assert_eq!(tags.len(), 1);
+                assert_eq!(tags[0].tag_name, "icebreaker");
+                assert_eq!(tags[0].bucket_uuid, bucket.uuid);
+
+                let permissions = repo
+                    .get_permissions(fixture.user2.uuid, bucket.uuid)
+                    .expect("User should be in the new bucket");
+                assert!(permissions.kick_permission);
+                assert!(!permissions.grant_permissions_permission);
+            },
+        )
+    }
+
+    #[test]
+    fn non_members_can_only_copy_public_templates() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let result = clone_bucket_handler(
+                    fixture.bucket.uuid,
+                    clone_request(false),
+                    fixture.user2.uuid,
+                    repo,
+                );
+                expect_not_authorized(result);
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let request = SaveTemplateRequest {
+                    template_name: "Template".to_string(),
+                    public: true,
+                };
+                let template =
+                    save_template_handler(fixture.bucket.uuid, request, fixture.user1.uuid, repo)
+                        .expect("Should save template");
+                assert!(template.is_template);
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let templates = get_public_templates_handler(PageQuery::default(), repo)
+                    .expect("Should get templates");
+                assert_eq!(templates.items, vec![template.clone()]);
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let result = clone_bucket_handler(
+                    template.uuid,
+                    clone_request(true),
+                    fixture.user2.uuid,
+                    repo,
+                );
+                expect_not_authorized(result);
+
+                let repo = provider.get_repo().expect("Should get repo.");
+                let bucket = clone_bucket_handler(
+                    template.uuid,
+                    clone_request(false),
+                    fixture.user2.uuid,
+                    repo,
+                )
+                .expect("Should make a bucket from the template");
+                assert!(!bucket.is_template);
+                assert_eq!(bucket.owner_uuid, Some(fixture.user2.uuid));
+            },
+        )
+    }
+}
@@ -1,11 +1,20 @@
 use crate::{
-    api::{answer::NewAnswerRequest, auth::test::get_jwt, routes},
+    api::{
+        answer::{NewAnswerRequest, ANSWER_EDIT_TOKEN_HEADER},
+        auth::test::{get_jwt, get_jwt_for_user},
+        routes,
+    },
     state::{test_util::execute_test_on_repository, State},
     util::test_util::deserialize,
 };
 use authorization::{Secret, AUTHORIZATION_HEADER_KEY, BEARER};
-use db::{answer::db_types::Answer, test::answer_fixture::AnswerFixture, RepositoryProvider};
+use db::{
+    answer::db_types::Answer, bucket::db_types::BucketFlagChangeset, page::Page,
+    question::db_types::Question, test::answer_fixture::AnswerFixture, user::db_types::NewUser,
+    RepositoryProvider,
+};
 use warp::{http::StatusCode, test::request};
+use wire::answer::{CreatedAnswer, EditAnswerRequest};
 
 #[test]
 fn answer_question_with_user() {
@@ -20,6 +29,7 @@ fn answer_question_with_user() {
             question_uuid: fix.question.uuid,
             publicly_visible: true,
             answer_text: "this is the answer".to_string(),
+            archive_question: false,
         };
 
         let resp = request()
@@ -51,6 +61,7 @@ fn answer_question_without_user() {
             question_uuid: fix.question.uuid,
             publicly_visible: true,
             answer_text: "This is the answer".to_string(),
+            archive_question: false,
         };
 
         let resp = request()
@@ -68,3 +79,260 @@ fn answer_question_without_user() {
         assert_eq!(response.user_uuid, None);
     });
 }
+
+#[test]
+fn answering_only_archives_when_allowed() {
+    execute_test_on_repository(|fix: &AnswerFixture, provider: RepositoryProvider| {
+        {
+            let repo = provider.get_repo().expect("Should get repo");
+            let changeset = BucketFlagChangeset {
+                uuid: fix.bucket.uuid,
+                public_viewable: None,
+                drawing_enabled: Some(false),
+                exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: None,
+            };
+            repo.change_bucket_flags(changeset)
+                .expect("Should disable drawing");
+        }
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+
+        let req = NewAnswerRequest {
+            question_uuid: fix.question.uuid,
+            publicly_visible: true,
+            answer_text: "Archive this".to_string(),
+            archive_question: true,
+        };
+        let resp = request()
+            .method("POST")
+            .json(&req)
+            .header("content-length", "500")
+            .path("/api/answer")
+            .reply(&filter);
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        // Only members can archive questions while drawing is disabled, so the question stays in the bucket.
+        let url = format!("/api/question/in_bucket?bucket_uuid={}", fix.bucket.uuid);
+        let resp = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let questions = deserialize::<Page<Question>>(&resp);
+        assert_eq!(questions.items, vec![fix.question.clone()]);
+    });
+}
+
+#[test]
+fn get_own_answers() {
+    execute_test_on_repository(|fix: &AnswerFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let resp = request()
+            .method("GET")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path("/api/answer/mine?limit=10")
+            .reply(&filter);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let answers = deserialize::<Page<Answer>>(&resp);
+        assert_eq!(answers.items, vec![fix.answer.clone()]);
+        assert_eq!(answers.next_cursor, None);
+
+        let resp = request()
+            .method("GET")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&format!(
+                "/api/answer/mine?limit=10&cursor={}",
+                fix.answer.uuid
+            ))
+            .reply(&filter);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let answers = deserialize::<Page<Answer>>(&resp);
+        assert!(answers.items.is_empty());
+    });
+}
+
+#[test]
+fn private_answers_hidden_from_others() {
+    execute_test_on_repository(|fix: &AnswerFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let urls = vec![
+            format!("/api/question/{}/answers?limit=10", fix.question.uuid),
+            format!("/api/bucket/{}/answers?limit=10", fix.bucket.uuid),
+        ];
+        for url in urls {
+            let resp = request().method("GET").path(&url).reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            let answers = deserialize::<Page<Answer>>(&resp).items;
+            assert!(answers.is_empty());
+
+            let resp = request()
+                .method("GET")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&url)
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            let answers = deserialize::<Page<Answer>>(&resp).items;
+            assert_eq!(answers, vec![fix.answer.clone()]);
+        }
+    });
+}
+
+#[test]
+fn only_those_who_can_see_the_bucket_see_answers() {
+    execute_test_on_repository(|fix: &AnswerFixture, provider: RepositoryProvider| {
+        let outsider = {
+            let repo = provider.get_repo().expect("Should get repo");
+            let changeset = BucketFlagChangeset {
+                uuid: fix.bucket.uuid,
+                public_viewable: Some(false),
+                drawing_enabled: None,
+                exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: None,
+            };
+            repo.change_bucket_flags(changeset)
+                .expect("Should hide bucket");
+            repo.create_user(NewUser {
+                google_user_id: "987654321".to_string(),
+                google_name: Some("Yote".to_owned()),
+            })
+            .expect("Should create user")
+        };
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+        let outsider_jwt = get_jwt_for_user(&state, outsider);
+
+        let urls = vec![
+            format!("/api/question/{}/answers?limit=10", fix.question.uuid),
+            format!("/api/bucket/{}/answers?limit=10", fix.bucket.uuid),
+        ];
+        for url in urls {
+            let resp = request().method("GET").path(&url).reply(&filter);
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+            let resp = request()
+                .method("GET")
+                .header(
+                    AUTHORIZATION_HEADER_KEY,
+                    format!("{} {}", BEARER, outsider_jwt),
+                )
+                .path(&url)
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+            let resp = request()
+                .method("GET")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&url)
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+    });
+}
+
+#[test]
+fn edit_and_delete_own_answer() {
+    execute_test_on_repository(|fix: &AnswerFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let url = format!("/api/answer/{}", fix.answer.uuid);
+        let req = EditAnswerRequest {
+            publicly_visible: Some(true),
+            answer_text: Some("A better answer".to_string()),
+        };
+
+        let resp = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let resp = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let answer = deserialize::<Answer>(&resp);
+        assert!(answer.publicly_visible);
+        assert_eq!(answer.answer_text, "A better answer");
+
+        let resp = request()
+            .method("DELETE")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(resp.status(), StatusCode::OK);
+    });
+}
+
+#[test]
+fn manage_anonymous_answer_with_edit_token() {
+    execute_test_on_repository(|fix: &AnswerFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let req = NewAnswerRequest {
+            question_uuid: fix.question.uuid,
+            publicly_visible: true,
+            answer_text: "Nobody knows I said this".to_string(),
+            archive_question: false,
+        };
+        let resp = request()
+            .method("POST")
+            .json(&req)
+            .header("content-length", "500")
+            .path("/api/answer")
+            .reply(&filter);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let created = deserialize::<CreatedAnswer>(&resp);
+        let edit_token = created.edit_token.expect("Anonymous answers get an edit token");
+
+        let url = format!("/api/answer/{}", created.answer.uuid);
+        let req = EditAnswerRequest {
+            publicly_visible: Some(false),
+            answer_text: None,
+        };
+
+        // Being logged in doesn't make someone the author of an anonymous answer.
+        let resp = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let resp = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .header(ANSWER_EDIT_TOKEN_HEADER, edit_token.to_string())
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let answer = deserialize::<Answer>(&resp);
+        assert!(!answer.publicly_visible);
+
+        let resp = request()
+            .method("DELETE")
+            .header(ANSWER_EDIT_TOKEN_HEADER, edit_token.to_string())
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(resp.status(), StatusCode::OK);
+    });
+}
@@ -1,7 +1,7 @@
 //! Tests for bucket apis.
 use crate::{
     api::{
-        auth::test::get_jwt,
+        auth::test::{get_jwt, get_jwt_for_user},
         bucket::{ChangeBucketFlagsRequest, NewBucketRequest, SetPermissionsRequest},
         routes,
     },
@@ -11,16 +11,23 @@ use crate::{
 };
 use authorization::{Secret, AUTHORIZATION_HEADER_KEY, BEARER};
 use db::{
-    bucket::db_types::Bucket,
+    audit::db_types::{AuditAction, AuditEvent},
+    ban::db_types::Ban,
+    bucket::db_types::{Bucket, BucketFlagChangeset},
     bucket_user_relation::db_types::{BucketUserPermissions, BucketUserRelation},
+    page::Page,
     test::{
         bucket_and_user_fixture::BucketAndUserFixture,
-        bucket_user_relation_fixture::UserBucketRelationFixture,
+        bucket_user_relation_fixture::{UserBucketRelationFixture, NO_PERMISSIONS},
     },
     user::db_types::User,
     RepositoryProvider,
 };
 use warp::{http::status::StatusCode, test::request};
+use wire::{
+    ban::NewBanRequest,
+    bucket::{BucketDeletionToken, CloneBucketRequest, RenameBucketRequest, SaveTemplateRequest},
+};
 
 #[test]
 fn create_bucket() {
@@ -171,7 +178,7 @@ fn get_buckets_user_is_in() {
 
             assert_eq!(resp.status(), StatusCode::OK);
 
-            let buckets = deserialize::<Vec<Bucket>>(&resp);
+            let buckets = deserialize::<Page<Bucket>>(&resp).items;
             assert_eq!(buckets.len(), 1);
             assert_eq!(buckets[0], fix.bucket);
         },
@@ -191,13 +198,61 @@ fn get_public_buckets() {
 
             assert_eq!(resp.status(), StatusCode::OK);
 
-            let buckets = deserialize::<Vec<Bucket>>(&resp);
+            let buckets = deserialize::<Page<Bucket>>(&resp).items;
             assert_eq!(buckets.len(), 1);
             assert_eq!(buckets[0], fix.bucket);
         },
     )
 }
 
+#[test]
+fn page_through_public_buckets() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt(&state);
+
+            let new_bucket = NewBucketRequest {
+                bucket_name: "Another bucket".to_string(),
+            };
+            let resp = request()
+                .method("POST")
+                .json(&new_bucket)
+                .header("content-length", "500")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path("/api/bucket")
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            let another_bucket = deserialize::<Bucket>(&resp);
+
+            let resp = request()
+                .method("GET")
+                .path("/api/bucket/public?limit=1&sort=newest")
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            let page = deserialize::<Page<Bucket>>(&resp);
+            assert_eq!(page.items.len(), 1);
+            assert_eq!(page.items[0].uuid, another_bucket.uuid);
+            let cursor = page.next_cursor.expect("Should have another page");
+
+            let url = format!("/api/bucket/public?limit=1&sort=newest&cursor={}", cursor);
+            let resp = request().method("GET").path(&url).reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            let page = deserialize::<Page<Bucket>>(&resp);
+            assert_eq!(page.items.len(), 1);
+            assert_eq!(page.items[0].uuid, fix.bucket.uuid);
+            assert_eq!(page.next_cursor, None);
+
+            let resp = request()
+                .method("GET")
+                .path("/api/bucket/public?cursor=not-a-cursor")
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        },
+    )
+}
+
 #[test]
 fn add_self_to_bucket() {
     execute_test_on_repository(|fix: &BucketAndUserFixture, provider: RepositoryProvider| {
@@ -296,6 +351,9 @@ fn set_permissions() {
                 set_exclusive_permission: Some(true),
                 kick_permission: None,
                 grant_permissions_permission: None,
+                moderate_questions_permission: None,
+                delete_questions_permission: None,
+                invite_permission: None,
             };
 
             let resp = request()
@@ -319,6 +377,493 @@ fn set_permissions() {
     );
 }
 
+/// The permissions of a member who can only grant permissions.
+const CAN_GRANT: BucketUserPermissions = BucketUserPermissions {
+    grant_permissions_permission: true,
+    ..NO_PERMISSIONS
+};
+
+#[test]
+fn set_permissions_for_other_user() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            let repo = provider.get_repo().expect("Should get repo.");
+            fix.add_user2(NO_PERMISSIONS, &repo);
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt(&state);
+
+            let url = format!("/api/bucket/{}/user", fix.bucket.uuid);
+
+            let req = SetPermissionsRequest {
+                target_user_uuid: fix.user2.uuid,
+                set_public_permission: None,
+                set_drawing_permission: None,
+                set_exclusive_permission: None,
+                kick_permission: Some(true),
+                grant_permissions_permission: None,
+                moderate_questions_permission: None,
+                delete_questions_permission: None,
+                invite_permission: None,
+            };
+
+            let resp = request()
+                .method("PUT")
+                .json(&req)
+                .header("content-length", "500")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&url)
+                .reply(&filter);
+
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            let res = deserialize::<BucketUserRelation>(&resp);
+            assert_eq!(res.user_uuid, fix.user2.uuid);
+            assert_eq!(res.kick_permission, true);
+
+            // The granter's own permissions are left alone.
+            let granter = repo
+                .get_user_bucket_relation(fix.user1.uuid, fix.bucket.uuid)
+                .expect("Should get relation");
+            assert_eq!(granter, fix.relation);
+
+            let grants = repo
+                .get_permission_grants(fix.bucket.uuid)
+                .expect("Should get grants");
+            assert_eq!(grants.len(), 1);
+            assert_eq!(grants[0].granter_uuid, Some(fix.user1.uuid));
+            assert_eq!(grants[0].target_uuid, fix.user2.uuid);
+            assert_eq!(grants[0].permission, "kick_permission");
+            assert_eq!(grants[0].granted, true);
+        },
+    );
+}
+
+#[test]
+fn cant_grant_permission_not_held() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            let repo = provider.get_repo().expect("Should get repo.");
+            fix.add_user2(CAN_GRANT, &repo);
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt_for_user(&state, fix.user2.clone());
+
+            let url = format!("/api/bucket/{}/user", fix.bucket.uuid);
+
+            // User 2 can grant permissions, but can't kick, so they can't give kicking to anyone, themselves included.
+            for target_user_uuid in &[fix.user2.uuid, fix.user1.uuid] {
+                let req = SetPermissionsRequest {
+                    target_user_uuid: *target_user_uuid,
+                    set_public_permission: None,
+                    set_drawing_permission: None,
+                    set_exclusive_permission: None,
+                    kick_permission: Some(true),
+                    grant_permissions_permission: None,
+                    moderate_questions_permission: None,
+                    delete_questions_permission: None,
+                    invite_permission: None,
+                };
+
+                let resp = request()
+                    .method("PUT")
+                    .json(&req)
+                    .header("content-length", "500")
+                    .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                    .path(&url)
+                    .reply(&filter);
+
+                assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+            }
+
+            let permissions = repo
+                .get_permissions(fix.user2.uuid, fix.bucket.uuid)
+                .expect("Should get permissions");
+            assert_eq!(permissions.kick_permission, false);
+            let grants = repo
+                .get_permission_grants(fix.bucket.uuid)
+                .expect("Should get grants");
+            assert!(grants.is_empty());
+        },
+    );
+}
+
+#[test]
+fn non_granter_cant_set_permissions() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            let repo = provider.get_repo().expect("Should get repo.");
+            fix.add_user2(NO_PERMISSIONS, &repo);
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt_for_user(&state, fix.user2.clone());
+
+            let url = format!("/api/bucket/{}/user", fix.bucket.uuid);
+
+            // Neither granting to themselves nor revoking from someone else is allowed.
+            let escalate = SetPermissionsRequest {
+                target_user_uuid: fix.user2.uuid,
+                set_public_permission: None,
+                set_drawing_permission: None,
+                set_exclusive_permission: None,
+                kick_permission: None,
+                grant_permissions_permission: Some(true),
+                moderate_questions_permission: None,
+                delete_questions_permission: None,
+                invite_permission: None,
+            };
+            let revoke = SetPermissionsRequest {
+                target_user_uuid: fix.user1.uuid,
+                set_public_permission: None,
+                set_drawing_permission: None,
+                set_exclusive_permission: None,
+                kick_permission: Some(false),
+                grant_permissions_permission: None,
+                moderate_questions_permission: None,
+                delete_questions_permission: None,
+                invite_permission: None,
+            };
+
+            for req in &[escalate, revoke] {
+                let resp = request()
+                    .method("PUT")
+                    .json(req)
+                    .header("content-length", "500")
+                    .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                    .path(&url)
+                    .reply(&filter);
+
+                assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+            }
+
+            let permissions = repo
+                .get_permissions(fix.user2.uuid, fix.bucket.uuid)
+                .expect("Should get permissions");
+            assert_eq!(permissions.grant_permissions_permission, false);
+            let granter = repo
+                .get_user_bucket_relation(fix.user1.uuid, fix.bucket.uuid)
+                .expect("Should get relation");
+            assert_eq!(granter, fix.relation);
+        },
+    );
+}
+
+#[test]
+fn kicks_are_audited() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            let repo = provider.get_repo().expect("Should get repo.");
+            fix.add_user2(NO_PERMISSIONS, &repo);
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt(&state);
+
+            let resp = request()
+                .method("DELETE")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!(
+                    "/api/bucket/{}/user?user_uuid={}",
+                    fix.bucket.uuid, fix.user2.uuid
+                ))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            let resp = request()
+                .method("GET")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}/audit", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            let events = deserialize::<Page<AuditEvent>>(&resp).items;
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].action, AuditAction::MemberRemoved);
+            assert_eq!(events[0].actor_uuid, Some(fix.user1.uuid));
+            assert_eq!(events[0].target_uuid, Some(fix.user2.uuid));
+        },
+    );
+}
+
+#[test]
+fn non_admin_cant_see_audit_log() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            let repo = provider.get_repo().expect("Should get repo.");
+            fix.add_user2(NO_PERMISSIONS, &repo);
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt_for_user(&state, fix.user2.clone());
+
+            let resp = request()
+                .method("GET")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}/audit", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        },
+    );
+}
+
+#[test]
+fn banned_user_cant_rejoin_until_ban_is_lifted() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            let repo = provider.get_repo().expect("Should get repo.");
+            fix.add_user2(NO_PERMISSIONS, &repo);
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt(&state);
+            let banned_jwt = get_jwt_for_user(&state, fix.user2.clone());
+
+            let req = NewBanRequest {
+                user_uuid: fix.user2.uuid,
+                reason: Some("Spam".to_string()),
+                expires_at: None,
+            };
+            let resp = request()
+                .method("POST")
+                .json(&req)
+                .header("content-length", "500")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}/bans", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            let rejoin = || {
+                request()
+                    .method("POST")
+                    .header(
+                        AUTHORIZATION_HEADER_KEY,
+                        format!("{} {}", BEARER, banned_jwt),
+                    )
+                    .path(&format!("/api/bucket/{}/user", fix.bucket.uuid))
+                    .reply(&filter)
+            };
+            assert_eq!(rejoin().status(), StatusCode::FORBIDDEN);
+
+            let resp = request()
+                .method("GET")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}/bans", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            let bans = deserialize::<Page<Ban>>(&resp).items;
+            assert_eq!(bans.len(), 1);
+            assert_eq!(bans[0].user_uuid, fix.user2.uuid);
+            assert_eq!(bans[0].banned_by_uuid, Some(fix.user1.uuid));
+
+            let resp = request()
+                .method("DELETE")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!(
+                    "/api/bucket/{}/bans/{}",
+                    fix.bucket.uuid, fix.user2.uuid
+                ))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            assert_eq!(rejoin().status(), StatusCode::OK);
+        },
+    );
+}
+
+#[test]
+fn non_kicker_cant_ban() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            let repo = provider.get_repo().expect("Should get repo.");
+            fix.add_user2(CAN_GRANT, &repo);
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt_for_user(&state, fix.user2.clone());
+
+            let req = NewBanRequest {
+                user_uuid: fix.user1.uuid,
+                reason: None,
+                expires_at: None,
+            };
+            let resp = request()
+                .method("POST")
+                .json(&req)
+                .header("content-length", "500")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}/bans", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+            let resp = request()
+                .method("GET")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}/bans", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        },
+    );
+}
+
+/// Makes the fixture's first user the owner of the bucket.
+fn make_user1_owner(fix: &UserBucketRelationFixture, provider: &RepositoryProvider) {
+    let repo = provider.get_repo().expect("Should get repo.");
+    repo.set_bucket_owner(fix.bucket.uuid, fix.user1.uuid)
+        .expect("Should set owner");
+}
+
+#[test]
+fn rename_bucket_keeps_old_slug_working() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            make_user1_owner(fix, &provider);
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt(&state);
+
+            let req = RenameBucketRequest {
+                bucket_name: "New Name".to_string(),
+                regenerate_slug: true,
+            };
+            let resp = request()
+                .method("PATCH")
+                .json(&req)
+                .header("content-length", "500")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            let bucket = deserialize::<Bucket>(&resp);
+            assert_eq!(bucket.bucket_name, "New Name");
+            assert_eq!(bucket.bucket_slug, "new-name");
+
+            let resp = request()
+                .method("GET")
+                .path(&format!("/api/bucket/slug/{}", fix.bucket.bucket_slug))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(deserialize::<Bucket>(&resp), bucket);
+        },
+    );
+}
+
+#[test]
+fn delete_bucket_requires_deletion_token() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            make_user1_owner(fix, &provider);
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt(&state);
+
+            let delete = |token: &str| {
+                request()
+                    .method("DELETE")
+                    .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                    .path(&format!("/api/bucket/{}?token={}", fix.bucket.uuid, token))
+                    .reply(&filter)
+            };
+            // A login token can't stand in for a deletion token.
+            assert_eq!(delete(&jwt).status(), StatusCode::PRECONDITION_FAILED);
+
+            let resp = request()
+                .method("POST")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}/deletion_token", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            let deletion_token = deserialize::<BucketDeletionToken>(&resp);
+
+            assert_eq!(delete(&deletion_token.token).status(), StatusCode::OK);
+
+            let resp = request()
+                .method("GET")
+                .path(&format!("/api/bucket/{}", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        },
+    );
+}
+
+#[test]
+fn non_owner_cant_delete_bucket() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            make_user1_owner(fix, &provider);
+            let repo = provider.get_repo().expect("Should get repo.");
+            fix.add_user2(CAN_GRANT, &repo);
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt_for_user(&state, fix.user2.clone());
+
+            let resp = request()
+                .method("POST")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}/deletion_token", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        },
+    );
+}
+
+#[test]
+fn templates_are_instantiated_instead_of_joined() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt(&state);
+            let user2_jwt = get_jwt_for_user(&state, fix.user2.clone());
+
+            let req = SaveTemplateRequest {
+                template_name: "Game night".to_string(),
+                public: true,
+            };
+            let resp = request()
+                .method("POST")
+                .json(&req)
+                .header("content-length", "500")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}/template", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            let template = deserialize::<Bucket>(&resp);
+
+            let resp = request()
+                .method("GET")
+                .path("/api/bucket/templates")
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(
+                deserialize::<Page<Bucket>>(&resp).items,
+                vec![template.clone()]
+            );
+
+            let resp = request()
+                .method("POST")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, user2_jwt))
+                .path(&format!("/api/bucket/{}/user", template.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+
+            let req = CloneBucketRequest {
+                bucket_name: "Game night".to_string(),
+                copy_tags: true,
+                copy_flags: true,
+                copy_member_roles: false,
+            };
+            let resp = request()
+                .method("POST")
+                .json(&req)
+                .header("content-length", "500")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, user2_jwt))
+                .path(&format!("/api/bucket/{}/clone", template.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            let bucket = deserialize::<Bucket>(&resp);
+            assert!(!bucket.is_template);
+            assert_eq!(bucket.owner_uuid, Some(fix.user2.uuid));
+            assert_ne!(bucket.bucket_slug, template.bucket_slug);
+        },
+    );
+}
+
 #[test]
 fn set_bucket_flags() {
     execute_test_on_repository(
@@ -333,6 +878,8 @@ fn set_bucket_flags() {
                 publicly_visible: Some(false),
                 drawing_enabled: None,
                 exclusive: Some(true),
+                draw_strategy: None,
+                duplicate_policy: None,
             };
 
             let resp = request()
@@ -371,9 +918,90 @@ fn get_users_in_bucket() {
 
             assert_eq!(resp.status(), StatusCode::OK);
 
-            let res = deserialize::<Vec<User>>(&resp);
+            let res = deserialize::<Page<User>>(&resp).items;
             assert_eq!(res.len(), 1);
             assert_eq!(res[0], fix.user1)
         },
     );
 }
+
+#[test]
+fn heartbeat_makes_user_present() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt(&state);
+
+            let url = format!("/api/bucket/{}/present", fix.bucket.uuid);
+            let resp = request().method("GET").path(&url).reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert!(deserialize::<Page<User>>(&resp).items.is_empty());
+
+            let resp = request()
+                .method("POST")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&format!("/api/bucket/{}/heartbeat", fix.bucket.uuid))
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            let resp = request().method("GET").path(&url).reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(
+                deserialize::<Page<User>>(&resp).items,
+                vec![fix.user1.clone()]
+            );
+        },
+    );
+}
+
+#[test]
+fn only_those_who_can_see_the_bucket_see_who_is_present() {
+    execute_test_on_repository(
+        |fix: &UserBucketRelationFixture, provider: RepositoryProvider| {
+            {
+                let repo = provider.get_repo().expect("Should get repo");
+                let changeset = BucketFlagChangeset {
+                    uuid: fix.bucket.uuid,
+                    public_viewable: Some(false),
+                    drawing_enabled: None,
+                    exclusive: None,
+                    draw_strategy: None,
+                    duplicate_policy: None,
+                };
+                repo.change_bucket_flags(changeset)
+                    .expect("Should hide bucket");
+            }
+            let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+            let filter = routes(&state);
+            let jwt = get_jwt(&state);
+
+            let url = format!("/api/bucket/{}/present", fix.bucket.uuid);
+            let resp = request().method("GET").path(&url).reply(&filter);
+            assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+            let resp = request()
+                .method("GET")
+                .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+                .path(&url)
+                .reply(&filter);
+            assert_eq!(resp.status(), StatusCode::OK);
+        },
+    );
+}
+
+#[test]
+fn non_member_cant_send_heartbeat() {
+    execute_test_on_repository(|fix: &BucketAndUserFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let resp = request()
+            .method("POST")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&format!("/api/bucket/{}/heartbeat", fix.bucket.uuid))
+            .reply(&filter);
+        assert_ne!(resp.status(), StatusCode::OK);
+    });
+}
@@ -9,11 +9,20 @@ use crate::{
 };
 use authorization::{Secret, AUTHORIZATION_HEADER_KEY, BEARER};
 use db::{
-    question::db_types::Question,
+    bucket::db_types::{BucketFlagChangeset, DuplicatePolicy},
+    bucket_user_relation::db_types::BucketUserPermissionsChangeset,
+    page::Page,
+    question::db_types::{NewQuestion, Question, QuestionDraw, QuestionRevision},
+    question_vote::db_types::{QuestionVote, UPVOTE},
+    tag::db_types::Tag,
     test::{bucket_fixture::BucketFixture, question_fixture::QuestionFixture},
     RepositoryProvider,
 };
 use warp::{http::StatusCode, test::request};
+use wire::{
+    question::{CreatedQuestion, EditQuestionRequest, Vote, VoteRequest},
+    tag::TagQuestionRequest,
+};
 
 #[test]
 fn create_question_with_user_login() {
@@ -27,6 +36,7 @@ fn create_question_with_user_login() {
         let req = NewQuestionRequest {
             bucket_uuid: fix.bucket.uuid,
             question_text: "Are you still there?".to_string(),
+            tags: vec![],
         };
 
         let res = request()
@@ -57,6 +67,7 @@ fn create_question_without_user_login() {
         let req = NewQuestionRequest {
             bucket_uuid: fix.bucket.uuid,
             question_text: "Are you still there?".to_string(),
+            tags: vec![],
         };
 
         let res = request()
@@ -76,7 +87,150 @@ fn create_question_without_user_login() {
 }
 
 #[test]
-fn delete_question() {}
+fn create_question_warns_about_similar_questions() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+
+        let req = NewQuestionRequest {
+            bucket_uuid: fix.bucket.uuid,
+            question_text: "is this the FIRST question".to_string(),
+            tags: vec![],
+        };
+
+        let res = request()
+            .method("POST")
+            .json(&req)
+            .header("content-length", "500")
+            .path("/api/question")
+            .reply(&filter);
+
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = deserialize::<CreatedQuestion>(&res);
+        assert_eq!(res.question.question_text, req.question_text);
+        assert_eq!(res.similar_questions.len(), 1);
+        assert_eq!(res.similar_questions[0].question.uuid, fix.question1.uuid);
+        assert!(res.similar_questions[0].exact);
+    });
+}
+
+#[test]
+fn create_question_rejects_duplicates_when_bucket_does() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        {
+            let repo = provider.get_repo().expect("Should get repo");
+            let changeset = BucketFlagChangeset {
+                uuid: fix.bucket.uuid,
+                public_viewable: None,
+                drawing_enabled: None,
+                exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: Some(DuplicatePolicy::Reject),
+            };
+            repo.change_bucket_flags(changeset)
+                .expect("Should change duplicate policy");
+        }
+
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+
+        let post = |question_text: &str| {
+            let req = NewQuestionRequest {
+                bucket_uuid: fix.bucket.uuid,
+                question_text: question_text.to_string(),
+                tags: vec![],
+            };
+            request()
+                .method("POST")
+                .json(&req)
+                .header("content-length", "500")
+                .path("/api/question")
+                .reply(&filter)
+        };
+
+        let res = post("Is this the very first question?");
+        assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+
+        let res = post("Are you still there?");
+        assert_eq!(res.status(), StatusCode::OK);
+        let res = deserialize::<CreatedQuestion>(&res);
+        assert!(res.similar_questions.is_empty());
+    });
+}
+
+#[test]
+fn delete_question() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let url = format!("/api/question/{}", fix.question1.uuid);
+        let res = request()
+            .method("DELETE")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let question = deserialize::<Question>(&res);
+        assert_eq!(question.uuid, fix.question1.uuid);
+    });
+}
+
+#[test]
+fn cant_delete_question_without_login() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+
+        let url = format!("/api/question/{}", fix.question1.uuid);
+        let res = request().method("DELETE").path(&url).reply(&filter);
+        assert!(res.status().is_client_error());
+    });
+}
+
+#[test]
+fn cant_delete_question_without_delete_permission() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        // The connection is released before the routes acquire their own.
+        let anonymous_question = {
+            let repo = provider.get_repo().expect("Should get repo");
+            let changeset = BucketUserPermissionsChangeset {
+                user_uuid: fix.user.uuid,
+                bucket_uuid: fix.bucket.uuid,
+                set_public_permission: None,
+                set_drawing_permission: None,
+                set_exclusive_permission: None,
+                kick_permission: None,
+                grant_permissions_permission: None,
+                moderate_questions_permission: None,
+                delete_questions_permission: Some(false),
+                invite_permission: Some(false),
+            };
+            repo.set_permissions(changeset)
+                .expect("Should set permissions");
+            repo.create_question(NewQuestion {
+                bucket_uuid: fix.bucket.uuid,
+                user_uuid: None,
+                question_text: "Who wrote this?".to_string(),
+            })
+            .expect("Should create question")
+        };
+
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let url = format!("/api/question/{}", anonymous_question.uuid);
+        let res = request()
+            .method("DELETE")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    });
+}
 
 #[test]
 fn random_question_populated() {
@@ -99,6 +253,70 @@ fn random_question_populated() {
     });
 }
 
+#[test]
+fn random_question_records_draw() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let url = format!("/api/question/random?bucket_uuid={}", fix.bucket.uuid);
+        let res = request()
+            .method("GET")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let question = deserialize::<Option<Question>>(&res).expect("Should draw a question");
+
+        let url = format!("/api/question/draws?bucket_uuid={}", fix.bucket.uuid);
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let draws = deserialize::<Page<QuestionDraw>>(&res).items;
+        assert_eq!(draws.len(), 1);
+        assert_eq!(draws[0].question_uuid, question.uuid);
+        assert_eq!(draws[0].user_uuid, Some(fix.user.uuid));
+    });
+}
+
+#[test]
+fn random_question_avoids_last_draw() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+
+        let url = format!(
+            "/api/question/random?bucket_uuid={}&avoid_recent_draws=1",
+            fix.bucket.uuid
+        );
+
+        let res = request().method("GET").path(&url).reply(&filter);
+        let first = deserialize::<Option<Question>>(&res).expect("Should draw a question");
+        let res = request().method("GET").path(&url).reply(&filter);
+        let second = deserialize::<Option<Question>>(&res).expect("Should draw a question");
+
+        assert_ne!(first.uuid, second.uuid);
+    });
+}
+
+#[test]
+fn random_question_ignores_negative_draws_to_avoid() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+
+        let url = format!(
+            "/api/question/random?bucket_uuid={}&avoid_recent_draws=-1",
+            fix.bucket.uuid
+        );
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let res = deserialize::<Option<Question>>(&res);
+        assert!(res.is_some());
+    });
+}
+
 #[test]
 fn random_question_unpopulated() {
     execute_test_on_repository(|fix: &BucketFixture, provider: RepositoryProvider| {
@@ -145,7 +363,7 @@ fn all_questions_in_bucket() {
 
         assert_eq!(res.status(), StatusCode::OK);
 
-        let res = deserialize::<Vec<Question>>(&res);
+        let res = deserialize::<Page<Question>>(&res).items;
         assert_eq!(res.len(), 2);
     });
 }
@@ -162,7 +380,7 @@ fn all_questions_on_floor() {
 
         assert_eq!(res.status(), StatusCode::OK);
 
-        let res = deserialize::<Vec<Question>>(&res);
+        let res = deserialize::<Page<Question>>(&res).items;
         assert_eq!(res.len(), 0);
     });
 }
@@ -172,6 +390,7 @@ fn set_question_archived_state() {
     execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
         let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
         let filter = routes(&state);
+        let jwt = get_jwt(&state);
 
         let url = "/api/question/archive";
 
@@ -184,6 +403,7 @@ fn set_question_archived_state() {
             .method("PUT")
             .json(&req)
             .header("content-length", "500")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
             .path(&url)
             .reply(&filter);
 
@@ -194,6 +414,26 @@ fn set_question_archived_state() {
     });
 }
 
+#[test]
+fn cant_set_question_archived_state_without_login() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+
+        let req = SetArchivedRequest {
+            question_uuid: fix.question1.uuid,
+            archived: true,
+        };
+        let res = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .path("/api/question/archive")
+            .reply(&filter);
+        assert!(res.status().is_client_error());
+    });
+}
+
 #[test]
 fn favorite_question() {
     execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
@@ -275,8 +515,373 @@ fn get_favorite_questions() {
             .path(&url)
             .reply(&filter);
 
-        let res = deserialize::<Vec<Question>>(&res);
+        let res = deserialize::<Page<Question>>(&res).items;
         assert_eq!(res.len(), 1);
         assert_eq!(res[0].uuid, fix.question1.uuid);
     });
 }
+
+#[test]
+fn vote_on_question() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let url = format!("/api/question/{}/vote", fix.question1.uuid);
+        let req = VoteRequest { vote: Vote::Up };
+        let res = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let question = deserialize::<Question>(&res);
+        assert_eq!(question.upvotes, 1);
+
+        let res = request()
+            .method("GET")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let vote = deserialize::<Option<QuestionVote>>(&res).expect("Vote should exist");
+        assert_eq!(vote.vote, UPVOTE);
+
+        let res = request()
+            .method("DELETE")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let question = deserialize::<Question>(&res);
+        assert_eq!(question.upvotes, 0);
+    });
+}
+
+#[test]
+fn cant_vote_without_login() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+
+        let url = format!("/api/question/{}/vote", fix.question1.uuid);
+        let req = VoteRequest { vote: Vote::Down };
+        let res = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .path(&url)
+            .reply(&filter);
+        assert!(res.status().is_client_error());
+    });
+}
+
+#[test]
+fn questions_in_bucket_sorted_by_score() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let url = format!("/api/question/{}/vote", fix.question2.uuid);
+        let req = VoteRequest { vote: Vote::Up };
+        let res = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let url = format!(
+            "/api/question/in_bucket?bucket_uuid={}&sort=score",
+            fix.bucket.uuid
+        );
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = deserialize::<Page<Question>>(&res).items;
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].uuid, fix.question2.uuid);
+    });
+}
+
+#[test]
+fn page_through_questions_in_bucket() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+
+        let url = format!(
+            "/api/question/in_bucket?bucket_uuid={}&limit=1",
+            fix.bucket.uuid
+        );
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let first_page = deserialize::<Page<Question>>(&res);
+        assert_eq!(first_page.items.len(), 1);
+        let cursor = first_page.next_cursor.expect("Should have another page");
+
+        let url = format!("{}&cursor={}", url, cursor);
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let second_page = deserialize::<Page<Question>>(&res);
+        assert_eq!(second_page.items.len(), 1);
+        assert_ne!(second_page.items[0].uuid, first_page.items[0].uuid);
+        assert_eq!(second_page.next_cursor, None);
+    });
+}
+
+#[test]
+fn create_question_with_tags() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+
+        let req = NewQuestionRequest {
+            bucket_uuid: fix.bucket.uuid,
+            question_text: "What is your favorite color?".to_string(),
+            tags: vec![" Silly".to_string(), "silly".to_string()],
+        };
+        let res = request()
+            .method("POST")
+            .json(&req)
+            .header("content-length", "500")
+            .path("/api/question")
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let question = deserialize::<Question>(&res);
+
+        let url = format!("/api/question/{}/tags", question.uuid);
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let tags = deserialize::<Vec<Tag>>(&res);
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].tag_name, "silly");
+
+        let url = format!(
+            "/api/question/random?bucket_uuid={}&include_tags=silly",
+            fix.bucket.uuid
+        );
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let drawn = deserialize::<Option<Question>>(&res).expect("Should draw a question");
+        assert_eq!(drawn.uuid, question.uuid);
+    });
+}
+
+#[test]
+fn tag_and_filter_questions_in_bucket() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let url = format!("/api/question/{}/tags", fix.question1.uuid);
+        let req = TagQuestionRequest {
+            tag_name: "serious".to_string(),
+        };
+        let res = request()
+            .method("POST")
+            .json(&req)
+            .header("content-length", "500")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let tags = deserialize::<Vec<Tag>>(&res);
+        assert_eq!(tags.len(), 1);
+
+        let url = format!(
+            "/api/question/in_bucket?bucket_uuid={}&exclude_tags=serious",
+            fix.bucket.uuid
+        );
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let questions = deserialize::<Page<Question>>(&res).items;
+        assert_eq!(questions.len(), 1);
+        assert_eq!(questions[0].uuid, fix.question2.uuid);
+
+        let url = format!("/api/question/{}/tags/{}", fix.question1.uuid, tags[0].uuid);
+        let res = request()
+            .method("DELETE")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let tags = deserialize::<Vec<Tag>>(&res);
+        assert!(tags.is_empty());
+    });
+}
+
+#[test]
+fn edit_and_revert_question() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let url = format!("/api/question/{}", fix.question1.uuid);
+        let req = EditQuestionRequest {
+            question_text: "Is this the edited question?".to_string(),
+        };
+        let res = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let question = deserialize::<Question>(&res);
+        assert_eq!(question.question_text, req.question_text);
+
+        let url = format!("/api/question/{}/revisions", fix.question1.uuid);
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let revisions = deserialize::<Page<QuestionRevision>>(&res).items;
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].question_text, fix.question1.question_text);
+
+        let url = format!(
+            "/api/question/{}/revisions/{}/revert",
+            fix.question1.uuid, revisions[0].uuid
+        );
+        let res = request()
+            .method("POST")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+        let question = deserialize::<Question>(&res);
+        assert_eq!(question.question_text, fix.question1.question_text);
+    });
+}
+
+#[test]
+fn only_those_who_can_see_the_bucket_see_revisions() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        {
+            let repo = provider.get_repo().expect("Should get repo");
+            let changeset = BucketFlagChangeset {
+                uuid: fix.bucket.uuid,
+                public_viewable: Some(false),
+                drawing_enabled: None,
+                exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: None,
+            };
+            repo.change_bucket_flags(changeset)
+                .expect("Should hide bucket");
+        }
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let url = format!("/api/question/{}/revisions", fix.question1.uuid);
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let res = request()
+            .method("GET")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+    });
+}
+
+#[test]
+fn only_those_who_can_see_the_bucket_see_draws() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        {
+            let repo = provider.get_repo().expect("Should get repo");
+            let changeset = BucketFlagChangeset {
+                uuid: fix.bucket.uuid,
+                public_viewable: Some(false),
+                drawing_enabled: None,
+                exclusive: None,
+                draw_strategy: None,
+                duplicate_policy: None,
+            };
+            repo.change_bucket_flags(changeset)
+                .expect("Should hide bucket");
+        }
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let url = format!("/api/question/draws?bucket_uuid={}", fix.bucket.uuid);
+        let res = request().method("GET").path(&url).reply(&filter);
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let res = request()
+            .method("GET")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+    });
+}
+
+#[test]
+fn cant_edit_question_without_moderate_permission() {
+    execute_test_on_repository(|fix: &QuestionFixture, provider: RepositoryProvider| {
+        // The connection is released before the routes acquire their own.
+        let anonymous_question = {
+            let repo = provider.get_repo().expect("Should get repo");
+            let changeset = BucketUserPermissionsChangeset {
+                user_uuid: fix.user.uuid,
+                bucket_uuid: fix.bucket.uuid,
+                set_public_permission: None,
+                set_drawing_permission: None,
+                set_exclusive_permission: None,
+                kick_permission: None,
+                grant_permissions_permission: None,
+                moderate_questions_permission: Some(false),
+                delete_questions_permission: None,
+                invite_permission: None,
+            };
+            repo.set_permissions(changeset)
+                .expect("Should set permissions");
+            repo.create_question(NewQuestion {
+                bucket_uuid: fix.bucket.uuid,
+                user_uuid: None,
+                question_text: "Who wrote this?".to_string(),
+            })
+            .expect("Should create question")
+        };
+
+        let state = State::testing_init(provider, Secret::new_hmac("hello".to_string()));
+        let filter = routes(&state);
+        let jwt = get_jwt(&state);
+
+        let req = EditQuestionRequest {
+            question_text: "I did.".to_string(),
+        };
+        let url = format!("/api/question/{}", anonymous_question.uuid);
+        let res = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // The author can still edit their own question.
+        let url = format!("/api/question/{}", fix.question1.uuid);
+        let res = request()
+            .method("PUT")
+            .json(&req)
+            .header("content-length", "500")
+            .header(AUTHORIZATION_HEADER_KEY, format!("{} {}", BEARER, jwt))
+            .path(&url)
+            .reply(&filter);
+        assert_eq!(res.status(), StatusCode::OK);
+    });
+}
@@ -0,0 +1,136 @@
+//! Reading and writing bucket backups.
+//!
+//! A backup is a tarball holding the bucket's archive as JSON,
+//! so that it can be moved between instances, or loaded into the fake database.
+use db::backup::db_types::{BucketArchive, ARCHIVE_VERSION};
+use serde::Deserialize;
+use std::{
+    fmt::{Display, Formatter},
+    io::{self, Read},
+};
+use tar::{Archive, Builder, Header};
+
+/// The name of the file within the tarball that holds the bucket's archive.
+pub const ARCHIVE_FILE_NAME: &str = "bucket.json";
+
+/// Reasons a backup can't be read.
+#[derive(Debug)]
+pub enum BackupError {
+    /// The backup isn't a tarball, or couldn't be read.
+    Io(io::Error),
+    /// The tarball doesn't contain the archive.
+    MissingArchive,
+    /// The archive isn't valid JSON, or is missing parts of the bucket.
+    Json(serde_json::Error),
+    /// The archive was written by a newer version of the server.
+    UnsupportedVersion(u32),
+}
+
+impl Display for BackupError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            BackupError::Io(e) => write!(f, "The backup could not be read: {}", e),
+            BackupError::MissingArchive => {
+                write!(f, "The backup does not contain '{}'.", ARCHIVE_FILE_NAME)
+            }
+            BackupError::Json(e) => write!(f, "The backup's archive is malformed: {}", e),
+            BackupError::UnsupportedVersion(version) => write!(
+                f,
+                "The backup's archive is version {}, but only up to version {} is supported.",
+                version, ARCHIVE_VERSION
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for BackupError {
+    fn from(error: io::Error) -> Self {
+        BackupError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for BackupError {
+    fn from(error: serde_json::Error) -> Self {
+        BackupError::Json(error)
+    }
+}
+
+/// Writes the archive as a tarball.
+pub fn write_backup(archive: &BucketArchive) -> Result<Vec<u8>, BackupError> {
+    let json = serde_json::to_vec_pretty(archive)?;
+    let mut header = Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp() as u64);
+    header.set_cksum();
+
+    let mut builder = Builder::new(Vec::new());
+    builder.append_data(&mut header, ARCHIVE_FILE_NAME, json.as_slice())?;
+    builder.into_inner().map_err(BackupError::from)
+}
+
+/// Reads the archive out of a tarball.
+pub fn read_backup(backup: &[u8]) -> Result<BucketArchive, BackupError> {
+    let mut tarball = Archive::new(backup);
+    for entry in tarball.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.to_str() != Some(ARCHIVE_FILE_NAME) {
+            continue;
+        }
+        let mut json = Vec::new();
+        entry.read_to_end(&mut json)?;
+        // The version is checked first, so that newer archives aren't reported as malformed.
+        let version: Versioned = serde_json::from_slice(&json)?;
+        if version.version > ARCHIVE_VERSION {
+            return Err(BackupError::UnsupportedVersion(version.version));
+        }
+        return serde_json::from_slice(&json).map_err(BackupError::from);
+    }
+    Err(BackupError::MissingArchive)
+}
+
+/// Just the version of an archive.
+#[derive(Deserialize)]
+struct Versioned {
+    version: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_util::execute_test_on_repository;
+    use db::{test::bucket_user_relation_fixture::UserBucketRelationFixture, RepositoryProvider};
+
+    #[test]
+    fn backup_round_trip() {
+        execute_test_on_repository(
+            |fixture: &UserBucketRelationFixture, provider: RepositoryProvider| {
+                let repo = provider.get_repo().expect("Should get repo.");
+                let archive = repo
+                    .export_bucket(fixture.bucket.uuid)
+                    .expect("Should export bucket");
+                let backup = write_backup(&archive).expect("Should write backup");
+                let read = read_backup(&backup).expect("Should read backup");
+                assert_eq!(read, archive);
+            },
+        )
+    }
+
+    #[test]
+    fn newer_archives_are_rejected() {
+        let json = br#"{"version": 999}"#;
+        let mut header = Header::new_gnu();
+        header.set_size(json.len() as u64);
+        header.set_cksum();
+        let mut builder = Builder::new(Vec::new());
+        builder
+            .append_data(&mut header, ARCHIVE_FILE_NAME, &json[..])
+            .unwrap();
+        let backup = builder.into_inner().unwrap();
+
+        match read_backup(&backup) {
+            Err(BackupError::UnsupportedVersion(999)) => {}
+            other => panic!("Expected an unsupported version, got: {:?}", other),
+        }
+    }
+}
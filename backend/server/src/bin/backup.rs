@@ -0,0 +1,124 @@
+//! Binary for backing up buckets straight from the database, and restoring them.
+//!
+//! It connects to the database at `DATABASE_URL`.
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use db::{backup::db_types::RestoreOptions, BoxedRepository};
+use server::backup::{read_backup, write_backup};
+use std::{fs, process};
+use uuid::Uuid;
+
+fn main() {
+    let matches = App::new("Bucket Backup")
+        .version("0.1.0")
+        .about("Backs up buckets, and restores them as new buckets.")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Writes a backup of the bucket.")
+                .arg(
+                    Arg::with_name("bucket")
+                        .long("bucket")
+                        .value_name("UUID")
+                        .help("The bucket to back up.")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("PATH")
+                        .help("Where the backup is written.")
+                        .takes_value(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("restore")
+                .about("Restores a backup as a new bucket.")
+                .arg(
+                    Arg::with_name("input")
+                        .short("i")
+                        .long("input")
+                        .value_name("PATH")
+                        .help("The backup to restore.")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("slug")
+                        .long("slug")
+                        .value_name("SLUG")
+                        .help("The slug of the restored bucket. Defaults to the slug the bucket was backed up with.")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("owner")
+                        .long("owner")
+                        .value_name("UUID")
+                        .help("The user who will own the restored bucket. Defaults to the bucket's original owner.")
+                        .takes_value(true),
+                ),
+        )
+        .get_matches();
+
+    let conn: BoxedRepository = Box::new(pool::create_single_connection(pool::DATABASE_URL));
+    let result = match matches.subcommand() {
+        ("export", Some(matches)) => export(matches, &conn),
+        ("restore", Some(matches)) => restore(matches, &conn),
+        _ => unreachable!("A subcommand is required"),
+    };
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+fn parse_uuid(matches: &ArgMatches, name: &str) -> Result<Option<Uuid>, String> {
+    matches
+        .value_of(name)
+        .map(|uuid| {
+            Uuid::parse_str(uuid).map_err(|e| format!("'{}' is not a valid uuid: {}", uuid, e))
+        })
+        .transpose()
+}
+
+fn export(matches: &ArgMatches, conn: &BoxedRepository) -> Result<(), String> {
+    let bucket_uuid = parse_uuid(matches, "bucket")?.expect("The bucket is required");
+    let output = matches.value_of("output").expect("The output is required");
+
+    let archive = conn
+        .export_bucket(bucket_uuid)
+        .map_err(|e| format!("Could not export the bucket: {}", e))?;
+    let backup = write_backup(&archive).map_err(|e| e.to_string())?;
+    fs::write(output, backup).map_err(|e| format!("Could not write '{}': {}", output, e))?;
+    println!(
+        "Backed up '{}' to '{}'.",
+        archive.bucket.bucket_name, output
+    );
+    Ok(())
+}
+
+fn restore(matches: &ArgMatches, conn: &BoxedRepository) -> Result<(), String> {
+    let input = matches.value_of("input").expect("The input is required");
+    let owner_uuid = parse_uuid(matches, "owner")?;
+
+    let backup = fs::read(input).map_err(|e| format!("Could not read '{}': {}", input, e))?;
+    let archive = read_backup(&backup).map_err(|e| e.to_string())?;
+    let options = RestoreOptions {
+        bucket_slug: matches
+            .value_of("slug")
+            .map(String::from)
+            .unwrap_or_else(|| archive.bucket.bucket_slug.clone()),
+        owner_uuid,
+        keep_users: true,
+    };
+    let bucket = conn
+        .restore_bucket(archive, options)
+        .map_err(|e| format!("Could not restore the bucket: {}", e))?;
+    println!(
+        "Restored '{}' as '{}' ({}).",
+        bucket.bucket_name, bucket.bucket_slug, bucket.uuid
+    );
+    Ok(())
+}
@@ -45,6 +45,9 @@ pub struct Config {
     pub running_environment: RunningEnvironment,
     /// Determines which data store will be used by the server.
     pub repository: RepositoryType,
+    /// A bucket backup that is loaded into the fake database when the server starts.
+    /// This allows problems seen with real data to be reproduced.
+    pub fake_backup: Option<PathBuf>,
 }
 
 impl Config {
@@ -110,6 +113,13 @@ impl Config {
                 .long("fake")
                 .help("If present, the server will start with a fake database instead of connecting to the real one.")
             )
+            .arg(Arg::with_name("fake_backup")
+                .long("fake-backup")
+                .value_name("PATH")
+                .requires("fake_database")
+                .help("A bucket backup to load into the fake database when the server starts.")
+                .takes_value(true)
+            )
             .get_matches_safe();
 
         match matches {
@@ -151,6 +161,8 @@ impl Config {
                     }
                 };
 
+                let fake_backup = matches.value_of("fake_backup").map(PathBuf::from);
+
                 let repository = if matches.is_present("fake_database") {
                     warn!("Application starting with a fake database. This is ok for testing purposes, but should not be done in production.");
                     RepositoryType::Fake
@@ -166,6 +178,7 @@ impl Config {
                     server_lib_root,
                     running_environment,
                     repository,
+                    fake_backup,
                 }
             }
             Err(error) => {
@@ -4,6 +4,7 @@
 //!
 //! Currently, this is tightly coupled to both Warp, Diesel, and Authorization.
 //! It likely should be broken off into its own crate with the warp-related functions allowed as a feature.
+use crate::backup::BackupError;
 use apply::Apply;
 use authorization::AuthError;
 use diesel::result::DatabaseErrorKind;
@@ -313,6 +314,12 @@ impl Error {
     }
 }
 
+impl From<BackupError> for Error {
+    fn from(error: BackupError) -> Self {
+        Error::BadRequest(error.to_string())
+    }
+}
+
 impl From<diesel::result::Error> for Error {
     fn from(error: diesel::result::Error) -> Self {
         use self::Error::*;
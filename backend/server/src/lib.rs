@@ -11,6 +11,7 @@
 )]
 
 mod api;
+pub mod backup;
 mod config;
 mod conversions;
 mod error;
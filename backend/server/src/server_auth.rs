@@ -121,6 +121,7 @@ mod unit {
             server_lib_root: None,
             environment: RunningEnvironment::default(),
             repository: RepositoryType::Fake,
+            fake_backup: None,
         };
         let state = State::new(conf);
         let uuid = Uuid::new_v4();
@@ -143,6 +144,7 @@ mod unit {
             server_lib_root: None,
             environment: RunningEnvironment::default(),
             repository: RepositoryType::Fake,
+            fake_backup: None,
         };
 
         let state = State::new(conf);
@@ -159,6 +161,7 @@ mod unit {
             server_lib_root: None,
             environment: RunningEnvironment::default(),
             repository: RepositoryType::Fake,
+            fake_backup: None,
         };
         let state = State::new(conf);
         let uuid = Uuid::new_v4();
@@ -186,6 +189,7 @@ mod unit {
             server_lib_root: None,
             environment: RunningEnvironment::default(),
             repository: RepositoryType::Fake,
+            fake_backup: None,
         };
 
         let state = State::new(conf);
@@ -202,6 +206,7 @@ mod unit {
             server_lib_root: None,
             environment: RunningEnvironment::default(),
             repository: RepositoryType::Fake,
+            fake_backup: None,
         };
         let state = State::new(conf);
         let uuid = Uuid::new_v4();
@@ -230,6 +235,7 @@ mod unit {
             server_lib_root: None,
             environment: RunningEnvironment::default(),
             repository: RepositoryType::Fake,
+            fake_backup: None,
         };
         let state = State::new(conf);
 
@@ -0,0 +1,234 @@
+//! Fans out live bucket events to the clients subscribed to each bucket.
+use futures::sync::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+use uuid::Uuid;
+use wire::live::BucketEvent;
+
+/// How many of a bucket's most recent events are kept around for clients that reconnect.
+pub const REPLAY_BUFFER_SIZE: usize = 64;
+
+/// An event, along with the id that it was published under.
+///
+/// Ids increase with every event published to the hub, regardless of bucket.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SequencedEvent {
+    /// The id of the event.
+    pub id: u64,
+    /// The event.
+    pub event: BucketEvent,
+}
+
+/// Keeps track of the subscribers for every bucket.
+///
+/// Cloning the hub is cheap, all clones share the same subscribers.
+#[derive(Clone, Debug, Default)]
+pub struct Hub {
+    inner: Arc<Mutex<HubInner>>,
+}
+
+#[derive(Debug, Default)]
+struct HubInner {
+    /// The id of the most recently published event.
+    last_id: u64,
+    buckets: HashMap<Uuid, BucketChannel>,
+}
+
+#[derive(Debug, Default)]
+struct BucketChannel {
+    subscribers: Vec<UnboundedSender<SequencedEvent>>,
+    recent: VecDeque<SequencedEvent>,
+}
+
+impl Hub {
+    /// Subscribes to the events of a bucket.
+    ///
+    /// If the id of the last event the client saw is provided,
+    /// the events published after it that are still in the replay buffer are sent first.
+    /// An id the hub hasn't handed out yet means the server was restarted,
+    /// so everything in the buffer is replayed.
+    ///
+    /// The subscription is dropped the next time an event is published after the receiver goes away.
+    pub fn subscribe(
+        &self,
+        bucket_uuid: Uuid,
+        last_event_id: Option<u64>,
+    ) -> UnboundedReceiver<SequencedEvent> {
+        let (tx, rx) = unbounded();
+        let mut inner = self.inner.lock().unwrap();
+        let last_id = inner.last_id;
+        let channel = inner.buckets.entry(bucket_uuid).or_default();
+
+        if let Some(last_event_id) = last_event_id {
+            let replay_after = if last_event_id > last_id {
+                0
+            } else {
+                last_event_id
+            };
+            channel
+                .recent
+                .iter()
+                .filter(|event| event.id > replay_after)
+                .for_each(|event| {
+                    // The receiver is still held here, so this can't fail.
+                    let _ = tx.unbounded_send(event.clone());
+                });
+        }
+
+        channel.subscribers.push(tx);
+        rx
+    }
+
+    /// Sends the event to every subscriber of the bucket,
+    /// and keeps it in the bucket's replay buffer.
+    pub fn publish(&self, bucket_uuid: Uuid, event: BucketEvent) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.last_id += 1;
+        let event = SequencedEvent {
+            id: inner.last_id,
+            event,
+        };
+        let channel = inner.buckets.entry(bucket_uuid).or_default();
+
+        channel
+            .subscribers
+            .retain(|sender| sender.unbounded_send(event.clone()).is_ok());
+
+        if channel.recent.len() == REPLAY_BUFFER_SIZE {
+            channel.recent.pop_front();
+        }
+        channel.recent.push_back(event);
+    }
+
+    /// Forgets the bucket, ending its subscriptions once they have received the events already sent to them.
+    ///
+    /// This is for buckets that were deleted, which won't have any more events.
+    pub fn remove_bucket(&self, bucket_uuid: Uuid) {
+        self.inner.lock().unwrap().buckets.remove(&bucket_uuid);
+    }
+
+    /// The number of live subscriptions to the bucket.
+    #[allow(dead_code)]
+    pub fn subscriber_count(&self, bucket_uuid: Uuid) -> usize {
+        self.inner
+            .lock()
+            .unwrap()
+            .buckets
+            .get(&bucket_uuid)
+            .map(|channel| channel.subscribers.len())
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::{future, Async, Future, Stream};
+
+    fn joined() -> BucketEvent {
+        BucketEvent::MemberJoined {
+            user_uuid: Uuid::new_v4(),
+        }
+    }
+
+    /// Gets the events that are ready to be received.
+    fn ready_events(rx: UnboundedReceiver<SequencedEvent>) -> Vec<SequencedEvent> {
+        // Polling a receiver that has nothing to yield requires a task context.
+        future::lazy(move || {
+            let mut rx = rx;
+            let mut events = vec![];
+            while let Ok(Async::Ready(Some(event))) = rx.poll() {
+                events.push(event);
+            }
+            Ok::<_, ()>(events)
+        })
+        .wait()
+        .unwrap()
+    }
+
+    #[test]
+    fn publish_reaches_only_subscribers_of_the_bucket() {
+        let hub = Hub::default();
+        let bucket_uuid = Uuid::new_v4();
+        let rx = hub.subscribe(bucket_uuid, None);
+        let other_rx = hub.subscribe(Uuid::new_v4(), None);
+
+        let event = joined();
+        hub.publish(bucket_uuid, event.clone());
+
+        let received = ready_events(rx);
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].event, event);
+        assert!(ready_events(other_rx).is_empty());
+    }
+
+    #[test]
+    fn dropped_subscribers_are_removed() {
+        let hub = Hub::default();
+        let bucket_uuid = Uuid::new_v4();
+        let rx = hub.subscribe(bucket_uuid, None);
+        assert_eq!(hub.subscriber_count(bucket_uuid), 1);
+
+        drop(rx);
+        hub.publish(bucket_uuid, joined());
+        assert_eq!(hub.subscriber_count(bucket_uuid), 0);
+    }
+
+    #[test]
+    fn resubscribing_replays_missed_events() {
+        let hub = Hub::default();
+        let bucket_uuid = Uuid::new_v4();
+        hub.publish(bucket_uuid, joined());
+        hub.publish(Uuid::new_v4(), joined());
+        hub.publish(bucket_uuid, joined());
+        hub.publish(bucket_uuid, joined());
+
+        let ids: Vec<u64> = ready_events(hub.subscribe(bucket_uuid, Some(1)))
+            .into_iter()
+            .map(|event| event.id)
+            .collect();
+        assert_eq!(ids, vec![3, 4]);
+
+        assert!(ready_events(hub.subscribe(bucket_uuid, None)).is_empty());
+    }
+
+    #[test]
+    fn replay_buffer_is_bounded() {
+        let hub = Hub::default();
+        let bucket_uuid = Uuid::new_v4();
+        for _ in 0..REPLAY_BUFFER_SIZE + 10 {
+            hub.publish(bucket_uuid, joined());
+        }
+
+        let events = ready_events(hub.subscribe(bucket_uuid, Some(0)));
+        assert_eq!(events.len(), REPLAY_BUFFER_SIZE);
+        assert_eq!(events[0].id, 11);
+    }
+
+    #[test]
+    fn removing_bucket_ends_subscriptions() {
+        let hub = Hub::default();
+        let bucket_uuid = Uuid::new_v4();
+        let rx = hub.subscribe(bucket_uuid, None);
+        hub.publish(bucket_uuid, BucketEvent::BucketDeleted);
+        hub.remove_bucket(bucket_uuid);
+        assert_eq!(hub.subscriber_count(bucket_uuid), 0);
+
+        // The last event still arrives before the subscription ends.
+        let events: Vec<BucketEvent> = rx.map(|event| event.event).collect().wait().unwrap();
+        assert_eq!(events, vec![BucketEvent::BucketDeleted]);
+    }
+
+    #[test]
+    fn unknown_last_event_id_replays_everything() {
+        let hub = Hub::default();
+        let bucket_uuid = Uuid::new_v4();
+        hub.publish(bucket_uuid, joined());
+        hub.publish(bucket_uuid, joined());
+
+        let events = ready_events(hub.subscribe(bucket_uuid, Some(500)));
+        assert_eq!(events.len(), 2);
+    }
+}
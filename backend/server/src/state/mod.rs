@@ -1,16 +1,23 @@
 //! Represents the shared server resources that all requests may utilize.
+pub mod hub;
 pub mod state_config;
 #[cfg(test)]
 pub mod test_util;
 
 use crate::config::RepositoryType;
 use crate::{
-    error::Error, server_auth::create_google_oauth_client, state::state_config::StateConfig,
+    backup::read_backup,
+    error::Error,
+    server_auth::create_google_oauth_client,
+    state::{hub::Hub, state_config::StateConfig},
 };
 use apply::Apply;
 use authorization::Secret;
 use db::fake::FakeDatabase;
-use db::{Repository, RepositoryProvider};
+use db::{
+    backup::{db_types::RestoreOptions, interface::BackupRepository},
+    Repository, RepositoryProvider,
+};
 use hyper::{
     client::{connect::dns::GaiResolver, HttpConnector},
     Body, Client,
@@ -22,11 +29,24 @@ use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use std::sync::{Arc, Mutex};
 use std::{
     fmt::{Debug, Formatter},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 use url::Url;
 use warp::{Filter, Rejection};
 
+/// Loads a bucket backup into the fake database, keeping the bucket's slug and users.
+fn restore_fake_backup(fake: &Arc<Mutex<FakeDatabase>>, path: &Path) {
+    let backup = std::fs::read(path).expect("Could not read the backup for the fake database.");
+    let archive = read_backup(&backup).unwrap_or_else(|e| panic!("{}", e));
+    let options = RestoreOptions {
+        bucket_slug: archive.bucket.bucket_slug.clone(),
+        owner_uuid: None,
+        keep_users: true,
+    };
+    fake.restore_bucket(archive, options)
+        .expect("Could not restore the backup into the fake database.");
+}
+
 /// Simplified type for representing a HttpClient.
 pub type HttpsClient = Client<HttpsConnector<HttpConnector<GaiResolver>>, Body>;
 
@@ -50,6 +70,8 @@ pub struct State {
     server_lib_root: PathBuf,
     /// Redirect url for Oauth
     redirect_url: Url,
+    /// Distributes live events to the clients watching a bucket.
+    hub: Hub,
 }
 
 impl Debug for State {
@@ -59,6 +81,7 @@ impl Debug for State {
             .field("secret", &self.secret) // Display for Secret self-censors
             .field("server_lib_root", &self.server_lib_root)
             .field("redirect_url", &self.redirect_url)
+            .field("hub", &self.hub)
             .finish()
     }
 }
@@ -91,7 +114,11 @@ impl State {
 
         let repository_provider = match conf.repository {
             RepositoryType::Fake => {
-                RepositoryProvider::Fake(Arc::new(Mutex::new(FakeDatabase::default())))
+                let fake = Arc::new(Mutex::new(FakeDatabase::default()));
+                if let Some(path) = conf.fake_backup {
+                    restore_fake_backup(&fake, &path);
+                }
+                RepositoryProvider::Fake(fake)
             }
             RepositoryType::Database => {
                 RepositoryProvider::Pool(init_pool(DATABASE_URL, pool_conf))
@@ -105,6 +132,7 @@ impl State {
             google_oauth_client,
             server_lib_root: root,
             redirect_url,
+            hub: Hub::default(),
         }
     }
 
@@ -125,12 +153,28 @@ impl State {
         )
     }
 
+    /// Gets the provider of repositories, for things that need to reach the backing store
+    /// again long after the request has been handled.
+    pub fn repository_provider(
+        &self,
+    ) -> impl Filter<Extract = (RepositoryProvider,), Error = Rejection> + Clone {
+        let provider = self.repository_provider.clone();
+        warp::any()
+            .and_then(move || -> Result<RepositoryProvider, Rejection> { Ok(provider.clone()) })
+    }
+
     /// Gets the secret used for authoring JWTs
     pub fn secret(&self) -> impl Filter<Extract = (Secret,), Error = Rejection> + Clone {
         let secret = self.secret.clone();
         warp::any().and_then(move || -> Result<Secret, Rejection> { Ok(secret.clone()) })
     }
 
+    /// Gets the hub used for publishing and subscribing to live bucket events.
+    pub fn hub(&self) -> impl Filter<Extract = (Hub,), Error = Rejection> + Clone {
+        let hub = self.hub.clone();
+        warp::any().and_then(move || -> Result<Hub, Rejection> { Ok(hub.clone()) })
+    }
+
     /// Gets the https client used for making dependent api calls.
     pub fn https_client(&self) -> impl Filter<Extract = (HttpsClient,), Error = Rejection> + Clone {
         let client = self.https.clone();
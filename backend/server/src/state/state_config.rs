@@ -14,6 +14,8 @@ pub struct StateConfig {
     pub server_lib_root: Option<PathBuf>,
     pub environment: RunningEnvironment,
     pub repository: RepositoryType,
+    /// A bucket backup to load into the fake database.
+    pub fake_backup: Option<PathBuf>,
 }
 
 impl From<Config> for StateConfig {
@@ -24,6 +26,7 @@ impl From<Config> for StateConfig {
             server_lib_root: config.server_lib_root,
             environment: config.running_environment,
             repository: config.repository,
+            fake_backup: config.fake_backup,
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     server_auth::create_google_oauth_client,
-    state::{state_config::RunningEnvironment, State},
+    state::{hub::Hub, state_config::RunningEnvironment, State},
 };
 use authorization::Secret;
 use db::{
@@ -35,6 +35,7 @@ impl State {
             google_oauth_client,
             server_lib_root: PathBuf::from("./"), // THIS makes the assumption that the tests are run from the backend/server dir.
             redirect_url,
+            hub: Hub::default(),
         }
     }
 }
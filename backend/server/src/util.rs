@@ -1,7 +1,11 @@
 //! Common utilities
 use crate::error::Error;
+use bytes::Buf;
+use db::page::Pagination;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use warp::{Filter, Rejection, Reply};
+use wire::page::PageQuery;
 
 /// A path filter that specifies the remaining segment(s) to match upon.
 #[allow(dead_code)]
@@ -22,6 +26,18 @@ where
     warp::body::content_length_limit(KILOBYTE * kb_limit).and(warp::body::json())
 }
 
+/// Extracts the raw bytes of the body of a request after stipulating that it has a reasonable size in kilobytes.
+///
+/// # Arguments
+/// * kb_limit - The maximum number of kilobytes, over which the request will be rejected.
+pub fn sized_body_bytes(
+    kb_limit: u64,
+) -> impl Filter<Extract = (Vec<u8>,), Error = Rejection> + Copy {
+    warp::body::content_length_limit(KILOBYTE * kb_limit)
+        .and(warp::body::concat())
+        .map(|body: warp::body::FullBody| body.bytes().to_vec())
+}
+
 /// Utility function that takes a value of a type and converts
 /// it to another type before serializing it.
 #[allow(dead_code)]
@@ -72,6 +88,37 @@ where
     warp::reply::json(&target)
 }
 
+/// How many items are in a page, unless the request specifies otherwise.
+pub const DEFAULT_PAGE_SIZE: i64 = 25;
+/// The most items that can be requested in a single page.
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+/// Gets the page of a list that the request selects, keeping the limit within reason.
+///
+/// The list is sorted in the default order if the request doesn't specify one.
+pub fn pagination<S, T>(page: PageQuery<S>, default_sort: S) -> Result<Pagination<T>, Error>
+where
+    S: Into<T>,
+{
+    let after = match page.cursor {
+        Some(cursor) => Some(
+            Uuid::parse_str(&cursor)
+                .map_err(|_| Error::BadRequest("The cursor isn't valid.".to_string()))?,
+        ),
+        None => None,
+    };
+    let limit = page
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .max(1)
+        .min(MAX_PAGE_SIZE);
+    Ok(Pagination {
+        after,
+        limit,
+        sort: page.sort.unwrap_or(default_sort).into(),
+    })
+}
+
 #[cfg(test)]
 pub mod test_util {
     use bytes::Bytes;
@@ -19,6 +19,8 @@ pub struct Answer {
     pub updated_at: NaiveDateTime,
     /// When the row was created.
     pub created_at: NaiveDateTime,
+    /// The session the answer was given in.
+    pub session_uuid: Option<Uuid>,
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -31,6 +33,18 @@ pub struct NewAnswer {
     pub publicly_visible: bool,
     /// The answer
     pub answer_text: String,
+    /// The session the answer was given in.
+    pub session_uuid: Option<Uuid>,
+}
+
+/// A newly created answer.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreatedAnswer {
+    #[serde(flatten)]
+    pub answer: Answer,
+    /// Allows the answer to be edited or deleted, if it was given without logging in.
+    /// This is only ever provided when the answer is created.
+    pub edit_token: Option<Uuid>,
 }
 
 //
@@ -46,3 +60,11 @@ pub struct NewAnswerRequest {
     /// The request should archive the question
     pub archive_question: bool
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditAnswerRequest {
+    /// Can the outside world see the answer.
+    pub publicly_visible: Option<bool>,
+    /// The answer
+    pub answer_text: Option<String>,
+}
@@ -0,0 +1,111 @@
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+/// A record of something that changed within a bucket.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// The unique identifier of the event.
+    pub uuid: Uuid,
+    /// The bucket the change was made in.
+    pub bucket_uuid: Uuid,
+    /// The user who made the change, if they were logged in.
+    pub actor_uuid: Option<Uuid>,
+    /// The user, question or answer that was changed, if any.
+    pub target_uuid: Option<Uuid>,
+    /// What was done.
+    pub action: AuditAction,
+    /// The fields that changed, as `{"field": {"old": .., "new": ..}}`.
+    pub diff: Value,
+    /// When the change was made.
+    pub created_at: NaiveDateTime,
+}
+
+/// The kinds of changes that are recorded in the audit log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    BucketCreated,
+    BucketRenamed,
+    /// The bucket was made as a copy of another bucket or template.
+    BucketCloned,
+    /// The bucket was made as a template out of another bucket.
+    TemplateSaved,
+    /// The bucket was restored from a backup of another bucket.
+    BucketRestored,
+    MemberJoined,
+    /// A member left the bucket, or was kicked from it.
+    MemberRemoved,
+    MemberBanned,
+    BanLifted,
+    PermissionsChanged,
+    RoleSet,
+    OwnershipTransferred,
+    InviteCreated,
+    InviteDeleted,
+    JoinCodeRegenerated,
+    /// The bucket's flags, or its draw strategy, were changed.
+    FlagsChanged,
+    TurnSkipped,
+    SessionStarted,
+    SessionEnded,
+    QuestionCreated,
+    /// Many questions were added to the bucket at once.
+    QuestionsImported,
+    QuestionEdited,
+    /// A question's text was restored from one of its revisions.
+    QuestionReverted,
+    QuestionDeleted,
+    QuestionDrawn,
+    /// A question was moved onto or off of the floor.
+    QuestionArchiveStateSet,
+    QuestionTagged,
+    QuestionUntagged,
+    QuestionVoted,
+    QuestionVoteRemoved,
+    AnswerPosted,
+    AnswerEdited,
+    AnswerDeleted,
+}
+
+impl AuditAction {
+    /// A short description of the action, for showing in the log.
+    pub fn description(self) -> &'static str {
+        match self {
+            AuditAction::BucketCreated => "Created the bucket",
+            AuditAction::BucketRenamed => "Renamed the bucket",
+            AuditAction::BucketCloned => "Copied the bucket from another",
+            AuditAction::TemplateSaved => "Saved another bucket as this template",
+            AuditAction::BucketRestored => "Restored the bucket from a backup",
+            AuditAction::MemberJoined => "Joined",
+            AuditAction::MemberRemoved => "Removed a member",
+            AuditAction::MemberBanned => "Banned a user",
+            AuditAction::BanLifted => "Lifted a ban",
+            AuditAction::PermissionsChanged => "Changed permissions",
+            AuditAction::RoleSet => "Set a role",
+            AuditAction::OwnershipTransferred => "Transferred ownership",
+            AuditAction::InviteCreated => "Created an invite",
+            AuditAction::InviteDeleted => "Deleted an invite",
+            AuditAction::JoinCodeRegenerated => "Replaced the join code",
+            AuditAction::FlagsChanged => "Changed settings",
+            AuditAction::TurnSkipped => "Skipped a turn",
+            AuditAction::SessionStarted => "Started a session",
+            AuditAction::SessionEnded => "Ended a session",
+            AuditAction::QuestionCreated => "Added a question",
+            AuditAction::QuestionsImported => "Imported questions",
+            AuditAction::QuestionEdited => "Edited a question",
+            AuditAction::QuestionReverted => "Reverted a question",
+            AuditAction::QuestionDeleted => "Deleted a question",
+            AuditAction::QuestionDrawn => "Drew a question",
+            AuditAction::QuestionArchiveStateSet => "Moved a question",
+            AuditAction::QuestionTagged => "Tagged a question",
+            AuditAction::QuestionUntagged => "Untagged a question",
+            AuditAction::QuestionVoted => "Voted on a question",
+            AuditAction::QuestionVoteRemoved => "Took back a vote",
+            AuditAction::AnswerPosted => "Answered a question",
+            AuditAction::AnswerEdited => "Edited an answer",
+            AuditAction::AnswerDeleted => "Deleted an answer",
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+/// A ban that keeps a user from joining a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Ban {
+    /// The bucket the user is banned from.
+    pub bucket_uuid: Uuid,
+    /// The user who is banned.
+    pub user_uuid: Uuid,
+    /// The user who banned them.
+    pub banned_by_uuid: Option<Uuid>,
+    /// Why the user was banned.
+    pub reason: Option<String>,
+    /// When the ban stops applying, if ever.
+    pub expires_at: Option<NaiveDateTime>,
+    /// When the user was banned.
+    pub created_at: NaiveDateTime,
+}
+
+/// Request to ban a user from a bucket.
+/// If they are a member of the bucket, they are removed from it as well.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct NewBanRequest {
+    pub user_uuid: Uuid,
+    /// Why the user is banned.
+    pub reason: Option<String>,
+    /// When the ban stops applying, if ever.
+    pub expires_at: Option<NaiveDateTime>,
+}
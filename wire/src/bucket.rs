@@ -1,6 +1,7 @@
 use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 use chrono::NaiveDateTime;
+use crate::bucket_user_relation::BucketRole;
 
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -22,6 +23,15 @@ pub struct Bucket {
     pub updated_at: NaiveDateTime,
     /// When the row was created.
     pub created_at: NaiveDateTime,
+    /// How questions are picked when drawing from the bucket.
+    pub draw_strategy: DrawStrategy,
+    /// The user who owns the bucket.
+    /// They hold every permission, and can't be kicked.
+    pub owner_uuid: Option<Uuid>,
+    /// Is the bucket a template that new buckets are made from, rather than one that is played in.
+    pub is_template: bool,
+    /// What happens when a question is asked that is already in the bucket.
+    pub duplicate_policy: DuplicatePolicy,
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -42,6 +52,50 @@ pub struct BucketFlagChangeset {
     pub drawing_enabled: Option<bool>,
     /// Can an unjoined user join the bucket.
     pub exclusive: Option<bool>,
+    /// How questions are picked when drawing from the bucket.
+    pub draw_strategy: Option<DrawStrategy>,
+    /// What happens when a question is asked that is already in the bucket.
+    pub duplicate_policy: Option<DuplicatePolicy>,
+}
+
+/// How questions are picked when drawing from a bucket.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrawStrategy {
+    /// Every question has the same chance of being drawn.
+    #[default]
+    Uniform,
+    /// Questions with more votes are more likely to be drawn.
+    Upvotes,
+    /// Questions that have been in the bucket longer are more likely to be drawn.
+    OlderFirst,
+    /// Questions written by members who haven't had one of their questions drawn recently
+    /// are more likely to be drawn.
+    MembersNotRecentlyDrawn,
+}
+
+/// What happens when a question is asked that is the same as, or nearly the same as,
+/// a question already in the bucket.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    /// The question is added, and the similar questions are returned alongside it.
+    #[default]
+    Warn,
+    /// The question is not added.
+    Reject,
+}
+
+/// The order in which lists of buckets are returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BucketSort {
+    /// Least recently updated first.
+    Updated,
+    /// Most recently created first.
+    Newest,
+    /// Alphabetically by name.
+    Name,
 }
 
 // Special types
@@ -65,6 +119,26 @@ pub struct SetPermissionsRequest {
     pub kick_permission: Option<bool>,
     /// Can the user grant permissions to other users.
     pub grant_permissions_permission: Option<bool>,
+    /// Can the user edit any of the bucket's questions.
+    pub moderate_questions_permission: Option<bool>,
+    /// Can the user delete any of the bucket's questions.
+    pub delete_questions_permission: Option<bool>,
+    /// Can the user invite others to the bucket.
+    pub invite_permission: Option<bool>,
+}
+
+/// Request to give a user all of the permissions of a role at once.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SetRoleRequest {
+    pub target_user_uuid: Uuid,
+    /// The role to give the user.
+    pub role: BucketRole,
+}
+
+/// Request to make another member the owner of the bucket.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransferOwnershipRequest {
+    pub new_owner_uuid: Uuid,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -85,6 +159,10 @@ pub struct ChangeBucketFlagsRequest {
     pub drawing_enabled: Option<bool>,
     /// Can an unjoined user join the bucket.
     pub exclusive: Option<bool>,
+    /// How questions are picked when drawing from the bucket.
+    pub draw_strategy: Option<DrawStrategy>,
+    /// What happens when a question is asked that is already in the bucket.
+    pub duplicate_policy: Option<DuplicatePolicy>,
 }
 
 /// Request to create a bucket.
@@ -92,3 +170,46 @@ pub struct ChangeBucketFlagsRequest {
 pub struct NewBucketRequest {
     pub bucket_name: String,
 }
+
+/// Request to change the name of a bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameBucketRequest {
+    pub bucket_name: String,
+    /// Should the bucket's slug be made from the new name.
+    /// Links with the old slug keep working.
+    pub regenerate_slug: bool,
+}
+
+/// A short lived token that must be sent along with a request to delete a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct BucketDeletionToken {
+    pub token: String,
+    /// When the token can no longer be used.
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeleteBucketQueryParam {
+    /// The token gotten from asking to delete the bucket.
+    pub token: String,
+}
+
+/// Request to make a new bucket out of the questions in another bucket or template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloneBucketRequest {
+    pub bucket_name: String,
+    /// Should the questions keep their tags.
+    pub copy_tags: bool,
+    /// Should the new bucket have the same visibility, drawing, exclusivity, and draw strategy.
+    pub copy_flags: bool,
+    /// Should the other members be added to the new bucket with the permissions they have now.
+    pub copy_member_roles: bool,
+}
+
+/// Request to save a bucket's questions and tags as a template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveTemplateRequest {
+    pub template_name: String,
+    /// Can other users find the template and make buckets from it.
+    pub public: bool,
+}
@@ -19,6 +19,12 @@ pub struct BucketUserRelation {
     pub kick_permission: bool,
     /// Can the user grant permissions to other users.
     pub grant_permissions_permission: bool,
+    /// Can the user edit any of the bucket's questions.
+    pub moderate_questions_permission: bool,
+    /// Can the user delete any of the bucket's questions.
+    pub delete_questions_permission: bool,
+    /// Can the user invite others to the bucket.
+    pub invite_permission: bool,
     /// When the row was last updated.
     pub updated_at: NaiveDateTime,
     /// When the row was created.
@@ -42,6 +48,12 @@ pub struct NewBucketUserRelation {
     pub kick_permission: bool,
     /// Can the user grant permissions to other users.
     pub grant_permissions_permission: bool,
+    /// Can the user edit any of the bucket's questions.
+    pub moderate_questions_permission: bool,
+    /// Can the user delete any of the bucket's questions.
+    pub delete_questions_permission: bool,
+    /// Can the user invite others to the bucket.
+    pub invite_permission: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -60,6 +72,12 @@ pub struct BucketUserPermissionsChangeset {
     pub kick_permission: Option<bool>,
     /// Can the user grant permissions to other users.
     pub grant_permissions_permission: Option<bool>,
+    /// Can the user edit any of the bucket's questions.
+    pub moderate_questions_permission: Option<bool>,
+    /// Can the user delete any of the bucket's questions.
+    pub delete_questions_permission: Option<bool>,
+    /// Can the user invite others to the bucket.
+    pub invite_permission: Option<bool>,
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -72,6 +90,12 @@ pub struct BucketUserPermissions {
     pub set_exclusive_permission: bool,
     /// Can the user grant permissions to other users.
     pub grant_permissions_permission: bool,
+    /// Can the user edit any of the bucket's questions.
+    pub moderate_questions_permission: bool,
+    /// Can the user delete any of the bucket's questions.
+    pub delete_questions_permission: bool,
+    /// Can the user invite others to the bucket.
+    pub invite_permission: bool,
     /// Can the user kick other users.
     pub kick_permission: bool,
 }
@@ -82,4 +106,59 @@ pub struct UserAndPermissions {
     pub user: User,
     /// The associated permissions
     pub permissions: BucketUserPermissions
-}
\ No newline at end of file
+}
+
+/// The order in which lists of a bucket's members are returned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemberSort {
+    /// In the order they joined the bucket.
+    Joined,
+    /// Alphabetically by name, with members who don't have a name first.
+    Name,
+}
+
+/// Named sets of permissions that can be given to a user all at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BucketRole {
+    /// Holds every permission.
+    /// Only one user can be the owner of a bucket, so this role is given by transferring ownership.
+    Owner,
+    /// Can run the game and keep the bucket tidy, but can't change who can do what.
+    Moderator,
+    /// Can run the game and bring in friends.
+    Player,
+    /// Has no permissions beyond what every member can do.
+    Spectator,
+}
+
+impl BucketRole {
+    /// The permissions a user with this role has.
+    pub fn permissions(self) -> BucketUserPermissions {
+        let (moderator, player, owner) = match self {
+            BucketRole::Owner => (true, true, true),
+            BucketRole::Moderator => (true, true, false),
+            BucketRole::Player => (false, true, false),
+            BucketRole::Spectator => (false, false, false),
+        };
+        BucketUserPermissions {
+            set_public_permission: owner,
+            set_drawing_permission: player,
+            set_exclusive_permission: owner,
+            grant_permissions_permission: owner,
+            moderate_questions_permission: moderator,
+            delete_questions_permission: moderator,
+            invite_permission: player,
+            kick_permission: moderator,
+        }
+    }
+
+    /// Gets the role whose permissions match exactly, if there is one.
+    pub fn from_permissions(permissions: &BucketUserPermissions) -> Option<BucketRole> {
+        [BucketRole::Owner, BucketRole::Moderator, BucketRole::Player, BucketRole::Spectator]
+            .iter()
+            .cloned()
+            .find(|role| &role.permissions() == permissions)
+    }
+}
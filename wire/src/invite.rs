@@ -0,0 +1,63 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+use crate::bucket_user_relation::BucketUserPermissions;
+
+/// An invite to a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Invite {
+    /// The unique identifier of the invite, which is shared with invitees.
+    pub uuid: Uuid,
+    /// The bucket the invite is for.
+    pub bucket_uuid: Uuid,
+    /// The user who created the invite.
+    pub user_uuid: Option<Uuid>,
+    /// When the invite stops being usable, if ever.
+    pub expires_at: Option<NaiveDateTime>,
+    /// How many users can join with the invite, if there is a limit.
+    pub max_uses: Option<i32>,
+    /// How many users have joined with the invite.
+    pub uses: i32,
+    /// Can the joining user set the visibility of the bucket.
+    pub set_public_permission: bool,
+    /// Can the joining user enable drawing from the bucket.
+    pub set_drawing_permission: bool,
+    /// Can the joining user set the bucket to private.
+    pub set_exclusive_permission: bool,
+    /// Can the joining user kick other users.
+    pub kick_permission: bool,
+    /// Can the joining user grant permissions to other users.
+    pub grant_permissions_permission: bool,
+    /// Can the joining user edit any of the bucket's questions.
+    pub moderate_questions_permission: bool,
+    /// Can the joining user delete any of the bucket's questions.
+    pub delete_questions_permission: bool,
+    /// Can the joining user invite others to the bucket.
+    pub invite_permission: bool,
+    /// When the invite was created.
+    pub created_at: NaiveDateTime,
+}
+
+/// Request to create an invite to a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct NewInviteRequest {
+    /// When the invite stops being usable, if ever.
+    pub expires_at: Option<NaiveDateTime>,
+    /// How many users can join with the invite, if there is a limit.
+    pub max_uses: Option<i32>,
+    /// The permissions users will have when they join with the invite.
+    ///
+    /// These can't exceed the permissions of the user creating the invite.
+    pub permissions: BucketUserPermissions,
+}
+
+/// A short code that can be typed in to join a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct JoinCode {
+    /// The bucket the code is for.
+    pub bucket_uuid: Uuid,
+    /// The code.
+    pub join_code: String,
+    /// When the code was created.
+    pub created_at: NaiveDateTime,
+}
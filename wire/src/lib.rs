@@ -3,3 +3,13 @@ pub mod question;
 pub mod bucket;
 pub mod answer;
 pub mod bucket_user_relation;
+pub mod turn;
+pub mod session;
+pub mod tag;
+pub mod live;
+pub mod presence;
+pub mod invite;
+pub mod audit;
+pub mod ban;
+pub mod search;
+pub mod page;
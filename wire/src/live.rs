@@ -0,0 +1,58 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::question::Question;
+use crate::answer::Answer;
+use crate::bucket::Bucket;
+use crate::bucket_user_relation::BucketUserPermissions;
+
+/// An event pushed to the clients subscribed to a bucket's live feed.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BucketEvent {
+    /// A question was added to the bucket.
+    QuestionAdded {
+        /// The new question.
+        question: Question
+    },
+    /// A question was drawn from the bucket.
+    QuestionDrawn {
+        /// The drawn question.
+        question: Question
+    },
+    /// An answer was posted to one of the bucket's questions.
+    AnswerPosted {
+        /// The question that was answered.
+        question_uuid: Uuid,
+        /// The answer, only present if it is publicly visible.
+        answer: Option<Answer>
+    },
+    /// The bucket's flags were changed.
+    FlagsChanged {
+        /// The bucket after the change.
+        bucket: Bucket
+    },
+    /// The bucket was renamed, or given a new slug.
+    BucketRenamed {
+        /// The bucket after the change.
+        bucket: Bucket
+    },
+    /// The bucket was deleted.
+    BucketDeleted,
+    /// A user joined the bucket.
+    MemberJoined {
+        /// The user who joined.
+        user_uuid: Uuid
+    },
+    /// A user was removed from the bucket.
+    MemberKicked {
+        /// The user who was removed.
+        user_uuid: Uuid
+    },
+    /// A user's permissions within the bucket were changed.
+    PermissionsChanged {
+        /// The user whose permissions changed.
+        user_uuid: Uuid,
+        /// The permissions after the change.
+        permissions: BucketUserPermissions
+    },
+}
@@ -0,0 +1,44 @@
+use serde::{Serialize, Deserialize};
+
+/// Selects a page of a list.
+///
+/// The first page is gotten by leaving out the cursor,
+/// and each page after it by passing the `next_cursor` of the page before.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PageQuery<S> {
+    /// The `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    /// How many items to get at most. The server picks a default if this isn't specified.
+    pub limit: Option<i64>,
+    /// The order of the list. Each list has its own default.
+    pub sort: Option<S>,
+}
+
+impl<S> Default for PageQuery<S> {
+    fn default() -> Self {
+        PageQuery {
+            cursor: None,
+            limit: None,
+            sort: None,
+        }
+    }
+}
+
+/// A page of a list.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// The cursor to get the next page with.
+    /// This is `None` for the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// The order of lists that are sorted by when their items were made.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecencySort {
+    /// Most recently created first.
+    Newest,
+    /// Least recently created first.
+    Oldest,
+}
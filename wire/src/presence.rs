@@ -0,0 +1,14 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+/// When a user was last seen in a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Presence {
+    /// The bucket the user was seen in.
+    pub bucket_uuid: Uuid,
+    /// The user.
+    pub user_uuid: Uuid,
+    /// When the user's last heartbeat was received.
+    pub last_seen_at: NaiveDateTime,
+}
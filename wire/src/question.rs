@@ -23,6 +23,17 @@ pub struct Question {
     pub updated_at: NaiveDateTime,
     /// When the row was created.
     pub created_at: NaiveDateTime,
+    /// The number of users that upvoted the question.
+    pub upvotes: i32,
+    /// The number of users that downvoted the question.
+    pub downvotes: i32,
+}
+
+impl Question {
+    /// The question's net votes.
+    pub fn score(&self) -> i32 {
+        self.upvotes - self.downvotes
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -35,6 +46,71 @@ pub struct NewQuestion {
     pub question_text: String,
 }
 
+/// A record of a question being drawn from a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct QuestionDraw {
+    /// The draw's unique identifier within the application.
+    pub uuid: Uuid,
+    /// The question that was drawn.
+    pub question_uuid: Uuid,
+    /// The bucket the question was drawn from.
+    pub bucket_uuid: Uuid,
+    /// The user that drew the question.
+    pub user_uuid: Option<Uuid>,
+    /// The session the question was drawn in.
+    pub session_uuid: Option<Uuid>,
+    /// When the question was drawn.
+    pub created_at: NaiveDateTime,
+}
+
+/// The text a question had before it was edited.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct QuestionRevision {
+    /// The revision's unique identifier within the application.
+    pub uuid: Uuid,
+    /// The question that was edited.
+    pub question_uuid: Uuid,
+    /// The content of the question before the edit.
+    pub question_text: String,
+    /// The user that made the edit.
+    pub user_uuid: Option<Uuid>,
+    /// When the edit was made.
+    pub created_at: NaiveDateTime,
+}
+
+/// Which way a user voted on a question.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Vote {
+    Up,
+    Down,
+}
+
+/// A user's vote on a question.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct QuestionVote {
+    /// The user that voted.
+    pub user_uuid: Uuid,
+    /// The question that was voted on.
+    pub question_uuid: Uuid,
+    pub vote: Vote,
+    /// When the row was last updated.
+    pub updated_at: NaiveDateTime,
+    /// When the row was created.
+    pub created_at: NaiveDateTime,
+}
+
+/// The order in which lists of questions are returned.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionSort {
+    /// Least recently updated first.
+    #[default]
+    Updated,
+    /// Highest score first.
+    Score,
+}
+
 // Request types//
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -42,12 +118,63 @@ pub struct BucketUuidQueryParam {
     pub bucket_uuid: Uuid,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuestionListQueryParam {
+    pub bucket_uuid: Uuid,
+    /// Comma separated tag names. If present, only questions with at least one of these tags are listed.
+    pub include_tags: Option<String>,
+    /// Comma separated tag names. Questions with any of these tags aren't listed.
+    pub exclude_tags: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RandomQuestionQueryParam {
+    pub bucket_uuid: Uuid,
+    /// How many of the bucket's most recent draws should be avoided.
+    /// The server picks a default if this isn't specified, and caps how many can be avoided.
+    pub avoid_recent_draws: Option<i64>,
+    /// Comma separated tag names. If present, only questions with at least one of these tags are drawn.
+    pub include_tags: Option<String>,
+    /// Comma separated tag names. Questions with any of these tags aren't drawn.
+    pub exclude_tags: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NewQuestionRequest {
     /// The bucket to which the question belongs.
     pub bucket_uuid: Uuid,
     /// The content of the question.
     pub question_text: String,
+    /// The names of the tags to tag the question with.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A question already in the bucket that is worded the same as, or nearly the same as, a new question.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimilarQuestion {
+    pub question: Question,
+    /// How similar the questions are, from 0.0 to 1.0.
+    pub similarity: f32,
+    /// Do the questions only differ in case, punctuation, or spacing.
+    pub exact: bool,
+}
+
+/// The question that was created.
+///
+/// If the bucket had questions like it, they are included as a warning, the most similar first.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreatedQuestion {
+    #[serde(flatten)]
+    pub question: Question,
+    #[serde(default)]
+    pub similar_questions: Vec<SimilarQuestion>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EditQuestionRequest {
+    /// The new content of the question.
+    pub question_text: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -55,3 +182,57 @@ pub struct SetArchivedRequest {
     pub question_uuid: Uuid,
     pub archived: bool,
 }
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct VoteRequest {
+    pub vote: Vote,
+}
+
+/// Request to add many questions to a bucket at once.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "format", content = "content", rename_all = "snake_case")]
+pub enum ImportQuestionsRequest {
+    /// One question per line.
+    Text(String),
+    /// One question per row, optionally followed by a column of comma separated tag names.
+    Csv(String),
+    /// The questions, as they would be sent when making them one at a time.
+    Json(Vec<NewQuestionRequest>),
+}
+
+/// What became of a question in an import.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ImportOutcome {
+    Imported { question_uuid: Uuid },
+    /// The bucket already had the question, or one like it, so it was skipped.
+    Duplicate,
+    /// The question couldn't be read, so it was skipped.
+    Invalid { reason: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImportLineResult {
+    /// The line, row, or array entry the question came from, starting at 1.
+    pub line: usize,
+    pub outcome: ImportOutcome,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImportQuestionsResponse {
+    /// The number of questions that were added to the bucket.
+    pub imported: usize,
+    /// The number of questions that were skipped.
+    pub skipped: usize,
+    pub lines: Vec<ImportLineResult>,
+}
+
+/// A question, along with its tags and how many times it has been answered.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportedQuestion {
+    pub question: Question,
+    /// The names of the question's tags.
+    pub tags: Vec<String>,
+    /// The number of answers given to the question.
+    pub answer_count: i64,
+}
@@ -0,0 +1,49 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use crate::question::Question;
+use crate::answer::Answer;
+
+/// Marks the start of a matched word in a snippet.
+pub const HIGHLIGHT_START: &str = "<b>";
+/// Marks the end of a matched word in a snippet.
+pub const HIGHLIGHT_END: &str = "</b>";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SearchQueryParam {
+    /// The words to search for.
+    /// Phrases can be quoted, alternatives can be separated by "or", and words can be excluded with "-".
+    pub q: String,
+}
+
+/// A question that matched a search.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuestionMatch {
+    pub question: Question,
+    /// The parts of the question that matched, with the matched words between
+    /// `HIGHLIGHT_START` and `HIGHLIGHT_END`.
+    /// The rest of the snippet is HTML-escaped.
+    pub snippet: String,
+    /// How well the question matched, relative to the other matches.
+    pub rank: f32,
+}
+
+/// A publicly visible answer that matched a search.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnswerMatch {
+    pub answer: Answer,
+    /// The bucket the answered question is in.
+    pub bucket_uuid: Uuid,
+    /// The parts of the answer that matched, with the matched words between
+    /// `HIGHLIGHT_START` and `HIGHLIGHT_END`.
+    /// The rest of the snippet is HTML-escaped.
+    pub snippet: String,
+    /// How well the answer matched, relative to the other matches.
+    pub rank: f32,
+}
+
+/// Everything in the buckets the user can see that matched a search, the best matches first.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchResults {
+    pub questions: Vec<QuestionMatch>,
+    pub answers: Vec<AnswerMatch>,
+}
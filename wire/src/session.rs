@@ -0,0 +1,51 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+use crate::{answer::Answer, question::Question, user::User};
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Session {
+    /// The session's unique identifier within the application.
+    pub uuid: Uuid,
+    /// The bucket the session is played in.
+    pub bucket_uuid: Uuid,
+    /// When the session started.
+    pub started_at: NaiveDateTime,
+    /// When the session ended.
+    /// If this is None, the session is still going on.
+    pub ended_at: Option<NaiveDateTime>,
+    /// When the row was last updated.
+    pub updated_at: NaiveDateTime,
+    /// When the row was created.
+    pub created_at: NaiveDateTime,
+}
+
+/// A question that was drawn during a session, and the answer that was given to it.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    /// The question that was drawn.
+    pub question: Question,
+    /// The answer that was given.
+    /// If this is None, the question wasn't answered during the session,
+    /// or the answer isn't visible to whoever asked for the transcript.
+    pub answer: Option<Answer>,
+}
+
+/// A record of everything that happened during a session.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct SessionTranscript {
+    /// The session
+    pub session: Session,
+    /// The users that took part in the session.
+    pub participants: Vec<User>,
+    /// The questions and answers, in the order they were drawn or answered.
+    pub entries: Vec<TranscriptEntry>,
+}
+
+// Request types
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StartSessionRequest {
+    /// The bucket to start the session in.
+    pub bucket_uuid: Uuid,
+}
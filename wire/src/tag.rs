@@ -0,0 +1,39 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+/// A tag used to categorize questions within a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Tag {
+    /// The tag's unique identifier within the application.
+    pub uuid: Uuid,
+    /// The bucket the tag belongs to.
+    pub bucket_uuid: Uuid,
+    /// The name of the tag. This is unique within a bucket.
+    pub tag_name: String,
+    /// When the row was last updated.
+    pub updated_at: NaiveDateTime,
+    /// When the row was created.
+    pub created_at: NaiveDateTime,
+}
+
+// Request types//
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TagQuestionRequest {
+    /// The name of the tag. The tag will be created if the bucket doesn't have it yet.
+    pub tag_name: String,
+}
+
+/// Splits a comma separated list of tag names, as used in query parameters.
+pub fn split_tag_names(tags: &str) -> Vec<String> {
+    tags.split(',')
+        .map(normalize_tag_name)
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Tags are case insensitive and ignore surrounding whitespace.
+pub fn normalize_tag_name(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
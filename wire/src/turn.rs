@@ -0,0 +1,20 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+/// Whose turn it is within a bucket.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Turn {
+    /// The bucket the turn belongs to.
+    pub bucket_uuid: Uuid,
+    /// The user that draws and asks the question.
+    pub asker_uuid: Uuid,
+    /// The user that has been chosen to answer the question.
+    pub answerer_uuid: Uuid,
+    /// How many times the rotation has advanced for this bucket.
+    pub turn_number: i32,
+    /// When the row was last updated.
+    pub updated_at: NaiveDateTime,
+    /// When the row was created.
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,113 @@
+use yew::{Component, ComponentLink, Html, html, Properties, ShouldRender};
+use yewtil::NeqAssign;
+use yewtil::fetch::{FetchState, fetch_to_state_msg};
+use uuid::Uuid;
+use wire::answer::Answer;
+use wire::page::{Page, PageQuery};
+use crate::requests::answer::GetAnswersForQuestion;
+
+/// How many answers are shown for a question.
+const ANSWERS_SHOWN: i64 = 25;
+
+/// Renders the answers as a list.
+pub fn render_answers<COMP: Component>(answers: &[Answer]) -> Html<COMP> {
+    if answers.is_empty() {
+        return html! {
+            <p class="has-text-grey">{"No answers yet."}</p>
+        }
+    }
+    answers
+        .iter()
+        .map(|answer| {
+            let visibility = if answer.publicly_visible {""} else {" (private)"};
+            html! {
+                <div class="box">
+                    <p>{&answer.answer_text}</p>
+                    <p class="is-size-7 has-text-grey">
+                        {answer.created_at.format("%Y-%m-%d %H:%M").to_string()}
+                        {visibility}
+                    </p>
+                </div>
+            }
+        })
+        .collect()
+}
+
+/// Shows the answers to a question that the user can see.
+pub struct QuestionAnswers {
+    props: Props,
+    answers: FetchState<Page<Answer>>,
+    link: ComponentLink<Self>
+}
+
+#[derive(Properties, PartialEq, Debug)]
+pub struct Props {
+    #[props(required)]
+    pub question_uuid: Uuid,
+    /// Changing this causes the answers to be fetched again.
+    pub answers_posted: usize
+}
+
+pub enum Msg {
+    FetchedAnswers(FetchState<Page<Answer>>)
+}
+
+impl QuestionAnswers {
+    fn fetch_answers(&mut self) {
+        self.answers.set_fetching();
+        let request = GetAnswersForQuestion {
+            question_uuid: self.props.question_uuid,
+            page: PageQuery {
+                limit: Some(ANSWERS_SHOWN),
+                ..Default::default()
+            }
+        };
+        self.link.send_future(fetch_to_state_msg(request, Msg::FetchedAnswers));
+    }
+}
+
+impl Component for QuestionAnswers {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        QuestionAnswers {
+            props,
+            answers: Default::default(),
+            link
+        }
+    }
+
+    fn mounted(&mut self) -> ShouldRender {
+        self.fetch_answers();
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::FetchedAnswers(answers) => self.answers.neq_assign(answers)
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props.neq_assign(props) {
+            self.fetch_answers();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn view(&self) -> Html<Self> {
+        match &self.answers {
+            FetchState::Success(answers) => render_answers(&answers.items),
+            FetchState::Fetching => html! {
+                <progress class="progress is-small is-dark is-radiusless" max="100"></progress>
+            },
+            FetchState::NotFetching => html! {},
+            FetchState::Failed(_) => html! {
+                {"Couldn't get the answers :("}
+            }
+        }
+    }
+}
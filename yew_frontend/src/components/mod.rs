@@ -1,5 +1,6 @@
 pub mod navbar;
 pub mod button;
+pub mod answer_list;
 
 //pub mod full_height;
 //pub mod centered;
@@ -7,6 +7,7 @@ use crate::{Model, Msg};
 use crate::components::login::login_or_user_panel::LoginUserPanel;
 use yew_router::unit_state::{RouterLink, Route};
 use crate::AppRoute;
+use yewtil::fetch::FetchState;
 
 thread_local! {
     static CSS: Css = css_file!("../../assets/navbar.css"); // TODO, not sure where the assets folder should go.
@@ -65,11 +66,30 @@ impl Model {
 //                classes = "navbar-item"
 //            />
         };
+        let my_answers = if let FetchState::Success(_) = &self.user {
+            html! {
+                <>
+                    <RouterLink
+                        link = Route::from(AppRoute::MyAnswers).route
+                        text = "My Answers"
+                        classes = "navbar-item"
+                    />
+                    <RouterLink
+                        link = Route::from(AppRoute::Join).route
+                        text = "Join Bucket"
+                        classes = "navbar-item"
+                    />
+                </>
+            }
+        } else {
+            html! {}
+        };
         let inner_content = html! {
         <>
             <div class="navbar-start">
             // TODO, maybe put the name of the bucket here? breadcrumbs?
                 {create_bucket}
+                {my_answers}
             </div>
 
             <div class="navbar-end">
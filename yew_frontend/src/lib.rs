@@ -19,6 +19,8 @@ use crate::requests::auth_and_user::GetUser;
 use yewtil::NeqAssign;
 use crate::pages::bucket::BucketPage;
 use crate::pages::create_bucket::CreateBucketPage;
+use crate::pages::my_answers::MyAnswersPage;
+use crate::pages::join::JoinPage;
 
 /// Non breaking space
 pub const NBS: char = '\u{00A0}';
@@ -38,7 +40,13 @@ pub enum AppRoute {
     #[to = "/bucket/{slug}"]
     Bucket{slug: String},
     #[to = "/create_bucket"]
-    CreateBucket
+    CreateBucket,
+    #[to = "/my_answers"]
+    MyAnswers,
+    #[to = "/invite/{invite}"]
+    Invite{invite: String},
+    #[to = "/join"]
+    Join
 }
 
 
@@ -103,7 +111,10 @@ impl Component for Model {
                         AppRoute::Index => html!{<IndexPage/>},
                         AppRoute::Bucket{slug} => html!{<BucketPage slug = slug is_settings_open = false/>},
                         AppRoute::BucketSettings{slug} => html!{<BucketPage slug = slug is_settings_open = true/>},
-                        AppRoute::CreateBucket => html!{<CreateBucketPage />}
+                        AppRoute::CreateBucket => html!{<CreateBucketPage />},
+                        AppRoute::MyAnswers => html!{<MyAnswersPage />},
+                        AppRoute::Invite{invite} => html!{<JoinPage invite = Some(invite) />},
+                        AppRoute::Join => html!{<JoinPage invite = None />}
                     }
                 })
                 redirect = Router::redirect(|_| {
@@ -13,6 +13,9 @@ use crate::pages::bucket::new_question::{NewQuestionAction, NewQuestionState};
 use crate::pages::bucket::num_questions::{NumQuestionAction, NumQuestionsState};
 use crate::pages::bucket::permissions::{PermissionsAction, PermissionsState, SettingsJoin};
 use crate::pages::bucket::active_question::{ActiveQuestionState, ActiveQuestionAction};
+use crate::pages::bucket::live::{LiveAction, LiveState};
+use crate::pages::bucket::heartbeat::{HeartbeatAction, HeartbeatState};
+use wire::live::BucketEvent;
 
 mod new_question;
 mod active_question;
@@ -20,6 +23,8 @@ mod permissions;
 mod num_questions;
 mod join_logic;
 mod answer;
+mod live;
+mod heartbeat;
 
 /// Shorthand alias for the link argument.
 type BucketLink = ComponentLink<BucketPage>;
@@ -34,7 +39,9 @@ pub struct BucketPage {
     new_question: NewQuestionState,
     num_questions: NumQuestionsState,
     permissions: PermissionsState,
-    active_question: ActiveQuestionState
+    active_question: ActiveQuestionState,
+    live: LiveState,
+    heartbeat: HeartbeatState
 }
 
 #[derive(Properties, PartialEq, Debug)]
@@ -53,7 +60,9 @@ pub enum Msg {
     NewQuestion(NewQuestionAction),
     NumQuestions(NumQuestionAction),
     Permissions(PermissionsAction),
-    ActiveQuestion(ActiveQuestionAction)
+    ActiveQuestion(ActiveQuestionAction),
+    Live(LiveAction),
+    Heartbeat(HeartbeatAction)
 }
 
 impl Component for BucketPage {
@@ -69,7 +78,9 @@ impl Component for BucketPage {
             permissions: Default::default(),
             answer: Default::default(),
             new_question: Default::default(),
-            num_questions: Default::default()
+            num_questions: Default::default(),
+            live: Default::default(),
+            heartbeat: Default::default()
         }
     }
 
@@ -91,7 +102,10 @@ impl Component for BucketPage {
             Msg::NewQuestion(action) => self.new_question.update(action, &mut self.link, &self.bucket),
             Msg::NumQuestions(action) => self.num_questions.update(action, &mut self.link, get_bucket_uuid()),
             Msg::Permissions(action) => self.permissions.update(action, &mut self.link, get_bucket_uuid()),
-            Msg::ActiveQuestion(action) => self.active_question.update(action, &mut self.link, get_bucket_uuid())
+            Msg::ActiveQuestion(action) => self.active_question.update(action, &mut self.link, get_bucket_uuid()),
+            Msg::Live(LiveAction::Received(Ok(event))) => self.handle_live_event(event),
+            Msg::Live(action) => self.live.update(action, &mut self.link),
+            Msg::Heartbeat(action) => self.heartbeat.update(action, &mut self.link)
         }
     }
 
@@ -107,6 +121,12 @@ impl Component for BucketPage {
 
 impl BucketPage {
     pub fn handle_fetched_bucket(&mut self, state: FetchState<Bucket>) -> ShouldRender {
+        if let FetchState::Success(bucket) = &state {
+            // The bucket may have been found by a slug it had before.
+            self.follow_slug(&bucket.bucket_slug);
+            self.link.send_self(Msg::Live(LiveAction::Connect(bucket.uuid)));
+            self.link.send_self(Msg::Heartbeat(HeartbeatAction::Start(bucket.uuid)));
+        }
         let rerender = self.bucket.neq_assign(state);
 
         // get permissions and number of questions.
@@ -118,6 +138,52 @@ impl BucketPage {
         rerender
     }
 
+    /// Applies a change made by someone else in the bucket to the page.
+    pub fn handle_live_event(&mut self, event: BucketEvent) -> ShouldRender {
+        match event {
+            BucketEvent::QuestionAdded { .. } => {
+                self.link.send_self(Msg::NumQuestions(NumQuestionAction::Get));
+                false
+            }
+            BucketEvent::QuestionDrawn { question } => self.active_question.show_drawn_question(question),
+            BucketEvent::AnswerPosted { .. } => {
+                // Answering may archive the question, and the answer lists need to include the new answer.
+                self.link.send_self(Msg::NumQuestions(NumQuestionAction::Get));
+                self.live.answers_posted += 1;
+                true
+            }
+            BucketEvent::FlagsChanged { bucket } => self.bucket.neq_assign(FetchState::Success(bucket)),
+            BucketEvent::BucketRenamed { bucket } => {
+                self.follow_slug(&bucket.bucket_slug);
+                self.bucket.neq_assign(FetchState::Success(bucket))
+            }
+            BucketEvent::BucketDeleted => {
+                RouteAgentDispatcher::new().send(RouteRequest::ChangeRoute(Route::from(AppRoute::Index)));
+                false
+            }
+            // Any of these could have changed what the user is allowed to do.
+            BucketEvent::MemberJoined { .. }
+            | BucketEvent::MemberKicked { .. }
+            | BucketEvent::PermissionsChanged { .. } => {
+                self.link.send_self(Msg::Permissions(PermissionsAction::Get));
+                false
+            }
+        }
+    }
+
+    /// Puts the bucket's current slug in the url, so it can be shared.
+    fn follow_slug(&self, slug: &str) {
+        if slug != self.props.slug {
+            let slug = slug.to_string();
+            let route = if self.props.is_settings_open {
+                AppRoute::BucketSettings{ slug }
+            } else {
+                AppRoute::Bucket{ slug }
+            };
+            RouteAgentDispatcher::new().send(RouteRequest::ReplaceRoute(Route::from(route)));
+        }
+    }
+
     pub fn show_settings_modal(&mut self) -> ShouldRender {
         let route = AppRoute::BucketSettings{ slug: self.props.slug.clone() };
         RouteAgentDispatcher::new().send(RouteRequest::ChangeRoute( Route::from(route)));
@@ -136,7 +202,7 @@ impl BucketPage {
                         <div class = "columns is-centered no_margin">
                             <div class="column is-two-thirds-tablet is-half-desktop is-centered">
                                 {self.render_title()}
-                                {self.active_question.render_q_and_a_card(&self.answer)} // TODO, consider moving the new answer inside of the active_question struct.
+                                {self.active_question.render_q_and_a_card(&self.answer, self.live.answers_posted)} // TODO, consider moving the new answer inside of the active_question struct.
                                 {self.new_question.render_new_question_card()}
                             </div>
                         </div>
@@ -1,23 +1,35 @@
 use crate::pages::bucket::answer::{AnswerState, AnswerAction};
 use yew::{Html, html, ShouldRender};
-use wire::question::Question;
+use wire::question::{Question, Vote, VoteRequest};
 use yewtil::fetch::{FetchState, fetch_to_state_msg};
 use crate::pages::bucket::{BucketLink, BucketPage, Msg};
 use uuid::Uuid;
 use yewtil::NeqAssign;
 use crate::pages::bucket::num_questions::NumQuestionAction;
-use crate::requests::question::{GetRandomQuestion, DeleteQuestion};
+use crate::requests::question::{GetRandomQuestion, DeleteQuestion, VoteOnQuestion};
+use crate::components::answer_list::QuestionAnswers;
 
 pub enum ActiveQuestionAction {
     Discard,
     Discarded(FetchState<Question>),
     GetRandom,
     GotRandom(FetchState<Option<Question>>),
-    PutBackInBucket
+    PutBackInBucket,
+    Vote(Vote),
+    Voted(FetchState<Question>),
+    UpdateIncludeTags(String),
+    UpdateExcludeTags(String)
 }
 
 #[derive(Debug, Default)]
-pub struct ActiveQuestionState(pub FetchState<Option<Question>>);
+pub struct ActiveQuestionState(pub FetchState<Option<Question>>, pub DrawFilter);
+
+/// Comma separated tag names used to restrict which questions can be drawn.
+#[derive(Debug, Default)]
+pub struct DrawFilter {
+    pub include_tags: String,
+    pub exclude_tags: String
+}
 
 impl ActiveQuestionState {
 
@@ -28,10 +40,14 @@ impl ActiveQuestionState {
             ActiveQuestionAction::GetRandom => self.get_a_random_question(link, bucket_uuid),
             ActiveQuestionAction::GotRandom(question) => self.handle_fetched_active_question(link, question),
             ActiveQuestionAction::PutBackInBucket => self.put_question_in_bucket(),
+            ActiveQuestionAction::Vote(vote) => self.vote_on_question(link, vote),
+            ActiveQuestionAction::Voted(question) => self.handle_voted_question(question),
+            ActiveQuestionAction::UpdateIncludeTags(tags) => self.1.include_tags.neq_assign(tags),
+            ActiveQuestionAction::UpdateExcludeTags(tags) => self.1.exclude_tags.neq_assign(tags),
         }
     }
 
-    pub fn render_q_and_a_card(&self, new_answer: &AnswerState) -> Html<BucketPage> {
+    pub fn render_q_and_a_card(&self, new_answer: &AnswerState, answers_posted: usize) -> Html<BucketPage> {
         let content = match &self.0 {
             FetchState::Fetching => html! {
                 <div class="card-footer">
@@ -44,14 +60,17 @@ impl ActiveQuestionState {
                 </div>
             },
             FetchState::NotFetching => html! {
-                <div class="card-footer">
-                    <button
-                        class = "button is-success card-footer-item is-radiusless"
-                        onclick=|_| Msg::ActiveQuestion(ActiveQuestionAction::GetRandom)
-                    >
-                        {"Get A Random Question"}
-                    </button>
-                </div>
+                <>
+                    {self.render_draw_filter()}
+                    <div class="card-footer">
+                        <button
+                            class = "button is-success card-footer-item is-radiusless"
+                            onclick=|_| Msg::ActiveQuestion(ActiveQuestionAction::GetRandom)
+                        >
+                            {"Get A Random Question"}
+                        </button>
+                    </div>
+                </>
             },
             FetchState::Success(Some(question)) => html! {
                 <>
@@ -59,6 +78,15 @@ impl ActiveQuestionState {
                         <div class="is-size-4">
                             <p>{&question.question_text}</p>
                         </div>
+                        <div class="level">
+                            <button class="button is-small" onclick = |_| Msg::ActiveQuestion(ActiveQuestionAction::Vote(Vote::Up))>
+                                {"▲"}
+                            </button>
+                            <span class="is-size-6">{question.score()}</span>
+                            <button class="button is-small" onclick = |_| Msg::ActiveQuestion(ActiveQuestionAction::Vote(Vote::Down))>
+                                {"▼"}
+                            </button>
+                        </div>
                         <br />
 
 
@@ -78,6 +106,9 @@ impl ActiveQuestionState {
                             oninput=|e| Msg::Answer(AnswerAction::UpdateNewAnswer(e.value))
                             placeholder="Answer"
                         />
+                        <br />
+                        <p class="is-size-6 has-text-weight-semibold">{"Previous Answers"}</p>
+                        <QuestionAnswers question_uuid = question.uuid answers_posted = answers_posted />
                     </div>
 
                     <div class="card-footer">
@@ -98,6 +129,7 @@ impl ActiveQuestionState {
                     <div class="card-content">
                         {"No questions in this bucket. Try adding some!"}
                     </div>
+                    {self.render_draw_filter()}
                     <div class="card-footer">
                         <button
                             class= "button is-success card-footer-item is-radiusless"
@@ -132,6 +164,31 @@ impl ActiveQuestionState {
         }
     }
 
+    fn render_draw_filter(&self) -> Html<BucketPage> {
+        html! {
+            <div class="card-content">
+                <div class="field">
+                    <input
+                        class="input is-small"
+                        type="text"
+                        value=&self.1.include_tags
+                        oninput=|e| Msg::ActiveQuestion(ActiveQuestionAction::UpdateIncludeTags(e.value))
+                        placeholder="Only draw questions tagged with (comma separated)"
+                    />
+                </div>
+                <div class="field">
+                    <input
+                        class="input is-small"
+                        type="text"
+                        value=&self.1.exclude_tags
+                        oninput=|e| Msg::ActiveQuestion(ActiveQuestionAction::UpdateExcludeTags(e.value))
+                        placeholder="Never draw questions tagged with (comma separated)"
+                    />
+                </div>
+            </div>
+        }
+    }
+
     fn discard_question(&mut self, link: &mut BucketLink) -> ShouldRender {
         // The question won't be able to be drawn from the bucket again.
         let mut should_clear_active_question = false;
@@ -155,11 +212,44 @@ impl ActiveQuestionState {
         retval
     }
 
+    /// Shows a question that someone else drew, unless there is already a question being answered.
+    pub fn show_drawn_question(&mut self, question: Question) -> ShouldRender {
+        match self.0 {
+            FetchState::NotFetching | FetchState::Success(None) => {
+                self.0 = FetchState::Success(Some(question));
+                true
+            }
+            _ => false
+        }
+    }
+
     fn put_question_in_bucket(&mut self) -> ShouldRender {
         self.0 = FetchState::NotFetching;
         true
     }
 
+    fn vote_on_question(&mut self, link: &mut BucketLink, vote: Vote) -> ShouldRender {
+        if let FetchState::Success(Some(question)) = &self.0 {
+            let request = VoteOnQuestion {
+                question_uuid: question.uuid,
+                request: VoteRequest { vote }
+            };
+            link.send_future(fetch_to_state_msg(request, |resp| Msg::ActiveQuestion(ActiveQuestionAction::Voted(resp))));
+        }
+        false
+    }
+
+    /// Updates the active question's vote totals, as long as it is still the active question.
+    fn handle_voted_question(&mut self, state: FetchState<Question>) -> ShouldRender {
+        match (&mut self.0, state) {
+            (FetchState::Success(Some(active)), FetchState::Success(voted)) if active.uuid == voted.uuid => {
+                *active = voted;
+                true
+            }
+            _ => false
+        }
+    }
+
     fn handle_fetched_active_question(&mut self, link: &mut BucketLink, state: FetchState<Option<Question>>) -> ShouldRender {
         let rerender = self.0.neq_assign(state);
         link.send_self(Msg::NumQuestions(NumQuestionAction::Get));
@@ -169,7 +259,11 @@ impl ActiveQuestionState {
     fn get_a_random_question(&mut self, link: &mut BucketLink, bucket_uuid: Option<Uuid>) -> ShouldRender {
         if let Some(bucket_uuid) = bucket_uuid {
             self.0.set_fetching();
-            let request = GetRandomQuestion{bucket_uuid};
+            let request = GetRandomQuestion{
+                bucket_uuid,
+                include_tags: self.1.include_tags.clone(),
+                exclude_tags: self.1.exclude_tags.clone()
+            };
             link.send_future(fetch_to_state_msg(request, |resp| Msg::ActiveQuestion(ActiveQuestionAction::GotRandom(resp))));
             true
         } else {
@@ -0,0 +1,52 @@
+use crate::pages::bucket::{BucketLink, Msg};
+use crate::requests::bucket::SendHeartbeat;
+use std::time::Duration;
+use uuid::Uuid;
+use yew::services::interval::{IntervalService, IntervalTask};
+use yew::ShouldRender;
+use yewtil::fetch::fetch_to_state_msg;
+
+/// How often the server is told that the user is still in the bucket.
+/// This needs to be comfortably shorter than the time the server waits before considering a user to be gone.
+const HEARTBEAT_INTERVAL_SECONDS: u64 = 20;
+
+pub enum HeartbeatAction {
+    Start(Uuid),
+    Beat(Uuid),
+    Sent
+}
+
+/// Periodically lets the server know that the user is playing in the bucket.
+#[derive(Default)]
+pub struct HeartbeatState {
+    task: Option<IntervalTask>
+}
+
+impl HeartbeatState {
+    pub fn update(&mut self, action: HeartbeatAction, link: &mut BucketLink) -> ShouldRender {
+        match action {
+            HeartbeatAction::Start(bucket_uuid) => self.start(link, bucket_uuid),
+            HeartbeatAction::Beat(bucket_uuid) => Self::beat(link, bucket_uuid),
+            HeartbeatAction::Sent => false
+        }
+    }
+
+    fn start(&mut self, link: &mut BucketLink, bucket_uuid: Uuid) -> ShouldRender {
+        // Only logged in members can be present, so there is nothing to report otherwise.
+        if !crate::auth::is_logged_in() {
+            return false;
+        }
+        link.send_self(Msg::Heartbeat(HeartbeatAction::Beat(bucket_uuid)));
+        let callback = link.send_back(move |_| Msg::Heartbeat(HeartbeatAction::Beat(bucket_uuid)));
+        let interval = Duration::from_secs(HEARTBEAT_INTERVAL_SECONDS);
+        self.task = Some(IntervalService::new().spawn(interval, callback));
+        false
+    }
+
+    fn beat(link: &mut BucketLink, bucket_uuid: Uuid) -> ShouldRender {
+        // Users that haven't joined the bucket will be turned away, which is fine.
+        let request = SendHeartbeat { bucket_uuid };
+        link.send_future(fetch_to_state_msg(request, |_resp| Msg::Heartbeat(HeartbeatAction::Sent)));
+        false
+    }
+}
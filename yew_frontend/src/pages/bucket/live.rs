@@ -0,0 +1,57 @@
+use crate::pages::bucket::{BucketLink, Msg};
+use crate::requests::bucket::bucket_live_url;
+use uuid::Uuid;
+use wire::live::BucketEvent;
+use yew::format::Json;
+use yew::services::websocket::{WebSocketService, WebSocketStatus, WebSocketTask};
+use yew::ShouldRender;
+
+pub enum LiveAction {
+    Connect(Uuid),
+    Received(Result<BucketEvent, failure::Error>),
+    StatusChanged(WebSocketStatus)
+}
+
+/// Keeps the page subscribed to the events of the bucket.
+#[derive(Default)]
+pub struct LiveState {
+    task: Option<WebSocketTask>,
+    /// Incremented whenever an answer is posted, which tells the answer lists to refresh.
+    pub answers_posted: usize
+}
+
+impl LiveState {
+    pub fn update(&mut self, action: LiveAction, link: &mut BucketLink) -> ShouldRender {
+        match action {
+            LiveAction::Connect(bucket_uuid) => self.connect(link, bucket_uuid),
+            LiveAction::Received(Err(error)) => {
+                log::warn!("Could not read live event: {}", error);
+                false
+            }
+            // Events change other parts of the page, so they are applied by the page itself.
+            LiveAction::Received(Ok(_)) => false,
+            LiveAction::StatusChanged(status) => self.status_changed(status)
+        }
+    }
+
+    fn connect(&mut self, link: &mut BucketLink, bucket_uuid: Uuid) -> ShouldRender {
+        let callback = link.send_back(|Json(event)| Msg::Live(LiveAction::Received(event)));
+        let notification = link.send_back(|status| Msg::Live(LiveAction::StatusChanged(status)));
+        match WebSocketService::new().connect(&bucket_live_url(bucket_uuid), callback, notification) {
+            Ok(task) => self.task = Some(task),
+            Err(error) => log::warn!("Could not connect to live events: {}", error)
+        }
+        false
+    }
+
+    fn status_changed(&mut self, status: WebSocketStatus) -> ShouldRender {
+        match status {
+            WebSocketStatus::Opened => log::info!("Listening to live events"),
+            WebSocketStatus::Closed | WebSocketStatus::Error => {
+                log::warn!("Stopped listening to live events");
+                self.task = None;
+            }
+        }
+        false
+    }
+}
@@ -4,19 +4,25 @@ use yewtil::fetch::{FetchState, fetch_to_state_msg};
 use crate::pages::bucket::{BucketPage, Msg, BucketLink};
 use crate::requests::question::CreateQuestion;
 use wire::bucket::Bucket;
-use wire::question::NewQuestionRequest;
+use wire::question::{CreatedQuestion, NewQuestionRequest, SimilarQuestion};
+use wire::tag::split_tag_names;
 use yewtil::NeqAssign;
 
 pub enum NewQuestionAction {
     UpdateText(String),
+    UpdateTags(String),
     Submit,
-    HandleSubmission(FetchState<()>)
+    HandleSubmission(FetchState<CreatedQuestion>)
 }
 
 #[derive(Default, Debug)]
 pub struct NewQuestionState {
     pub upload_state: FetchState<()>,
-    pub new_question_text: String
+    pub new_question_text: String,
+    /// Comma separated tag names.
+    pub new_question_tags: String,
+    /// Questions already in the bucket that are like the last question that was submitted.
+    pub similar_questions: Vec<SimilarQuestion>
 }
 
 impl NewQuestionState {
@@ -62,6 +68,14 @@ impl NewQuestionState {
                 </header>
                 <div class="card-content">
                     {textarea}
+                    <input
+                        class="input"
+                        type="text"
+                        value=&self.new_question_tags
+                        oninput=|e| Msg::NewQuestion(NewQuestionAction::UpdateTags(e.value))
+                        placeholder="Tags (comma separated)"
+                    />
+                    {self.render_similar_questions()}
                 </div>
                 <div class="card-footer">
                     <button
@@ -75,11 +89,32 @@ impl NewQuestionState {
             </div>
         }
     }
+    fn render_similar_questions(&self) -> Html<BucketPage> {
+        if self.similar_questions.is_empty() {
+            return html! {}
+        }
+        let similar_questions: Html<BucketPage> = self.similar_questions
+            .iter()
+            .map(|similar| html! {
+                <li>{&similar.question.question_text}</li>
+            })
+            .collect();
+        html! {
+            <div class="notification is-warning">
+                {"The bucket already has questions like the one you just added:"}
+                <ul>
+                    {similar_questions}
+                </ul>
+            </div>
+        }
+    }
+
     pub fn update(&mut self, action: NewQuestionAction, link: &mut BucketLink, bucket: &FetchState<Bucket>) -> ShouldRender {
         match action {
             NewQuestionAction::Submit => self.submit_new_question(link, bucket),
             NewQuestionAction::HandleSubmission(response) => self.fetched_new_question_create(link, response),
             NewQuestionAction::UpdateText(question_text) => self.update_new_question_text(question_text),
+            NewQuestionAction::UpdateTags(tags) => self.new_question_tags.neq_assign(tags),
         }
     }
 
@@ -91,10 +126,11 @@ impl NewQuestionState {
             let request = CreateQuestion{
                 new_question: NewQuestionRequest {
                     bucket_uuid: bucket.as_ref().unwrap().uuid,
-                    question_text: self.new_question_text.clone()
+                    question_text: self.new_question_text.clone(),
+                    tags: split_tag_names(&self.new_question_tags)
                 }
             };
-            link.send_future(fetch_to_state_msg(request, |resp| Msg::NewQuestion(NewQuestionAction::HandleSubmission(resp.map(|_|())))));
+            link.send_future(fetch_to_state_msg(request, |resp| Msg::NewQuestion(NewQuestionAction::HandleSubmission(resp))));
             true
         } else {
             log::warn!("Tried to add a new question when the question was empty, or the bucket uuid was unknown.");
@@ -102,11 +138,13 @@ impl NewQuestionState {
         }
     }
 
-    fn fetched_new_question_create(&mut self, link: &mut BucketLink, new_question: FetchState<()>) -> ShouldRender {
+    fn fetched_new_question_create(&mut self, link: &mut BucketLink, new_question: FetchState<CreatedQuestion>) -> ShouldRender {
         // Since a new question has been created, it is a good idea to also update the number of questions.
         link.send_self(Msg::NumQuestions(NumQuestionAction::Get));
-        if let FetchState::Success(question) = new_question {
+        if let FetchState::Success(created) = new_question {
             self.new_question_text = "".to_string();
+            self.new_question_tags = "".to_string();
+            self.similar_questions = created.similar_questions;
         } else {
             // Notify the toast agent.
         }
@@ -1,16 +1,18 @@
 use yew::{Component, ComponentLink, html, ShouldRender, Html};
 use yew::virtual_dom::VNode;
 use yewtil::fetch::{FetchState, fetch_to_state_msg};
-use wire::bucket::Bucket;
-use crate::requests::bucket::{GetPublicBuckets, GetParticipatingBuckets};
+use wire::bucket::{Bucket, CloneBucketRequest};
+use wire::page::Page;
+use crate::requests::bucket::{GetPublicBuckets, GetParticipatingBuckets, GetPublicTemplates, CloneBucket};
 use yewtil::NeqAssign;
 use crate::AppRoute;
 use yew_router::agent::RouteRequest;
 use crate::auth::is_logged_in;
 
 pub struct IndexPage {
-    public_buckets: FetchState<Vec<Bucket>>,
-    users_buckets: FetchState<Vec<Bucket>>,
+    public_buckets: FetchState<Page<Bucket>>,
+    users_buckets: FetchState<Page<Bucket>>,
+    templates: FetchState<Page<Bucket>>,
     /// For holding failure values for the create bucket request
 //    create_bucket: FetchState<()>,
     link: ComponentLink<Self>
@@ -18,8 +20,11 @@ pub struct IndexPage {
 
 #[derive(Debug, Clone)]
 pub enum Msg {
-    FetchedPublicBuckets(FetchState<Vec<Bucket>>),
-    FetchedUserBuckets(FetchState<Vec<Bucket>>),
+    FetchedPublicBuckets(FetchState<Page<Bucket>>),
+    FetchedUserBuckets(FetchState<Page<Bucket>>),
+    FetchedTemplates(FetchState<Page<Bucket>>),
+    UseTemplate(Bucket),
+    FetchedBucketFromTemplate(FetchState<Bucket>),
 //    RequestCreateBucket(CreateBucket),
 //    FetchedCreatedBucket(FetchState<Bucket>),
     GoTo(AppRoute)
@@ -33,6 +38,7 @@ impl Component for IndexPage {
         IndexPage {
             public_buckets: Default::default(),
             users_buckets: Default::default(),
+            templates: Default::default(),
 //            create_bucket: Default::default(),
             link,
         }
@@ -48,6 +54,10 @@ impl Component for IndexPage {
         let fetch = fetch_to_state_msg(GetParticipatingBuckets, Msg::FetchedUserBuckets);
         self.link.send_future(fetch);
 
+        self.templates.set_fetching();
+        let fetch = fetch_to_state_msg(GetPublicTemplates, Msg::FetchedTemplates);
+        self.link.send_future(fetch);
+
         false
     }
 
@@ -55,6 +65,26 @@ impl Component for IndexPage {
         match msg {
             Msg::FetchedPublicBuckets(state) => self.public_buckets.neq_assign(state),
             Msg::FetchedUserBuckets(state) => self.users_buckets.neq_assign(state),
+            Msg::FetchedTemplates(state) => self.templates.neq_assign(state),
+            Msg::UseTemplate(template) => {
+                let request = CloneBucket {
+                    bucket_uuid: template.uuid,
+                    request: CloneBucketRequest {
+                        bucket_name: template.bucket_name,
+                        copy_tags: true,
+                        copy_flags: true,
+                        copy_member_roles: false
+                    }
+                };
+                self.link.send_future(fetch_to_state_msg(request, Msg::FetchedBucketFromTemplate));
+                false
+            }
+            Msg::FetchedBucketFromTemplate(FetchState::Success(bucket)) => {
+                let route = AppRoute::Bucket{slug: bucket.bucket_slug};
+                yew_router::unit_state::RouteAgentDispatcher::new().send(RouteRequest::ChangeRoute(route.into()));
+                false
+            }
+            Msg::FetchedBucketFromTemplate(_) => false,
 //            Msg::RequestCreateBucket(create_bucket) => {
 //                self.create_bucket.set_fetching();
 //                let fetch = fetch_to_state_msg(create_bucket, Msg::FetchedCreatedBucket);
@@ -87,7 +117,7 @@ impl Component for IndexPage {
     fn view(&self) -> VNode<Self> {
         let public_buckets = match &self.public_buckets {
             FetchState::Success(buckets) => {
-                buckets.iter().map(Self::bucket_card).collect::<Html<Self>>()
+                buckets.items.iter().map(Self::bucket_card).collect::<Html<Self>>()
             }
             FetchState::NotFetching => {
                 html!{}
@@ -102,7 +132,7 @@ impl Component for IndexPage {
 
         let users_buckets = match &self.users_buckets {
             FetchState::Success(buckets) => {
-                buckets.iter().map(Self::bucket_card).collect::<Html<Self>>()
+                buckets.items.iter().map(Self::bucket_card).collect::<Html<Self>>()
             }
             FetchState::NotFetching => {
                 html!{}
@@ -115,6 +145,29 @@ impl Component for IndexPage {
             }
         };
 
+        // Only logged in users can make buckets from templates.
+        let templates = match &self.templates {
+            FetchState::Success(templates) if is_logged_in() && !templates.items.is_empty() => {
+                let templates = templates.items.iter().map(Self::template_card).collect::<Html<Self>>();
+                html! {
+                    <div class = "column full_height2">
+                        <div class = "card full_height2 vert_flex">
+                            <div class="card-header">
+                                <p class="card-header-title">{"Templates"}</p>
+                            </div>
+
+                            <div class="card-content full_height2 is-paddingless">
+                                <div class="panel full_height_scrollable2">
+                                    {templates}
+                                </div>
+                            </div>
+                        </div>
+                    </div>
+                }
+            }
+            _ => html!{}
+        };
+
         html! {
             <div class= "full_height has-background-primary">
                 <div class = "container full_height2" style="width: 100%; padding-top: 10px;">
@@ -146,6 +199,8 @@ impl Component for IndexPage {
                                 </div>
                             </div>
                         </div>
+
+                        {templates}
                     </div>
                 </div>
                 {Self::create_bucket_button()}
@@ -169,6 +224,19 @@ impl IndexPage {
         }
     }
 
+    /// Clicking on a template makes a new bucket from it.
+    fn template_card(template: &Bucket) -> Html<Self> {
+        let template = template.clone();
+        html! {
+            <a
+                class = "panel-block is-white"
+                onclick = |_| Msg::UseTemplate(template.clone())
+            >
+                <label class="is-size-5">{&template.bucket_name} </label>
+            </a>
+        }
+    }
+
     fn create_bucket_button() -> Html<Self> {
         if is_logged_in() {
             // TODO add a tooltip.
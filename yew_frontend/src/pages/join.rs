@@ -0,0 +1,139 @@
+use yew::{Component, ComponentLink, html, Properties, ShouldRender};
+use yew::virtual_dom::VNode;
+use yewtil::NeqAssign;
+use yewtil::fetch::{FetchState, fetch_to_state_msg};
+use wire::bucket::Bucket;
+use crate::requests::bucket::{JoinWithCode, RedeemInvite};
+use yew_router::unit_state::RouteAgentDispatcher;
+use yew_router::agent::RouteRequest;
+use crate::AppRoute;
+
+/// Joins a bucket by typing in its join code, or by following an invite link.
+pub struct JoinPage {
+    props: Props,
+    join_code: String,
+    join: FetchState<Bucket>,
+    link: ComponentLink<JoinPage>
+}
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct Props {
+    /// The invite being redeemed, if the page was reached through an invite link.
+    pub invite: Option<String>
+}
+
+pub enum Msg {
+    UpdateJoinCode(String),
+    JoinWithCode,
+    FetchedJoin(FetchState<Bucket>)
+}
+
+impl Component for JoinPage {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            props,
+            join_code: "".to_string(),
+            join: Default::default(),
+            link
+        }
+    }
+
+    fn mounted(&mut self) -> ShouldRender {
+        if let Some(invite) = self.props.invite.clone() {
+            self.join.set_fetching();
+            let fetch = fetch_to_state_msg(RedeemInvite { invite }, Msg::FetchedJoin);
+            self.link.send_future(fetch);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateJoinCode(join_code) => self.join_code.neq_assign(join_code),
+            Msg::JoinWithCode => {
+                self.join.set_fetching();
+                let request = JoinWithCode { join_code: self.join_code.trim().to_string() };
+                let fetch = fetch_to_state_msg(request, Msg::FetchedJoin);
+                self.link.send_future(fetch);
+                true
+            }
+            Msg::FetchedJoin(state) => {
+                self.join.neq_assign(state);
+                if let FetchState::Success(bucket) = &self.join {
+                    let route = AppRoute::Bucket { slug: bucket.bucket_slug.clone() };
+                    RouteAgentDispatcher::new().send(RouteRequest::ChangeRoute(route.into()));
+                }
+                true
+            }
+        }
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        self.props.neq_assign(props)
+    }
+
+    fn view(&self) -> VNode<Self> {
+        let failure = if let FetchState::Failed(_) = &self.join {
+            if self.props.invite.is_some() {
+                html! {<p class="help is-danger">{"This invite has expired, or you are already in the bucket."}</p>}
+            } else {
+                html! {<p class="help is-danger">{"Couldn't join with that code."}</p>}
+            }
+        } else {
+            html! {}
+        };
+
+        let content = if self.props.invite.is_some() {
+            html! {
+                <>
+                    {failure}
+                </>
+            }
+        } else {
+            html! {
+                <div class="field">
+                    <label class="label">{"Join Code"}</label>
+                    <div class="control">
+                        <input class="input"
+                            type="text"
+                            value=self.join_code
+                            oninput = |i| Msg::UpdateJoinCode(i.value)
+                        />
+                    </div>
+                    {failure}
+                    <div class="field is-grouped is-grouped-centered">
+                        <p class="control">
+                            <a class="button is-primary" onclick=|_| Msg::JoinWithCode>
+                                {"Join"}
+                            </a>
+                        </p>
+                    </div>
+                </div>
+            }
+        };
+
+        html! {
+            <div class= "has-background-primary full_height_scrollable">
+                <div class = "columns is-centered full_width is-marginless">
+                    <div class="column is-two-thirds-tablet is-half-desktop">
+                        <div class = "card min_height_200">
+                            <div class="card-header">
+                                <p class="card-header-title">
+                                    {"Join Bucket"}
+                                </p>
+                            </div>
+                            <div class="card-content">
+                                {content}
+                            </div>
+                        </div>
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
@@ -1,4 +1,6 @@
 pub mod index;
 pub mod bucket;
 pub mod create_bucket;
+pub mod my_answers;
+pub mod join;
 pub mod settings_modal;
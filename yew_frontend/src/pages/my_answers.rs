@@ -0,0 +1,128 @@
+use yew::{Component, ComponentLink, html, ShouldRender};
+use yew::virtual_dom::VNode;
+use yewtil::NeqAssign;
+use yewtil::fetch::{FetchState, fetch_to_state_msg};
+use wire::answer::Answer;
+use wire::page::{Page, PageQuery};
+use crate::requests::answer::GetOwnAnswers;
+use crate::components::answer_list::render_answers;
+
+/// How many answers are shown on each page.
+const PAGE_SIZE: i64 = 25;
+
+/// Lists the answers the user has given, across every bucket.
+pub struct MyAnswersPage {
+    /// The cursors of the pages that led up to the one being shown, ending with its own.
+    /// The first page doesn't have a cursor.
+    cursors: Vec<Option<String>>,
+    answers: FetchState<Page<Answer>>,
+    link: ComponentLink<MyAnswersPage>
+}
+
+pub enum Msg {
+    NextPage,
+    PreviousPage,
+    FetchedAnswers(FetchState<Page<Answer>>)
+}
+
+impl MyAnswersPage {
+    fn fetch_answers(&mut self) {
+        self.answers.set_fetching();
+        let request = GetOwnAnswers(PageQuery {
+            cursor: self.cursors.last().cloned().unwrap_or_default(),
+            limit: Some(PAGE_SIZE),
+            sort: None
+        });
+        self.link.send_future(fetch_to_state_msg(request, Msg::FetchedAnswers));
+    }
+
+    /// The cursor of the page after the one being shown, if there is one.
+    fn next_cursor(&self) -> Option<String> {
+        self.answers.success().and_then(|answers| answers.next_cursor.clone())
+    }
+}
+
+impl Component for MyAnswersPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            cursors: vec![None],
+            answers: Default::default(),
+            link
+        }
+    }
+
+    fn mounted(&mut self) -> ShouldRender {
+        self.fetch_answers();
+        false
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::NextPage => {
+                if let Some(cursor) = self.next_cursor() {
+                    self.cursors.push(Some(cursor));
+                    self.fetch_answers();
+                }
+                true
+            }
+            Msg::PreviousPage => {
+                if self.cursors.len() > 1 {
+                    self.cursors.pop();
+                    self.fetch_answers();
+                }
+                true
+            }
+            Msg::FetchedAnswers(answers) => self.answers.neq_assign(answers)
+        }
+    }
+
+    fn view(&self) -> VNode<Self> {
+        let content = match &self.answers {
+            FetchState::Success(answers) => render_answers(&answers.items),
+            FetchState::Fetching => html! {
+                <progress class="progress is-small is-dark is-radiusless" max="100"></progress>
+            },
+            FetchState::NotFetching => html! {},
+            FetchState::Failed(_) => html! {
+                {"Couldn't get your answers. Are you logged in?"}
+            }
+        };
+        html! {
+            <div class= "has-background-primary full_height_scrollable">
+                <div class = "columns is-centered full_width is-marginless">
+                    <div class="column is-two-thirds-tablet is-half-desktop">
+                        <div class = "card min_height_200">
+                            <div class="card-header">
+                                <p class="card-header-title">
+                                    {"My Answers"}
+                                </p>
+                            </div>
+                            <div class="card-content">
+                                {content}
+                            </div>
+                            <div class="card-footer">
+                                <button
+                                    class="button card-footer-item is-radiusless"
+                                    disabled = self.cursors.len() <= 1
+                                    onclick=|_| Msg::PreviousPage
+                                >
+                                    {"Previous"}
+                                </button>
+                                <button
+                                    class="button card-footer-item is-radiusless"
+                                    disabled = self.next_cursor().is_none()
+                                    onclick=|_| Msg::NextPage
+                                >
+                                    {"Next"}
+                                </button>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
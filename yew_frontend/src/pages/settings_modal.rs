@@ -1,34 +1,52 @@
 use yew::{Component, ComponentLink, ShouldRender, Html, html, Properties};
 use yewtil::NeqAssign;
 use yewtil::fetch::{FetchState, fetch_to_state_msg};
-use wire::bucket::{Bucket, ChangeBucketFlagsRequest};
+use wire::bucket::{Bucket, ChangeBucketFlagsRequest, DrawStrategy, DuplicatePolicy};
 use yew_router::unit_state::RouteAgentDispatcher;
 use yew_router::agent::RouteRequest;
 use yew_router::unit_state::Route;
 use crate::AppRoute;
-use wire::bucket_user_relation::{BucketUserPermissions, UserAndPermissions};
-use crate::requests::bucket::{SetBucketFlags, GetUsersAndPermissionsInBucket};
+use wire::bucket_user_relation::{BucketUserPermissions, UserAndPermissions, BucketRole, BucketUserRelation};
+use crate::requests::bucket::{SetBucketFlags, GetUsersAndPermissionsInBucket, GetPresentUsers, GetJoinCode, RegenerateJoinCode, SetRoleForUser, TransferOwnership, GetAuditLog, RenameBucket, GetDeletionToken, DeleteBucket, CloneBucket, SaveTemplate};
+use crate::requests::auth_and_user::GetUserUuid;
+use wire::bucket::{SetRoleRequest, TransferOwnershipRequest, RenameBucketRequest, BucketDeletionToken, CloneBucketRequest, SaveTemplateRequest};
+use uuid::Uuid;
+use wire::invite::JoinCode;
+use wire::user::User;
+use wire::audit::AuditEvent;
+use wire::page::Page;
 use crate::pages::settings_modal::Msg::FetchedUsersPermissions;
 
 pub struct SettingsModal {
     props: Props,
     link: ComponentLink<SettingsModal>,
     settings: Settings,
-    users_and_their_settings: FetchState<Vec<UserAndPermissions>>,
+    users_and_their_settings: FetchState<Page<UserAndPermissions>>,
+    present_users: FetchState<Page<User>>,
+    join_code: FetchState<JoinCode>,
+    self_uuid: FetchState<Uuid>,
+    audit_log: FetchState<Page<AuditEvent>>,
     active_tab: SettingsTab,
+    new_name: String,
+    regenerate_slug: bool,
+    deletion_token: FetchState<BucketDeletionToken>,
+    copy_members: bool,
 }
 
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum SettingsTab {
     Bucket,
-    Users
+    Users,
+    Audit
 }
 
 #[derive(Default, Debug, )]
 pub struct Settings {
     is_public: bool,
     is_exclusive: bool,
-    is_drawing: bool
+    is_drawing: bool,
+    draw_strategy: DrawStrategy,
+    duplicate_policy: DuplicatePolicy
 }
 
 impl Settings {
@@ -36,7 +54,9 @@ impl Settings {
         Self {
             is_public: bucket.public_viewable,
             is_exclusive: bucket.exclusive,
-            is_drawing: bucket.drawing_enabled
+            is_drawing: bucket.drawing_enabled,
+            draw_strategy: bucket.draw_strategy,
+            duplicate_policy: bucket.duplicate_policy
         }
     }
 
@@ -57,10 +77,24 @@ impl Settings {
             None
         };
 
+        let draw_strategy = if self.draw_strategy != bucket.draw_strategy {
+            Some(self.draw_strategy)
+        } else {
+            None
+        };
+
+        let duplicate_policy = if self.duplicate_policy != bucket.duplicate_policy {
+            Some(self.duplicate_policy)
+        } else {
+            None
+        };
+
         ChangeBucketFlagsRequest {
             publicly_visible,
             drawing_enabled,
-            exclusive
+            exclusive,
+            draw_strategy,
+            duplicate_policy
         }
     }
 }
@@ -80,10 +114,33 @@ pub enum Msg {
     TogglePublic,
     ToggleExclusive,
     ToggleDrawing,
+    CycleDrawStrategy,
+    ToggleRejectDuplicates,
     SaveSettings,
     FetchedPutSettings(FetchState<Bucket>),
-    FetchedUsersPermissions(FetchState<Vec<UserAndPermissions>>),
-    SetTab(SettingsTab)
+    FetchedUsersPermissions(FetchState<Page<UserAndPermissions>>),
+    FetchedPresentUsers(FetchState<Page<User>>),
+    RegenerateJoinCode,
+    FetchedJoinCode(FetchState<JoinCode>),
+    FetchedSelfUuid(FetchState<Uuid>),
+    SetRole(Uuid, BucketRole),
+    FetchedSetRole(FetchState<BucketUserRelation>),
+    TransferOwnership(Uuid),
+    FetchedTransferOwnership(FetchState<Bucket>),
+    FetchedAuditLog(FetchState<Page<AuditEvent>>),
+    SetTab(SettingsTab),
+    UpdateName(String),
+    ToggleRegenerateSlug,
+    Rename,
+    FetchedRename(FetchState<Bucket>),
+    RequestDeletion,
+    FetchedDeletionToken(FetchState<BucketDeletionToken>),
+    ConfirmDeletion,
+    FetchedDeletion(FetchState<Bucket>),
+    ToggleCopyMembers,
+    CopyBucket,
+    SaveAsTemplate,
+    FetchedCopy(FetchState<Bucket>)
 }
 
 impl Component for SettingsModal {
@@ -92,18 +149,35 @@ impl Component for SettingsModal {
 
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
         let settings = Settings::from_bucket(&props.bucket);
+        let new_name = props.bucket.bucket_name.clone();
         SettingsModal {
             props,
             link,
             settings,
             users_and_their_settings: Default::default(),
-            active_tab: SettingsTab::Bucket
+            present_users: Default::default(),
+            join_code: Default::default(),
+            self_uuid: Default::default(),
+            audit_log: Default::default(),
+            active_tab: SettingsTab::Bucket,
+            new_name,
+            regenerate_slug: false,
+            deletion_token: Default::default(),
+            copy_members: false
         }
     }
 
     fn mounted(&mut self) -> ShouldRender {
         let fetch = fetch_to_state_msg(GetUsersAndPermissionsInBucket{bucket_uuid: self.props.bucket.uuid}, FetchedUsersPermissions);
         self.link.send_future(fetch);
+        let fetch = fetch_to_state_msg(GetPresentUsers{bucket_uuid: self.props.bucket.uuid}, Msg::FetchedPresentUsers);
+        self.link.send_future(fetch);
+        let fetch = fetch_to_state_msg(GetUserUuid, Msg::FetchedSelfUuid);
+        self.link.send_future(fetch);
+        if self.props.permissions.invite_permission {
+            let fetch = fetch_to_state_msg(GetJoinCode{bucket_uuid: self.props.bucket.uuid}, Msg::FetchedJoinCode);
+            self.link.send_future(fetch);
+        }
         false
     }
 
@@ -126,6 +200,17 @@ impl Component for SettingsModal {
                 self.settings.is_drawing = !self.settings.is_drawing;
                 true
             }
+            Msg::CycleDrawStrategy => {
+                self.settings.draw_strategy = next_draw_strategy(self.settings.draw_strategy);
+                true
+            }
+            Msg::ToggleRejectDuplicates => {
+                self.settings.duplicate_policy = match self.settings.duplicate_policy {
+                    DuplicatePolicy::Warn => DuplicatePolicy::Reject,
+                    DuplicatePolicy::Reject => DuplicatePolicy::Warn,
+                };
+                true
+            }
             Msg::SaveSettings => {
                 let request = SetBucketFlags{ bucket_uuid: self.props.bucket.uuid, flag_changeset: self.settings.create_request(&self.props.bucket) };
                 let fetch = fetch_to_state_msg(request, Msg::FetchedPutSettings);
@@ -136,8 +221,126 @@ impl Component for SettingsModal {
                 // TODO replace the old settings. -> Use a callback to replace the parent's bucket
                 false
             }
-            Msg::SetTab(settings_tab) => self.active_tab.neq_assign(settings_tab),
+            Msg::SetTab(settings_tab) => {
+                // The log is fetched every time the tab is opened, so it is never stale.
+                if settings_tab == SettingsTab::Audit {
+                    self.audit_log.set_fetching();
+                    let fetch = fetch_to_state_msg(GetAuditLog{bucket_uuid: self.props.bucket.uuid}, Msg::FetchedAuditLog);
+                    self.link.send_future(fetch);
+                }
+                self.active_tab.neq_assign(settings_tab)
+            }
+            Msg::FetchedAuditLog(audit_log) => self.audit_log.neq_assign(audit_log),
             Msg::FetchedUsersPermissions(permissions) => {self.users_and_their_settings.neq_assign(permissions)}
+            Msg::FetchedPresentUsers(users) => self.present_users.neq_assign(users),
+            Msg::RegenerateJoinCode => {
+                let fetch = fetch_to_state_msg(RegenerateJoinCode{bucket_uuid: self.props.bucket.uuid}, Msg::FetchedJoinCode);
+                self.link.send_future(fetch);
+                false
+            }
+            Msg::FetchedJoinCode(join_code) => self.join_code.neq_assign(join_code),
+            Msg::FetchedSelfUuid(self_uuid) => self.self_uuid.neq_assign(self_uuid),
+            Msg::SetRole(target_user_uuid, role) => {
+                let request = SetRoleForUser {
+                    bucket_uuid: self.props.bucket.uuid,
+                    request: SetRoleRequest { target_user_uuid, role }
+                };
+                self.link.send_future(fetch_to_state_msg(request, Msg::FetchedSetRole));
+                false
+            }
+            Msg::TransferOwnership(new_owner_uuid) => {
+                let request = TransferOwnership {
+                    bucket_uuid: self.props.bucket.uuid,
+                    request: TransferOwnershipRequest { new_owner_uuid }
+                };
+                self.link.send_future(fetch_to_state_msg(request, Msg::FetchedTransferOwnership));
+                false
+            }
+            Msg::FetchedSetRole(FetchState::Success(_)) | Msg::FetchedTransferOwnership(FetchState::Success(_)) => {
+                let fetch = fetch_to_state_msg(GetUsersAndPermissionsInBucket{bucket_uuid: self.props.bucket.uuid}, FetchedUsersPermissions);
+                self.link.send_future(fetch);
+                false
+            }
+            Msg::FetchedSetRole(_) | Msg::FetchedTransferOwnership(_) => false,
+            Msg::UpdateName(new_name) => self.new_name.neq_assign(new_name),
+            Msg::ToggleRegenerateSlug => {
+                self.regenerate_slug = !self.regenerate_slug;
+                true
+            }
+            Msg::Rename => {
+                let request = RenameBucket {
+                    bucket_uuid: self.props.bucket.uuid,
+                    request: RenameBucketRequest {
+                        bucket_name: self.new_name.clone(),
+                        regenerate_slug: self.regenerate_slug
+                    }
+                };
+                self.link.send_future(fetch_to_state_msg(request, Msg::FetchedRename));
+                false
+            }
+            Msg::FetchedRename(FetchState::Success(bucket)) => {
+                // The settings are under the bucket's slug, which may have changed.
+                let route = AppRoute::BucketSettings{ slug: bucket.bucket_slug };
+                RouteAgentDispatcher::new().send(RouteRequest::ReplaceRoute(Route::from(route)));
+                false
+            }
+            Msg::FetchedRename(_) => false,
+            Msg::RequestDeletion => {
+                self.deletion_token.set_fetching();
+                let fetch = fetch_to_state_msg(GetDeletionToken{bucket_uuid: self.props.bucket.uuid}, Msg::FetchedDeletionToken);
+                self.link.send_future(fetch);
+                true
+            }
+            Msg::FetchedDeletionToken(deletion_token) => self.deletion_token.neq_assign(deletion_token),
+            Msg::ConfirmDeletion => {
+                if let FetchState::Success(deletion_token) = &self.deletion_token {
+                    let request = DeleteBucket {
+                        bucket_uuid: self.props.bucket.uuid,
+                        token: deletion_token.token.clone()
+                    };
+                    self.link.send_future(fetch_to_state_msg(request, Msg::FetchedDeletion));
+                }
+                false
+            }
+            Msg::FetchedDeletion(FetchState::Success(_)) => {
+                RouteAgentDispatcher::new().send(RouteRequest::ChangeRoute(Route::from(AppRoute::Index)));
+                false
+            }
+            Msg::FetchedDeletion(_) => false,
+            Msg::ToggleCopyMembers => {
+                self.copy_members = !self.copy_members;
+                true
+            }
+            Msg::CopyBucket => {
+                let request = CloneBucket {
+                    bucket_uuid: self.props.bucket.uuid,
+                    request: CloneBucketRequest {
+                        bucket_name: format!("{} (copy)", self.props.bucket.bucket_name),
+                        copy_tags: true,
+                        copy_flags: true,
+                        copy_member_roles: self.copy_members
+                    }
+                };
+                self.link.send_future(fetch_to_state_msg(request, Msg::FetchedCopy));
+                false
+            }
+            Msg::SaveAsTemplate => {
+                let request = SaveTemplate {
+                    bucket_uuid: self.props.bucket.uuid,
+                    request: SaveTemplateRequest {
+                        template_name: self.props.bucket.bucket_name.clone(),
+                        public: self.props.bucket.public_viewable
+                    }
+                };
+                self.link.send_future(fetch_to_state_msg(request, Msg::FetchedCopy));
+                false
+            }
+            Msg::FetchedCopy(FetchState::Success(bucket)) => {
+                let route = AppRoute::Bucket{ slug: bucket.bucket_slug };
+                RouteAgentDispatcher::new().send(RouteRequest::ChangeRoute(Route::from(route)));
+                false
+            }
+            Msg::FetchedCopy(_) => false,
         }
     }
 
@@ -175,24 +378,24 @@ impl Component for SettingsModal {
 impl SettingsModal {
 
     fn tab_panel_and_respective_children(&self) -> Html<Self> {
-        let tab_links = match self.active_tab {
-            SettingsTab::Bucket => html! {
-                <>
-                    <a class="is-active">{"Bucket"}</a>
-                    <a onclick = |_| Msg::SetTab(SettingsTab::Users)>{"Users"}</a>
-                </>
-            },
-            SettingsTab::Users => html! {
-                <>
-                    <a onclick = |_| Msg::SetTab(SettingsTab::Bucket)>{"Bucket"}</a>
-                    <a class="is-active">{"Users"}</a>
-                </>
-            }
+        // Only members who manage other members can see the audit log.
+        let audit_link = if self.props.permissions.grant_permissions_permission || self.props.permissions.kick_permission {
+            self.tab_link(SettingsTab::Audit, "Audit")
+        } else {
+            html! {}
+        };
+        let tab_links = html! {
+            <>
+                {self.tab_link(SettingsTab::Bucket, "Bucket")}
+                {self.tab_link(SettingsTab::Users, "Users")}
+                {audit_link}
+            </>
         };
 
         let tab_content = match self.active_tab {
             SettingsTab::Bucket => self.bucket_permissions(),
-            SettingsTab::Users => self.player_permissions()
+            SettingsTab::Users => self.player_permissions(),
+            SettingsTab::Audit => self.audit_log()
         };
 
         html! {
@@ -205,6 +408,80 @@ impl SettingsModal {
         }
     }
 
+    fn tab_link(&self, tab: SettingsTab, name: &str) -> Html<Self> {
+        if self.active_tab == tab {
+            html! {<a class="is-active">{name}</a>}
+        } else {
+            html! {<a onclick = |_| Msg::SetTab(tab)>{name}</a>}
+        }
+    }
+
+    /// Lists who did what in the bucket, the most recent first.
+    fn audit_log(&self) -> Html<Self> {
+        let user_name = |uuid: Uuid| -> Option<String> {
+            match &self.users_and_their_settings {
+                FetchState::Success(users_and_permissions) => users_and_permissions
+                    .items
+                    .iter()
+                    .find(|user_permissions| user_permissions.user.uuid == uuid)
+                    .map(|user_permissions| user_permissions.user.google_name.clone().unwrap_or_else(|| uuid.to_string())),
+                _ => None
+            }
+        };
+
+        match &self.audit_log {
+            FetchState::Success(events) if events.items.is_empty() => html! {
+                <div class="panel-block">
+                    {"Nothing has happened in this bucket yet."}
+                </div>
+            },
+            FetchState::Success(events) => {
+                events
+                    .items
+                    .iter()
+                    .map(|event| {
+                        let actor = event.actor_uuid
+                            .and_then(&user_name)
+                            .unwrap_or_else(|| "Someone".to_string());
+                        // Targets can also be questions or answers, which aren't named here.
+                        let target = match event.target_uuid.and_then(&user_name) {
+                            Some(target) => format!(" ({})", target),
+                            None => "".to_string()
+                        };
+                        let changed_fields = event.diff
+                            .as_object()
+                            .map(|fields| fields.keys().cloned().collect::<Vec<String>>().join(", "))
+                            .unwrap_or_default();
+                        html! {
+                            <div class="panel-block">
+                                <div class="level full_width">
+                                    <div>
+                                        <strong>{actor}</strong>
+                                        {crate::NBS}
+                                        {event.action.description()}
+                                        {target}
+                                        <div class="is-size-7">
+                                            {changed_fields}
+                                        </div>
+                                    </div>
+                                    <div class="level-right is-size-7">
+                                        {event.created_at.format("%Y-%m-%d %H:%M").to_string()}
+                                    </div>
+                                </div>
+                            </div>
+                        }
+                    })
+                    .collect()
+            }
+            FetchState::Fetching => html! {
+                <div class="panel-block">
+                    <progress class="progress is-small is-dark is-radiusless" max="100"></progress>
+                </div>
+            },
+            FetchState::NotFetching | FetchState::Failed(_) => html! {}
+        }
+    }
+
     fn bucket_permissions(&self) -> Html<Self> {
         html! {
             <>
@@ -246,6 +523,41 @@ impl SettingsModal {
                         </div>
                     </div>
                 </a>
+                <a class="panel-block" onclick=|_| Msg::CycleDrawStrategy>
+                    <div class="level full_width">
+                        <label>{"Draw Strategy"}</label>
+                        <div class="is-size-7">
+                           {"Decides which questions are more likely to be drawn."}
+                        </div>
+                        <div class="level-right">
+                            <button
+                                class="button is-small"
+                                disabled= !self.props.permissions.set_drawing_permission
+                            >
+                                {draw_strategy_name(self.settings.draw_strategy)}
+                            </button>
+                        </div>
+                    </div>
+                </a>
+                <a class="panel-block" onclick=|_| Msg::ToggleRejectDuplicates>
+                    <div class="level full_width">
+                        <label>{"Reject Duplicates"}</label>
+                        <div class="is-size-7">
+                           {"Refuses questions that are already in the bucket, instead of just warning about them."}
+                        </div>
+                        <div class="level-right">
+                            <input
+                                id="rejectDuplicatesSwitch"
+                                type="checkbox"
+                                name="rejectDuplicatesSwitch"
+                                class="switch"
+                                checked= self.settings.duplicate_policy == DuplicatePolicy::Reject
+                                disabled= !self.props.permissions.moderate_questions_permission
+                            />
+                            <label for="rejectDuplicatesSwitch">{'\u{00A0}'}</label> // Non-breaking space. The switch is targeted to this label.
+                        </div>
+                    </div>
+                </a>
                 <a class="panel-block" onclick=|_| Msg::ToggleExclusive >
                     <div class="level full_width">
                         <label>{"Exclusive"}</label>
@@ -266,14 +578,217 @@ impl SettingsModal {
                         </div>
                     </div>
                 </a>
+                {self.join_code_block()}
+                {self.copy_block()}
+                {self.owner_block()}
             </>
         }
     }
 
+    fn viewer_is_owner(&self) -> bool {
+        match &self.self_uuid {
+            FetchState::Success(self_uuid) => self.props.bucket.owner_uuid == Some(*self_uuid),
+            _ => false
+        }
+    }
+
+    /// Lets the owner rename the bucket, or delete it.
+    fn owner_block(&self) -> Html<Self> {
+        if !self.viewer_is_owner() {
+            return html! {};
+        }
+        // Deleting takes a second click, once the token confirming it has been gotten.
+        let delete_button = match &self.deletion_token {
+            FetchState::Success(_) => html! {
+                <button class="button is-small is-danger" onclick=|_| Msg::ConfirmDeletion>
+                    {"Really Delete"}
+                </button>
+            },
+            FetchState::Fetching => html! {
+                <button class="button is-small is-danger is-loading"></button>
+            },
+            FetchState::NotFetching | FetchState::Failed(_) => html! {
+                <button class="button is-small is-danger is-outlined" onclick=|_| Msg::RequestDeletion>
+                    {"Delete Bucket"}
+                </button>
+            }
+        };
+        html! {
+            <>
+                <div class="panel-block">
+                    <div class="level full_width">
+                        <label>{"Name"}</label>
+                        <div class="level-right">
+                            <input
+                                class="input is-small"
+                                value=&self.new_name
+                                oninput=|e| Msg::UpdateName(e.value)
+                            />
+                            {crate::NBS}
+                            <label class="checkbox is-size-7">
+                                <input
+                                    type="checkbox"
+                                    checked=self.regenerate_slug
+                                    onclick=|_| Msg::ToggleRegenerateSlug
+                                />
+                                {" Change link"}
+                            </label>
+                            {crate::NBS}
+                            <button class="button is-small" onclick=|_| Msg::Rename>
+                                {"Rename"}
+                            </button>
+                        </div>
+                    </div>
+                </div>
+                <div class="panel-block">
+                    <div class="level full_width">
+                        <label>{"Delete"}</label>
+                        <div class="is-size-7">
+                            {"Removes the bucket, along with all of its questions and answers."}
+                        </div>
+                        <div class="level-right">
+                            {delete_button}
+                        </div>
+                    </div>
+                </div>
+            </>
+        }
+    }
+
+    /// Lets members copy the bucket's questions into a new bucket, or into a template.
+    fn copy_block(&self) -> Html<Self> {
+        // Only members who can grant permissions can bring the other members along.
+        let copy_members = if self.props.permissions.grant_permissions_permission {
+            html! {
+                <>
+                    <label class="checkbox is-size-7">
+                        <input
+                            type="checkbox"
+                            checked=self.copy_members
+                            onclick=|_| Msg::ToggleCopyMembers
+                        />
+                        {" Bring members"}
+                    </label>
+                    {crate::NBS}
+                </>
+            }
+        } else {
+            html! {}
+        };
+        html! {
+            <div class="panel-block">
+                <div class="level full_width">
+                    <label>{"Copy"}</label>
+                    <div class="is-size-7">
+                        {"Makes a new bucket with the same questions, or saves them as a template others can start from."}
+                    </div>
+                    <div class="level-right">
+                        {copy_members}
+                        <button class="button is-small" onclick=|_| Msg::CopyBucket>
+                            {"Copy Bucket"}
+                        </button>
+                        {crate::NBS}
+                        <button class="button is-small" onclick=|_| Msg::SaveAsTemplate>
+                            {"Save as Template"}
+                        </button>
+                    </div>
+                </div>
+            </div>
+        }
+    }
+
+    /// Shows the code others can type in to join the bucket, even if it is exclusive.
+    fn join_code_block(&self) -> Html<Self> {
+        if !self.props.permissions.invite_permission {
+            return html! {};
+        }
+        let code = match &self.join_code {
+            FetchState::Success(join_code) => html! {<span class="tag is-medium">{&join_code.join_code}</span>},
+            _ => html! {<span class="is-size-7">{"No join code yet."}</span>}
+        };
+        html! {
+            <div class="panel-block">
+                <div class="level full_width">
+                    <label>{"Join Code"}</label>
+                    <div class="is-size-7">
+                        {"Lets anyone with the code join, even if the bucket is exclusive."}
+                    </div>
+                    <div class="level-right">
+                        {code}
+                        {crate::NBS}
+                        <button class="button is-small" onclick=|_| Msg::RegenerateJoinCode>
+                            {"New Code"}
+                        </button>
+                    </div>
+                </div>
+            </div>
+        }
+    }
+
+    /// Buttons for giving the user a role, or making them the owner.
+    /// The owner's role can't be changed, ownership has to be transferred away first.
+    fn role_controls(&self, user_permissions: &UserAndPermissions, is_owner: bool) -> Html<Self> {
+        if is_owner {
+            return html! {};
+        }
+        let user_uuid = user_permissions.user.uuid;
+        let current_role = BucketRole::from_permissions(&user_permissions.permissions);
+
+        let role_buttons = if self.props.permissions.grant_permissions_permission {
+            [(BucketRole::Moderator, "Moderator"), (BucketRole::Player, "Player"), (BucketRole::Spectator, "Spectator")]
+                .iter()
+                .map(|(role, name)| {
+                    let role = *role;
+                    let class = if current_role == Some(role) {
+                        "button is-small is-info"
+                    } else {
+                        "button is-small"
+                    };
+                    html! {
+                        <button class=class onclick=|_| Msg::SetRole(user_uuid, role)>
+                            {name}
+                        </button>
+                    }
+                })
+                .collect()
+        } else {
+            html! {}
+        };
+
+        let make_owner = if self.viewer_is_owner() {
+            html! {
+                <button class="button is-small is-warning" onclick=|_| Msg::TransferOwnership(user_uuid)>
+                    {"Make Owner"}
+                </button>
+            }
+        } else {
+            html! {}
+        };
+
+        html! {
+            <div class="buttons are-small">
+                {role_buttons}
+                {make_owner}
+            </div>
+        }
+    }
+
     fn player_permissions(&self) -> Html<Self> {
-        fn render_user_and_permissions(user_permissions: &UserAndPermissions) -> Html<SettingsModal> {
+        fn render_user_and_permissions(user_permissions: &UserAndPermissions, is_present: bool, is_owner: bool, role_controls: Html<SettingsModal>) -> Html<SettingsModal> {
             let user_name = &user_permissions.user.google_name.clone().unwrap_or_else(||user_permissions.user.uuid.to_string());
 
+            let presence = if is_present {
+                html! {<span class="tag is-success">{"Active"}</span>}
+            } else {
+                html! {<span class="tag is-light">{"Dormant"}</span>}
+            };
+
+            let owner = if is_owner {
+                html! {<span class="tag is-primary">{"Owner"}</span>}
+            } else {
+                html! {}
+            };
+
             let mangle_id_switch = |permission: &str| -> String {
                 format!("{}{}Switch", user_permissions.user.uuid, permission)
             };
@@ -282,6 +797,11 @@ impl SettingsModal {
                 <div class = "panel-block columns is-marginless">
                     <div class="is-4 column">
                         {user_name}
+                        {crate::NBS}
+                        {presence}
+                        {crate::NBS}
+                        {owner}
+                        {role_controls}
                     </div>
 
                     <div class="horiz_flex_wrap columns is-marginless">
@@ -361,13 +881,74 @@ impl SettingsModal {
                                 {"Kick"}
                             </label>
                         </div>
+
+                        <div class="is-4 column">
+                            <input
+                                class="switch"
+                                id=mangle_id_switch("moderate")
+                                name=mangle_id_switch("moderate")
+                                type="checkbox"
+                                checked = user_permissions.permissions.moderate_questions_permission
+                            />
+                            <label
+                                for=mangle_id_switch("moderate")
+                            >
+                                {"Moderate"}
+                            </label>
+                        </div>
+
+                        <div class="is-4 column">
+                            <input
+                                class="switch"
+                                id=mangle_id_switch("delete")
+                                name=mangle_id_switch("delete")
+                                type="checkbox"
+                                checked = user_permissions.permissions.delete_questions_permission
+                            />
+                            <label
+                                for=mangle_id_switch("delete")
+                            >
+                                {"Delete"}
+                            </label>
+                        </div>
+
+                        <div class="is-4 column">
+                            <input
+                                class="switch"
+                                id=mangle_id_switch("invite")
+                                name=mangle_id_switch("invite")
+                                type="checkbox"
+                                checked = user_permissions.permissions.invite_permission
+                            />
+                            <label
+                                for=mangle_id_switch("invite")
+                            >
+                                {"Invite"}
+                            </label>
+                        </div>
                     </div>
                 </div>
             }
         }
         match &self.users_and_their_settings {
             FetchState::Success(users_and_permissions) => {
-                users_and_permissions.iter().map(render_user_and_permissions).collect()
+                let is_present = |user: &User| match &self.present_users {
+                    FetchState::Success(present_users) => present_users.items.iter().any(|present| present.uuid == user.uuid),
+                    _ => false
+                };
+                users_and_permissions
+                    .items
+                    .iter()
+                    .map(|user_permissions| {
+                        let is_owner = self.props.bucket.owner_uuid == Some(user_permissions.user.uuid);
+                        render_user_and_permissions(
+                            user_permissions,
+                            is_present(&user_permissions.user),
+                            is_owner,
+                            self.role_controls(user_permissions, is_owner)
+                        )
+                    })
+                    .collect()
             },
             FetchState::NotFetching => html!{},
             FetchState::Fetching => html!{
@@ -378,4 +959,23 @@ impl SettingsModal {
             FetchState::Failed(_) => html!{},
         }
     }
-}
\ No newline at end of file
+}
+
+/// Gets the strategy that follows the provided one when cycling through them in the settings.
+fn next_draw_strategy(strategy: DrawStrategy) -> DrawStrategy {
+    match strategy {
+        DrawStrategy::Uniform => DrawStrategy::Upvotes,
+        DrawStrategy::Upvotes => DrawStrategy::OlderFirst,
+        DrawStrategy::OlderFirst => DrawStrategy::MembersNotRecentlyDrawn,
+        DrawStrategy::MembersNotRecentlyDrawn => DrawStrategy::Uniform,
+    }
+}
+
+fn draw_strategy_name(strategy: DrawStrategy) -> &'static str {
+    match strategy {
+        DrawStrategy::Uniform => "Uniform",
+        DrawStrategy::Upvotes => "Upvoted First",
+        DrawStrategy::OlderFirst => "Older First",
+        DrawStrategy::MembersNotRecentlyDrawn => "Members Not Recently Drawn",
+    }
+}
@@ -1,11 +1,13 @@
 use super::*;
-use wire::answer::{NewAnswerRequest, Answer};
+use wire::answer::{NewAnswerRequest, Answer, CreatedAnswer};
+use wire::page::{Page, PageQuery, RecencySort};
+use uuid::Uuid;
 
 pub struct CreateAnswer(pub NewAnswerRequest);
 
 impl FetchRequest for CreateAnswer {
     type RequestBody = NewAnswerRequest;
-    type ResponseBody = Answer;
+    type ResponseBody = CreatedAnswer;
 
     fn url(&self) -> String {
         create_url("answer")
@@ -21,3 +23,47 @@ impl FetchRequest for CreateAnswer {
 
     fn use_cors(&self) -> bool {cors()}
 }
+
+/// Gets a page of the answers the user gave.
+pub struct GetOwnAnswers(pub PageQuery<RecencySort>);
+
+impl FetchRequest for GetOwnAnswers {
+    type RequestBody = ();
+    type ResponseBody = Page<Answer>;
+
+    fn url(&self) -> String {
+        create_url(&format!("answer/mine?{}", page_query(&self.0)))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Get
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Gets a page of the answers to the question that the user can see.
+pub struct GetAnswersForQuestion{pub question_uuid: Uuid, pub page: PageQuery<RecencySort>}
+
+impl FetchRequest for GetAnswersForQuestion {
+    type RequestBody = ();
+    type ResponseBody = Page<Answer>;
+
+    fn url(&self) -> String {
+        create_url(&format!("question/{}/answers?{}", self.question_uuid, page_query(&self.page)))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Get
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
@@ -1,8 +1,12 @@
 use super::*;
 use uuid::Uuid;
 use wire::bucket_user_relation::{BucketUserRelation, BucketUserPermissions, UserAndPermissions};
-use wire::bucket::{SetPermissionsRequest, ChangeBucketFlagsRequest};
+use wire::bucket::{SetPermissionsRequest, ChangeBucketFlagsRequest, SetRoleRequest, TransferOwnershipRequest, RenameBucketRequest, BucketDeletionToken, CloneBucketRequest, SaveTemplateRequest};
 use wire::user::User;
+use wire::page::Page;
+use wire::presence::Presence;
+use wire::invite::JoinCode;
+use wire::audit::AuditEvent;
 
 /// Creates a bucket
 #[derive(Clone, Debug)]
@@ -32,7 +36,7 @@ pub struct GetPublicBuckets;
 
 impl FetchRequest for GetPublicBuckets {
     type RequestBody = ();
-    type ResponseBody = Vec<Bucket>;
+    type ResponseBody = Page<Bucket>;
 
     fn url(&self) -> String {
         create_url("bucket/public")
@@ -56,7 +60,7 @@ pub struct GetParticipatingBuckets;
 
 impl FetchRequest for GetParticipatingBuckets {
     type RequestBody = ();
-    type ResponseBody = Vec<Bucket>;
+    type ResponseBody = Page<Bucket>;
 
     fn url(&self) -> String {
         create_url("bucket/in")
@@ -199,6 +203,50 @@ impl FetchRequest for SetPermissionsForUser {
     fn use_cors(&self) -> bool {cors()}
 }
 
+/// Gives a user every permission of a role.
+pub struct SetRoleForUser{pub bucket_uuid: Uuid, pub request: SetRoleRequest}
+
+impl FetchRequest for SetRoleForUser {
+    type RequestBody = SetRoleRequest;
+    type ResponseBody = BucketUserRelation;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/user/role", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Put(&self.request)
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Makes another member the owner of the bucket.
+pub struct TransferOwnership{pub bucket_uuid: Uuid, pub request: TransferOwnershipRequest}
+
+impl FetchRequest for TransferOwnership {
+    type RequestBody = TransferOwnershipRequest;
+    type ResponseBody = Bucket;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/owner", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Put(&self.request)
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
 pub struct SetBucketFlags{pub bucket_uuid: Uuid, pub flag_changeset: ChangeBucketFlagsRequest}
 
 impl FetchRequest for SetBucketFlags {
@@ -220,11 +268,143 @@ impl FetchRequest for SetBucketFlags {
     fn use_cors(&self) -> bool {cors()}
 }
 
+/// Changes the bucket's name, and optionally its slug.
+pub struct RenameBucket{pub bucket_uuid: Uuid, pub request: RenameBucketRequest}
+
+impl FetchRequest for RenameBucket {
+    type RequestBody = RenameBucketRequest;
+    type ResponseBody = Bucket;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Patch(&self.request)
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Gets the token needed to delete the bucket.
+pub struct GetDeletionToken{pub bucket_uuid: Uuid}
+
+impl FetchRequest for GetDeletionToken {
+    type RequestBody = ();
+    type ResponseBody = BucketDeletionToken;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/deletion_token", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Post(&())
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Deletes the bucket, confirmed by a token from `GetDeletionToken`.
+pub struct DeleteBucket{pub bucket_uuid: Uuid, pub token: String}
+
+impl FetchRequest for DeleteBucket {
+    type RequestBody = ();
+    type ResponseBody = Bucket;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}?token={}", self.bucket_uuid, self.token))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Delete
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Makes a new bucket out of the questions in a bucket or template.
+pub struct CloneBucket{pub bucket_uuid: Uuid, pub request: CloneBucketRequest}
+
+impl FetchRequest for CloneBucket {
+    type RequestBody = CloneBucketRequest;
+    type ResponseBody = Bucket;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/clone", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Post(&self.request)
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Saves the bucket's questions and tags as a template.
+pub struct SaveTemplate{pub bucket_uuid: Uuid, pub request: SaveTemplateRequest}
+
+impl FetchRequest for SaveTemplate {
+    type RequestBody = SaveTemplateRequest;
+    type ResponseBody = Bucket;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/template", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Post(&self.request)
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Gets the templates that anyone can make buckets from.
+pub struct GetPublicTemplates;
+
+impl FetchRequest for GetPublicTemplates {
+    type RequestBody = ();
+    type ResponseBody = Page<Bucket>;
+
+    fn url(&self) -> String {
+        create_url("bucket/templates")
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Get
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
 pub struct GetUsersInBucket{pub bucket_uuid: Uuid}
 
 impl FetchRequest for GetUsersInBucket {
     type RequestBody = ();
-    type ResponseBody = Vec<User>;
+    type ResponseBody = Page<User>;
 
     fn url(&self) -> String {
         create_url(&format!("bucket/{}/users", self.bucket_uuid))
@@ -246,7 +426,7 @@ pub struct GetUsersAndPermissionsInBucket{pub bucket_uuid: Uuid}
 
 impl FetchRequest for GetUsersAndPermissionsInBucket {
     type RequestBody = ();
-    type ResponseBody = Vec<UserAndPermissions>;
+    type ResponseBody = Page<UserAndPermissions>;
 
     fn url(&self) -> String {
         create_url(&format!("bucket/{}/all_user_permissions", self.bucket_uuid))
@@ -262,3 +442,162 @@ impl FetchRequest for GetUsersAndPermissionsInBucket {
 
     fn use_cors(&self) -> bool {cors()}
 }
+
+/// Tells the server that the user is still in the bucket.
+pub struct SendHeartbeat{pub bucket_uuid: Uuid}
+
+impl FetchRequest for SendHeartbeat {
+    type RequestBody = ();
+    type ResponseBody = Presence;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/heartbeat", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Post(&())
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Gets the users that are currently in the bucket.
+pub struct GetPresentUsers{pub bucket_uuid: Uuid}
+
+impl FetchRequest for GetPresentUsers {
+    type RequestBody = ();
+    type ResponseBody = Page<User>;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/present", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Get
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// The websocket url that pushes the bucket's events as they happen.
+pub fn bucket_live_url(bucket_uuid: Uuid) -> String {
+    create_ws_url(&format!("bucket/{}/live", bucket_uuid))
+}
+
+/// Gets the code that can be typed in to join the bucket.
+pub struct GetJoinCode{pub bucket_uuid: Uuid}
+
+impl FetchRequest for GetJoinCode {
+    type RequestBody = ();
+    type ResponseBody = JoinCode;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/join_code", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Get
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Replaces the bucket's join code with a new one.
+pub struct RegenerateJoinCode{pub bucket_uuid: Uuid}
+
+impl FetchRequest for RegenerateJoinCode {
+    type RequestBody = ();
+    type ResponseBody = JoinCode;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/join_code", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Post(&())
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Joins the bucket that the code belongs to.
+pub struct JoinWithCode{pub join_code: String}
+
+impl FetchRequest for JoinWithCode {
+    type RequestBody = ();
+    type ResponseBody = Bucket;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/join/{}", self.join_code))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Post(&())
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Joins the bucket that the invite is for.
+pub struct RedeemInvite{pub invite: String}
+
+impl FetchRequest for RedeemInvite {
+    type RequestBody = ();
+    type ResponseBody = Bucket;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/invites/{}/redeem", self.invite))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Post(&())
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Gets the most recent changes made within the bucket.
+pub struct GetAuditLog{pub bucket_uuid: Uuid}
+
+impl FetchRequest for GetAuditLog {
+    type RequestBody = ();
+    type ResponseBody = Page<AuditEvent>;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/audit", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Get
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
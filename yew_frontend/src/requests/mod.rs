@@ -1,6 +1,7 @@
 use yewtil::fetch::{FetchRequest, MethodBody};
 use serde::{Serialize, Deserialize};
 use wire::user::BEARER;
+use wire::page::PageQuery;
 
 use wire::bucket::{Bucket, NewBucketRequest};
 
@@ -43,6 +44,44 @@ fn create_url(path: &str) -> String {
     [URL_BASE, path].into_iter().cloned().collect()
 }
 
+const WS_URL_BASE: &str = "ws://0.0.0.0:8080/api/";
+
+fn create_ws_url(path: &str) -> String {
+    [WS_URL_BASE, path].into_iter().cloned().collect()
+}
+
+/// Percent-encodes a value so that it can be used within a query string.
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b',' => (b as char).to_string(),
+            _ => format!("%{:02X}", b)
+        })
+        .collect()
+}
+
+/// Formats the page as a query string.
+///
+/// The sort is written the way it is serialized, which is how the server expects it.
+fn page_query<S: Serialize>(page: &PageQuery<S>) -> String {
+    let mut query = Vec::new();
+    if let Some(cursor) = &page.cursor {
+        query.push(format!("cursor={}", encode_query_value(cursor)));
+    }
+    if let Some(limit) = page.limit {
+        query.push(format!("limit={}", limit));
+    }
+    let sort = page.sort
+        .as_ref()
+        .and_then(|sort| serde_json::to_value(sort).ok())
+        .and_then(|sort| sort.as_str().map(String::from));
+    if let Some(sort) = sort {
+        query.push(format!("sort={}", sort));
+    }
+    query.join("&")
+}
+
 // TODO this should be compiled later.
 pub fn cors() -> bool {
     true
@@ -54,4 +93,6 @@ pub mod bucket;
 
 pub mod question;
 
-pub mod answer;
\ No newline at end of file
+pub mod answer;
+
+pub mod search;
@@ -1,12 +1,13 @@
 use super::*;
-use wire::question::{NewQuestionRequest, Question, SetArchivedRequest};
+use wire::question::{CreatedQuestion, EditQuestionRequest, ExportedQuestion, ImportQuestionsRequest, ImportQuestionsResponse, NewQuestionRequest, Question, QuestionRevision, SetArchivedRequest, VoteRequest};
+use wire::page::Page;
 use uuid::Uuid;
 
 pub struct CreateQuestion{pub new_question: NewQuestionRequest}
 
 impl FetchRequest for CreateQuestion {
     type RequestBody = NewQuestionRequest;
-    type ResponseBody = Question;
+    type ResponseBody = CreatedQuestion;
 
     fn url(&self) -> String {
         create_url("question")
@@ -44,13 +45,67 @@ impl FetchRequest for DeleteQuestion {
     fn use_cors(&self) -> bool {cors()}
 }
 
-pub struct GetRandomQuestion{pub bucket_uuid: Uuid}
+/// Replaces the question's text.
+pub struct EditQuestion{pub question_uuid: Uuid, pub request: EditQuestionRequest}
+
+impl FetchRequest for EditQuestion {
+    type RequestBody = EditQuestionRequest;
+    type ResponseBody = Question;
+
+    fn url(&self) -> String {
+        create_url(&format!("question/{}", self.question_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Put(&self.request)
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Gets the previous versions of the question's text, the most recent first.
+pub struct GetQuestionRevisions{pub question_uuid: Uuid}
+
+impl FetchRequest for GetQuestionRevisions {
+    type RequestBody = ();
+    type ResponseBody = Page<QuestionRevision>;
+
+    fn url(&self) -> String {
+        create_url(&format!("question/{}/revisions", self.question_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Get
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Draws a random question.
+///
+/// The tags are comma separated lists of tag names, and are ignored if empty.
+pub struct GetRandomQuestion{pub bucket_uuid: Uuid, pub include_tags: String, pub exclude_tags: String}
 impl FetchRequest for GetRandomQuestion {
     type RequestBody = ();
     type ResponseBody = Option<Question>;
 
     fn url(&self) -> String {
-       create_url(&format!("question/random?bucket_uuid={}", self.bucket_uuid))
+        let mut url = format!("question/random?bucket_uuid={}", self.bucket_uuid);
+        if !self.include_tags.trim().is_empty() {
+            url.push_str(&format!("&include_tags={}", encode_query_value(&self.include_tags)));
+        }
+        if !self.exclude_tags.trim().is_empty() {
+            url.push_str(&format!("&exclude_tags={}", encode_query_value(&self.exclude_tags)));
+        }
+        create_url(&url)
     }
 
     fn method(&self) -> MethodBody<Self::RequestBody> {
@@ -89,7 +144,7 @@ pub struct GetEveryQuestionInBucket{pub bucket_uuid: Uuid}
 
 impl FetchRequest for GetEveryQuestionInBucket {
     type RequestBody = ();
-    type ResponseBody = Vec<Question>;
+    type ResponseBody = Page<Question>;
 
     fn url(&self) -> String {
         create_url(&format!("question/in_bucket?bucket_uuid={}", self.bucket_uuid))
@@ -110,7 +165,7 @@ pub struct GetEveryQuestionOnFloor{pub bucket_uuid: Uuid}
 
 impl FetchRequest for GetEveryQuestionOnFloor {
     type RequestBody = ();
-    type ResponseBody = Vec<Question>;
+    type ResponseBody = Page<Question>;
 
     fn url(&self) -> String {
         create_url(&format!("question/on_floor?bucket_uuid={}", self.bucket_uuid))
@@ -195,7 +250,7 @@ pub struct GetFavoriteQuestions;
 
 impl FetchRequest for GetFavoriteQuestions {
     type RequestBody = ();
-    type ResponseBody = Vec<Question>;
+    type ResponseBody = Page<Question>;
 
     fn url(&self) -> String {
         create_url("question/favorites")
@@ -211,3 +266,91 @@ impl FetchRequest for GetFavoriteQuestions {
 
     fn use_cors(&self) -> bool {cors()}
 }
+
+/// Casts the user's vote on the question, replacing their previous vote.
+pub struct VoteOnQuestion{pub question_uuid: Uuid, pub request: VoteRequest}
+
+impl FetchRequest for VoteOnQuestion {
+    type RequestBody = VoteRequest;
+    type ResponseBody = Question;
+
+    fn url(&self) -> String {
+        create_url(&format!("question/{}/vote", self.question_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Put(&self.request)
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Removes the user's vote on the question.
+pub struct RemoveVoteOnQuestion{pub question_uuid: Uuid}
+
+impl FetchRequest for RemoveVoteOnQuestion {
+    type RequestBody = ();
+    type ResponseBody = Question;
+
+    fn url(&self) -> String {
+        create_url(&format!("question/{}/vote", self.question_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Delete
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Adds many questions to the bucket at once, skipping any it already has.
+pub struct ImportQuestions{pub bucket_uuid: Uuid, pub request: ImportQuestionsRequest}
+
+impl FetchRequest for ImportQuestions {
+    type RequestBody = ImportQuestionsRequest;
+    type ResponseBody = ImportQuestionsResponse;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/questions/import", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Post(&self.request)
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
+
+/// Gets every question in the bucket, along with its tags and answer count.
+pub struct ExportQuestions{pub bucket_uuid: Uuid}
+
+impl FetchRequest for ExportQuestions {
+    type RequestBody = ();
+    type ResponseBody = Vec<ExportedQuestion>;
+
+    fn url(&self) -> String {
+        create_url(&format!("bucket/{}/questions/export", self.bucket_uuid))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Get
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}
@@ -0,0 +1,24 @@
+use super::*;
+use wire::search::SearchResults;
+
+/// Searches the questions and answers in the buckets the user can see.
+pub struct Search{pub query: String}
+
+impl FetchRequest for Search {
+    type RequestBody = ();
+    type ResponseBody = SearchResults;
+
+    fn url(&self) -> String {
+        create_url(&format!("search?q={}", encode_query_value(&self.query)))
+    }
+
+    fn method(&self) -> MethodBody<Self::RequestBody> {
+        MethodBody::Get
+    }
+
+    fn headers(&self) -> Vec<(String, String)> {
+        default_headers()
+    }
+
+    fn use_cors(&self) -> bool {cors()}
+}